@@ -1,4 +1,4 @@
-use crate::core::logic::{BindingExpr, BinaryOp, UnaryOp, NodeInputBinding};
+use crate::core::logic::{call_scalar_function, BindingExpr, BinaryOp, UnaryOp, NodeInputBinding};
 use crate::core::registry::MetaRegistry;
 use crate::core::types::{DotPath, MetaValue, ScopeId};
 use std::collections::HashMap;
@@ -167,6 +167,11 @@ impl EvaluationContext {
                     MetaValue::from(0.0)
                 }
             }
+            BinaryOp::Power => {
+                let a = left.as_scalar().unwrap_or(0.0);
+                let b = right.as_scalar().unwrap_or(1.0);
+                MetaValue::from(a.powf(b))
+            }
             
             // Comparison operations
             BinaryOp::Equal => {
@@ -316,8 +321,14 @@ impl EvaluationContext {
                 }
             }
             _ => {
-                warn!("Unknown function: {}", function);
-                MetaValue::default()
+                let scalars: Vec<f64> = arg_values.iter().map(|v| v.as_scalar().unwrap_or(0.0)).collect();
+                match call_scalar_function(function, &scalars) {
+                    Ok(value) => MetaValue::from(value),
+                    Err(e) => {
+                        warn!("{}", e);
+                        MetaValue::default()
+                    }
+                }
             }
         }
     }
@@ -1,7 +1,11 @@
 use crate::core::logic::{InputMap, OutputMap};
 use crate::core::registry::MetaRegistry;
-use crate::core::types::ScopeId;
-use std::sync::Arc;
+use crate::core::types::{DotPath, MetaValue, ScopeId};
+use crate::graph::runtime::NodeId;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
 
 /// Context for evaluating expressions and logic nodes
 /// 
@@ -16,6 +20,18 @@ pub struct EvalContext {
     pub scope_id: ScopeId,
     /// Optional parent context for nested evaluations
     pub parent: Option<Box<EvalContext>>,
+    /// Fixed evaluation time in seconds (None = read `system.time` from the registry)
+    pub time_seconds: Option<f64>,
+    /// Random number source shared by every node in this evaluation
+    pub rng: EvalRng,
+    /// Named channels that probe nodes stream values into
+    pub probes: ProbeChannels,
+    /// Seconds the host clock has advanced, added to `time_seconds()`
+    pub elapsed_time: f64,
+    /// Scratch state that nodes keep between evaluation passes
+    pub node_state: NodeStateStore,
+    /// Node being evaluated, set by the evaluator through `for_node`
+    pub current_node: Option<NodeId>,
 }
 
 impl Default for EvalContext {
@@ -24,6 +40,12 @@ impl Default for EvalContext {
             registry: Arc::new(MetaRegistry::new()),
             scope_id: ScopeId::GLOBAL,
             parent: None,
+            time_seconds: None,
+            rng: EvalRng::from_entropy(),
+            probes: ProbeChannels::default(),
+            elapsed_time: 0.0,
+            node_state: NodeStateStore::default(),
+            current_node: None,
         }
     }
 }
@@ -35,6 +57,38 @@ impl EvalContext {
             registry,
             scope_id,
             parent: None,
+            time_seconds: None,
+            rng: EvalRng::from_entropy(),
+            probes: ProbeChannels::default(),
+            elapsed_time: 0.0,
+            node_state: NodeStateStore::default(),
+            current_node: None,
+        }
+    }
+
+    /// Use `probes` for probe node output, sharing its subscribers
+    pub fn with_probes(mut self, probes: ProbeChannels) -> Self {
+        self.probes = probes;
+        self
+    }
+
+    /// Use `node_state` for per-node state, sharing it with other contexts
+    pub fn with_node_state(mut self, node_state: NodeStateStore) -> Self {
+        self.node_state = node_state;
+        self
+    }
+
+    /// Advance the clock seen by nodes by `elapsed_time` seconds
+    pub fn with_elapsed_time(mut self, elapsed_time: f64) -> Self {
+        self.elapsed_time = elapsed_time;
+        self
+    }
+
+    /// Copy of this context for evaluating `node_id`
+    pub fn for_node(&self, node_id: NodeId) -> Self {
+        Self {
+            current_node: Some(node_id),
+            ..self.clone()
         }
     }
 
@@ -44,6 +98,12 @@ impl EvalContext {
             registry: self.registry.clone(),
             scope_id,
             parent: Some(Box::new(self.clone())),
+            time_seconds: self.time_seconds,
+            rng: self.rng.clone(),
+            probes: self.probes.clone(),
+            elapsed_time: self.elapsed_time,
+            node_state: self.node_state.clone(),
+            current_node: None,
         }
     }
 
@@ -56,6 +116,173 @@ impl EvalContext {
     pub fn registry(&self) -> &MetaRegistry {
         &self.registry
     }
+
+    /// Current evaluation time in seconds
+    ///
+    /// Uses the fixed clock when one is set (deterministic mode), otherwise
+    /// falls back to the `system.time` registry value. `elapsed_time` is
+    /// added on top.
+    pub fn time_seconds(&self) -> f64 {
+        let base = self.time_seconds.unwrap_or_else(|| {
+            self.registry
+                .get_scoped(&self.scope_id, &DotPath::from("system.time"))
+                .ok()
+                .and_then(|v| v.as_scalar())
+                .unwrap_or(0.0)
+        });
+        base + self.elapsed_time
+    }
+
+    /// Read a state value of the node being evaluated
+    pub fn node_state(&self, key: &str) -> Option<MetaValue> {
+        self.node_state.get(self.current_node.as_ref()?, key)
+    }
+
+    /// Store a state value for the node being evaluated
+    ///
+    /// Does nothing when the context has no current node.
+    pub fn set_node_state(&self, key: &str, value: MetaValue) {
+        if let Some(node_id) = &self.current_node {
+            self.node_state.set(node_id, key, value);
+        }
+    }
+
+    /// Draw the next random value in `[0, 1)` from the context RNG
+    pub fn random(&self) -> f64 {
+        self.rng.next_f64()
+    }
+}
+
+/// Seedable random number source for node evaluation
+///
+/// Uses SplitMix64 so sequences are reproducible from a seed. Clones share
+/// state, which keeps draws unique across nodes within one evaluation pass.
+#[derive(Debug, Clone)]
+pub struct EvalRng {
+    state: Arc<AtomicU64>,
+}
+
+impl EvalRng {
+    /// Create an RNG with a fixed seed
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            state: Arc::new(AtomicU64::new(seed)),
+        }
+    }
+
+    /// Create an RNG seeded from the system clock
+    pub fn from_entropy() -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Self::from_seed(seed)
+    }
+
+    /// Next raw 64-bit value
+    pub fn next_u64(&self) -> u64 {
+        let mut z = self
+            .state
+            .fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed)
+            .wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Next value in `[0, 1)`
+    pub fn next_f64(&self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Named channels streaming probe node values to external subscribers
+///
+/// Clones share the same channels, so a subscriber registered on one clone
+/// receives values sent through any context built from it.
+#[derive(Debug, Clone, Default)]
+pub struct ProbeChannels {
+    senders: Arc<Mutex<HashMap<String, Sender<MetaValue>>>>,
+}
+
+impl ProbeChannels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe to the probe called `name`, replacing any earlier subscriber
+    pub fn register(&self, name: &str) -> Receiver<MetaValue> {
+        let (sender, receiver) = channel();
+        if let Ok(mut senders) = self.senders.lock() {
+            senders.insert(name.to_string(), sender);
+        }
+        receiver
+    }
+
+    pub fn unregister(&self, name: &str) -> bool {
+        self.senders.lock().map(|mut senders| senders.remove(name).is_some()).unwrap_or(false)
+    }
+
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.senders.lock().map(|senders| senders.contains_key(name)).unwrap_or(false)
+    }
+
+    /// Send a value to the probe's subscriber
+    ///
+    /// Returns false when nobody is subscribed. A subscriber that dropped its
+    /// receiver is unregistered.
+    pub fn send(&self, name: &str, value: MetaValue) -> bool {
+        let Ok(mut senders) = self.senders.lock() else {
+            return false;
+        };
+        match senders.get(name).map(|sender| sender.send(value)) {
+            Some(Ok(())) => true,
+            Some(Err(_)) => {
+                senders.remove(name);
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+/// Scratch state that nodes keep between evaluation passes, keyed by node
+///
+/// Clones share the same store, so the owner (editor or runtime) keeps state
+/// alive across the contexts it builds for each pass.
+#[derive(Debug, Clone, Default)]
+pub struct NodeStateStore {
+    states: Arc<Mutex<HashMap<NodeId, HashMap<String, MetaValue>>>>,
+}
+
+impl NodeStateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, node_id: &NodeId, key: &str) -> Option<MetaValue> {
+        let states = self.states.lock().ok()?;
+        states.get(node_id)?.get(key).cloned()
+    }
+
+    pub fn set(&self, node_id: &NodeId, key: &str, value: MetaValue) {
+        if let Ok(mut states) = self.states.lock() {
+            states.entry(node_id.clone()).or_default().insert(key.to_string(), value);
+        }
+    }
+
+    /// Forget everything stored for a node
+    pub fn remove_node(&self, node_id: &NodeId) {
+        if let Ok(mut states) = self.states.lock() {
+            states.remove(node_id);
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut states) = self.states.lock() {
+            states.clear();
+        }
+    }
 }
 
 /// Core trait for evaluatable logic nodes
@@ -108,6 +335,14 @@ pub trait Evaluatable: std::fmt::Debug + Send + Sync {
         Vec::new()
     }
 
+    /// Whether outputs depend on time or randomness rather than inputs alone
+    ///
+    /// Volatile nodes are re-evaluated on every pass even when their inputs
+    /// have not changed.
+    fn is_volatile(&self) -> bool {
+        false
+    }
+
     /// Validate inputs before evaluation (optional)
     fn validate_inputs(&self, inputs: &InputMap) -> Result<(), String> {
         let _ = inputs; // Suppress unused parameter warning
@@ -115,6 +350,33 @@ pub trait Evaluatable: std::fmt::Debug + Send + Sync {
     }
 }
 
+/// Shared nodes evaluate as the node they point to
+impl<T: Evaluatable + ?Sized> Evaluatable for std::sync::Arc<T> {
+    fn evaluate(&self, inputs: &InputMap, ctx: &EvalContext) -> Result<OutputMap, crate::core::types::error::MarcoError> {
+        (**self).evaluate(inputs, ctx)
+    }
+
+    fn node_type(&self) -> &'static str {
+        (**self).node_type()
+    }
+
+    fn input_specs(&self) -> Vec<InputSpec> {
+        (**self).input_specs()
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        (**self).output_specs()
+    }
+
+    fn is_volatile(&self) -> bool {
+        (**self).is_volatile()
+    }
+
+    fn validate_inputs(&self, inputs: &InputMap) -> Result<(), String> {
+        (**self).validate_inputs(inputs)
+    }
+}
+
 /// Specification for a node input
 #[derive(Debug, Clone)]
 pub struct InputSpec {
@@ -128,6 +390,8 @@ pub struct InputSpec {
     pub default_value: Option<crate::core::types::MetaValue>,
     /// Human-readable description
     pub description: Option<String>,
+    /// Socket label shown in the editor; derived from the name when unset
+    pub label: Option<String>,
 }
 
 impl InputSpec {
@@ -139,6 +403,7 @@ impl InputSpec {
             required: true,
             default_value: None,
             description: None,
+            label: None,
         }
     }
 
@@ -150,6 +415,7 @@ impl InputSpec {
             required: false,
             default_value: Some(default),
             description: None,
+            label: None,
         }
     }
 
@@ -158,6 +424,12 @@ impl InputSpec {
         self.description = Some(description.to_string());
         self
     }
+
+    /// Override the editor label for this spec
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
 }
 
 /// Specification for a node output
@@ -169,6 +441,8 @@ pub struct OutputSpec {
     pub output_type: String,
     /// Human-readable description
     pub description: Option<String>,
+    /// Socket label shown in the editor; derived from the name when unset
+    pub label: Option<String>,
 }
 
 impl OutputSpec {
@@ -178,6 +452,7 @@ impl OutputSpec {
             name: name.to_string(),
             output_type: output_type.to_string(),
             description: None,
+            label: None,
         }
     }
 
@@ -186,6 +461,12 @@ impl OutputSpec {
         self.description = Some(description.to_string());
         self
     }
+
+    /// Override the editor label for this spec
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
 }
 
 #[cfg(test)]
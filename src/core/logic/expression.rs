@@ -1,5 +1,7 @@
+use crate::core::types::error::MarcoError;
 use crate::core::types::{DotPath, MetaValue};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Binary operations for expressions
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -10,6 +12,8 @@ pub enum BinaryOp {
     Multiply,
     Divide,
     Modulo,
+    /// `a ^ b`, right-associative
+    Power,
     
     // Comparison
     Equal,
@@ -86,7 +90,7 @@ pub enum BindingExpr {
         else_expr: Box<BindingExpr>,
     },
     
-    /// Function call (for future extensibility)
+    /// Function call, e.g. `sqrt(x)`; see [`call_scalar_function`]
     Call {
         function: String,
         args: Vec<BindingExpr>,
@@ -229,6 +233,256 @@ impl BindingExpr {
     }
 }
 
+impl BindingExpr {
+    /// Parse an arithmetic expression such as `x * 2 + y`
+    ///
+    /// Supports numbers, `+ - * / % ^`, parentheses, unary minus and
+    /// function calls such as `pow(x, 2)` with the usual precedence; `^`
+    /// binds tighter than unary minus, so `-x^2` is `-(x^2)`. Other
+    /// identifiers (letters, digits, `_` and `.`) become `Ref` expressions.
+    /// Errors report the character position of the problem.
+    pub fn parse(source: &str) -> Result<Self, MarcoError> {
+        let mut parser = ExprParser { chars: source.chars().collect(), pos: 0 };
+        let expr = parser.parse_sum()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(expr),
+            Some(c) => Err(parser.error(&format!("unexpected '{}'", c))),
+        }
+    }
+
+    /// Evaluate an arithmetic expression tree to a number
+    ///
+    /// `Ref` paths are looked up in `variables` by their dotted name.
+    /// Division or modulo by zero and non-arithmetic operators are errors.
+    pub fn evaluate_scalar(&self, variables: &HashMap<String, f64>) -> Result<f64, MarcoError> {
+        match self {
+            BindingExpr::Const(value) => value.as_scalar()
+                .ok_or_else(|| MarcoError::NodeEval(format!("Not a number: {:?}", value))),
+            BindingExpr::Ref(path) => variables.get(&path.to_string())
+                .copied()
+                .ok_or_else(|| MarcoError::NodeEval(format!("unknown variable: {}", path))),
+            BindingExpr::Unary { op: UnaryOp::Negate, expr } => Ok(-expr.evaluate_scalar(variables)?),
+            BindingExpr::Binary { op, left, right } => {
+                let a = left.evaluate_scalar(variables)?;
+                let b = right.evaluate_scalar(variables)?;
+                match op {
+                    BinaryOp::Add => Ok(a + b),
+                    BinaryOp::Subtract => Ok(a - b),
+                    BinaryOp::Multiply => Ok(a * b),
+                    BinaryOp::Divide | BinaryOp::Modulo if b == 0.0 => {
+                        Err(MarcoError::NodeEval("division by zero".to_string()))
+                    }
+                    BinaryOp::Divide => Ok(a / b),
+                    BinaryOp::Modulo => Ok(a % b),
+                    BinaryOp::Power => Ok(a.powf(b)),
+                    other => Err(MarcoError::NodeEval(format!("{:?} is not an arithmetic operator", other))),
+                }
+            }
+            BindingExpr::Call { function, args } => {
+                let values = args.iter()
+                    .map(|arg| arg.evaluate_scalar(variables))
+                    .collect::<Result<Vec<f64>, MarcoError>>()?;
+                call_scalar_function(function, &values)
+            }
+            other => Err(MarcoError::NodeEval(format!("{} expressions are not arithmetic", other.expr_type()))),
+        }
+    }
+}
+
+/// Apply a built-in numeric function by name
+///
+/// Available: `pow`, `sqrt`, `sin`, `cos`, `tan`, `abs`, `floor`, `ceil`,
+/// `min` and `max` (one or more arguments), `clamp(x, lo, hi)`, `avg`,
+/// `distance(x, y)` from the origin and `lerp(a, b, t)`.
+pub fn call_scalar_function(name: &str, args: &[f64]) -> Result<f64, MarcoError> {
+    let arity = |expected: usize| -> Result<(), MarcoError> {
+        if args.len() == expected {
+            Ok(())
+        } else {
+            Err(MarcoError::NodeEval(format!("{} expects {} arguments, got {}", name, expected, args.len())))
+        }
+    };
+    let at_least_one = || -> Result<(), MarcoError> {
+        if args.is_empty() {
+            Err(MarcoError::NodeEval(format!("{} expects at least one argument", name)))
+        } else {
+            Ok(())
+        }
+    };
+
+    match name {
+        "pow" => arity(2).map(|_| args[0].powf(args[1])),
+        "sqrt" => arity(1).map(|_| args[0].sqrt()),
+        "sin" => arity(1).map(|_| args[0].sin()),
+        "cos" => arity(1).map(|_| args[0].cos()),
+        "tan" => arity(1).map(|_| args[0].tan()),
+        "abs" => arity(1).map(|_| args[0].abs()),
+        "floor" => arity(1).map(|_| args[0].floor()),
+        "ceil" => arity(1).map(|_| args[0].ceil()),
+        "min" => at_least_one().map(|_| args.iter().copied().fold(f64::INFINITY, f64::min)),
+        "max" => at_least_one().map(|_| args.iter().copied().fold(f64::NEG_INFINITY, f64::max)),
+        "avg" => at_least_one().map(|_| args.iter().sum::<f64>() / args.len() as f64),
+        "clamp" => arity(3).map(|_| args[0].max(args[1]).min(args[2])),
+        "distance" => arity(2).map(|_| args[0].hypot(args[1])),
+        "lerp" => arity(3).map(|_| args[0] + (args[1] - args[0]) * args[2].clamp(0.0, 1.0)),
+        _ => Err(MarcoError::NodeEval(format!("unknown function: {}", name))),
+    }
+}
+
+/// Recursive-descent parser behind [`BindingExpr::parse`]
+///
+/// ```text
+/// sum     := product (('+' | '-') product)*
+/// product := unary (('*' | '/' | '%') unary)*
+/// unary   := '-' unary | power
+/// power   := primary ('^' unary)?
+/// primary := number | identifier | identifier '(' (sum (',' sum)*)? ')' | '(' sum ')'
+/// ```
+struct ExprParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl ExprParser {
+    fn parse_sum(&mut self) -> Result<BindingExpr, MarcoError> {
+        let mut left = self.parse_product()?;
+        loop {
+            let op = match self.peek_token() {
+                Some('+') => BinaryOp::Add,
+                Some('-') => BinaryOp::Subtract,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            left = BindingExpr::binary(op, left, self.parse_product()?);
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<BindingExpr, MarcoError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let op = match self.peek_token() {
+                Some('*') => BinaryOp::Multiply,
+                Some('/') => BinaryOp::Divide,
+                Some('%') => BinaryOp::Modulo,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            left = BindingExpr::binary(op, left, self.parse_unary()?);
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<BindingExpr, MarcoError> {
+        if self.peek_token() == Some('-') {
+            self.pos += 1;
+            return Ok(BindingExpr::negate(self.parse_unary()?));
+        }
+        self.parse_power()
+    }
+
+    fn parse_power(&mut self) -> Result<BindingExpr, MarcoError> {
+        let base = self.parse_primary()?;
+        if self.peek_token() != Some('^') {
+            return Ok(base);
+        }
+        self.pos += 1;
+        Ok(BindingExpr::binary(BinaryOp::Power, base, self.parse_unary()?))
+    }
+
+    fn parse_call_args(&mut self) -> Result<Vec<BindingExpr>, MarcoError> {
+        let mut args = Vec::new();
+        if self.peek_token() == Some(')') {
+            self.pos += 1;
+            return Ok(args);
+        }
+        loop {
+            args.push(self.parse_sum()?);
+            match self.peek_token() {
+                Some(',') => self.pos += 1,
+                Some(')') => {
+                    self.pos += 1;
+                    return Ok(args);
+                }
+                _ => return Err(self.error("expected ',' or ')'")),
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<BindingExpr, MarcoError> {
+        match self.peek_token() {
+            Some('(') => {
+                self.pos += 1;
+                let expr = self.parse_sum()?;
+                if self.peek_token() != Some(')') {
+                    return Err(self.error("expected ')'"));
+                }
+                self.pos += 1;
+                Ok(expr)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.parse_number(),
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+                    self.pos += 1;
+                }
+                let name: String = self.chars[start..self.pos].iter().collect();
+                if self.peek_token() == Some('(') {
+                    self.pos += 1;
+                    return Ok(BindingExpr::call(&name, self.parse_call_args()?));
+                }
+                Ok(BindingExpr::reference(name.as_str()))
+            }
+            Some(c) => Err(self.error(&format!("unexpected '{}'", c))),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<BindingExpr, MarcoError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+            self.pos += 1;
+        }
+        // Scientific notation: 1e3, 2.5E-4
+        if matches!(self.peek(), Some('e' | 'E')) {
+            let mark = self.pos;
+            self.pos += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.pos += 1;
+            }
+            if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.pos = mark;
+            }
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.pos += 1;
+            }
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse::<f64>()
+            .map(BindingExpr::constant)
+            .map_err(|_| MarcoError::NodeEval(format!("invalid number '{}' at position {}", text, start)))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    /// Next non-whitespace character, leaving the position on it
+    fn peek_token(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.peek()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self, message: &str) -> MarcoError {
+        MarcoError::NodeEval(format!("{} at position {}", message, self.pos))
+    }
+}
+
 // Convenient From implementations
 impl From<MetaValue> for BindingExpr {
     fn from(value: MetaValue) -> Self {
@@ -320,6 +574,54 @@ mod tests {
         assert_eq!(expr.expr_type(), "if_else");
     }
 
+    #[test]
+    fn test_parse_respects_precedence_and_variables() {
+        let vars = HashMap::from([("x".to_string(), 4.0), ("y".to_string(), 1.5)]);
+        let eval = |src: &str| BindingExpr::parse(src).unwrap().evaluate_scalar(&vars).unwrap();
+
+        assert_eq!(eval("x * 2 + y"), 9.5);
+        assert_eq!(eval("2 + 3 * 4"), 14.0);
+        assert_eq!(eval("(2 + 3) * 4"), 20.0);
+        assert_eq!(eval("-x - -2"), -2.0);
+        assert_eq!(eval("10 % 4 / 2"), 1.0);
+        assert_eq!(eval("1.5e2"), 150.0);
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        let message = |src: &str| match BindingExpr::parse(src) {
+            Err(MarcoError::NodeEval(message)) => message,
+            other => panic!("expected a parse error for {:?}, got {:?}", src, other),
+        };
+        assert_eq!(message("2 + + 3"), "unexpected '+' at position 4");
+        assert_eq!(message("(1 + 2"), "expected ')' at position 6");
+        assert_eq!(message("1 2"), "unexpected '2' at position 2");
+
+        let unknown = BindingExpr::parse("z + 1").unwrap().evaluate_scalar(&HashMap::new());
+        assert!(matches!(unknown, Err(MarcoError::NodeEval(m)) if m == "unknown variable: z"));
+        assert!(BindingExpr::parse("1 / (2 - 2)").unwrap().evaluate_scalar(&HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_power_and_function_calls() {
+        let vars = HashMap::from([("x".to_string(), 3.0), ("y".to_string(), 4.0)]);
+        let eval = |src: &str| BindingExpr::parse(src).unwrap().evaluate_scalar(&vars).unwrap();
+
+        assert_eq!(eval("sqrt(pow(x,2)+pow(y,2))"), 5.0);
+        assert_eq!(eval("sqrt(x^2 + y^2)"), 5.0);
+        assert_eq!(eval("2 ^ 3 ^ 2"), 512.0);
+        assert_eq!(eval("-x^2"), -9.0);
+        assert_eq!(eval("2 ^ -1"), 0.5);
+        assert_eq!(eval("max(min(x, y), 1, abs(-2))"), 3.0);
+        assert_eq!(eval("floor(2.7) + ceil(2.1)"), 5.0);
+        assert_eq!(eval("sin(0) + cos(0) + tan(0)"), 1.0);
+
+        let unknown = BindingExpr::parse("foo(1)").unwrap().evaluate_scalar(&vars);
+        assert!(matches!(unknown, Err(MarcoError::NodeEval(m)) if m == "unknown function: foo"));
+        assert!(BindingExpr::parse("pow(1)").unwrap().evaluate_scalar(&vars).is_err());
+        assert!(BindingExpr::parse("pow(1 2)").is_err());
+    }
+
     #[test]
     fn test_from_conversions() {
         let const_f64 = BindingExpr::from(3.14);
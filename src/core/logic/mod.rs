@@ -7,12 +7,16 @@ mod binding;
 mod expression;
 mod evaluatable;
 mod context;
+mod deterministic;
 pub mod nodes;
 pub mod node_registry;
+pub mod safe_math;
+pub mod number_parse;
 
 pub use binding::{NodeInputBinding, InputMap, OutputMap};
-pub use expression::{BindingExpr, BinaryOp, UnaryOp};
-pub use evaluatable::{Evaluatable, EvalContext, InputSpec, OutputSpec};
+pub use expression::{BindingExpr, BinaryOp, UnaryOp, call_scalar_function};
+pub use evaluatable::{Evaluatable, EvalContext, EvalRng, NodeStateStore, ProbeChannels, InputSpec, OutputSpec};
+pub use deterministic::DeterministicContext;
 pub use context::EvaluationContext;
 pub use nodes::*;
 pub use node_registry::*;
@@ -8,16 +8,23 @@ use crate::core::logic::nodes::{
     math_node::MathNode,
     string_node::StringNode,
     timer_node::TimerNode,
+    throttle_node::ThrottleNode,
+    probe_node::ProbeNode,
+    random_node::RandomNode,
+    sample_image_node::SampleImageNode,
     calculator_node::CalculatorNode,
     database_node::DatabaseNode,
     validation_node::ValidationNode,
     api_node::ApiNode,
     data_transform_node::DataTransformNode,
+    filesystem_node::FilesystemNode,
 };
 // use crate::ui::nodes::{button_node::ButtonNode, slider_node::SliderNode};
-use crate::core::logic::Evaluatable;
+use crate::core::logic::{Evaluatable, InputSpec, OutputSpec};
 use crate::core::types::error::MarcoError;
+use crate::core::types::{ColorRGBA, MetaValue};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub enum NodeType {
@@ -43,9 +50,61 @@ pub enum UINodeType {
     Container,
 }
 
+/// Title and sockets of a node type, as shown in the editor
+#[derive(Debug, Clone)]
+pub struct NodeSpecification {
+    pub node_type: String,
+    pub title: String,
+    pub inputs: Vec<InputSpec>,
+    pub outputs: Vec<OutputSpec>,
+}
+
+impl NodeSpecification {
+    pub fn new(node_type: &str, title: &str) -> Self {
+        Self {
+            node_type: node_type.to_string(),
+            title: title.to_string(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Specification declared by a node's `input_specs` and `output_specs`
+    pub fn from_node(node_type: &str, title: &str, node: &dyn Evaluatable) -> Self {
+        Self {
+            inputs: node.input_specs(),
+            outputs: node.output_specs(),
+            ..Self::new(node_type, title)
+        }
+    }
+
+    pub fn with_input(mut self, input: InputSpec) -> Self {
+        self.inputs.push(input);
+        self
+    }
+
+    pub fn with_output(mut self, output: OutputSpec) -> Self {
+        self.outputs.push(output);
+        self
+    }
+}
+
+/// Creates a fresh instance of a runtime-registered node type
+pub type NodeFactory = Arc<dyn Fn() -> Arc<dyn Evaluatable> + Send + Sync>;
+
+/// Node types known to the editor and evaluator
+///
+/// Custom nodes are added at runtime with [`NodeRegistry::register`] or
+/// [`NodeRegistry::register_with_spec`]. Registration takes `&mut self`, so it
+/// happens before the owning `VisualNodeEditor` is shared; factories must be
+/// `Send + Sync` because the editor may be moved to or read from other
+/// threads, and every instance they return is shared through an `Arc`, so
+/// nodes needing per-instance state should keep it behind a lock.
 pub struct NodeRegistry {
     logic_nodes: HashMap<String, Box<dyn Evaluatable>>,
     ui_nodes: HashMap<String, Box<dyn Evaluatable>>,
+    factories: HashMap<String, NodeFactory>,
+    specifications: HashMap<String, NodeSpecification>,
 }
 
 impl std::fmt::Debug for NodeRegistry {
@@ -53,6 +112,8 @@ impl std::fmt::Debug for NodeRegistry {
         f.debug_struct("NodeRegistry")
             .field("logic_nodes", &format!("{} logic nodes", self.logic_nodes.len()))
             .field("ui_nodes", &format!("{} ui nodes", self.ui_nodes.len()))
+            .field("factories", &format!("{} registered factories", self.factories.len()))
+            .field("specifications", &format!("{} specifications", self.specifications.len()))
             .finish()
     }
 }
@@ -62,38 +123,137 @@ impl NodeRegistry {
         let mut registry = Self {
             logic_nodes: HashMap::new(),
             ui_nodes: HashMap::new(),
+            factories: HashMap::new(),
+            specifications: HashMap::new(),
         };
         registry.register_builtin_nodes();
+        registry.register_editor_only_specifications();
         registry
     }
 
     fn register_builtin_nodes(&mut self) {
         // Basic logic nodes
-        self.logic_nodes.insert("add".to_string(), Box::new(AddNode));
-        self.logic_nodes.insert("multiply".to_string(), Box::new(MultiplyNode));
-        self.logic_nodes.insert("branch".to_string(), Box::new(BranchNode));
-        self.logic_nodes.insert("compare".to_string(), Box::new(CompareNode));
-        self.logic_nodes.insert("clamp".to_string(), Box::new(ClampNode));
+        self.register_logic("add", "Add (Legacy)", Box::new(AddNode));
+        self.register_logic("multiply", "Multiply", Box::new(MultiplyNode));
+        self.register_logic("branch", "Branch", Box::new(BranchNode));
+        self.register_logic("compare", "Compare", Box::new(CompareNode));
+        self.register_logic("clamp", "Clamp", Box::new(ClampNode));
         
         // Comprehensive utility nodes
-        self.logic_nodes.insert("math".to_string(), Box::new(MathNode));
-        self.logic_nodes.insert("string".to_string(), Box::new(StringNode));
-        self.logic_nodes.insert("timer".to_string(), Box::new(TimerNode));
+        self.register_logic("math", "Math Operations", Box::new(MathNode));
+        self.register_logic("string", "String Operations", Box::new(StringNode));
+        self.register_logic("timer", "Timer", Box::new(TimerNode));
+        self.register_logic("throttle", "Throttle", Box::new(ThrottleNode));
+        self.register_logic("probe", "Probe", Box::new(ProbeNode));
+        self.register_logic("random", "Random", Box::new(RandomNode));
+        self.register_logic("sample_image", "Sample Image", Box::new(SampleImageNode));
         
         // Hybrid/composite nodes
-        self.logic_nodes.insert("calculator".to_string(), Box::new(CalculatorNode));
-        self.logic_nodes.insert("database".to_string(), Box::new(DatabaseNode));
-        self.logic_nodes.insert("validation".to_string(), Box::new(ValidationNode));
-        self.logic_nodes.insert("api".to_string(), Box::new(ApiNode));
-        self.logic_nodes.insert("data_transform".to_string(), Box::new(DataTransformNode));
+        self.register_logic("calculator", "Expression Calculator", Box::new(CalculatorNode));
+        self.register_logic("database", "Database Operations", Box::new(DatabaseNode));
+        self.register_logic("validation", "Data Validation", Box::new(ValidationNode));
+        self.register_logic("api", "API Request", Box::new(ApiNode));
+        self.register_logic("data_transform", "Data Transform", Box::new(DataTransformNode));
+        self.register_logic("filesystem", "File Operations", Box::new(Self::default_filesystem()));
         
         // Register UI nodes
         // self.ui_nodes.insert("button".to_string(), Box::new(ButtonNode));
         // self.ui_nodes.insert("slider".to_string(), Box::new(SliderNode));
     }
 
+    /// Builtin file node, jailed to the working directory; register a
+    /// `FilesystemNode` with another root to change it
+    fn default_filesystem() -> FilesystemNode {
+        FilesystemNode::new(std::env::current_dir().unwrap_or_else(|_| ".".into()))
+    }
+
+    fn register_logic(&mut self, node_type: &str, title: &str, node: Box<dyn Evaluatable>) {
+        self.specifications.insert(node_type.to_string(), NodeSpecification::from_node(node_type, title, node.as_ref()));
+        self.logic_nodes.insert(node_type.to_string(), node);
+    }
+
+    /// Node types the editor can place that have no evaluator yet
+    fn register_editor_only_specifications(&mut self) {
+        let string = |v: &str| MetaValue::String(v.to_string());
+        let color = |r, g, b| MetaValue::Color(ColorRGBA { r, g, b, a: 1.0 });
+
+        self.register_specification(NodeSpecification::new("audio", "Audio Synthesis")
+            .with_input(InputSpec::optional("waveform", "string", string("sine")))
+            .with_input(InputSpec::optional("frequency", "scalar", MetaValue::Scalar(440.0)))
+            .with_input(InputSpec::optional("amplitude", "scalar", MetaValue::Scalar(0.5)))
+            .with_input(InputSpec::optional("duration", "scalar", MetaValue::Scalar(1.0)))
+            .with_output(OutputSpec::new("audio_data", "list"))
+            .with_output(OutputSpec::new("sample_rate", "scalar")));
+
+        self.register_specification(NodeSpecification::new("animation", "Animation Controller")
+            .with_input(InputSpec::optional("easing_type", "string", string("ease_in_out")))
+            .with_input(InputSpec::optional("progress", "scalar", MetaValue::Scalar(0.5)))
+            .with_input(InputSpec::optional("start_value", "scalar", MetaValue::Scalar(0.0)))
+            .with_input(InputSpec::optional("end_value", "scalar", MetaValue::Scalar(100.0)))
+            .with_output(OutputSpec::new("value", "scalar"))
+            .with_output(OutputSpec::new("eased_progress", "scalar")));
+
+        self.register_specification(NodeSpecification::new("network", "Network Utilities")
+            .with_input(InputSpec::optional("operation", "string", string("ping")))
+            .with_input(InputSpec::optional("target", "string", string("google.com")))
+            .with_output(OutputSpec::new("latency", "scalar"))
+            .with_output(OutputSpec::new("bandwidth", "scalar"))
+            .with_output(OutputSpec::new("connected", "bool")));
+
+        self.register_specification(NodeSpecification::new("color", "Color Processing")
+            .with_input(InputSpec::optional("operation", "string", string("blend")))
+            .with_input(InputSpec::optional("color1", "color", color(1.0, 0.0, 0.0)).with_label("Color 1"))
+            .with_input(InputSpec::optional("color2", "color", color(0.0, 0.0, 1.0)).with_label("Color 2"))
+            .with_input(InputSpec::optional("factor", "scalar", MetaValue::Scalar(0.5)))
+            .with_output(OutputSpec::new("result_color", "color"))
+            .with_output(OutputSpec::new("brightness", "scalar"))
+            .with_output(OutputSpec::new("contrast", "scalar")));
+
+        self.register_specification(NodeSpecification::new("button", "Button")
+            .with_input(InputSpec::optional("label", "string", string("Button")))
+            .with_input(InputSpec::optional("enabled", "bool", MetaValue::Bool(true)))
+            .with_output(OutputSpec::new("clicked", "bool")));
+
+        self.register_specification(NodeSpecification::new("slider", "Slider")
+            .with_input(InputSpec::optional("min", "scalar", MetaValue::Scalar(0.0)))
+            .with_input(InputSpec::optional("max", "scalar", MetaValue::Scalar(100.0)))
+            .with_input(InputSpec::optional("value", "scalar", MetaValue::Scalar(50.0)))
+            .with_output(OutputSpec::new("value", "scalar")));
+    }
+
+    /// Register a custom node type, replacing any earlier registration
+    ///
+    /// Registered factories take precedence over builtin nodes of the same
+    /// name. The editor specification is taken from the node's
+    /// `input_specs` and `output_specs`, titled with `type_name`.
+    pub fn register(&mut self, type_name: &str, factory: NodeFactory) {
+        let specification = NodeSpecification::from_node(type_name, type_name, factory().as_ref());
+        self.register_with_spec(type_name, factory, specification);
+    }
+
+    /// Register a custom node type with an explicit editor specification
+    pub fn register_with_spec(&mut self, type_name: &str, factory: NodeFactory, specification: NodeSpecification) {
+        self.factories.insert(type_name.to_string(), factory);
+        self.specifications.insert(type_name.to_string(), NodeSpecification {
+            node_type: type_name.to_string(),
+            ..specification
+        });
+    }
+
+    /// Register or replace the editor specification of a node type
+    pub fn register_specification(&mut self, specification: NodeSpecification) {
+        self.specifications.insert(specification.node_type.clone(), specification);
+    }
+
+    /// Title and sockets of `node_type`, if it is known to the registry
+    pub fn specification(&self, node_type: &str) -> Option<NodeSpecification> {
+        self.specifications.get(node_type).cloned()
+    }
+
     pub fn create_node(&self, node_type: &str) -> Result<Box<dyn Evaluatable>, MarcoError> {
-        if let Some(_node) = self.logic_nodes.get(node_type) {
+        if let Some(factory) = self.factories.get(node_type) {
+            Ok(Box::new(factory()))
+        } else if let Some(_node) = self.logic_nodes.get(node_type) {
             // Clone pattern for boxed traits (simplified for now)
             match node_type {
                 // Basic logic nodes
@@ -107,6 +267,10 @@ impl NodeRegistry {
                 "math" => Ok(Box::new(MathNode)),
                 "string" => Ok(Box::new(StringNode)),
                 "timer" => Ok(Box::new(TimerNode)),
+                "throttle" => Ok(Box::new(ThrottleNode)),
+                "probe" => Ok(Box::new(ProbeNode)),
+                "random" => Ok(Box::new(RandomNode)),
+                "sample_image" => Ok(Box::new(SampleImageNode)),
                 
                 // Hybrid nodes
                 "calculator" => Ok(Box::new(CalculatorNode)),
@@ -114,6 +278,7 @@ impl NodeRegistry {
                 "validation" => Ok(Box::new(ValidationNode)),
                 "api" => Ok(Box::new(ApiNode)),
                 "data_transform" => Ok(Box::new(DataTransformNode)),
+                "filesystem" => Ok(Box::new(Self::default_filesystem())),
                 
                 _ => Err(MarcoError::NodeEval(format!("Unknown logic node: {}", node_type))),
             }
@@ -132,6 +297,39 @@ impl NodeRegistry {
         let mut nodes = Vec::new();
         nodes.extend(self.logic_nodes.keys().cloned());
         nodes.extend(self.ui_nodes.keys().cloned());
+        nodes.extend(self.factories.keys().filter(|name| !self.logic_nodes.contains_key(*name)).cloned());
         nodes
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::logic::{EvalContext, InputMap};
+    use crate::graph::runtime::NodeId;
+
+    #[test]
+    fn test_builtin_nodes_emit_every_declared_output() {
+        let registry = NodeRegistry::new();
+        let ctx = EvalContext::default().for_node(NodeId::new());
+
+        for node_type in registry.logic_nodes.keys() {
+            let node = registry.create_node(node_type).unwrap();
+            let specification = registry.specification(node_type).unwrap();
+            let inputs: InputMap = specification.inputs.iter()
+                .filter_map(|input| Some((input.name.clone(), input.default_value.clone()?)))
+                .collect();
+
+            // Nodes may refuse to run without the inputs they mark required
+            let needs_input = specification.inputs.iter().any(|input| input.required && input.default_value.is_none());
+            let outputs = match node.evaluate(&inputs, &ctx) {
+                Ok(outputs) => outputs,
+                Err(_) if needs_input => continue,
+                Err(e) => panic!("{} failed with its spec defaults: {}", node_type, e),
+            };
+            for output in &specification.outputs {
+                assert!(outputs.contains_key(&output.name), "{} declares '{}' but does not emit it", node_type, output.name);
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext};
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
 use crate::core::types::MetaValue;
 use crate::core::types::error::MarcoError;
 use std::collections::HashMap;
@@ -10,59 +10,24 @@ impl Evaluatable for ApiNode {
     fn evaluate(&self, inputs: &InputMap, _ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
         let url = inputs.get("url").and_then(|v| v.as_string()).unwrap_or_default();
         let method = inputs.get("method").and_then(|v| v.as_string()).unwrap_or("GET".to_string());
-        let _headers = inputs.get("headers").cloned().unwrap_or(MetaValue::Object(HashMap::new()));
-        let _body = inputs.get("body").cloned().unwrap_or(MetaValue::String("".to_string()));
+        let headers = inputs.get("headers").cloned().unwrap_or(MetaValue::Object(HashMap::new()));
+        let body = inputs.get("body").cloned().unwrap_or(MetaValue::String("".to_string()));
         let timeout = inputs.get("timeout").and_then(|v| v.as_scalar()).unwrap_or(30.0);
         
         let mut result = HashMap::new();
         
-        // Simulate API response (in practice, this would make real HTTP requests)
-        let mock_success = !url.is_empty() && url.starts_with("http");
+        #[cfg(feature = "http")]
+        let (success, status_code, response, error) = http::send(&method, &url, &headers, &body, timeout);
+        #[cfg(not(feature = "http"))]
+        let (success, status_code, response, error) = {
+            let _ = (&headers, &body);
+            simulate(&url)
+        };
         
-        if mock_success {
-            // Simulate successful response
-            let mock_response = match url.as_str() {
-                url if url.contains("users") => {
-                    MetaValue::List(vec![
-                        MetaValue::Object({
-                            let mut user = HashMap::new();
-                            user.insert("id".to_string(), MetaValue::Scalar(1.0));
-                            user.insert("name".to_string(), MetaValue::String("John Doe".to_string()));
-                            user.insert("email".to_string(), MetaValue::String("john@example.com".to_string()));
-                            user
-                        })
-                    ])
-                },
-                url if url.contains("weather") => {
-                    MetaValue::Object({
-                        let mut weather = HashMap::new();
-                        weather.insert("temperature".to_string(), MetaValue::Scalar(22.5));
-                        weather.insert("humidity".to_string(), MetaValue::Scalar(65.0));
-                        weather.insert("condition".to_string(), MetaValue::String("Sunny".to_string()));
-                        weather
-                    })
-                },
-                _ => {
-                    MetaValue::Object({
-                        let mut generic = HashMap::new();
-                        generic.insert("message".to_string(), MetaValue::String("Success".to_string()));
-                        generic.insert("timestamp".to_string(), MetaValue::Scalar(1642678800.0));
-                        generic
-                    })
-                }
-            };
-            
-            result.insert("success".to_string(), MetaValue::Bool(true));
-            result.insert("status_code".to_string(), MetaValue::Scalar(200.0));
-            result.insert("response".to_string(), mock_response);
-            result.insert("error".to_string(), MetaValue::String("".to_string()));
-        } else {
-            // Simulate error response
-            result.insert("success".to_string(), MetaValue::Bool(false));
-            result.insert("status_code".to_string(), MetaValue::Scalar(400.0));
-            result.insert("response".to_string(), MetaValue::String("".to_string()));
-            result.insert("error".to_string(), MetaValue::String("Invalid URL or request failed".to_string()));
-        }
+        result.insert("success".to_string(), MetaValue::Bool(success));
+        result.insert("status_code".to_string(), MetaValue::Scalar(status_code));
+        result.insert("response".to_string(), response);
+        result.insert("error".to_string(), MetaValue::String(error));
         
         // Request information
         result.insert("url".to_string(), MetaValue::String(url));
@@ -81,13 +46,185 @@ impl Evaluatable for ApiNode {
         }
         
         // Connection info
-        result.insert("is_online".to_string(), MetaValue::Bool(mock_success));
+        result.insert("is_online".to_string(), MetaValue::Bool(success));
         result.insert("retry_count".to_string(), MetaValue::Scalar(0.0));
         
         Ok(result)
     }
 
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("method", "string", MetaValue::String("GET".to_string())),
+            InputSpec::optional("url", "string", MetaValue::String("https://api.example.com/data".to_string())).with_label("URL"),
+            InputSpec::required("headers", "any"),
+            InputSpec::required("body", "any"),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::new("response", "any"),
+            OutputSpec::new("status_code", "scalar"),
+            OutputSpec::new("success", "bool"),
+        ]
+    }
+
     fn node_type(&self) -> &'static str {
         "api"
     }
 }
+
+/// Canned responses used when the `http` feature is off
+///
+/// Returns `(success, status_code, response, error)`.
+#[cfg(not(feature = "http"))]
+fn simulate(url: &str) -> (bool, f64, MetaValue, String) {
+    if url.is_empty() || !url.starts_with("http") {
+        return (false, 400.0, MetaValue::new_object(), "Invalid URL or request failed".to_string());
+    }
+
+    let response = match url {
+        url if url.contains("users") => {
+            MetaValue::Object({
+                let mut user = HashMap::new();
+                user.insert("id".to_string(), MetaValue::Scalar(1.0));
+                user.insert("name".to_string(), MetaValue::String("John Doe".to_string()));
+                user.insert("email".to_string(), MetaValue::String("john@example.com".to_string()));
+                
+                let mut body = HashMap::new();
+                body.insert("users".to_string(), MetaValue::List(vec![MetaValue::Object(user)]));
+                body
+            })
+        },
+        url if url.contains("weather") => {
+            MetaValue::Object({
+                let mut weather = HashMap::new();
+                weather.insert("temperature".to_string(), MetaValue::Scalar(22.5));
+                weather.insert("humidity".to_string(), MetaValue::Scalar(65.0));
+                weather.insert("condition".to_string(), MetaValue::String("Sunny".to_string()));
+                weather
+            })
+        },
+        _ => {
+            MetaValue::Object({
+                let mut generic = HashMap::new();
+                generic.insert("message".to_string(), MetaValue::String("Success".to_string()));
+                generic.insert("timestamp".to_string(), MetaValue::Scalar(1642678800.0));
+                generic
+            })
+        }
+    };
+    (true, 200.0, response, String::new())
+}
+
+/// Blocking HTTP transport used when the `http` feature is on
+#[cfg(feature = "http")]
+mod http {
+    use crate::core::types::MetaValue;
+    use std::time::Duration;
+
+    /// Perform the request; returns `(success, status_code, response, error)`
+    ///
+    /// Network failures report `success = false`, status code 0 and the
+    /// error text as the response string.
+    pub(super) fn send(method: &str, url: &str, headers: &MetaValue, body: &MetaValue, timeout: f64) -> (bool, f64, MetaValue, String) {
+        match request(method, url, headers, body, timeout) {
+            Ok((status, response)) => {
+                let success = status.is_success();
+                let error = if success { String::new() } else { status.to_string() };
+                (success, status.as_u16() as f64, response, error)
+            }
+            Err(e) => {
+                let message = e.to_string();
+                (false, 0.0, MetaValue::String(message.clone()), message)
+            }
+        }
+    }
+
+    fn request(method: &str, url: &str, headers: &MetaValue, body: &MetaValue, timeout: f64) -> Result<(reqwest::StatusCode, MetaValue), reqwest::Error> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs_f64(timeout.max(0.0)))
+            .build()?;
+        let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes()).unwrap_or(reqwest::Method::GET);
+        let sends_body = !matches!(method, reqwest::Method::GET | reqwest::Method::HEAD);
+        let mut request = client.request(method, url);
+
+        if let MetaValue::Object(fields) = headers {
+            for (name, value) in fields {
+                if let Some(value) = value.as_string() {
+                    request = request.header(name.as_str(), value);
+                }
+            }
+        }
+        if sends_body {
+            request = match body {
+                MetaValue::String(text) if text.is_empty() => request,
+                MetaValue::String(text) => request.body(text.clone()),
+                other => request.json(&meta_to_json(other)),
+            };
+        }
+
+        let response = request.send()?;
+        let status = response.status();
+        let text = response.text()?;
+        // A `null` body reads as an empty object; other text stays a string
+        let value = match serde_json::from_str(&text) {
+            Ok(json) => MetaValue::from_json(&json).unwrap_or_else(MetaValue::new_object),
+            Err(_) => MetaValue::String(text),
+        };
+        Ok((status, value))
+    }
+
+    fn meta_to_json(value: &MetaValue) -> serde_json::Value {
+        match value {
+            MetaValue::Scalar(n) => serde_json::json!(n),
+            MetaValue::Bool(b) => serde_json::Value::Bool(*b),
+            MetaValue::String(s) => serde_json::Value::String(s.clone()),
+            MetaValue::List(items) => serde_json::Value::Array(items.iter().map(meta_to_json).collect()),
+            MetaValue::Object(fields) => serde_json::Value::Object(
+                fields.iter().map(|(k, v)| (k.clone(), meta_to_json(v))).collect()
+            ),
+            other => serde_json::to_value(other).unwrap_or(serde_json::Value::Null),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::collections::HashMap;
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        #[test]
+        fn test_send_parses_json_and_reports_network_errors() {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/items", listener.local_addr().unwrap());
+            let server = std::thread::spawn(move || {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = [0u8; 1024];
+                let read = stream.read(&mut request).unwrap();
+                let body = r#"{"items":[1,2]}"#;
+                write!(stream, "HTTP/1.1 201 Created\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body).unwrap();
+                String::from_utf8_lossy(&request[..read]).to_string()
+            });
+
+            let headers = MetaValue::Object(HashMap::from([("X-Token".to_string(), MetaValue::from("abc"))]));
+            let body = MetaValue::Object(HashMap::from([("name".to_string(), MetaValue::from("test"))]));
+            let (success, status, response, _) = send("post", &url, &headers, &body, 5.0);
+
+            let request = server.join().unwrap().to_lowercase();
+            assert!(request.starts_with("post /items") && request.contains("x-token: abc"));
+            assert!(success);
+            assert_eq!(status, 201.0);
+            assert_eq!(response, MetaValue::Object(HashMap::from([(
+                "items".to_string(),
+                MetaValue::List(vec![MetaValue::Scalar(1.0), MetaValue::Scalar(2.0)]),
+            )])));
+
+            let (success, status, response, error) = send("GET", "http://127.0.0.1:1/", &MetaValue::new_object(), &MetaValue::from(""), 5.0);
+            assert!(!success);
+            assert_eq!(status, 0.0);
+            assert_eq!(response, MetaValue::String(error));
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext};
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
 use crate::core::types::MetaValue;
 use crate::core::types::error::MarcoError;
 use std::collections::HashMap;
@@ -16,4 +16,20 @@ impl Evaluatable for BranchNode {
         result.insert("result".to_string(), if cond { true_val } else { false_val });
         Ok(result)
     }
+
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("condition", "bool", MetaValue::Bool(false)),
+            InputSpec::optional("true_value", "any", MetaValue::Scalar(1.0)).with_label("True"),
+            InputSpec::optional("false_value", "any", MetaValue::Scalar(0.0)).with_label("False"),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::new("result", "any")]
+    }
+
+    fn node_type(&self) -> &'static str {
+        "branch"
+    }
 }
@@ -1,4 +1,4 @@
-use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext};
+use crate::core::logic::{BindingExpr, InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
 use crate::core::types::MetaValue;
 use crate::core::types::error::MarcoError;
 use std::collections::HashMap;
@@ -9,34 +9,29 @@ pub struct CalculatorNode;
 impl Evaluatable for CalculatorNode {
     fn evaluate(&self, inputs: &InputMap, _ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
         let expression = inputs.get("expression").and_then(|v| v.as_string()).unwrap_or_default();
-        let x = inputs.get("x").and_then(|v| v.as_scalar()).unwrap_or(0.0);
-        let y = inputs.get("y").and_then(|v| v.as_scalar()).unwrap_or(0.0);
-        let z = inputs.get("z").and_then(|v| v.as_scalar()).unwrap_or(0.0);
+        
+        // x, y and z inputs are always available; the variables object can add or override
+        let mut variables: HashMap<String, f64> = ["x", "y", "z"].iter()
+            .map(|name| (name.to_string(), inputs.get(*name).and_then(|v| v.as_scalar()).unwrap_or(0.0)))
+            .collect();
+        if let Some(MetaValue::Object(values)) = inputs.get("variables") {
+            for (name, value) in values {
+                if let Some(number) = value.as_scalar() {
+                    variables.insert(name.clone(), number);
+                }
+            }
+        }
         
         let mut result = HashMap::new();
         
-        // Simple expression evaluator (simplified - in practice you'd use a proper parser)
-        let computed_result = match expression.as_str() {
-            "x + y" => x + y,
-            "x - y" => x - y,
-            "x * y" => x * y,
-            "x / y" => if y != 0.0 { x / y } else { 0.0 },
-            "x^2" => x * x,
-            "sqrt(x)" => x.sqrt(),
-            "sin(x)" => x.sin(),
-            "cos(x)" => x.cos(),
-            "x + y + z" => x + y + z,
-            "x * y * z" => x * y * z,
-            "avg(x,y)" => (x + y) / 2.0,
-            "avg(x,y,z)" => (x + y + z) / 3.0,
-            "distance(x,y)" => (x * x + y * y).sqrt(), // 2D distance from origin
-            "lerp(x,y,z)" => x + (y - x) * z.clamp(0.0, 1.0), // z as interpolation factor
-            "clamp(x,y,z)" => x.clamp(y, z), // x clamped between y and z
-            _ => {
-                // Try to parse as a simple number
-                expression.parse::<f64>().unwrap_or(0.0)
-            }
+        let evaluated = BindingExpr::parse(&expression)
+            .and_then(|expr| expr.evaluate_scalar(&variables));
+        let (computed_result, error) = match evaluated {
+            Ok(value) => (value, String::new()),
+            Err(e) => (0.0, e.to_string()),
         };
+        result.insert("valid".to_string(), MetaValue::Bool(error.is_empty()));
+        result.insert("error".to_string(), MetaValue::String(error));
         
         result.insert("result".to_string(), MetaValue::Scalar(computed_result));
         result.insert("expression".to_string(), MetaValue::String(expression));
@@ -56,6 +51,20 @@ impl Evaluatable for CalculatorNode {
         Ok(result)
     }
 
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("expression", "string", MetaValue::String("x * 2 + y".to_string())),
+            InputSpec::required("variables", "any"),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::new("result", "scalar"),
+            OutputSpec::new("error", "string"),
+        ]
+    }
+
     fn node_type(&self) -> &'static str {
         "calculator"
     }
@@ -1,8 +1,18 @@
-use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext};
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
 use crate::core::types::MetaValue;
 use crate::core::types::error::MarcoError;
 use std::collections::HashMap;
 
+/// Equality tolerance used when no `epsilon` input is given
+pub const DEFAULT_COMPARE_EPSILON: f64 = 1e-9;
+
+/// Compares `a` with `b`
+///
+/// Equality is approximate by default: values within `epsilon` (default
+/// [`DEFAULT_COMPARE_EPSILON`]) are equal, so computed floats such as
+/// `0.1 + 0.2` match `0.3`. Set `mode` to "exact" for bit-for-bit equality.
+/// The ordering outputs agree with `equal`, so `greater` and `less` are false
+/// for values that compare equal.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CompareNode;
 
@@ -10,16 +20,41 @@ impl Evaluatable for CompareNode {
     fn evaluate(&self, inputs: &InputMap, _ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
         let a = inputs.get("a").and_then(|v| v.as_scalar()).unwrap_or(0.0);
         let b = inputs.get("b").and_then(|v| v.as_scalar()).unwrap_or(0.0);
+        let epsilon = inputs.get("epsilon").and_then(|v| v.as_scalar()).unwrap_or(DEFAULT_COMPARE_EPSILON).abs();
+        let exact = inputs.get("mode").and_then(|v| v.as_string()).is_some_and(|mode| mode == "exact");
+        
+        let equal = if exact { a == b } else { (a - b).abs() <= epsilon };
         
         let mut result = HashMap::new();
-        result.insert("greater".to_string(), MetaValue::Bool(a > b));
-        result.insert("less".to_string(), MetaValue::Bool(a < b));
-        result.insert("equal".to_string(), MetaValue::Bool((a - b).abs() < f64::EPSILON));
-        result.insert("greater_equal".to_string(), MetaValue::Bool(a >= b));
-        result.insert("less_equal".to_string(), MetaValue::Bool(a <= b));
+        result.insert("greater".to_string(), MetaValue::Bool(!equal && a > b));
+        result.insert("less".to_string(), MetaValue::Bool(!equal && a < b));
+        result.insert("equal".to_string(), MetaValue::Bool(equal));
+        result.insert("not_equal".to_string(), MetaValue::Bool(!equal));
+        result.insert("greater_equal".to_string(), MetaValue::Bool(equal || a > b));
+        result.insert("less_equal".to_string(), MetaValue::Bool(equal || a < b));
         Ok(result)
     }
 
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("a", "scalar", MetaValue::Scalar(0.0)),
+            InputSpec::optional("b", "scalar", MetaValue::Scalar(0.0)),
+            InputSpec::optional("epsilon", "scalar", MetaValue::Scalar(DEFAULT_COMPARE_EPSILON)),
+            InputSpec::optional("mode", "string", MetaValue::String("tolerance".to_string())),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::new("greater", "bool"),
+            OutputSpec::new("less", "bool"),
+            OutputSpec::new("equal", "bool"),
+            OutputSpec::new("not_equal", "bool"),
+            OutputSpec::new("greater_equal", "bool"),
+            OutputSpec::new("less_equal", "bool"),
+        ]
+    }
+
     fn node_type(&self) -> &'static str {
         "compare"
     }
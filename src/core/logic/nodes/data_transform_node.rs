@@ -1,4 +1,4 @@
-use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext};
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
 use crate::core::types::MetaValue;
 use crate::core::types::error::MarcoError;
 use std::collections::HashMap;
@@ -162,6 +162,25 @@ impl Evaluatable for DataTransformNode {
         Ok(result)
     }
 
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("operation", "string", MetaValue::String("filter".to_string())),
+            InputSpec::required("data", "list"),
+            InputSpec::optional("field", "string", MetaValue::String("value".to_string())),
+            InputSpec::optional("value", "any", MetaValue::Scalar(0.0)),
+            InputSpec::optional("condition", "string", MetaValue::String("equals".to_string())),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::new("result", "any"),
+            OutputSpec::new("result_count", "scalar"),
+            OutputSpec::new("original_count", "scalar"),
+            OutputSpec::new("success", "bool"),
+        ]
+    }
+
     fn node_type(&self) -> &'static str {
         "data_transform"
     }
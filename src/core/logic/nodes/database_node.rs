@@ -1,4 +1,4 @@
-use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext};
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
 use crate::core::types::MetaValue;
 use crate::core::types::error::MarcoError;
 use std::collections::HashMap;
@@ -66,6 +66,10 @@ impl Evaluatable for DatabaseNode {
         // Common outputs for all operations
         result.insert("operation".to_string(), MetaValue::String(operation.clone()));
         result.insert("table".to_string(), MetaValue::String(table.clone()));
+        
+        // Everything above as one object for the `result` port
+        let summary = MetaValue::Object(result.clone());
+        result.insert("result".to_string(), summary);
         result.insert("connection_status".to_string(), MetaValue::String("connected".to_string()));
         
         // Query building helper
@@ -85,6 +89,26 @@ impl Evaluatable for DatabaseNode {
         Ok(result)
     }
 
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("operation", "string", MetaValue::String("select".to_string())),
+            InputSpec::optional("table", "string", MetaValue::String("users".to_string())),
+            InputSpec::optional("key", "string", MetaValue::String("".to_string())),
+            InputSpec::optional("value", "any", MetaValue::String("".to_string())),
+            InputSpec::optional("query", "string", MetaValue::String("".to_string())),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::new("result", "any"),
+            OutputSpec::new("data", "list"),
+            OutputSpec::new("count", "scalar"),
+            OutputSpec::new("success", "bool"),
+            OutputSpec::new("generated_query", "string"),
+        ]
+    }
+
     fn node_type(&self) -> &'static str {
         "database"
     }
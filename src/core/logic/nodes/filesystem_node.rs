@@ -0,0 +1,142 @@
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
+use crate::core::types::MetaValue;
+use crate::core::types::error::MarcoError;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File operations confined to a root directory
+///
+/// Every `path` input is resolved against the root and canonicalized
+/// (following symlinks) before use; paths that land outside the root are
+/// rejected with `success = false` and the reason in `result`. Paths that do
+/// not exist yet are checked through their nearest existing ancestor.
+#[derive(Debug, Clone)]
+pub struct FilesystemNode {
+    root: PathBuf,
+}
+
+impl FilesystemNode {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Absolute location of `path` inside the jail
+    pub fn resolve(&self, path: &str) -> Result<PathBuf, MarcoError> {
+        let root = self.root.canonicalize()
+            .map_err(|e| MarcoError::InvalidOperation(format!("Filesystem root {} is unavailable: {}", self.root.display(), e)))?;
+        let requested = root.join(path.trim_start_matches(['/', '\\']));
+
+        // Canonicalize the longest existing prefix, then re-append the rest
+        let mut existing = requested.as_path();
+        let mut missing = Vec::new();
+        while !existing.exists() {
+            match (existing.file_name(), existing.parent()) {
+                (Some(name), Some(parent)) => {
+                    missing.push(name.to_os_string());
+                    existing = parent;
+                }
+                _ => break,
+            }
+        }
+        let mut resolved = existing.canonicalize()
+            .map_err(|e| MarcoError::InvalidOperation(format!("Cannot resolve '{}': {}", path, e)))?;
+        resolved.extend(missing.iter().rev());
+
+        if resolved.starts_with(&root) {
+            Ok(resolved)
+        } else {
+            Err(MarcoError::InvalidOperation(format!("Path '{}' escapes the filesystem root", path)))
+        }
+    }
+
+    fn run(&self, operation: &str, path: &str, content: &str) -> Result<(MetaValue, f64), MarcoError> {
+        let target = self.resolve(path)?;
+        let io_error = |e: std::io::Error| MarcoError::InvalidOperation(format!("{} '{}' failed: {}", operation, path, e));
+
+        match operation {
+            "read" => {
+                let text = fs::read_to_string(&target).map_err(io_error)?;
+                let size = text.len() as f64;
+                Ok((MetaValue::String(text), size))
+            }
+            "write" => {
+                fs::write(&target, content).map_err(io_error)?;
+                Ok((MetaValue::String(path.to_string()), content.len() as f64))
+            }
+            "exists" => Ok((MetaValue::Bool(target.exists()), 0.0)),
+            "list" => {
+                let mut names = fs::read_dir(&target).map_err(io_error)?
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                    .collect::<Vec<_>>();
+                names.sort();
+                let count = names.len() as f64;
+                Ok((MetaValue::List(names.into_iter().map(MetaValue::String).collect()), count))
+            }
+            "mkdir" => {
+                fs::create_dir_all(&target).map_err(io_error)?;
+                Ok((MetaValue::String(path.to_string()), 0.0))
+            }
+            "delete" => {
+                if self.root.canonicalize().is_ok_and(|root| root == target) {
+                    return Err(MarcoError::InvalidOperation("Refusing to delete the filesystem root".to_string()));
+                }
+                if target.is_dir() {
+                    fs::remove_dir_all(&target).map_err(io_error)?;
+                } else {
+                    fs::remove_file(&target).map_err(io_error)?;
+                }
+                Ok((MetaValue::String(path.to_string()), 0.0))
+            }
+            other => Err(MarcoError::InvalidOperation(format!("Unknown filesystem operation: {}", other))),
+        }
+    }
+}
+
+impl Evaluatable for FilesystemNode {
+    fn evaluate(&self, inputs: &InputMap, _ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
+        let operation = inputs.get("operation").and_then(|v| v.as_string()).unwrap_or("read".to_string());
+        let path = inputs.get("path").and_then(|v| v.as_string()).unwrap_or_default();
+        let content = inputs.get("content").and_then(|v| v.as_string()).unwrap_or_default();
+
+        let mut result = HashMap::new();
+        match self.run(&operation, &path, &content) {
+            Ok((value, size)) => {
+                result.insert("result".to_string(), value);
+                result.insert("success".to_string(), MetaValue::Bool(true));
+                result.insert("size".to_string(), MetaValue::Scalar(size));
+            }
+            Err(e) => {
+                result.insert("result".to_string(), MetaValue::String(e.to_string()));
+                result.insert("success".to_string(), MetaValue::Bool(false));
+                result.insert("size".to_string(), MetaValue::Scalar(0.0));
+            }
+        }
+        Ok(result)
+    }
+
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("operation", "string", MetaValue::String("read".to_string())),
+            InputSpec::optional("path", "string", MetaValue::String("./data.txt".to_string())),
+            InputSpec::optional("content", "string", MetaValue::String("".to_string())),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::new("result", "any"),
+            OutputSpec::new("success", "bool"),
+            OutputSpec::new("size", "scalar"),
+        ]
+    }
+
+    fn node_type(&self) -> &'static str {
+        "filesystem"
+    }
+}
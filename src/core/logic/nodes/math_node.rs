@@ -1,15 +1,79 @@
-use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext};
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
+use crate::core::logic::safe_math::{self, BitwiseOp, OverflowMode};
 use crate::core::types::MetaValue;
 use crate::core::types::error::MarcoError;
+use glam::{Vec2, Vec3};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MathNode;
 
+impl MathNode {
+    /// Component-wise operations when either operand is a vector
+    ///
+    /// Scalars are broadcast to every component. Mixing a Vec2 with a Vec3
+    /// is an error.
+    fn evaluate_vector(a: Option<&MetaValue>, b: Option<&MetaValue>) -> Result<OutputMap, MarcoError> {
+        let mut result = HashMap::new();
+        match (a, b) {
+            (Some(MetaValue::Vec3(_)), Some(MetaValue::Vec2(_)))
+            | (Some(MetaValue::Vec2(_)), Some(MetaValue::Vec3(_))) => {
+                return Err(MarcoError::NodeEval("Cannot combine a Vec2 with a Vec3".to_string()));
+            }
+            (Some(MetaValue::Vec3(_)), _) | (_, Some(MetaValue::Vec3(_))) => {
+                let a = Self::to_vec3(a);
+                let b = Self::to_vec3(b);
+                result.insert("add".to_string(), MetaValue::Vec3(a + b));
+                result.insert("subtract".to_string(), MetaValue::Vec3(a - b));
+                result.insert("multiply".to_string(), MetaValue::Vec3(a * b));
+                result.insert("min".to_string(), MetaValue::Vec3(a.min(b)));
+                result.insert("max".to_string(), MetaValue::Vec3(a.max(b)));
+                result.insert("dot".to_string(), MetaValue::Scalar(a.dot(b) as f64));
+                result.insert("length".to_string(), MetaValue::Scalar(a.length() as f64));
+            }
+            _ => {
+                let a = Self::to_vec2(a);
+                let b = Self::to_vec2(b);
+                result.insert("add".to_string(), MetaValue::Vec2(a + b));
+                result.insert("subtract".to_string(), MetaValue::Vec2(a - b));
+                result.insert("multiply".to_string(), MetaValue::Vec2(a * b));
+                result.insert("min".to_string(), MetaValue::Vec2(a.min(b)));
+                result.insert("max".to_string(), MetaValue::Vec2(a.max(b)));
+                result.insert("dot".to_string(), MetaValue::Scalar(a.dot(b) as f64));
+                result.insert("length".to_string(), MetaValue::Scalar(a.length() as f64));
+            }
+        }
+        Ok(result)
+    }
+
+    fn to_vec2(value: Option<&MetaValue>) -> Vec2 {
+        match value {
+            Some(MetaValue::Vec2(v)) => *v,
+            other => Vec2::splat(other.and_then(|v| v.as_scalar()).unwrap_or(0.0) as f32),
+        }
+    }
+
+    fn to_vec3(value: Option<&MetaValue>) -> Vec3 {
+        match value {
+            Some(MetaValue::Vec3(v)) => *v,
+            other => Vec3::splat(other.and_then(|v| v.as_scalar()).unwrap_or(0.0) as f32),
+        }
+    }
+}
+
 impl Evaluatable for MathNode {
     fn evaluate(&self, inputs: &InputMap, _ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
+        let is_vector = |v: &MetaValue| matches!(v, MetaValue::Vec2(_) | MetaValue::Vec3(_));
+        if inputs.get("a").is_some_and(is_vector) || inputs.get("b").is_some_and(is_vector) {
+            return Self::evaluate_vector(inputs.get("a"), inputs.get("b"));
+        }
+        
         let a = inputs.get("a").and_then(|v| v.as_scalar()).unwrap_or(0.0);
         let b = inputs.get("b").and_then(|v| v.as_scalar()).unwrap_or(0.0);
+        let overflow = inputs.get("overflow")
+            .and_then(|v| v.as_string())
+            .map(|name| OverflowMode::from_name(&name))
+            .unwrap_or_default();
         
         let mut result = HashMap::new();
         // Basic operations
@@ -34,9 +98,53 @@ impl Evaluatable for MathNode {
         result.insert("min".to_string(), MetaValue::Scalar(a.min(b)));
         result.insert("max".to_string(), MetaValue::Scalar(a.max(b)));
         
+        // Bitwise operations on the integer portion
+        let bitwise_ops = [
+            ("bit_and", BitwiseOp::And),
+            ("bit_or", BitwiseOp::Or),
+            ("bit_xor", BitwiseOp::Xor),
+            ("shift_left", BitwiseOp::ShiftLeft),
+            ("shift_right", BitwiseOp::ShiftRight),
+        ];
+        // Like the other nodes, `error` is only present when something failed
+        let mut bitwise_error = None;
+        for (name, op) in bitwise_ops {
+            match safe_math::bitwise(op, a, b, overflow) {
+                Ok(value) => {
+                    result.insert(name.to_string(), MetaValue::Scalar(value));
+                }
+                Err(err) => {
+                    bitwise_error.get_or_insert(err);
+                }
+            }
+        }
+        if let Some(err) = bitwise_error {
+            result.insert("error".to_string(), MetaValue::String(err.to_string()));
+        }
+        
         Ok(result)
     }
 
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("a", "any", MetaValue::Scalar(0.0)),
+            InputSpec::optional("b", "any", MetaValue::Scalar(0.0)),
+            InputSpec::optional("overflow", "string", MetaValue::String("wrapping".to_string())),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        // Component-wise results are vectors when either operand is one
+        let mut outputs: Vec<OutputSpec> = ["add", "subtract", "multiply", "min", "max"].iter()
+            .map(|name| OutputSpec::new(name, "any"))
+            .collect();
+        outputs.extend([
+            "divide", "modulo", "power", "sqrt", "sin", "cos", "tan", "abs", "floor", "ceil", "round",
+            "bit_and", "bit_or", "bit_xor", "shift_left", "shift_right",
+        ].iter().map(|name| OutputSpec::new(name, "scalar")));
+        outputs
+    }
+
     fn node_type(&self) -> &'static str {
         "math"
     }
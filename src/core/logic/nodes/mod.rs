@@ -6,11 +6,16 @@ pub mod clamp_node;
 pub mod math_node;
 pub mod string_node;
 pub mod timer_node;
+pub mod throttle_node;
+pub mod probe_node;
+pub mod random_node;
+pub mod sample_image_node;
 pub mod calculator_node;
 pub mod database_node;
 pub mod validation_node;
 pub mod api_node;
 pub mod data_transform_node;
+pub mod filesystem_node;
 
 // Tests
 #[cfg(test)]
@@ -26,9 +31,14 @@ pub use clamp_node::ClampNode;
 pub use math_node::MathNode;
 pub use string_node::StringNode;
 pub use timer_node::TimerNode;
+pub use throttle_node::ThrottleNode;
+pub use probe_node::ProbeNode;
+pub use random_node::RandomNode;
+pub use sample_image_node::SampleImageNode;
 
 pub use calculator_node::CalculatorNode;
 pub use database_node::DatabaseNode;
 pub use validation_node::ValidationNode;
 pub use api_node::ApiNode;
 pub use data_transform_node::DataTransformNode;
+pub use filesystem_node::FilesystemNode;
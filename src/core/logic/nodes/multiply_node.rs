@@ -1,4 +1,4 @@
-use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext};
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
 use crate::core::types::MetaValue;
 use crate::core::types::error::MarcoError;
 use std::collections::HashMap;
@@ -16,6 +16,17 @@ impl Evaluatable for MultiplyNode {
         Ok(result)
     }
 
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("a", "scalar", MetaValue::Scalar(1.0)),
+            InputSpec::optional("b", "scalar", MetaValue::Scalar(1.0)),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::new("result", "scalar")]
+    }
+
     fn node_type(&self) -> &'static str {
         "multiply"
     }
@@ -0,0 +1,53 @@
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
+use crate::core::types::MetaValue;
+use crate::core::types::error::MarcoError;
+use std::collections::HashMap;
+use tracing::trace;
+
+/// Probe name used when the `name` input is not set
+pub const DEFAULT_PROBE_NAME: &str = "probe";
+
+/// Streams its input to an external subscriber
+///
+/// Each evaluation sends `value` to the probe channel called `name` on the
+/// context's [`ProbeChannels`](crate::core::logic::ProbeChannels), then passes
+/// it through unchanged as `value`. Outputs `delivered` (true when a
+/// subscriber received it). Without a subscriber the node is a plain
+/// pass-through.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ProbeNode;
+
+impl Evaluatable for ProbeNode {
+    fn evaluate(&self, inputs: &InputMap, ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
+        let value = inputs.get("value").cloned().unwrap_or_default();
+        let name = inputs.get("name").and_then(|v| v.as_string()).unwrap_or_else(|| DEFAULT_PROBE_NAME.to_string());
+
+        let delivered = ctx.probes.send(&name, value.clone());
+        if !delivered {
+            trace!("Probe '{}' has no subscriber", name);
+        }
+
+        let mut result = HashMap::new();
+        result.insert("value".to_string(), value);
+        result.insert("delivered".to_string(), MetaValue::Bool(delivered));
+        Ok(result)
+    }
+
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::required("value", "any"),
+            InputSpec::optional("name", "string", MetaValue::String(DEFAULT_PROBE_NAME.to_string())),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::new("value", "any"),
+            OutputSpec::new("delivered", "bool"),
+        ]
+    }
+
+    fn node_type(&self) -> &'static str {
+        "probe"
+    }
+}
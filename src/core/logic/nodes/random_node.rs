@@ -0,0 +1,47 @@
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
+use crate::core::types::MetaValue;
+use crate::core::types::error::MarcoError;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RandomNode;
+
+impl Evaluatable for RandomNode {
+    fn evaluate(&self, inputs: &InputMap, ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
+        let min = inputs.get("min").and_then(|v| v.as_scalar()).unwrap_or(0.0);
+        let max = inputs.get("max").and_then(|v| v.as_scalar()).unwrap_or(1.0);
+        
+        // Draw from the context RNG so seeded contexts reproduce the same values
+        let unit = ctx.random();
+        let value = min + (max - min) * unit;
+        
+        let mut result = HashMap::new();
+        result.insert("value".to_string(), MetaValue::Scalar(value));
+        result.insert("unit".to_string(), MetaValue::Scalar(unit));
+        result.insert("integer".to_string(), MetaValue::Scalar(value.floor()));
+        Ok(result)
+    }
+
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("min", "scalar", MetaValue::Scalar(0.0)),
+            InputSpec::optional("max", "scalar", MetaValue::Scalar(1.0)),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::new("value", "scalar"),
+            OutputSpec::new("unit", "scalar"),
+            OutputSpec::new("integer", "scalar"),
+        ]
+    }
+
+    fn node_type(&self) -> &'static str {
+        "random"
+    }
+
+    fn is_volatile(&self) -> bool {
+        true
+    }
+}
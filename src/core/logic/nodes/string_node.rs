@@ -1,4 +1,6 @@
-use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext};
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
+use crate::core::logic::safe_math;
+use crate::core::logic::number_parse::{self, ParseOptions};
 use crate::core::types::MetaValue;
 use crate::core::types::error::MarcoError;
 use std::collections::HashMap;
@@ -11,12 +13,21 @@ impl Evaluatable for StringNode {
         let text = inputs.get("text").and_then(|v| v.as_string()).unwrap_or_default();
         let other = inputs.get("other").and_then(|v| v.as_string()).unwrap_or_default();
         let separator = inputs.get("separator").and_then(|v| v.as_string()).unwrap_or(" ".to_string());
-        let index = inputs.get("index").and_then(|v| v.as_scalar()).unwrap_or(0.0) as usize;
+        let index = inputs.get("index").and_then(|v| v.as_scalar()).unwrap_or(0.0);
+        let scalar = number_parse::parse_scalar(&text, ParseOptions::from_inputs(inputs));
         
         let mut result = HashMap::new();
         
+        if let Some(operation) = inputs.get("operation").and_then(|v| v.as_string()) {
+            let param = ["parameter", "param", "other"].iter()
+                .find_map(|name| inputs.get(*name).and_then(|v| v.as_string()))
+                .unwrap_or_default();
+            let replacement = inputs.get("replacement").and_then(|v| v.as_string()).unwrap_or_default();
+            result.insert("result".to_string(), apply_operation(&operation, &text, &param, &replacement, index, scalar)?);
+        }
+        
         // String operations
-        result.insert("length".to_string(), MetaValue::Scalar(text.len() as f64));
+        result.insert("length".to_string(), MetaValue::Scalar(text.chars().count() as f64));
         result.insert("uppercase".to_string(), MetaValue::String(text.to_uppercase()));
         result.insert("lowercase".to_string(), MetaValue::String(text.to_lowercase()));
         result.insert("trimmed".to_string(), MetaValue::String(text.trim().to_string()));
@@ -29,8 +40,11 @@ impl Evaluatable for StringNode {
         result.insert("starts_with".to_string(), MetaValue::Bool(text.starts_with(&other)));
         result.insert("ends_with".to_string(), MetaValue::Bool(text.ends_with(&other)));
         
-        // Character access
-        let char_at = text.chars().nth(index).map(|c| c.to_string()).unwrap_or_default();
+        // Character access; an invalid index only fails the `char_at` operation
+        let char_at = safe_math::to_index(index, "index").ok()
+            .and_then(|index| text.chars().nth(index))
+            .map(|c| c.to_string())
+            .unwrap_or_default();
         result.insert("char_at".to_string(), MetaValue::String(char_at));
         
         // Split operations
@@ -44,10 +58,79 @@ impl Evaluatable for StringNode {
             .collect();
         result.insert("lines".to_string(), MetaValue::List(lines));
         
+        // Number conversion; absent when the text is not numeric
+        if let Some(value) = scalar {
+            result.insert("to_scalar".to_string(), MetaValue::Scalar(value));
+        }
+        
         Ok(result)
     }
 
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("operation", "string", MetaValue::String("uppercase".to_string())),
+            InputSpec::optional("text", "string", MetaValue::String("Hello World".to_string())),
+            InputSpec::optional("parameter", "string", MetaValue::String("".to_string())),
+            InputSpec::optional("replacement", "string", MetaValue::String("".to_string())),
+            InputSpec::optional("other", "string", MetaValue::String("".to_string())),
+            InputSpec::optional("separator", "string", MetaValue::String(" ".to_string())),
+            InputSpec::optional("index", "scalar", MetaValue::Scalar(0.0)),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        let mut outputs = vec![
+            // Depends on the operation: text, a scalar length, a bool or a list
+            OutputSpec::new("result", "any"),
+            OutputSpec::new("length", "scalar"),
+            OutputSpec::new("is_empty", "bool"),
+            OutputSpec::new("contains", "bool"),
+            OutputSpec::new("starts_with", "bool"),
+            OutputSpec::new("ends_with", "bool"),
+            OutputSpec::new("words", "list"),
+            OutputSpec::new("lines", "list"),
+        ];
+        outputs.extend(["uppercase", "lowercase", "trimmed", "concat", "join", "char_at"].iter()
+            .map(|name| OutputSpec::new(name, "string")));
+        outputs
+    }
+
     fn node_type(&self) -> &'static str {
         "string"
     }
 }
+
+/// Result of the operation selected by the `operation` input
+///
+/// Lengths and indices count characters, not bytes, so multibyte text is
+/// handled per character. `scalar` is the text parsed as a number, which
+/// only the `to_scalar` operation requires.
+fn apply_operation(operation: &str, text: &str, param: &str, replacement: &str, index: f64, scalar: Option<f64>) -> Result<MetaValue, MarcoError> {
+    let value = match operation {
+        "length" => MetaValue::Scalar(text.chars().count() as f64),
+        "uppercase" => MetaValue::String(text.to_uppercase()),
+        "lowercase" => MetaValue::String(text.to_lowercase()),
+        "trim" => MetaValue::String(text.trim().to_string()),
+        "reverse" => MetaValue::String(text.chars().rev().collect()),
+        "concat" => MetaValue::String(format!("{}{}", text, param)),
+        "contains" => MetaValue::Bool(text.contains(param)),
+        "starts_with" => MetaValue::Bool(text.starts_with(param)),
+        "ends_with" => MetaValue::Bool(text.ends_with(param)),
+        "char_at" => {
+            let index = safe_math::to_index(index, "index")?;
+            MetaValue::String(text.chars().nth(index).map(|c| c.to_string()).unwrap_or_default())
+        }
+        "to_scalar" => MetaValue::Scalar(scalar.ok_or_else(|| {
+            MarcoError::NodeEval(format!("Cannot parse '{}' as a number", text))
+        })?),
+        // An empty separator splits into characters rather than yielding
+        // empty leading and trailing items
+        "split" if param.is_empty() => MetaValue::List(text.chars().map(|c| MetaValue::String(c.to_string())).collect()),
+        "split" => MetaValue::List(text.split(param).map(|s| MetaValue::String(s.to_string())).collect()),
+        // Replacing an empty pattern would insert between every character
+        "replace" if param.is_empty() => MetaValue::String(text.to_string()),
+        "replace" => MetaValue::String(text.replace(param, replacement)),
+        other => return Err(MarcoError::NodeEval(format!("Unknown string operation: {}", other))),
+    };
+    Ok(value)
+}
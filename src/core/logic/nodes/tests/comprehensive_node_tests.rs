@@ -1,5 +1,5 @@
 use crate::core::types::{MetaValue, ScopeId};
-use crate::core::logic::{EvalContext, Evaluatable};
+use crate::core::logic::{EvalContext, EvalRng, Evaluatable, NodeStateStore, ProbeChannels};
 use crate::core::logic::nodes::*;
 use crate::core::registry::MetaRegistry;
 use std::collections::HashMap;
@@ -15,12 +15,19 @@ pub fn create_test_context() -> EvalContext {
         registry: Arc::new(MetaRegistry::new()),
         scope_id: ScopeId::new(),
         parent: None,
+        time_seconds: None,
+        rng: EvalRng::from_entropy(),
+        probes: ProbeChannels::default(),
+        elapsed_time: 0.0,
+        node_state: NodeStateStore::default(),
+        current_node: None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::runtime::NodeId;
 
     #[test]
     fn test_math_node_operations() {
@@ -103,6 +110,79 @@ mod tests {
         assert_eq!(result.get("result"), Some(&MetaValue::Bool(true)));
     }
 
+    #[test]
+    fn test_string_node_multibyte_text() {
+        let node = StringNode;
+        let ctx = create_test_context();
+        let text = MetaValue::String("héllo wörld 🌍".to_string());
+
+        // Length counts characters, not UTF-8 bytes
+        let inputs = create_test_inputs(&[
+            ("text", text.clone()),
+            ("operation", MetaValue::String("length".to_string())),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert_eq!(result.get("result"), Some(&MetaValue::Scalar(13.0)));
+        assert_eq!(result.get("length"), Some(&MetaValue::Scalar(13.0)));
+
+        let inputs = create_test_inputs(&[
+            ("text", text.clone()),
+            ("operation", MetaValue::String("split".to_string())),
+            ("param", MetaValue::String(" ".to_string())),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert_eq!(result.get("result"), Some(&MetaValue::List(vec![
+            MetaValue::String("héllo".to_string()),
+            MetaValue::String("wörld".to_string()),
+            MetaValue::String("🌍".to_string()),
+        ])));
+
+        let inputs = create_test_inputs(&[
+            ("text", MetaValue::String("ñö".to_string())),
+            ("operation", MetaValue::String("split".to_string())),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert_eq!(result.get("result"), Some(&MetaValue::List(vec![
+            MetaValue::String("ñ".to_string()),
+            MetaValue::String("ö".to_string()),
+        ])));
+
+        // An empty search pattern leaves the text untouched
+        let inputs = create_test_inputs(&[
+            ("text", text.clone()),
+            ("operation", MetaValue::String("replace".to_string())),
+            ("param", MetaValue::String("".to_string())),
+            ("replacement", MetaValue::String("x".to_string())),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert_eq!(result.get("result"), Some(&text));
+
+        let inputs = create_test_inputs(&[
+            ("text", text.clone()),
+            ("operation", MetaValue::String("replace".to_string())),
+            ("param", MetaValue::String("ö".to_string())),
+            ("replacement", MetaValue::String("o".to_string())),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert_eq!(result.get("result"), Some(&MetaValue::String("héllo world 🌍".to_string())));
+
+        let inputs = create_test_inputs(&[
+            ("text", text.clone()),
+            ("operation", MetaValue::String("contains".to_string())),
+            ("param", MetaValue::String("🌍".to_string())),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert_eq!(result.get("result"), Some(&MetaValue::Bool(true)));
+
+        let inputs = create_test_inputs(&[
+            ("text", text),
+            ("operation", MetaValue::String("char_at".to_string())),
+            ("index", MetaValue::Scalar(12.0)),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert_eq!(result.get("result"), Some(&MetaValue::String("🌍".to_string())));
+    }
+
     #[test]
     fn test_timer_node_functionality() {
         let mut node = TimerNode;
@@ -359,4 +439,330 @@ mod tests {
         assert!(result.contains_key("result"));
         assert!(result.contains_key("length"));
     }
+
+    #[test]
+    fn test_math_node_bitwise_xor() {
+        let node = MathNode;
+        let ctx = create_test_context();
+
+        let inputs = create_test_inputs(&[
+            ("a", MetaValue::Scalar(0b1100 as f64)),
+            ("b", MetaValue::Scalar(0b1010 as f64)),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert_eq!(result.get("bit_xor"), Some(&MetaValue::Scalar(0b0110 as f64)));
+        assert_eq!(result.get("bit_and"), Some(&MetaValue::Scalar(0b1000 as f64)));
+        assert!(!result.contains_key("error"));
+
+        // Saturating shift stays within the safe integer range
+        let inputs = create_test_inputs(&[
+            ("a", MetaValue::Scalar(2f64.powi(52))),
+            ("b", MetaValue::Scalar(4.0)),
+            ("overflow", MetaValue::String("saturating".to_string())),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert_eq!(result.get("shift_left"), Some(&MetaValue::Scalar(crate::core::logic::safe_math::MAX_SAFE_INTEGER)));
+
+        // A negative shift fails those outputs and reports why once
+        let inputs = create_test_inputs(&[
+            ("a", MetaValue::Scalar(8.0)),
+            ("b", MetaValue::Scalar(-1.0)),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert!(!result.contains_key("shift_left"));
+        assert_eq!(result.get("bit_or"), Some(&MetaValue::Scalar(-1.0)));
+        assert!(matches!(result.get("error"), Some(MetaValue::String(message)) if message.contains("negative")));
+    }
+
+    #[test]
+    fn test_string_node_unsafe_index_errors() {
+        let node = StringNode;
+        let ctx = create_test_context();
+
+        let inputs = create_test_inputs(&[
+            ("operation", MetaValue::String("char_at".to_string())),
+            ("text", MetaValue::String("hello".to_string())),
+            ("index", MetaValue::Scalar(1e18)),
+        ]);
+        let err = node.evaluate(&inputs, &ctx).expect_err("Index beyond 2^53 should be rejected");
+        assert!(err.to_string().contains("safe integer range"));
+
+        // Operations that ignore the index are unaffected by a bad one
+        let inputs = create_test_inputs(&[
+            ("operation", MetaValue::String("uppercase".to_string())),
+            ("text", MetaValue::String("hello".to_string())),
+            ("index", MetaValue::Scalar(-1.0)),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("uppercase should not validate the index");
+        assert_eq!(result.get("result"), Some(&MetaValue::String("HELLO".to_string())));
+        assert_eq!(result.get("char_at"), Some(&MetaValue::String(String::new())));
+    }
+
+    fn checker_image() -> MetaValue {
+        use crate::core::types::ColorRGBA;
+        MetaValue::new_image(2, 2, vec![
+            ColorRGBA::new(1.0, 0.0, 0.0, 1.0), ColorRGBA::new(0.0, 1.0, 0.0, 1.0),
+            ColorRGBA::new(0.0, 0.0, 1.0, 1.0), ColorRGBA::new(1.0, 1.0, 1.0, 1.0),
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_sample_image_bilinear_center_averages() {
+        let node = SampleImageNode;
+        let ctx = create_test_context();
+
+        let inputs = create_test_inputs(&[
+            ("image", checker_image()),
+            ("u", MetaValue::Scalar(0.5)),
+            ("v", MetaValue::Scalar(0.5)),
+            ("filter", MetaValue::String("bilinear".to_string())),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        let color = result.get("color").and_then(|v| v.as_color()).unwrap();
+        assert!((color.r - 0.5).abs() < 1e-6);
+        assert!((color.g - 0.5).abs() < 1e-6);
+        assert!((color.b - 0.5).abs() < 1e-6);
+        assert!((color.a - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sample_image_nearest_and_wrap_modes() {
+        let node = SampleImageNode;
+        let ctx = create_test_context();
+        let sample = |u: f64, wrap: &str| {
+            let inputs = create_test_inputs(&[
+                ("image", checker_image()),
+                ("u", MetaValue::Scalar(u)),
+                ("v", MetaValue::Scalar(0.25)),
+                ("filter", MetaValue::String("nearest".to_string())),
+                ("wrap", MetaValue::String(wrap.to_string())),
+            ]);
+            let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+            result.get("g").and_then(|v| v.as_scalar()).unwrap()
+        };
+
+        // Top-right texel is green; 1.25 clamps to it but repeats to top-left
+        assert_eq!(sample(0.75, "clamp"), 1.0);
+        assert_eq!(sample(1.25, "clamp"), 1.0);
+        assert_eq!(sample(1.25, "repeat"), 0.0);
+    }
+
+    #[test]
+    fn test_string_node_parses_formatted_numbers() {
+        let node = StringNode;
+        let ctx = create_test_context();
+        let parse = |text: &str| {
+            let inputs = create_test_inputs(&[("text", MetaValue::String(text.to_string()))]);
+            node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed")
+        };
+
+        assert_eq!(parse("1,234.5").get("to_scalar"), Some(&MetaValue::Scalar(1234.5)));
+        assert_eq!(parse("50%").get("to_scalar"), Some(&MetaValue::Scalar(0.5)));
+        assert_eq!(parse("1e3").get("to_scalar"), Some(&MetaValue::Scalar(1000.0)));
+
+        let invalid = parse("abc");
+        assert!(!invalid.contains_key("to_scalar"));
+        assert!(!invalid.contains_key("parse_error"));
+
+        // Conversion errors only surface when the conversion is requested
+        let convert = |text: &str| {
+            let inputs = create_test_inputs(&[
+                ("operation", MetaValue::String("to_scalar".to_string())),
+                ("text", MetaValue::String(text.to_string())),
+            ]);
+            node.evaluate(&inputs, &ctx)
+        };
+        assert_eq!(convert("1,234.5").unwrap().get("result"), Some(&MetaValue::Scalar(1234.5)));
+        assert!(convert("abc").is_err());
+    }
+
+    /// Evaluate a throttle node whose state lives in `ctx`
+    fn tick(ctx: &EvalContext, time: f64, value: f64, mode: &str) -> (bool, MetaValue) {
+        let mut ctx = ctx.clone();
+        ctx.time_seconds = Some(time);
+        let inputs = create_test_inputs(&[
+            ("value", MetaValue::Scalar(value)),
+            ("interval", MetaValue::Scalar(0.1)),
+            ("mode", MetaValue::String(mode.to_string())),
+        ]);
+        let result = ThrottleNode.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        (result["passed"].as_bool().unwrap(), result["value"].clone())
+    }
+
+    #[test]
+    fn test_throttle_emits_once_per_interval() {
+        let ctx = create_test_context().for_node(NodeId::new());
+
+        // Rapid ticks within one interval: only the first passes
+        let fired: Vec<bool> = (0..5).map(|i| tick(&ctx, i as f64 * 0.02, i as f64, "throttle").0).collect();
+        assert_eq!(fired, vec![true, false, false, false, false]);
+        assert_eq!(tick(&ctx, 0.09, 9.0, "throttle"), (false, MetaValue::Scalar(0.0)));
+
+        // Once the interval has elapsed the latest value goes through
+        assert_eq!(tick(&ctx, 0.1, 10.0, "throttle"), (true, MetaValue::Scalar(10.0)));
+        assert!(!tick(&ctx, 0.15, 11.0, "throttle").0);
+
+        // Another node keeps its own timing
+        let other = ctx.for_node(NodeId::new());
+        assert!(tick(&other, 0.16, 12.0, "throttle").0);
+    }
+
+    #[test]
+    fn test_debounce_waits_for_stable_input() {
+        let ctx = create_test_context().for_node(NodeId::new());
+
+        assert!(!tick(&ctx, 0.0, 1.0, "debounce").0);
+        assert!(!tick(&ctx, 0.05, 2.0, "debounce").0);
+        assert!(!tick(&ctx, 0.1, 2.0, "debounce").0);
+        assert_eq!(tick(&ctx, 0.16, 2.0, "debounce"), (true, MetaValue::Scalar(2.0)));
+        // Stays quiet until the input changes and settles again
+        assert!(!tick(&ctx, 0.3, 2.0, "debounce").0);
+    }
+
+    fn compare(pairs: &[(&str, MetaValue)]) -> HashMap<String, MetaValue> {
+        CompareNode.evaluate(&create_test_inputs(pairs), &create_test_context())
+            .expect("Node evaluation should succeed")
+    }
+
+    #[test]
+    fn test_compare_equality_uses_epsilon() {
+        let sum = 0.1 + 0.2;
+        let result = compare(&[("a", MetaValue::Scalar(sum)), ("b", MetaValue::Scalar(0.3))]);
+        assert_eq!(result["equal"], MetaValue::Bool(true));
+        assert_eq!(result["not_equal"], MetaValue::Bool(false));
+        assert_eq!(result["greater"], MetaValue::Bool(false));
+        assert_eq!(result["less_equal"], MetaValue::Bool(true));
+
+        let exact = compare(&[
+            ("a", MetaValue::Scalar(sum)),
+            ("b", MetaValue::Scalar(0.3)),
+            ("mode", MetaValue::String("exact".to_string())),
+        ]);
+        assert_eq!(exact["equal"], MetaValue::Bool(false));
+    }
+
+    #[test]
+    fn test_compare_outside_epsilon_is_not_equal() {
+        let result = compare(&[("a", MetaValue::Scalar(1.0)), ("b", MetaValue::Scalar(1.0 + 1e-6))]);
+        assert_eq!(result["equal"], MetaValue::Bool(false));
+        assert_eq!(result["less"], MetaValue::Bool(true));
+
+        let loose = compare(&[
+            ("a", MetaValue::Scalar(1.0)),
+            ("b", MetaValue::Scalar(1.05)),
+            ("epsilon", MetaValue::Scalar(0.1)),
+        ]);
+        assert_eq!(loose["equal"], MetaValue::Bool(true));
+    }
+
+    #[test]
+    fn test_probe_streams_each_evaluation_in_order() {
+        use crate::core::logic::NodeInputBinding;
+        use crate::graph::runtime::GraphRuntime;
+
+        let mut runtime = GraphRuntime::new(Arc::new(MetaRegistry::new()));
+        let scope = ScopeId::new();
+        let probe = runtime.add_node_to_graph(&scope, Arc::new(ProbeNode));
+        let receiver = runtime.probes().register("speed");
+
+        for value in [1.0, 2.5, -4.0] {
+            let node = runtime.get_graph_mut(&scope).unwrap().get_node_mut(&probe).unwrap();
+            node.set_input("name", NodeInputBinding::Literal(MetaValue::String("speed".to_string())));
+            node.set_input("value", NodeInputBinding::literal(value));
+            runtime.execute_graph(&scope).unwrap();
+
+            let outputs = runtime.get_graph(&scope).unwrap().get_node(&probe).unwrap().cached_outputs.clone().unwrap();
+            assert_eq!(outputs["value"], MetaValue::Scalar(value));
+        }
+
+        let received: Vec<MetaValue> = receiver.try_iter().collect();
+        assert_eq!(received, vec![MetaValue::Scalar(1.0), MetaValue::Scalar(2.5), MetaValue::Scalar(-4.0)]);
+    }
+
+    #[test]
+    fn test_math_node_adds_vectors() {
+        use glam::{Vec2, Vec3};
+
+        let node = MathNode;
+        let ctx = create_test_context();
+        let inputs = create_test_inputs(&[
+            ("a", MetaValue::Vec2(Vec2::new(1.0, 2.0))),
+            ("b", MetaValue::Vec2(Vec2::new(0.5, -4.0))),
+        ]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert_eq!(result["add"], MetaValue::Vec2(Vec2::new(1.5, -2.0)));
+        assert_eq!(result["dot"], MetaValue::Scalar(-7.5));
+
+        // Scalars broadcast across components
+        let inputs = create_test_inputs(&[("a", MetaValue::Vec3(Vec3::ONE)), ("b", MetaValue::Scalar(2.0))]);
+        let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+        assert_eq!(result["multiply"], MetaValue::Vec3(Vec3::splat(2.0)));
+
+        let mixed = create_test_inputs(&[("a", MetaValue::Vec2(Vec2::ONE)), ("b", MetaValue::Vec3(Vec3::ONE))]);
+        assert!(node.evaluate(&mixed, &ctx).is_err());
+
+        let json = serde_json::to_string(&MetaValue::Vec2(Vec2::new(1.0, 2.0))).unwrap();
+        assert_eq!(serde_json::from_str::<MetaValue>(&json).unwrap(), MetaValue::Vec2(Vec2::new(1.0, 2.0)));
+    }
+
+    fn filesystem_sandbox() -> (std::path::PathBuf, FilesystemNode) {
+        let base = std::env::temp_dir().join(format!("marco2-fs-{}", uuid::Uuid::new_v4()));
+        let root = base.join("root");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(base.join("secret.txt"), "outside").unwrap();
+        (base, FilesystemNode::new(root))
+    }
+
+    #[test]
+    fn test_filesystem_node_denies_escaping_the_root() {
+        let (base, node) = filesystem_sandbox();
+        let ctx = create_test_context();
+
+        for (operation, path) in [("read", "../secret.txt"), ("write", "../escape.txt"), ("delete", "nested/../../secret.txt"), ("list", ".."), ("mkdir", "/../escape")] {
+            let inputs = create_test_inputs(&[
+                ("operation", MetaValue::String(operation.to_string())),
+                ("path", MetaValue::String(path.to_string())),
+                ("content", MetaValue::String("pwned".to_string())),
+            ]);
+            let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+            assert_eq!(result["success"], MetaValue::Bool(false), "{} {} should be denied", operation, path);
+            assert!(result["result"].as_string().is_some_and(|e| !e.is_empty()));
+        }
+        assert_eq!(std::fs::read_to_string(base.join("secret.txt")).unwrap(), "outside");
+        assert!(!base.join("escape.txt").exists() && !base.join("escape").exists());
+
+        // Symlinks pointing out of the jail are caught after canonicalization
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&base, base.join("root/link")).unwrap();
+            let inputs = create_test_inputs(&[("operation", MetaValue::String("read".to_string())), ("path", MetaValue::String("link/secret.txt".to_string()))]);
+            assert_eq!(node.evaluate(&inputs, &ctx).unwrap()["success"], MetaValue::Bool(false));
+        }
+        std::fs::remove_dir_all(base).unwrap();
+    }
+
+    #[test]
+    fn test_filesystem_node_allows_nested_paths() {
+        let (base, node) = filesystem_sandbox();
+        let ctx = create_test_context();
+        let run = |operation: &str, path: &str, content: &str| {
+            let inputs = create_test_inputs(&[
+                ("operation", MetaValue::String(operation.to_string())),
+                ("path", MetaValue::String(path.to_string())),
+                ("content", MetaValue::String(content.to_string())),
+            ]);
+            let result = node.evaluate(&inputs, &ctx).expect("Node evaluation should succeed");
+            assert_eq!(result["success"], MetaValue::Bool(true), "{} {} failed: {:?}", operation, path, result["result"]);
+            result
+        };
+
+        run("mkdir", "data/nested", "");
+        assert_eq!(run("write", "data/nested/notes.txt", "hello")["size"], MetaValue::Scalar(5.0));
+        assert_eq!(run("read", "data/./nested/../nested/notes.txt", "")["result"], MetaValue::String("hello".to_string()));
+        assert_eq!(run("exists", "data/nested/notes.txt", "")["result"], MetaValue::Bool(true));
+        assert_eq!(run("list", "data/nested", "")["result"], MetaValue::List(vec![MetaValue::String("notes.txt".to_string())]));
+        run("delete", "data/nested/notes.txt", "");
+        assert_eq!(run("exists", "data/nested/notes.txt", "")["result"], MetaValue::Bool(false));
+        std::fs::remove_dir_all(base).unwrap();
+    }
 }
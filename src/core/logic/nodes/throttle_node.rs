@@ -0,0 +1,137 @@
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
+use crate::core::types::MetaValue;
+use crate::core::types::error::MarcoError;
+use std::collections::HashMap;
+
+/// Rate-limits a changing value using the context clock
+///
+/// Inputs: `value`, `interval` in seconds (default 0.1) and `mode`
+/// ("throttle" or "debounce", default throttle). Throttle passes the value
+/// through at most once per interval; debounce passes it once it has stayed
+/// unchanged for a full interval. Outputs `value` (the last value let
+/// through) and `passed` (true on evaluations that let a value through).
+///
+/// The last emission and the pending debounce value live in the context's
+/// node state, so suppression holds across evaluation passes.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ThrottleNode;
+
+#[derive(Debug, Clone, Default)]
+struct ThrottleState {
+    /// Time and value of the last emission
+    emitted: Option<(f64, MetaValue)>,
+    /// Debounce: latest input and when it last changed
+    pending: Option<(f64, MetaValue)>,
+}
+
+impl ThrottleState {
+    fn load(ctx: &EvalContext) -> Self {
+        Self {
+            emitted: ctx.node_state("emitted").and_then(Self::unstamp),
+            pending: ctx.node_state("pending").and_then(Self::unstamp),
+        }
+    }
+
+    fn save(&self, ctx: &EvalContext) {
+        ctx.set_node_state("emitted", Self::stamp(&self.emitted));
+        ctx.set_node_state("pending", Self::stamp(&self.pending));
+    }
+
+    /// Stored as `[time, value]`, or an empty list when unset
+    fn stamp(entry: &Option<(f64, MetaValue)>) -> MetaValue {
+        match entry {
+            Some((at, value)) => MetaValue::List(vec![MetaValue::Scalar(*at), value.clone()]),
+            None => MetaValue::List(Vec::new()),
+        }
+    }
+
+    fn unstamp(stored: MetaValue) -> Option<(f64, MetaValue)> {
+        match stored {
+            MetaValue::List(mut pair) if pair.len() == 2 => {
+                let value = pair.pop()?;
+                Some((pair[0].as_scalar()?, value))
+            }
+            _ => None,
+        }
+    }
+
+    fn throttle(&mut self, now: f64, interval: f64, value: &MetaValue) -> bool {
+        let ready = match &self.emitted {
+            Some((at, _)) => now - at >= interval,
+            None => true,
+        };
+        if ready {
+            self.emitted = Some((now, value.clone()));
+        }
+        ready
+    }
+
+    fn debounce(&mut self, now: f64, interval: f64, value: &MetaValue) -> bool {
+        match &self.pending {
+            Some((_, pending)) if pending == value => {}
+            _ => self.pending = Some((now, value.clone())),
+        }
+
+        let (changed_at, pending) = self.pending.as_ref().expect("pending set above");
+        let stable = now - changed_at >= interval;
+        let already_emitted = matches!(&self.emitted, Some((_, emitted)) if emitted == pending);
+        if stable && !already_emitted {
+            self.emitted = Some((now, pending.clone()));
+            return true;
+        }
+        false
+    }
+}
+
+impl Evaluatable for ThrottleNode {
+    fn evaluate(&self, inputs: &InputMap, ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
+        let value = inputs.get("value").cloned().unwrap_or_default();
+        let interval = inputs.get("interval").and_then(|v| v.as_scalar()).unwrap_or(0.1).max(0.0);
+        let mode = inputs.get("mode").and_then(|v| v.as_string()).unwrap_or_else(|| "throttle".to_string());
+        let now = ctx.time_seconds();
+
+        let mut state = ThrottleState::load(ctx);
+
+        // The clock went backwards (e.g. a restarted simulation): start over
+        let last_seen = state.emitted.iter().chain(state.pending.iter()).map(|(at, _)| *at).fold(f64::MIN, f64::max);
+        if now < last_seen {
+            state = ThrottleState::default();
+        }
+
+        let passed = match mode.as_str() {
+            "debounce" => state.debounce(now, interval, &value),
+            _ => state.throttle(now, interval, &value),
+        };
+
+        state.save(ctx);
+
+        let mut result = HashMap::new();
+        let output = state.emitted.as_ref().map(|(_, v)| v.clone()).unwrap_or_default();
+        result.insert("value".to_string(), output);
+        result.insert("passed".to_string(), MetaValue::Bool(passed));
+        Ok(result)
+    }
+
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::required("value", "any"),
+            InputSpec::optional("interval", "scalar", MetaValue::Scalar(0.1)),
+            InputSpec::optional("mode", "string", MetaValue::String("throttle".to_string())),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::new("value", "any"),
+            OutputSpec::new("passed", "bool"),
+        ]
+    }
+
+    fn node_type(&self) -> &'static str {
+        "throttle"
+    }
+
+    fn is_volatile(&self) -> bool {
+        true
+    }
+}
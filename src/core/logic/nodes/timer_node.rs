@@ -1,35 +1,54 @@
-use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext};
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
 use crate::core::types::MetaValue;
 use crate::core::types::error::MarcoError;
 use std::collections::HashMap;
 
+/// Countdown timer
+///
+/// The start timestamp and running flag live in the context's node state,
+/// so the timer keeps counting across evaluation passes. It starts on its
+/// first evaluation; `reset` restarts it, `start` restarts it once finished
+/// and `auto_reset` loops it. Without a current node in the context the
+/// timer counts from time zero.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TimerNode;
 
 impl Evaluatable for TimerNode {
     fn evaluate(&self, inputs: &InputMap, ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
         let duration = inputs.get("duration").and_then(|v| v.as_scalar()).unwrap_or(1.0);
-        let _auto_reset = inputs.get("auto_reset").and_then(|v| v.as_bool()).unwrap_or(false);
+        let auto_reset = inputs.get("auto_reset").and_then(|v| v.as_bool()).unwrap_or(false);
         let start_trigger = inputs.get("start").and_then(|v| v.as_bool()).unwrap_or(false);
-        let _reset_trigger = inputs.get("reset").and_then(|v| v.as_bool()).unwrap_or(false);
+        let reset_trigger = inputs.get("reset").and_then(|v| v.as_bool()).unwrap_or(false);
         
-        // Get current time from context or use a simple counter approach
-        // In a real implementation, this would track actual time
-        let current_time = ctx.registry.get_scoped(&ctx.scope_id, &crate::core::types::DotPath::from("system.time"))
-            .and_then(|v| Ok(v.as_scalar().unwrap_or(0.0)))
-            .unwrap_or(0.0);
+        // Get current time from the context clock (fixed in deterministic mode)
+        let current_time = ctx.time_seconds();
         
-        // Simple timer logic (simplified for demonstration)
-        let elapsed = current_time % (duration + 1.0); // Simple cycling
-        let progress = (elapsed / duration).min(1.0);
+        let was_running = ctx.node_state("running").and_then(|v| v.as_bool()).unwrap_or(true);
+        let stored_start = ctx.node_state("start_time").and_then(|v| v.as_scalar());
+        let mut start_time = match stored_start {
+            _ if ctx.current_node.is_none() => 0.0,
+            Some(start) if !reset_trigger && (was_running || !start_trigger) => start,
+            _ => current_time,
+        };
+        
+        let mut elapsed = (current_time - start_time).max(0.0);
+        if auto_reset && duration > 0.0 && elapsed >= duration {
+            elapsed %= duration;
+            start_time = current_time - elapsed;
+        }
         let is_finished = elapsed >= duration;
+        let elapsed = elapsed.min(duration.max(0.0));
+        let progress = if duration > 0.0 { (elapsed / duration).min(1.0) } else { 1.0 };
+        
+        ctx.set_node_state("start_time", MetaValue::Scalar(start_time));
+        ctx.set_node_state("running", MetaValue::Bool(!is_finished));
         
         let mut result = HashMap::new();
         result.insert("elapsed".to_string(), MetaValue::Scalar(elapsed));
         result.insert("progress".to_string(), MetaValue::Scalar(progress));
         result.insert("remaining".to_string(), MetaValue::Scalar((duration - elapsed).max(0.0)));
         result.insert("finished".to_string(), MetaValue::Bool(is_finished));
-        result.insert("running".to_string(), MetaValue::Bool(!is_finished && start_trigger));
+        result.insert("running".to_string(), MetaValue::Bool(!is_finished));
         
         // Percentage as integer for display
         result.insert("percentage".to_string(), MetaValue::Scalar((progress * 100.0).round()));
@@ -37,7 +56,31 @@ impl Evaluatable for TimerNode {
         Ok(result)
     }
 
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("duration", "scalar", MetaValue::Scalar(5.0)),
+            InputSpec::optional("start", "bool", MetaValue::Bool(false)),
+            InputSpec::optional("reset", "bool", MetaValue::Bool(false)),
+            InputSpec::optional("auto_reset", "bool", MetaValue::Bool(false)),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::new("elapsed", "scalar"),
+            OutputSpec::new("progress", "scalar"),
+            OutputSpec::new("percentage", "scalar"),
+            OutputSpec::new("remaining", "scalar"),
+            OutputSpec::new("finished", "bool"),
+            OutputSpec::new("running", "bool"),
+        ]
+    }
+
     fn node_type(&self) -> &'static str {
         "timer"
     }
+
+    fn is_volatile(&self) -> bool {
+        true
+    }
 }
@@ -1,4 +1,6 @@
-use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext};
+use crate::core::logic::{InputMap, OutputMap, Evaluatable, EvalContext, InputSpec, OutputSpec};
+use crate::core::logic::safe_math;
+use crate::core::logic::number_parse::{self, ParseOptions};
 use crate::core::types::MetaValue;
 use crate::core::types::error::MarcoError;
 use std::collections::HashMap;
@@ -10,8 +12,8 @@ impl Evaluatable for ValidationNode {
     fn evaluate(&self, inputs: &InputMap, _ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
         let input_value = inputs.get("value").cloned().unwrap_or(MetaValue::String("".to_string()));
         let validation_type = inputs.get("type").and_then(|v| v.as_string()).unwrap_or("text".to_string());
-        let min_length = inputs.get("min_length").and_then(|v| v.as_scalar()).unwrap_or(0.0) as usize;
-        let max_length = inputs.get("max_length").and_then(|v| v.as_scalar()).unwrap_or(1000.0) as usize;
+        let min_length = safe_math::to_index(inputs.get("min_length").and_then(|v| v.as_scalar()).unwrap_or(0.0), "min_length")?;
+        let max_length = safe_math::to_index(inputs.get("max_length").and_then(|v| v.as_scalar()).unwrap_or(1000.0), "max_length")?;
         let required = inputs.get("required").and_then(|v| v.as_bool()).unwrap_or(false);
         let _pattern = inputs.get("pattern").and_then(|v| v.as_string()).unwrap_or_default();
         
@@ -46,7 +48,7 @@ impl Evaluatable for ValidationNode {
                         }
                     },
                     "number" => {
-                        if text.parse::<f64>().is_err() {
+                        if number_parse::parse_scalar(text, ParseOptions::from_inputs(inputs)).is_none() {
                             is_valid = false;
                             errors.push(MetaValue::String("Must be a number".to_string()));
                         }
@@ -110,6 +112,29 @@ impl Evaluatable for ValidationNode {
         Ok(result)
     }
 
+    fn input_specs(&self) -> Vec<InputSpec> {
+        vec![
+            InputSpec::optional("value", "any", MetaValue::String("user@example.com".to_string())),
+            InputSpec::optional("type", "string", MetaValue::String("email".to_string())),
+            InputSpec::optional("min_length", "scalar", MetaValue::Scalar(0.0)),
+            InputSpec::optional("max_length", "scalar", MetaValue::Scalar(1000.0)),
+            InputSpec::optional("required", "bool", MetaValue::Bool(false)),
+            InputSpec::optional("thousands_separator", "string", MetaValue::String(",".to_string())),
+            InputSpec::optional("decimal_separator", "string", MetaValue::String(".".to_string())),
+        ]
+    }
+
+    fn output_specs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::new("is_valid", "bool"),
+            OutputSpec::new("errors", "list"),
+            OutputSpec::new("error_count", "scalar"),
+            OutputSpec::new("validated_value", "any"),
+            OutputSpec::new("sanitized", "any"),
+            OutputSpec::new("message", "string"),
+        ]
+    }
+
     fn node_type(&self) -> &'static str {
         "validation"
     }
@@ -0,0 +1,148 @@
+//! Lenient string-to-number parsing for node inputs
+//!
+//! Accepts the forms people type into fields: thousands separators,
+//! trailing percent signs, scientific notation and surrounding whitespace.
+use crate::core::logic::InputMap;
+
+/// Options for [`parse_scalar`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Digit grouping character, e.g. ',' in "1,234"; None disallows grouping
+    pub thousands_separator: Option<char>,
+    /// Character between the integer and fractional parts
+    pub decimal_separator: char,
+    /// Treat a trailing '%' as "divide by 100"
+    pub allow_percent: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            thousands_separator: Some(','),
+            decimal_separator: '.',
+            allow_percent: true,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Read options from node inputs, falling back to the defaults
+    pub fn from_inputs(inputs: &InputMap) -> Self {
+        let mut options = Self::default();
+        if let Some(sep) = inputs.get("thousands_separator").and_then(|v| v.as_string()) {
+            options.thousands_separator = sep.chars().next();
+        }
+        if let Some(sep) = inputs.get("decimal_separator").and_then(|v| v.as_string()).and_then(|s| s.chars().next()) {
+            options.decimal_separator = sep;
+        }
+        options
+    }
+}
+
+/// Parse a human-formatted number
+///
+/// Returns None for empty, malformed or non-finite input. Grouped digits
+/// must use groups of three ("1,234" but not "1,23").
+pub fn parse_scalar(s: &str, options: ParseOptions) -> Option<f64> {
+    let mut text = s.trim();
+    let mut scale = 1.0;
+    if options.allow_percent {
+        if let Some(stripped) = text.strip_suffix('%') {
+            text = stripped.trim_end();
+            scale = 0.01;
+        }
+    }
+    if text.is_empty() {
+        return None;
+    }
+
+    let (sign, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", text.strip_prefix('+').unwrap_or(text)),
+    };
+
+    // Split off the exponent, then the fractional part
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(pos) => (&unsigned[..pos], Some(&unsigned[pos + 1..])),
+        None => (unsigned, None),
+    };
+    let (integer, fraction) = match mantissa.split_once(options.decimal_separator) {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (mantissa, None),
+    };
+
+    let integer = ungroup(integer, options.thousands_separator)?;
+    let all_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if !all_digits(&integer) || !fraction.is_none_or(all_digits) {
+        return None;
+    }
+    if integer.is_empty() && fraction.is_none_or(str::is_empty) {
+        return None;
+    }
+
+    let mut normalized = format!("{}{}", sign, if integer.is_empty() { "0" } else { &integer });
+    if let Some(fraction) = fraction {
+        normalized.push('.');
+        normalized.push_str(fraction);
+    }
+    if let Some(exponent) = exponent {
+        let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        if digits.is_empty() || !all_digits(digits) {
+            return None;
+        }
+        normalized.push('e');
+        normalized.push_str(exponent);
+    }
+
+    let value = normalized.parse::<f64>().ok()? * scale;
+    value.is_finite().then_some(value)
+}
+
+/// Remove thousands separators after checking the grouping is well formed
+fn ungroup(integer: &str, separator: Option<char>) -> Option<String> {
+    let Some(separator) = separator.filter(|sep| integer.contains(*sep)) else {
+        return Some(integer.to_string());
+    };
+
+    let groups: Vec<&str> = integer.split(separator).collect();
+    let first_ok = (1..=3).contains(&groups[0].len());
+    let rest_ok = groups[1..].iter().all(|group| group.len() == 3);
+    (first_ok && rest_ok).then(|| groups.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Option<f64> {
+        parse_scalar(s, ParseOptions::default())
+    }
+
+    #[test]
+    fn test_parse_common_forms() {
+        assert_eq!(parse("1,234.5"), Some(1234.5));
+        assert_eq!(parse("50%"), Some(0.5));
+        assert_eq!(parse("1e3"), Some(1000.0));
+        assert_eq!(parse("  -2.5E-1 "), Some(-0.25));
+        assert_eq!(parse(".5"), Some(0.5));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_input() {
+        assert_eq!(parse("abc"), None);
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("1,23"), None);
+        assert_eq!(parse("inf"), None);
+        assert_eq!(parse("1e"), None);
+    }
+
+    #[test]
+    fn test_parse_with_european_separators() {
+        let options = ParseOptions {
+            thousands_separator: Some('.'),
+            decimal_separator: ',',
+            allow_percent: true,
+        };
+        assert_eq!(parse_scalar("1.234,5", options), Some(1234.5));
+    }
+}
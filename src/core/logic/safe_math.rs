@@ -0,0 +1,133 @@
+//! Integer-semantic helpers for scalar MetaValues
+//!
+//! Scalars are `f64`, which only represents integers exactly up to 2^53.
+//! These helpers validate index/count style inputs and implement bitwise
+//! operations on the integer portion with explicit overflow behavior.
+use crate::core::types::error::MarcoError;
+
+/// Largest integer that `f64` represents exactly (2^53 - 1)
+pub const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// How integer results outside the safe range are brought back into it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowMode {
+    /// Wrap around within the 54-bit signed range (two's complement)
+    #[default]
+    Wrapping,
+    /// Clamp to `-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER`
+    Saturating,
+}
+
+impl OverflowMode {
+    /// Parse from a node input string, defaulting to wrapping
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "saturating" | "saturate" | "clamp" => OverflowMode::Saturating,
+            _ => OverflowMode::Wrapping,
+        }
+    }
+
+    /// Bring an arbitrary integer into the safe range
+    pub fn apply(&self, value: i128) -> i64 {
+        let max = MAX_SAFE_INTEGER as i128;
+        match self {
+            OverflowMode::Saturating => value.clamp(-max, max) as i64,
+            OverflowMode::Wrapping => {
+                // Keep the low 54 bits and sign-extend
+                let shifted = (value << 74) >> 74;
+                shifted as i64
+            }
+        }
+    }
+}
+
+/// Bitwise operations available on the math node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitwiseOp {
+    And,
+    Or,
+    Xor,
+    ShiftLeft,
+    ShiftRight,
+}
+
+/// Convert a scalar to an exact integer, rounding fractional values
+///
+/// Fails for non-finite values and magnitudes beyond `MAX_SAFE_INTEGER`,
+/// where neighbouring integers are no longer distinguishable.
+pub fn to_safe_integer(value: f64, name: &str) -> Result<i64, MarcoError> {
+    if !value.is_finite() {
+        return Err(MarcoError::NodeEval(format!("'{}' must be a finite number, got {}", name, value)));
+    }
+    let rounded = value.round();
+    if rounded.abs() > MAX_SAFE_INTEGER {
+        return Err(MarcoError::NodeEval(format!(
+            "'{}' = {} exceeds the safe integer range (±{})",
+            name, value, MAX_SAFE_INTEGER
+        )));
+    }
+    Ok(rounded as i64)
+}
+
+/// Convert a scalar to a non-negative index or count
+pub fn to_index(value: f64, name: &str) -> Result<usize, MarcoError> {
+    let integer = to_safe_integer(value, name)?;
+    if integer < 0 {
+        return Err(MarcoError::NodeEval(format!("'{}' must not be negative, got {}", name, integer)));
+    }
+    Ok(integer as usize)
+}
+
+/// Apply a bitwise operation to the integer portions of `a` and `b`
+pub fn bitwise(op: BitwiseOp, a: f64, b: f64, mode: OverflowMode) -> Result<f64, MarcoError> {
+    let a = to_safe_integer(a.trunc(), "a")? as i128;
+    let b = to_safe_integer(b.trunc(), "b")? as i128;
+
+    let result = match op {
+        BitwiseOp::And => a & b,
+        BitwiseOp::Or => a | b,
+        BitwiseOp::Xor => a ^ b,
+        BitwiseOp::ShiftLeft | BitwiseOp::ShiftRight => {
+            if b < 0 {
+                return Err(MarcoError::NodeEval(format!("Shift amount must not be negative, got {}", b)));
+            }
+            // Anything past 64 bits has already shifted every bit out
+            let shift = b.min(64) as u32;
+            if op == BitwiseOp::ShiftLeft { a << shift } else { a >> shift }
+        }
+    };
+
+    Ok(mode.apply(result) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitwise_xor() {
+        let result = bitwise(BitwiseOp::Xor, 12.0, 10.0, OverflowMode::Wrapping).unwrap();
+        assert_eq!(result, 6.0);
+    }
+
+    #[test]
+    fn test_bitwise_uses_integer_portion() {
+        assert_eq!(bitwise(BitwiseOp::And, 7.9, 3.2, OverflowMode::Wrapping).unwrap(), 3.0);
+        assert_eq!(bitwise(BitwiseOp::ShiftRight, -8.0, 1.0, OverflowMode::Wrapping).unwrap(), -4.0);
+    }
+
+    #[test]
+    fn test_shift_overflow_modes() {
+        let big = 2f64.powi(52);
+        assert_eq!(bitwise(BitwiseOp::ShiftLeft, big, 1.0, OverflowMode::Saturating).unwrap(), MAX_SAFE_INTEGER);
+        assert_eq!(bitwise(BitwiseOp::ShiftLeft, big, 1.0, OverflowMode::Wrapping).unwrap(), -(2f64.powi(53)));
+    }
+
+    #[test]
+    fn test_index_beyond_safe_range_errors() {
+        let err = to_index(2f64.powi(60), "index").unwrap_err();
+        assert!(err.to_string().contains("safe integer range"));
+        assert!(to_index(-1.0, "index").is_err());
+        assert_eq!(to_index(2.6, "index").unwrap(), 3);
+    }
+}
@@ -155,6 +155,31 @@ impl RegistryDiff {
         self.changes.insert(path, change);
     }
 
+    /// Fold a later change to `path` into this diff
+    ///
+    /// The result describes the net change since the diff began, so adding
+    /// then removing a path leaves no entry for it.
+    pub fn merge_change(&mut self, path: DotPath, change: ChangeType) {
+        let merged = match (self.changes.remove(&path), change) {
+            (None, change) => Some(change),
+            (Some(ChangeType::Added { .. }), ChangeType::Removed { .. }) => None,
+            (Some(ChangeType::Added { .. }), ChangeType::Modified { new_value, .. }) => {
+                Some(ChangeType::Added { value: new_value })
+            }
+            (Some(ChangeType::Modified { old_value, .. }), ChangeType::Removed { .. }) => {
+                Some(ChangeType::Removed { old_value })
+            }
+            (Some(ChangeType::Modified { old_value, .. }), ChangeType::Modified { new_value, .. })
+            | (Some(ChangeType::Removed { old_value }), ChangeType::Added { value: new_value }) => {
+                (old_value != new_value).then_some(ChangeType::Modified { old_value, new_value })
+            }
+            (Some(_), change) => Some(change),
+        };
+        if let Some(change) = merged {
+            self.changes.insert(path, change);
+        }
+    }
+
     /// Check if this diff is empty (no changes)
     pub fn is_empty(&self) -> bool {
         self.changes.is_empty()
@@ -6,7 +6,9 @@
 mod registry;
 mod snapshot;
 mod diff;
+mod watch;
 
 pub use registry::{MetaRegistry, RegistryError};
 pub use snapshot::{Snapshot, CompressedSnapshot, HashKey};
 pub use diff::{RegistryDiff, ChangeType};
+pub use watch::{WatchHandle, WatchSample, DEFAULT_WATCH_CAPACITY};
@@ -1,7 +1,10 @@
+use crate::core::registry::{ChangeType, RegistryDiff, Snapshot, WatchHandle, DEFAULT_WATCH_CAPACITY};
+use crate::core::registry::watch::WatchBuffer;
 use crate::core::types::{DotPath, MetaValue, ScopeId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock, Weak};
 use thiserror::Error;
 use tracing::{info, warn};
 
@@ -26,7 +29,8 @@ pub enum RegistryError {
 /// with path-based navigation. It supports:
 /// 
 /// - Scoped access control
-/// - Change tracking and diffing
+/// - Change tracking and diffing, streamed to subscribers on `commit`
+/// - Watches recording the recent history of individual paths
 /// - Thread-safe concurrent access
 /// - Snapshot creation and restoration
 /// 
@@ -48,6 +52,12 @@ pub struct MetaRegistry {
     scopes: Arc<RwLock<HashMap<ScopeId, HashMap<DotPath, MetaValue>>>>,
     /// Change listeners and tracking
     listeners: Arc<RwLock<HashMap<DotPath, Vec<ListenerCallback>>>>,
+    /// Changes since the last `commit`, per scope
+    pending: Arc<Mutex<HashMap<ScopeId, RegistryDiff>>>,
+    /// Receivers of committed diffs
+    subscribers: Arc<Mutex<Vec<Sender<RegistryDiff>>>>,
+    /// History buffers of live watches, per path
+    watches: Arc<Mutex<WatchMap>>,
 }
 
 impl std::fmt::Debug for MetaRegistry {
@@ -61,6 +71,7 @@ impl std::fmt::Debug for MetaRegistry {
 }
 
 type ListenerCallback = Box<dyn Fn(&DotPath, &MetaValue, &MetaValue) + Send + Sync>;
+type WatchMap = HashMap<DotPath, Vec<Weak<Mutex<WatchBuffer>>>>;
 
 impl MetaRegistry {
     /// Create a new empty registry
@@ -69,6 +80,9 @@ impl MetaRegistry {
         Self {
             scopes: Arc::new(RwLock::new(HashMap::new())),
             listeners: Arc::new(RwLock::new(HashMap::new())),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            watches: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -110,11 +124,16 @@ impl MetaRegistry {
 
         let old_value = scope_data.get(path).cloned();
         scope_data.insert(path.clone(), value.clone());
+        drop(scopes);
 
         // Notify listeners
         if let Some(old) = old_value {
+            if old != value {
+                self.record_change(scope_id, path, ChangeType::Modified { old_value: old.clone(), new_value: value.clone() });
+            }
             self.notify_listeners(path, &old, &value);
         } else {
+            self.record_change(scope_id, path, ChangeType::Added { value: value.clone() });
             // Use default value for "old" when creating new entries
             self.notify_listeners(path, &MetaValue::default(), &value);
         }
@@ -122,6 +141,26 @@ impl MetaRegistry {
         Ok(())
     }
 
+    /// Remove the value at a scoped path, returning it
+    pub fn remove_scoped(
+        &self,
+        scope_id: &ScopeId,
+        path: &DotPath,
+    ) -> Result<MetaValue, RegistryError> {
+        let mut scopes = self.scopes.write().unwrap();
+        let scope_data = scopes
+            .get_mut(scope_id)
+            .ok_or_else(|| RegistryError::ScopeNotFound(scope_id.to_string()))?;
+        let old_value = scope_data
+            .remove(path)
+            .ok_or_else(|| RegistryError::PathNotFound(path.to_string()))?;
+        drop(scopes);
+
+        self.record_change(scope_id, path, ChangeType::Removed { old_value: old_value.clone() });
+        self.notify_listeners(path, &old_value, &MetaValue::default());
+        Ok(old_value)
+    }
+
     /// Get a value from a scoped path
     pub fn get_scoped(
         &self,
@@ -169,6 +208,37 @@ impl MetaRegistry {
         Ok(scope_data.keys().cloned().collect())
     }
 
+    /// Values in a scope whose paths match a glob pattern, sorted by path
+    ///
+    /// `*` matches one path segment and `**` any depth, as in
+    /// `scene.objects.*.position`. Unknown scopes and patterns that match
+    /// nothing give an empty result.
+    pub fn query_scoped(&self, scope_id: &ScopeId, pattern: &str) -> Vec<(DotPath, MetaValue)> {
+        let pattern = DotPath::new(pattern);
+        let scopes = self.scopes.read().unwrap();
+        let mut matches: Vec<(DotPath, MetaValue)> = scopes
+            .get(scope_id)
+            .into_iter()
+            .flat_map(|scope_data| scope_data.iter())
+            .filter(|(path, _)| path.matches_pattern(&pattern))
+            .map(|(path, value)| (path.clone(), value.clone()))
+            .collect();
+        matches.sort_by_key(|(path, _)| path.to_string());
+        matches
+    }
+
+    /// Values in every scope whose paths match a glob pattern
+    ///
+    /// See [`MetaRegistry::query_scoped`] for the pattern syntax.
+    pub fn query(&self, pattern: &str) -> Vec<(DotPath, MetaValue)> {
+        let mut matches: Vec<(DotPath, MetaValue)> = self.list_scopes()
+            .iter()
+            .flat_map(|scope_id| self.query_scoped(scope_id, pattern))
+            .collect();
+        matches.sort_by_key(|(path, _)| path.to_string());
+        matches
+    }
+
     /// Get all scopes
     pub fn list_scopes(&self) -> Vec<ScopeId> {
         let scopes = self.scopes.read().unwrap();
@@ -182,11 +252,61 @@ impl MetaRegistry {
             .get_mut(scope_id)
             .ok_or_else(|| RegistryError::ScopeNotFound(scope_id.to_string()))?;
 
-        scope_data.clear();
+        let removed: Vec<(DotPath, MetaValue)> = scope_data.drain().collect();
+        drop(scopes);
+
+        for (path, old_value) in removed {
+            self.record_change(scope_id, &path, ChangeType::Removed { old_value });
+        }
         info!("Cleared scope: {}", scope_id);
         Ok(())
     }
 
+    /// Capture every value in a scope
+    pub fn snapshot(&self, scope_id: &ScopeId) -> Result<Snapshot, RegistryError> {
+        let scopes = self.scopes.read().unwrap();
+        let scope_data = scopes
+            .get(scope_id)
+            .ok_or_else(|| RegistryError::ScopeNotFound(scope_id.to_string()))?;
+
+        Ok(Snapshot::new(scope_data.clone()))
+    }
+
+    /// Replace a scope's contents with a snapshot
+    ///
+    /// Paths missing from the snapshot are removed. Listeners are notified
+    /// for every path whose value changes.
+    pub fn restore(&self, scope_id: &ScopeId, snapshot: &Snapshot) -> Result<(), RegistryError> {
+        let previous = {
+            let mut scopes = self.scopes.write().unwrap();
+            let scope_data = scopes
+                .get_mut(scope_id)
+                .ok_or_else(|| RegistryError::ScopeNotFound(scope_id.to_string()))?;
+            std::mem::replace(scope_data, snapshot.entries.clone())
+        };
+
+        for (path, value) in &snapshot.entries {
+            match previous.get(path) {
+                Some(old) if old == value => {}
+                Some(old) => {
+                    self.record_change(scope_id, path, ChangeType::Modified { old_value: old.clone(), new_value: value.clone() });
+                    self.notify_listeners(path, old, value);
+                }
+                None => {
+                    self.record_change(scope_id, path, ChangeType::Added { value: value.clone() });
+                    self.notify_listeners(path, &MetaValue::default(), value);
+                }
+            }
+        }
+        for (path, old) in previous.iter().filter(|(path, _)| !snapshot.entries.contains_key(*path)) {
+            self.record_change(scope_id, path, ChangeType::Removed { old_value: old.clone() });
+            self.notify_listeners(path, old, &MetaValue::default());
+        }
+
+        info!("Restored scope {} from snapshot ({} paths)", scope_id, snapshot.entries.len());
+        Ok(())
+    }
+
     /// Register a change listener for a specific path
     pub fn add_listener<F>(&self, path: DotPath, callback: F)
     where
@@ -199,7 +319,89 @@ impl MetaRegistry {
             .push(Box::new(callback));
     }
 
-    /// Notify all listeners of a change
+    /// Record every value written to `path`, in any scope
+    ///
+    /// Keeps the last `DEFAULT_WATCH_CAPACITY` values; see
+    /// `watch_with_capacity`.
+    pub fn watch(&self, path: DotPath) -> WatchHandle {
+        self.watch_with_capacity(path, DEFAULT_WATCH_CAPACITY)
+    }
+
+    /// Record every value written to `path`, keeping the last `capacity`
+    ///
+    /// Writes are recorded even when the value did not change, so a value
+    /// set once per frame gets one sample per frame. Removing the path
+    /// records the default value, as listeners see it.
+    pub fn watch_with_capacity(&self, path: DotPath, capacity: usize) -> WatchHandle {
+        let handle = WatchHandle::new(path.clone(), capacity);
+        self.watches.lock().unwrap()
+            .entry(path)
+            .or_default()
+            .push(handle.downgrade());
+        handle
+    }
+
+    /// Stop recording and free the history of `handle`
+    ///
+    /// Dropping the handle has the same effect; the registry forgets it on
+    /// the next write to its path.
+    pub fn unwatch(&self, handle: WatchHandle) {
+        let mut watches = self.watches.lock().unwrap();
+        let path = handle.path().clone();
+        drop(handle);
+        if let Some(buffers) = watches.get_mut(&path) {
+            buffers.retain(|buffer| buffer.strong_count() > 0);
+            if buffers.is_empty() {
+                watches.remove(&path);
+            }
+        }
+    }
+
+    /// Number of paths with at least one live watch
+    pub fn watched_path_count(&self) -> usize {
+        self.watches.lock().unwrap()
+            .values()
+            .filter(|buffers| buffers.iter().any(|buffer| buffer.strong_count() > 0))
+            .count()
+    }
+
+    /// Receive a diff for every scope that changed, each time `commit` runs
+    pub fn subscribe(&self) -> Receiver<RegistryDiff> {
+        let (sender, receiver) = channel();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// Broadcast the changes made since the last commit
+    ///
+    /// Sends one diff per changed scope, with the scope id as the diff's
+    /// `source`. Changes to the same path within a batch are folded into one
+    /// net change. Subscribers whose receiver was dropped are removed.
+    /// Returns the number of diffs sent to each subscriber.
+    pub fn commit(&self) -> usize {
+        let batches: Vec<(ScopeId, RegistryDiff)> = self.pending.lock().unwrap()
+            .drain()
+            .filter(|(_, diff)| !diff.is_empty())
+            .collect();
+
+        let mut subscribers = self.subscribers.lock().unwrap();
+        for (scope_id, changes) in &batches {
+            let mut diff = RegistryDiff::with_metadata(None, Some(scope_id.to_string()));
+            diff.changes = changes.changes.clone();
+            subscribers.retain(|sender| sender.send(diff.clone()).is_ok());
+        }
+        batches.len()
+    }
+
+    /// Add a change to the scope's pending batch
+    fn record_change(&self, scope_id: &ScopeId, path: &DotPath, change: ChangeType) {
+        self.pending.lock().unwrap()
+            .entry(scope_id.clone())
+            .or_default()
+            .merge_change(path.clone(), change);
+    }
+
+    /// Notify all listeners and watches of a change
     fn notify_listeners(&self, path: &DotPath, old_value: &MetaValue, new_value: &MetaValue) {
         let listeners = self.listeners.read().unwrap();
         if let Some(path_listeners) = listeners.get(path) {
@@ -207,6 +409,26 @@ impl MetaRegistry {
                 listener(path, old_value, new_value);
             }
         }
+        drop(listeners);
+        self.record_watches(path, new_value);
+    }
+
+    /// Append `value` to every watch on `path`, forgetting dropped watches
+    fn record_watches(&self, path: &DotPath, value: &MetaValue) {
+        let mut watches = self.watches.lock().unwrap();
+        let Some(buffers) = watches.get_mut(path) else {
+            return;
+        };
+        buffers.retain(|buffer| match buffer.upgrade() {
+            Some(buffer) => {
+                buffer.lock().unwrap().record(value.clone());
+                true
+            }
+            None => false,
+        });
+        if buffers.is_empty() {
+            watches.remove(path);
+        }
     }
 }
 
@@ -255,6 +477,80 @@ mod tests {
         assert_eq!(registry.get_scoped(&scope2, &path).unwrap().as_scalar(), Some(2.0));
     }
 
+    #[test]
+    fn test_snapshot_restore_replaces_scope() {
+        let mut registry = MetaRegistry::new();
+        let scope = registry.create_scope();
+        let kept = DotPath::from("canvas.width");
+        let added = DotPath::from("canvas.height");
+
+        registry.set_scoped(&scope, &kept, MetaValue::from(800.0)).unwrap();
+        let snapshot = registry.snapshot(&scope).unwrap();
+        registry.set_scoped(&scope, &kept, MetaValue::from(1024.0)).unwrap();
+        registry.set_scoped(&scope, &added, MetaValue::from(600.0)).unwrap();
+
+        registry.restore(&scope, &snapshot).unwrap();
+        assert_eq!(registry.get_scoped(&scope, &kept).unwrap().as_scalar(), Some(800.0));
+        assert!(!registry.exists_scoped(&scope, &added));
+        assert!(registry.snapshot(&ScopeId::new()).is_err());
+    }
+
+    #[test]
+    fn test_commit_streams_set_and_remove_diffs() {
+        let mut registry = MetaRegistry::new();
+        let scope = registry.create_scope();
+        let path = DotPath::from("doc.title");
+        let diffs = registry.subscribe();
+
+        registry.set_scoped(&scope, &path, MetaValue::from("draft")).unwrap();
+        assert_eq!(registry.commit(), 1);
+        registry.remove_scoped(&scope, &path).unwrap();
+        registry.commit();
+
+        let added = diffs.try_recv().unwrap();
+        assert_eq!(added.metadata.source, Some(scope.to_string()));
+        assert_eq!(added.changes.get(&path), Some(&ChangeType::Added { value: MetaValue::from("draft") }));
+        let removed = diffs.try_recv().unwrap();
+        assert_eq!(removed.changes.get(&path), Some(&ChangeType::Removed { old_value: MetaValue::from("draft") }));
+        assert!(diffs.try_recv().is_err());
+
+        // Changes within one batch fold into their net effect
+        registry.set_scoped(&scope, &path, MetaValue::from("a")).unwrap();
+        registry.set_scoped(&scope, &path, MetaValue::from("b")).unwrap();
+        registry.remove_scoped(&scope, &path).unwrap();
+        assert_eq!(registry.commit(), 0);
+        assert!(registry.remove_scoped(&scope, &path).is_err());
+    }
+
+    #[test]
+    fn test_query_matches_single_segment_and_recursive_wildcards() {
+        let mut registry = MetaRegistry::new();
+        let scope = registry.create_scope();
+        for (path, value) in [
+            ("scene.objects.0.position", 1.0),
+            ("scene.objects.1.position", 2.0),
+            ("scene.objects.1.scale", 3.0),
+            ("scene.lights.0.position", 4.0),
+            ("scene.objects.1.transform.position", 5.0),
+        ] {
+            registry.set_scoped(&scope, &DotPath::from(path), MetaValue::from(value)).unwrap();
+        }
+
+        let paths = |pattern: &str| -> Vec<String> {
+            registry.query(pattern).into_iter().map(|(path, _)| path.to_string()).collect()
+        };
+        assert_eq!(paths("scene.objects.*.position"), vec!["scene.objects.0.position", "scene.objects.1.position"]);
+        assert_eq!(paths("scene.**.position"), vec![
+            "scene.lights.0.position",
+            "scene.objects.0.position",
+            "scene.objects.1.position",
+            "scene.objects.1.transform.position",
+        ]);
+        assert_eq!(registry.query_scoped(&scope, "scene.objects.1.*")[0].1, MetaValue::from(2.0));
+        assert!(registry.query("scene.cameras.*").is_empty());
+        assert!(registry.query_scoped(&ScopeId::new(), "**").is_empty());
+    }
+
     #[test]
     fn test_error_handling() {
         let registry = MetaRegistry::new();
@@ -264,4 +560,33 @@ mod tests {
         // Should error on nonexistent scope
         assert!(registry.get_scoped(&nonexistent_scope, &path).is_err());
     }
+
+    #[test]
+    fn test_watch_keeps_the_latest_values_in_order() {
+        let mut registry = MetaRegistry::new();
+        let scope = registry.create_scope();
+        let path = DotPath::from("player.x");
+        let mut watch = registry.watch_with_capacity(path.clone(), 3);
+
+        for x in 0..5 {
+            registry.set_scoped(&scope, &path, MetaValue::from(x as f64)).unwrap();
+        }
+        registry.set_scoped(&scope, &DotPath::from("player.y"), MetaValue::from(9.0)).unwrap();
+
+        let history = watch.history();
+        let values: Vec<f64> = history.iter().map(|(_, value)| value.as_scalar().unwrap()).collect();
+        assert_eq!(values, vec![2.0, 3.0, 4.0]);
+        assert!(history.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert_eq!(watch.capacity(), 3);
+
+        // Unwatching frees the buffer and stops recording
+        assert_eq!(registry.watched_path_count(), 1);
+        registry.unwatch(watch);
+        assert_eq!(registry.watched_path_count(), 0);
+
+        let dropped = registry.watch(path.clone());
+        drop(dropped);
+        registry.set_scoped(&scope, &path, MetaValue::from(5.0)).unwrap();
+        assert_eq!(registry.watched_path_count(), 0);
+    }
 }
@@ -0,0 +1,87 @@
+use crate::core::types::{DotPath, MetaValue};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Instant;
+
+/// Samples a new watch keeps before dropping the oldest
+pub const DEFAULT_WATCH_CAPACITY: usize = 256;
+
+/// A timestamped value recorded by a watch
+pub type WatchSample = (Instant, MetaValue);
+
+/// Fixed-size history of the values written to one path
+#[derive(Debug)]
+pub(crate) struct WatchBuffer {
+    samples: VecDeque<WatchSample>,
+    capacity: usize,
+}
+
+impl WatchBuffer {
+    /// Append a sample, dropping the oldest once full
+    pub(crate) fn record(&mut self, value: MetaValue) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((Instant::now(), value));
+    }
+}
+
+/// Records the values written to a registry path, newest last
+///
+/// The registry holds only a weak reference to the history, so dropping
+/// the handle (or passing it to `MetaRegistry::unwatch`) frees the buffer
+/// and stops recording.
+#[derive(Debug)]
+pub struct WatchHandle {
+    path: DotPath,
+    buffer: Arc<Mutex<WatchBuffer>>,
+    /// Copy of the buffer handed out by `history`
+    view: Vec<WatchSample>,
+}
+
+impl WatchHandle {
+    pub(crate) fn new(path: DotPath, capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            path,
+            buffer: Arc::new(Mutex::new(WatchBuffer { samples: VecDeque::with_capacity(capacity), capacity })),
+            view: Vec::new(),
+        }
+    }
+
+    /// Reference the registry records through
+    pub(crate) fn downgrade(&self) -> Weak<Mutex<WatchBuffer>> {
+        Arc::downgrade(&self.buffer)
+    }
+
+    pub fn path(&self) -> &DotPath {
+        &self.path
+    }
+
+    /// Most samples kept
+    pub fn capacity(&self) -> usize {
+        self.buffer.lock().unwrap().capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.lock().unwrap().samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Recorded values, oldest first
+    pub fn history(&mut self) -> &[WatchSample] {
+        let buffer = self.buffer.lock().unwrap();
+        self.view.clear();
+        self.view.extend(buffer.samples.iter().cloned());
+        &self.view
+    }
+
+    /// Forget every sample recorded so far
+    pub fn clear(&mut self) {
+        self.buffer.lock().unwrap().samples.clear();
+        self.view.clear();
+    }
+}
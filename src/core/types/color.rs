@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use super::error::MarcoError;
 
 /// RGBA color representation for Marco 2.0
 /// 
@@ -48,6 +49,78 @@ impl ColorRGBA {
         )
     }
 
+    /// Hue in degrees [0, 360), saturation and value in [0, 1]
+    ///
+    /// Grays have no hue and report 0.
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let delta = max - min;
+        let hue = if delta <= f32::EPSILON {
+            0.0
+        } else if max == self.r {
+            60.0 * ((self.g - self.b) / delta).rem_euclid(6.0)
+        } else if max == self.g {
+            60.0 * ((self.b - self.r) / delta + 2.0)
+        } else {
+            60.0 * ((self.r - self.g) / delta + 4.0)
+        };
+        let saturation = if max <= f32::EPSILON { 0.0 } else { delta / max };
+        (hue.rem_euclid(360.0), saturation, max)
+    }
+
+    /// Create a color from hue in degrees (wrapped to [0, 360)), saturation
+    /// and value
+    pub fn from_hsv(h: f32, s: f32, v: f32, a: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let s = s.clamp(0.0, 1.0);
+        let v = v.clamp(0.0, 1.0);
+        let chroma = v * s;
+        let x = chroma * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = v - chroma;
+        Self::new(r + m, g + m, b + m, a)
+    }
+
+    /// `#RRGGBB`, or `#RRGGBBAA` when not fully opaque
+    pub fn to_hex(&self) -> String {
+        let [r, g, b, a] = [self.r, self.g, self.b, self.a].map(|c| (c * 255.0).round() as u8);
+        if a == 255 {
+            format!("#{:02X}{:02X}{:02X}", r, g, b)
+        } else {
+            format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+        }
+    }
+
+    /// Parse `#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA`; the `#` is optional
+    pub fn from_hex(hex: &str) -> Result<Self, MarcoError> {
+        let invalid = || MarcoError::InvalidOperation(format!("Invalid hex color: {}", hex));
+        let digits = hex.trim().trim_start_matches('#');
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channels: Vec<u8> = match digits.len() {
+            // Short forms repeat each digit, so "f80" is "ff8800"
+            3 | 4 => digits.chars()
+                .map(|c| c.to_digit(16).map(|d| d as u8 * 17))
+                .collect::<Option<_>>()
+                .ok_or_else(invalid)?,
+            6 | 8 => (0..digits.len()).step_by(2)
+                .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+                .collect::<Option<_>>()
+                .ok_or_else(invalid)?,
+            _ => return Err(invalid()),
+        };
+        Ok(Self::from_u8(channels[0], channels[1], channels[2], channels.get(3).copied().unwrap_or(255)))
+    }
+
     /// Create a transparent color
     pub fn transparent() -> Self {
         Self::new(0.0, 0.0, 0.0, 0.0)
@@ -98,4 +171,32 @@ mod tests {
         assert_eq!(b, 64);
         assert_eq!(a, 255);
     }
+
+    #[test]
+    fn test_hsv_round_trip() {
+        for (r, g, b) in [(1.0, 0.0, 0.0), (0.2, 0.6, 0.4), (0.9, 0.85, 0.1), (0.3, 0.1, 0.7), (0.5, 0.5, 0.5)] {
+            let color = ColorRGBA::new(r, g, b, 0.5);
+            let (h, s, v) = color.to_hsv();
+            let back = ColorRGBA::from_hsv(h, s, v, color.a);
+            for (a, b) in [(back.r, r), (back.g, g), (back.b, b), (back.a, 0.5)] {
+                assert!((a - b).abs() < 1e-5, "{:?} -> {:?}", color, back);
+            }
+        }
+        let (h, s, v) = ColorRGBA::rgb(0.0, 0.0, 1.0).to_hsv();
+        assert!((h - 240.0).abs() < 1e-4 && s == 1.0 && v == 1.0);
+        assert_eq!(ColorRGBA::from_hsv(-240.0, 1.0, 1.0, 1.0), ColorRGBA::green());
+    }
+
+    #[test]
+    fn test_hex_parsing() {
+        assert_eq!(ColorRGBA::from_hex("#F80").unwrap(), ColorRGBA::from_u8(255, 136, 0, 255));
+        assert_eq!(ColorRGBA::from_hex("#336699CC").unwrap(), ColorRGBA::from_u8(0x33, 0x66, 0x99, 0xCC));
+        assert_eq!(ColorRGBA::from_hex("00ff00").unwrap(), ColorRGBA::green());
+        for bad in ["#12", "#12345", "#GG0000", "", "#ü00"] {
+            assert!(ColorRGBA::from_hex(bad).is_err(), "{}", bad);
+        }
+
+        assert_eq!(ColorRGBA::from_u8(255, 136, 0, 255).to_hex(), "#FF8800");
+        assert_eq!(ColorRGBA::from_hex("#336699CC").unwrap().to_hex(), "#336699CC");
+    }
 }
@@ -87,6 +87,26 @@ impl DotPath {
         }
     }
 
+    /// Check this path against a glob pattern
+    ///
+    /// In the pattern `*` matches exactly one segment and `**` matches any
+    /// number of segments, including none. Scopes are ignored.
+    pub fn matches_pattern(&self, pattern: &DotPath) -> bool {
+        fn matches(pattern: &[String], segments: &[String]) -> bool {
+            match pattern.split_first() {
+                None => segments.is_empty(),
+                Some((head, rest)) if head == "**" => {
+                    (0..=segments.len()).any(|skip| matches(rest, &segments[skip..]))
+                }
+                Some((head, rest)) => match segments.split_first() {
+                    Some((segment, remaining)) => (head == "*" || head == segment) && matches(rest, remaining),
+                    None => false,
+                },
+            }
+        }
+        matches(&pattern.segments, &self.segments)
+    }
+
     /// Convert to a string representation
     pub fn to_string_with_scope(&self) -> String {
         match &self.scope {
@@ -1,4 +1,5 @@
-use super::ColorRGBA;
+use super::{ColorRGBA, DotPath};
+use super::error::MarcoError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -28,6 +29,16 @@ pub enum MetaValue {
     List(Vec<MetaValue>),
     /// Object with string keys and MetaValue values (maps, records)
     Object(HashMap<String, MetaValue>),
+    /// Row-major RGBA image, `pixels.len() == width * height`
+    Image {
+        width: u32,
+        height: u32,
+        pixels: Vec<ColorRGBA>,
+    },
+    /// 2D vector, serialized as `[x, y]`
+    Vec2(glam::Vec2),
+    /// 3D vector, serialized as `[x, y, z]`
+    Vec3(glam::Vec3),
 }
 
 impl MetaValue {
@@ -83,6 +94,30 @@ impl MetaValue {
         }
     }
 
+    /// Safe conversion to image as `(width, height, pixels)`
+    pub fn as_image(&self) -> Option<(u32, u32, &[ColorRGBA])> {
+        match self {
+            MetaValue::Image { width, height, pixels } => Some((*width, *height, pixels)),
+            _ => None,
+        }
+    }
+
+    /// Safe conversion to a 2D vector
+    pub fn as_vec2(&self) -> Option<glam::Vec2> {
+        match self {
+            MetaValue::Vec2(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Safe conversion to a 3D vector
+    pub fn as_vec3(&self) -> Option<glam::Vec3> {
+        match self {
+            MetaValue::Vec3(value) => Some(*value),
+            _ => None,
+        }
+    }
+
     /// Mutable access to object
     pub fn as_object_mut(&mut self) -> Option<&mut HashMap<String, MetaValue>> {
         match self {
@@ -100,6 +135,9 @@ impl MetaValue {
             MetaValue::Color(_) => "color",
             MetaValue::List(_) => "list",
             MetaValue::Object(_) => "object",
+            MetaValue::Image { .. } => "image",
+            MetaValue::Vec2(_) => "vec2",
+            MetaValue::Vec3(_) => "vec3",
         }
     }
 
@@ -118,6 +156,31 @@ impl MetaValue {
         MetaValue::List(Vec::new())
     }
 
+    /// Create an image MetaValue, checking the pixel count matches the size
+    pub fn new_image(width: u32, height: u32, pixels: Vec<ColorRGBA>) -> Option<Self> {
+        if pixels.len() != width as usize * height as usize {
+            return None;
+        }
+        Some(MetaValue::Image { width, height, pixels })
+    }
+
+    /// Convert a JSON value, or None for JSON `null`
+    ///
+    /// There is no null MetaValue, so nulls inside arrays and objects are
+    /// dropped rather than replaced with a stand-in value.
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        match value {
+            serde_json::Value::Null => None,
+            serde_json::Value::Bool(b) => Some(MetaValue::Bool(*b)),
+            serde_json::Value::Number(n) => n.as_f64().map(MetaValue::Scalar),
+            serde_json::Value::String(s) => Some(MetaValue::String(s.clone())),
+            serde_json::Value::Array(items) => Some(MetaValue::List(items.iter().filter_map(Self::from_json).collect())),
+            serde_json::Value::Object(fields) => Some(MetaValue::Object(
+                fields.iter().filter_map(|(k, v)| Self::from_json(v).map(|v| (k.clone(), v))).collect()
+            )),
+        }
+    }
+
     /// Insert a value into an object (if this MetaValue is an object)
     pub fn insert(&mut self, key: String, value: MetaValue) -> Option<MetaValue> {
         self.as_object_mut()?.insert(key, value)
@@ -127,6 +190,61 @@ impl MetaValue {
     pub fn get(&self, key: &str) -> Option<&MetaValue> {
         self.as_object()?.get(key)
     }
+
+    /// Look up a nested value, one path segment per object key or list index
+    pub fn get_path(&self, path: &DotPath) -> Option<&MetaValue> {
+        path.segments().iter().try_fold(self, |value, segment| value.child(segment))
+    }
+
+    /// Store `value` at a nested path, returning the value it replaced
+    ///
+    /// Missing object keys along the way are created as empty objects. List
+    /// indices must already exist. Fails if the path runs into a value that
+    /// is neither an object nor a list.
+    pub fn set_path(&mut self, path: &DotPath, value: MetaValue) -> Result<Option<MetaValue>, MarcoError> {
+        let Some((last, parents)) = path.segments().split_last() else {
+            return Err(MarcoError::InvalidOperation("Empty path".to_string()));
+        };
+
+        let mut current = self;
+        for segment in parents {
+            current = match current {
+                MetaValue::Object(map) => map.entry(segment.clone()).or_insert_with(MetaValue::new_object),
+                MetaValue::List(items) => Self::list_slot(items, segment, path)?,
+                other => return Err(Self::not_a_container(other, segment, path)),
+            };
+        }
+
+        match current {
+            MetaValue::Object(map) => Ok(map.insert(last.clone(), value)),
+            MetaValue::List(items) => Ok(Some(std::mem::replace(Self::list_slot(items, last, path)?, value))),
+            other => Err(Self::not_a_container(other, last, path)),
+        }
+    }
+
+    /// Entry of an object or list addressed by one path segment
+    fn child(&self, segment: &str) -> Option<&MetaValue> {
+        match self {
+            MetaValue::Object(map) => map.get(segment),
+            MetaValue::List(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        }
+    }
+
+    fn list_slot<'a>(items: &'a mut [MetaValue], segment: &str, path: &DotPath) -> Result<&'a mut MetaValue, MarcoError> {
+        let len = items.len();
+        segment.parse::<usize>().ok()
+            .and_then(|index| items.get_mut(index))
+            .ok_or_else(|| MarcoError::InvalidOperation(format!(
+                "'{}' is not an index into a list of {} at '{}'", segment, len, path
+            )))
+    }
+
+    fn not_a_container(value: &MetaValue, segment: &str, path: &DotPath) -> MarcoError {
+        MarcoError::InvalidOperation(format!(
+            "Cannot descend into {} at '{}' of '{}'", value.type_name(), segment, path
+        ))
+    }
 }
 
 // Convenient From implementations for common types
@@ -172,6 +290,18 @@ impl From<ColorRGBA> for MetaValue {
     }
 }
 
+impl From<glam::Vec2> for MetaValue {
+    fn from(value: glam::Vec2) -> Self {
+        MetaValue::Vec2(value)
+    }
+}
+
+impl From<glam::Vec3> for MetaValue {
+    fn from(value: glam::Vec3) -> Self {
+        MetaValue::Vec3(value)
+    }
+}
+
 impl From<Vec<MetaValue>> for MetaValue {
     fn from(value: Vec<MetaValue>) -> Self {
         MetaValue::List(value)
@@ -228,6 +358,40 @@ mod tests {
         assert!(obj.get("z").is_none());
     }
 
+    #[test]
+    fn test_from_json_drops_nulls() {
+        let json = serde_json::json!({ "name": "cube", "size": 2, "tags": ["a", null], "parent": null });
+        let value = MetaValue::from_json(&json).unwrap();
+
+        assert_eq!(value.get("name"), Some(&MetaValue::from("cube")));
+        assert_eq!(value.get("size"), Some(&MetaValue::from(2.0)));
+        assert_eq!(value.get("tags"), Some(&MetaValue::List(vec![MetaValue::from("a")])));
+        assert!(value.get("parent").is_none());
+        assert!(MetaValue::from_json(&serde_json::Value::Null).is_none());
+    }
+
+    #[test]
+    fn test_nested_paths_get_set_and_roundtrip() {
+        let mut scene = MetaValue::new_object();
+        scene.set_path(&DotPath::new("camera.position"), MetaValue::from(glam::Vec2::new(1.0, 2.0))).unwrap();
+        scene.set_path(&DotPath::new("objects"), MetaValue::List(vec![MetaValue::new_object()])).unwrap();
+        scene.set_path(&DotPath::new("objects.0.name"), MetaValue::from("cube")).unwrap();
+
+        assert_eq!(scene.get_path(&DotPath::new("objects.0.name")), Some(&MetaValue::from("cube")));
+        assert_eq!(scene.get_path(&DotPath::new("camera.position")).and_then(|v| v.as_vec2()), Some(glam::Vec2::new(1.0, 2.0)));
+        assert!(scene.get_path(&DotPath::new("objects.1.name")).is_none());
+
+        let previous = scene.set_path(&DotPath::new("objects.0.name"), MetaValue::from("sphere")).unwrap();
+        assert_eq!(previous, Some(MetaValue::from("cube")));
+        assert!(scene.set_path(&DotPath::new("objects.0.name.first"), MetaValue::from(1.0)).is_err());
+        assert!(scene.set_path(&DotPath::new("objects.5"), MetaValue::from(1.0)).is_err());
+
+        let json = serde_json::to_string(&scene).unwrap();
+        let restored: MetaValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, scene);
+        assert_eq!(restored.get_path(&DotPath::new("objects.0.name")), Some(&MetaValue::from("sphere")));
+    }
+
     #[test]
     fn test_type_names() {
         assert_eq!(MetaValue::from(1.0).type_name(), "scalar");
@@ -235,5 +399,17 @@ mod tests {
         assert_eq!(MetaValue::from("test").type_name(), "string");
         assert_eq!(MetaValue::new_list().type_name(), "list");
         assert_eq!(MetaValue::new_object().type_name(), "object");
+        assert_eq!(MetaValue::new_image(1, 1, vec![ColorRGBA::white()]).unwrap().type_name(), "image");
+    }
+
+    #[test]
+    fn test_image_roundtrip_and_size_check() {
+        let image = MetaValue::new_image(2, 1, vec![ColorRGBA::red(), ColorRGBA::blue()]).unwrap();
+        let json = serde_json::to_string(&image).unwrap();
+        let restored: MetaValue = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, image);
+        assert_eq!(restored.as_image().map(|(w, h, p)| (w, h, p.len())), Some((2, 1, 2)));
+
+        assert!(MetaValue::new_image(2, 2, vec![ColorRGBA::red()]).is_none());
     }
 }
@@ -1,8 +1,10 @@
 use super::graph::{LogicGraph, GraphError};
 use super::types::{NodeId, GraphValidation};
-use crate::core::logic::{EvaluationContext, InputMap, OutputMap, NodeInputBinding};
+use crate::core::logic::{EvalContext, EvaluationContext, InputMap, OutputMap, NodeInputBinding};
 use crate::core::types::{DotPath, MetaValue};
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn, error};
 
 /// Execution result for a single node
@@ -23,6 +25,139 @@ pub struct GraphExecutionStats {
     pub nodes_skipped: usize,
     pub nodes_failed: usize,
     pub execution_order: Vec<NodeId>,
+    /// Wall time of each node run in this pass, including input resolution
+    pub per_node_times: HashMap<NodeId, Duration>,
+}
+
+impl GraphExecutionStats {
+    /// Node that took longest in this pass
+    pub fn slowest_node(&self) -> Option<(&NodeId, Duration)> {
+        self.per_node_times.iter()
+            .max_by_key(|(_, time)| **time)
+            .map(|(id, time)| (id, *time))
+    }
+}
+
+/// How a pass with breakpoints ended
+#[derive(Debug)]
+pub enum ExecutionOutcome {
+    /// Every scheduled node ran
+    Completed(GraphExecutionStats),
+    /// Execution stopped before a node; continue with `step` or `resume`
+    Paused(Box<PausedExecution>),
+}
+
+/// A pass stopped before a breakpointed node
+///
+/// Nodes before the pause have run and cached their outputs; the paused
+/// node and everything after it in the schedule have not.
+#[derive(Debug)]
+pub struct PausedExecution {
+    run: ExecutionRun,
+    inputs: InputMap,
+}
+
+impl PausedExecution {
+    /// Node execution stopped before
+    pub fn node_id(&self) -> &NodeId {
+        &self.run.schedule[self.run.next]
+    }
+
+    /// Inputs the paused node will be evaluated with
+    pub fn inputs(&self) -> &InputMap {
+        &self.inputs
+    }
+
+    /// Nodes still to run, starting with the paused node
+    pub fn remaining(&self) -> &[NodeId] {
+        &self.run.schedule[self.run.next..]
+    }
+
+    /// Statistics for the nodes run so far
+    pub fn stats(&self) -> &GraphExecutionStats {
+        &self.run.stats
+    }
+
+    /// Run the paused node, then pause again before the next one
+    pub fn step(self, graph: &mut LogicGraph) -> Result<ExecutionOutcome, GraphError> {
+        self.run.advance(graph, true, true)
+    }
+
+    /// Run the paused node and continue until the next breakpoint
+    pub fn resume(self, graph: &mut LogicGraph) -> Result<ExecutionOutcome, GraphError> {
+        self.run.advance(graph, true, false)
+    }
+}
+
+/// A pass over a fixed schedule that can stop between nodes
+#[derive(Debug)]
+struct ExecutionRun {
+    schedule: Vec<NodeId>,
+    /// Index of the next node to run
+    next: usize,
+    eval_context: EvaluationContext,
+    node_context: EvalContext,
+    max_node_time: Option<Duration>,
+    breakpoints: HashSet<NodeId>,
+    stats: GraphExecutionStats,
+    start_time: Instant,
+}
+
+impl ExecutionRun {
+    fn new(
+        graph: &LogicGraph,
+        schedule: Vec<NodeId>,
+        node_context: &EvalContext,
+        max_node_time: Option<Duration>,
+        stats: GraphExecutionStats,
+    ) -> Self {
+        Self {
+            schedule,
+            next: 0,
+            eval_context: EvaluationContext::new(graph.registry().clone(), graph.scope_id().clone()),
+            node_context: node_context.clone(),
+            max_node_time,
+            breakpoints: HashSet::new(),
+            stats,
+            start_time: Instant::now(),
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.next >= self.schedule.len()
+    }
+
+    /// Run the next scheduled node
+    fn run_next(&mut self, graph: &mut LogicGraph) -> Result<(), GraphError> {
+        let node_id = self.schedule[self.next].clone();
+        self.next += 1;
+        GraphExecutor::run_node(graph, &node_id, &mut self.eval_context, &self.node_context, self.max_node_time, &mut self.stats)
+    }
+
+    /// Run nodes until one should pause, or the schedule ends
+    ///
+    /// `from_pause` runs the next node even if it has a breakpoint, since
+    /// execution already stopped there; `single_step` pauses before every
+    /// node after the first.
+    fn advance(mut self, graph: &mut LogicGraph, from_pause: bool, single_step: bool) -> Result<ExecutionOutcome, GraphError> {
+        let mut ran = 0;
+        while !self.is_finished() {
+            let node_id = &self.schedule[self.next];
+            let may_pause = !(from_pause && ran == 0);
+            if may_pause && (self.breakpoints.contains(node_id) || (single_step && ran > 0)) {
+                debug!("Paused before node {}", node_id);
+                let inputs = GraphExecutor::gather_inputs(graph, node_id, &mut self.eval_context);
+                return Ok(ExecutionOutcome::Paused(Box::new(PausedExecution { run: self, inputs })));
+            }
+            self.run_next(graph)?;
+            ran += 1;
+        }
+
+        self.stats.total_execution_time_ms = self.start_time.elapsed().as_secs_f64() * 1000.0;
+        info!("Graph execution completed: {} nodes executed, {} failed, {:.2}ms total",
+            self.stats.nodes_executed, self.stats.nodes_failed, self.stats.total_execution_time_ms);
+        Ok(ExecutionOutcome::Completed(self.stats))
+    }
 }
 
 /// The graph execution engine
@@ -31,64 +166,269 @@ pub struct GraphExecutor;
 impl GraphExecutor {
     /// Execute the entire graph, respecting dependencies
     pub fn execute_graph(graph: &mut LogicGraph) -> Result<GraphExecutionStats, GraphError> {
+        let node_context = EvalContext::new(graph.registry().clone(), graph.scope_id().clone());
+        Self::execute_graph_with_context(graph, &node_context)
+    }
+
+    /// Execute the entire graph using a caller-provided node context
+    ///
+    /// The context supplies the clock and RNG seen by every node, so a
+    /// deterministic context makes the whole pass reproducible.
+    pub fn execute_graph_with_context(
+        graph: &mut LogicGraph,
+        node_context: &EvalContext,
+    ) -> Result<GraphExecutionStats, GraphError> {
+        Self::execute_graph_with_limit(graph, node_context, None)
+    }
+
+    /// Execute the entire graph, failing if any node runs longer than `max_node_time`
+    ///
+    /// Nodes cannot be interrupted, so the offending node completes and
+    /// execution stops after it.
+    pub fn execute_graph_with_limit(
+        graph: &mut LogicGraph,
+        node_context: &EvalContext,
+        max_node_time: Option<Duration>,
+    ) -> Result<GraphExecutionStats, GraphError> {
         let start_time = std::time::Instant::now();
         info!("Starting graph execution for scope: {}", graph.scope_id().to_string());
 
-        // Validate graph first
+        let execution_order = Self::full_schedule(graph)?;
+        let stats = GraphExecutionStats {
+            total_execution_time_ms: 0.0,
+            nodes_executed: 0,
+            nodes_skipped: 0,
+            nodes_failed: 0,
+            execution_order: execution_order.clone(),
+            per_node_times: HashMap::new(),
+        };
+
+        let mut stats = Self::run_nodes(graph, execution_order, node_context, max_node_time, stats)?;
+
+        stats.total_execution_time_ms = start_time.elapsed().as_secs_f64() * 1000.0;
+        
+        info!("Graph execution completed: {} nodes executed, {} failed, {:.2}ms total",
+            stats.nodes_executed, stats.nodes_failed, stats.total_execution_time_ms);
+
+        Ok(stats)
+    }
+
+    /// Execute the entire graph, pausing before any node in `breakpoints`
+    ///
+    /// A paused pass holds on to its schedule and evaluation state until
+    /// it is stepped or resumed with the same graph. Its total time
+    /// includes the time spent paused; `max_node_time` limits each node.
+    pub fn execute_graph_with_breakpoints(
+        graph: &mut LogicGraph,
+        node_context: &EvalContext,
+        breakpoints: &HashSet<NodeId>,
+        max_node_time: Option<Duration>,
+    ) -> Result<ExecutionOutcome, GraphError> {
+        info!("Starting graph execution with {} breakpoints for scope: {}", breakpoints.len(), graph.scope_id().to_string());
+
+        let execution_order = Self::full_schedule(graph)?;
+        let stats = GraphExecutionStats {
+            total_execution_time_ms: 0.0,
+            nodes_executed: 0,
+            nodes_skipped: 0,
+            nodes_failed: 0,
+            execution_order: execution_order.clone(),
+            per_node_times: HashMap::new(),
+        };
+
+        let mut run = ExecutionRun::new(graph, execution_order, node_context, max_node_time, stats);
+        run.breakpoints = breakpoints.clone();
+        run.advance(graph, false, false)
+    }
+
+    /// Validate the graph and order all of its nodes for execution
+    fn full_schedule(graph: &LogicGraph) -> Result<Vec<NodeId>, GraphError> {
         let validation = Self::validate_graph(graph);
         if !validation.is_valid {
             error!("Graph validation failed: {}", validation.summary());
             return Err(GraphError::ValidationError(validation.summary()));
         }
 
-        // Calculate execution order
         let execution_order = Self::calculate_execution_order(graph)?;
         debug!("Execution order: {:?}", execution_order.iter().map(|id| id.as_str()[..8].to_string()).collect::<Vec<_>>());
+        Ok(execution_order)
+    }
+
+    /// Execute the graph by pulling from its sinks, skipping untaken branch arms
+    ///
+    /// Nodes without outgoing connections are the roots. Each node pulls its
+    /// upstream nodes before running, except `branch` nodes: they pull only
+    /// their `condition` source, then only the source of the selected
+    /// `true_value` or `false_value` input. Nodes feeding nothing but an
+    /// untaken arm do not run, keep their dirty flag and count as skipped.
+    pub fn execute_graph_lazy(
+        graph: &mut LogicGraph,
+        node_context: &EvalContext,
+        max_node_time: Option<Duration>,
+    ) -> Result<GraphExecutionStats, GraphError> {
+        let start_time = std::time::Instant::now();
+        info!("Starting lazy graph execution for scope: {}", graph.scope_id().to_string());
+
+        let validation = Self::validate_graph(graph);
+        if !validation.is_valid {
+            error!("Graph validation failed: {}", validation.summary());
+            return Err(GraphError::ValidationError(validation.summary()));
+        }
+
+        // Topological order rejects cycles and keeps root order stable
+        let roots: Vec<NodeId> = Self::calculate_execution_order(graph)?
+            .into_iter()
+            .filter(|id| graph.get_outgoing_connections(id).is_empty())
+            .collect();
 
         let mut stats = GraphExecutionStats {
             total_execution_time_ms: 0.0,
             nodes_executed: 0,
             nodes_skipped: 0,
             nodes_failed: 0,
-            execution_order: execution_order.clone(),
+            execution_order: Vec::new(),
+            per_node_times: HashMap::new(),
         };
-
-        // Create evaluation context
         let mut eval_context = EvaluationContext::new(
             graph.registry().clone(),
             graph.scope_id().clone()
         );
+        let mut visited = HashSet::new();
 
-        // Execute nodes in order
-        for node_id in execution_order {
-            match Self::execute_node(graph, &node_id, &mut eval_context) {
-                Ok(result) => {
-                    stats.total_execution_time_ms += result.execution_time_ms;
-                    if result.success {
-                        stats.nodes_executed += 1;
-                        debug!("Node {} executed successfully in {:.2}ms", 
-                            result.node_id.as_str()[..8].to_string(), 
-                            result.execution_time_ms);
-                    } else {
-                        stats.nodes_failed += 1;
-                        warn!("Node {} failed: {}", 
-                            result.node_id.as_str()[..8].to_string(),
-                            result.error.unwrap_or_else(|| "Unknown error".to_string()));
-                    }
-                }
-                Err(e) => {
+        for root in roots {
+            Self::pull_node(graph, &root, &mut visited, &mut eval_context, node_context, max_node_time, &mut stats)?;
+        }
+
+        stats.nodes_skipped = graph.node_count() - stats.execution_order.len();
+        stats.total_execution_time_ms = start_time.elapsed().as_secs_f64() * 1000.0;
+
+        info!("Lazy graph execution completed: {} nodes executed, {} skipped, {} failed, {:.2}ms total",
+            stats.nodes_executed, stats.nodes_skipped, stats.nodes_failed, stats.total_execution_time_ms);
+
+        Ok(stats)
+    }
+
+    /// Run `node_id` after the upstream nodes it needs
+    fn pull_node(
+        graph: &mut LogicGraph,
+        node_id: &NodeId,
+        visited: &mut HashSet<NodeId>,
+        eval_context: &mut EvaluationContext,
+        node_context: &EvalContext,
+        max_node_time: Option<Duration>,
+        stats: &mut GraphExecutionStats,
+    ) -> Result<(), GraphError> {
+        if !visited.insert(node_id.clone()) {
+            return Ok(());
+        }
+        let is_branch = graph.get_node(node_id)
+            .is_some_and(|node| node.evaluatable.node_type() == "branch");
+        let incoming: Vec<(String, NodeId)> = graph.get_incoming_connections(node_id)
+            .into_iter()
+            .map(|conn| (conn.to_input.clone(), conn.from_node.clone()))
+            .collect();
+
+        let needed: Vec<&NodeId> = if is_branch {
+            for (_, source) in incoming.iter().filter(|(input, _)| input == "condition") {
+                Self::pull_node(graph, source, visited, eval_context, node_context, max_node_time, stats)?;
+            }
+            let condition = Self::resolve_input(graph, node_id, "condition", eval_context)
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false);
+            let arm = if condition { "true_value" } else { "false_value" };
+            debug!("Branch {} takes '{}'", node_id, arm);
+            incoming.iter().filter(|(input, _)| input == arm).map(|(_, source)| source).collect()
+        } else {
+            incoming.iter().map(|(_, source)| source).collect()
+        };
+        for source in needed {
+            Self::pull_node(graph, source, visited, eval_context, node_context, max_node_time, stats)?;
+        }
+
+        stats.execution_order.push(node_id.clone());
+        Self::run_node(graph, node_id, eval_context, node_context, max_node_time, stats)
+    }
+
+    /// Value an input will see, with bindings taking precedence over connections
+    fn resolve_input(
+        graph: &LogicGraph,
+        node_id: &NodeId,
+        input: &str,
+        eval_context: &mut EvaluationContext,
+    ) -> Option<MetaValue> {
+        if let Some(binding) = graph.get_node(node_id)?.input_bindings.get(input) {
+            return Some(eval_context.evaluate_binding(binding));
+        }
+        graph.get_incoming_connections(node_id)
+            .into_iter()
+            .find(|conn| conn.to_input == input)
+            .and_then(|conn| {
+                graph.get_node(&conn.from_node)?
+                    .cached_outputs.as_ref()?
+                    .get(&conn.from_output)
+                    .cloned()
+            })
+    }
+
+    /// Execute nodes in order, recording each node's time in `stats`
+    fn run_nodes(
+        graph: &mut LogicGraph,
+        execution_order: Vec<NodeId>,
+        node_context: &EvalContext,
+        max_node_time: Option<Duration>,
+        stats: GraphExecutionStats,
+    ) -> Result<GraphExecutionStats, GraphError> {
+        let mut run = ExecutionRun::new(graph, execution_order, node_context, max_node_time, stats);
+        while !run.is_finished() {
+            run.run_next(graph)?;
+        }
+        Ok(run.stats)
+    }
+
+    /// Execute one node, recording its time and enforcing the time limit
+    fn run_node(
+        graph: &mut LogicGraph,
+        node_id: &NodeId,
+        eval_context: &mut EvaluationContext,
+        node_context: &EvalContext,
+        max_node_time: Option<Duration>,
+        stats: &mut GraphExecutionStats,
+    ) -> Result<(), GraphError> {
+        let node_start = Instant::now();
+        let outcome = Self::execute_node_with_context(graph, node_id, eval_context, &node_context.for_node(node_id.clone()));
+        let elapsed = node_start.elapsed();
+        stats.per_node_times.insert(node_id.clone(), elapsed);
+
+        match outcome {
+            Ok(result) => {
+                stats.total_execution_time_ms += result.execution_time_ms;
+                if result.success {
+                    stats.nodes_executed += 1;
+                    debug!("Node {} executed successfully in {:.2}ms", 
+                        result.node_id.as_str()[..8].to_string(), 
+                        result.execution_time_ms);
+                } else {
                     stats.nodes_failed += 1;
-                    error!("Failed to execute node {}: {}", node_id.as_str()[..8].to_string(), e);
+                    warn!("Node {} failed: {}", 
+                        result.node_id.as_str()[..8].to_string(),
+                        result.error.unwrap_or_else(|| "Unknown error".to_string()));
                 }
             }
+            Err(e) => {
+                stats.nodes_failed += 1;
+                error!("Failed to execute node {}: {}", node_id.as_str()[..8].to_string(), e);
+            }
         }
 
-        stats.total_execution_time_ms = start_time.elapsed().as_secs_f64() * 1000.0;
-        
-        info!("Graph execution completed: {} nodes executed, {} failed, {:.2}ms total",
-            stats.nodes_executed, stats.nodes_failed, stats.total_execution_time_ms);
+        if let Some(limit) = max_node_time.filter(|limit| elapsed > *limit) {
+            error!("Node {} exceeded the execution time limit", node_id);
+            return Err(GraphError::EvaluationError(format!(
+                "Node {} took {:.2}ms, exceeding the {:.2}ms limit",
+                node_id, elapsed.as_secs_f64() * 1000.0, limit.as_secs_f64() * 1000.0
+            )));
+        }
 
-        Ok(stats)
+        Ok(())
     }
 
     /// Execute a single node
@@ -96,14 +436,25 @@ impl GraphExecutor {
         graph: &mut LogicGraph,
         node_id: &NodeId,
         eval_context: &mut EvaluationContext,
+    ) -> Result<NodeExecutionResult, GraphError> {
+        let node_context = EvalContext::new(graph.registry().clone(), graph.scope_id().clone());
+        Self::execute_node_with_context(graph, node_id, eval_context, &node_context)
+    }
+
+    /// Execute a single node with a caller-provided node context
+    pub fn execute_node_with_context(
+        graph: &mut LogicGraph,
+        node_id: &NodeId,
+        eval_context: &mut EvaluationContext,
+        node_context: &EvalContext,
     ) -> Result<NodeExecutionResult, GraphError> {
         let start_time = std::time::Instant::now();
 
         let node = graph.get_node_mut(node_id)
             .ok_or_else(|| GraphError::NodeNotFound(node_id.as_str()))?;
 
-        // Skip if not dirty and we have cached outputs
-        if !node.is_dirty() && node.cached_outputs.is_some() {
+        // Skip if not dirty and we have cached outputs (volatile nodes always re-run)
+        if !node.is_dirty() && node.cached_outputs.is_some() && !node.evaluatable.is_volatile() {
             return Ok(NodeExecutionResult {
                 node_id: node_id.clone(),
                 outputs: node.cached_outputs.as_ref().unwrap().clone(),
@@ -115,37 +466,12 @@ impl GraphExecutor {
 
         debug!("Executing node: {} (type: {})", node.short_id(), node.node_type());
 
-        // Prepare input map by resolving all input bindings
-        let mut input_map = InputMap::new();
-        
-        // First, collect inputs from connections
-        let incoming_connections: Vec<_> = graph.get_incoming_connections(node_id)
-            .into_iter().cloned().collect();
-
-        for connection in incoming_connections {
-            // Get output from source node
-            if let Some(source_node) = graph.get_node(&connection.from_node) {
-                if let Some(cached_outputs) = &source_node.cached_outputs {
-                    if let Some(output_value) = cached_outputs.get(&connection.from_output) {
-                        input_map.insert(connection.to_input.clone(), output_value.clone());
-                    }
-                }
-            }
-        }
-
-        // Then, resolve input bindings (these can override connection values)
+        let input_map = Self::gather_inputs(graph, node_id, eval_context);
         let node = graph.get_node(node_id).unwrap(); // Safe after earlier check
-        for (input_name, binding) in &node.input_bindings {
-            let resolved_value = eval_context.evaluate_binding(binding);
-            input_map.insert(input_name.clone(), resolved_value);
-        }
 
         // Execute the node's evaluatable logic
         let execution_result = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            node.evaluatable.evaluate(&input_map, &crate::core::logic::EvalContext::new(
-                graph.registry().clone(),
-                graph.scope_id().clone(),
-            ))
+            node.evaluatable.evaluate(&input_map, node_context)
         })) {
             Ok(eval_result) => {
                 match eval_result {
@@ -196,56 +522,36 @@ impl GraphExecutor {
         Ok(execution_result)
     }
 
-    /// Calculate the topological execution order for the graph
-    pub fn calculate_execution_order(graph: &LogicGraph) -> Result<Vec<NodeId>, GraphError> {
-        let mut in_degree: HashMap<NodeId, usize> = HashMap::new();
-        let mut adjacency: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
-
-        // Initialize all nodes with 0 in-degree
-        for node_id in graph.node_ids() {
-            in_degree.insert(node_id.clone(), 0);
-            adjacency.insert(node_id, Vec::new());
-        }
+    /// Input map `node_id` would be evaluated with right now
+    ///
+    /// Connected inputs take their source's cached output; input bindings
+    /// override connections.
+    fn gather_inputs(graph: &LogicGraph, node_id: &NodeId, eval_context: &mut EvaluationContext) -> InputMap {
+        let mut input_map = InputMap::new();
 
-        // Build adjacency list and calculate in-degrees
-        for connection in graph.connections() {
-            adjacency.get_mut(&connection.from_node)
-                .unwrap()
-                .push(connection.to_node.clone());
-            
-            *in_degree.get_mut(&connection.to_node).unwrap() += 1;
+        for connection in graph.get_incoming_connections(node_id) {
+            let value = graph.get_node(&connection.from_node)
+                .and_then(|source| source.cached_outputs.as_ref())
+                .and_then(|outputs| outputs.get(&connection.from_output));
+            if let Some(value) = value {
+                input_map.insert(connection.to_input.clone(), value.clone());
+            }
         }
 
-        // Kahn's algorithm for topological sorting
-        let mut queue: VecDeque<NodeId> = in_degree.iter()
-            .filter(|(_, &degree)| degree == 0)
-            .map(|(node_id, _)| node_id.clone())
-            .collect();
-
-        let mut execution_order = Vec::new();
-
-        while let Some(current_node) = queue.pop_front() {
-            execution_order.push(current_node.clone());
-
-            // Process all neighbors
-            if let Some(neighbors) = adjacency.get(&current_node) {
-                for neighbor in neighbors {
-                    let degree = in_degree.get_mut(neighbor).unwrap();
-                    *degree -= 1;
-                    
-                    if *degree == 0 {
-                        queue.push_back(neighbor.clone());
-                    }
-                }
+        if let Some(node) = graph.get_node(node_id) {
+            for (input_name, binding) in &node.input_bindings {
+                input_map.insert(input_name.clone(), eval_context.evaluate_binding(binding));
             }
         }
+        input_map
+    }
 
-        // Check for cycles
-        if execution_order.len() != graph.node_count() {
-            return Err(GraphError::CircularDependency(
-                "Graph contains cycles and cannot be executed".to_string()
-            ));
-        }
+    /// Calculate the topological execution order for the graph
+    pub fn calculate_execution_order(graph: &LogicGraph) -> Result<Vec<NodeId>, GraphError> {
+        let edges = graph.connections().iter().map(|connection| (connection.from_node.clone(), connection.to_node.clone()));
+        let execution_order = topological_sort(graph.node_ids(), edges).ok_or_else(|| {
+            GraphError::CircularDependency("Graph contains cycles and cannot be executed".to_string())
+        })?;
 
         debug!("Calculated execution order for {} nodes", execution_order.len());
         Ok(execution_order)
@@ -349,6 +655,24 @@ impl GraphExecutor {
 
     /// Execute only dirty nodes (incremental execution)
     pub fn execute_dirty_nodes(graph: &mut LogicGraph) -> Result<GraphExecutionStats, GraphError> {
+        let node_context = EvalContext::new(graph.registry().clone(), graph.scope_id().clone());
+        Self::execute_dirty_nodes_with_context(graph, &node_context)
+    }
+
+    /// Execute only dirty nodes using a caller-provided node context
+    pub fn execute_dirty_nodes_with_context(
+        graph: &mut LogicGraph,
+        node_context: &EvalContext,
+    ) -> Result<GraphExecutionStats, GraphError> {
+        Self::execute_dirty_nodes_with_limit(graph, node_context, None)
+    }
+
+    /// Execute only dirty nodes, failing if any runs longer than `max_node_time`
+    pub fn execute_dirty_nodes_with_limit(
+        graph: &mut LogicGraph,
+        node_context: &EvalContext,
+        max_node_time: Option<Duration>,
+    ) -> Result<GraphExecutionStats, GraphError> {
         let start_time = std::time::Instant::now();
         info!("Starting incremental execution for dirty nodes");
 
@@ -356,7 +680,7 @@ impl GraphExecutor {
         let dirty_nodes: Vec<NodeId> = graph.node_ids().into_iter()
             .filter(|id| {
                 graph.get_node(id)
-                    .map(|node| node.is_dirty())
+                    .map(|node| node.is_dirty() || node.evaluatable.is_volatile())
                     .unwrap_or(false)
             })
             .collect();
@@ -369,6 +693,7 @@ impl GraphExecutor {
                 nodes_skipped: graph.node_count(),
                 nodes_failed: 0,
                 execution_order: Vec::new(),
+                per_node_times: HashMap::new(),
             });
         }
 
@@ -383,37 +708,16 @@ impl GraphExecutor {
             .filter(|id| affected_nodes.contains(id))
             .collect();
 
-        let mut stats = GraphExecutionStats {
+        let stats = GraphExecutionStats {
             total_execution_time_ms: 0.0,
             nodes_executed: 0,
             nodes_skipped: graph.node_count() - execution_order.len(),
             nodes_failed: 0,
             execution_order: execution_order.clone(),
+            per_node_times: HashMap::new(),
         };
 
-        // Create evaluation context
-        let mut eval_context = EvaluationContext::new(
-            graph.registry().clone(),
-            graph.scope_id().clone()
-        );
-
-        // Execute affected nodes
-        for node_id in execution_order {
-            match Self::execute_node(graph, &node_id, &mut eval_context) {
-                Ok(result) => {
-                    stats.total_execution_time_ms += result.execution_time_ms;
-                    if result.success {
-                        stats.nodes_executed += 1;
-                    } else {
-                        stats.nodes_failed += 1;
-                    }
-                }
-                Err(e) => {
-                    stats.nodes_failed += 1;
-                    error!("Failed to execute node {}: {}", node_id.as_str()[..8].to_string(), e);
-                }
-            }
-        }
+        let mut stats = Self::run_nodes(graph, execution_order, node_context, max_node_time, stats)?;
 
         stats.total_execution_time_ms = start_time.elapsed().as_secs_f64() * 1000.0;
         
@@ -446,3 +750,49 @@ impl GraphExecutor {
         affected
     }
 }
+
+/// Order `nodes` so every edge's source comes before its target
+///
+/// Kahn's algorithm; nodes with no pending inputs keep their relative order
+/// from `nodes`. Edges touching unknown nodes are ignored. Returns None when
+/// the edges form a cycle.
+pub fn topological_sort<T>(
+    nodes: impl IntoIterator<Item = T>,
+    edges: impl IntoIterator<Item = (T, T)>,
+) -> Option<Vec<T>>
+where
+    T: Clone + Eq + Hash,
+{
+    let nodes: Vec<T> = nodes.into_iter().collect();
+    let mut in_degree: HashMap<T, usize> = nodes.iter().map(|node| (node.clone(), 0)).collect();
+    let mut adjacency: HashMap<T, Vec<T>> = HashMap::new();
+
+    for (from, to) in edges {
+        if !in_degree.contains_key(&from) {
+            continue;
+        }
+        if let Some(degree) = in_degree.get_mut(&to) {
+            *degree += 1;
+            adjacency.entry(from).or_default().push(to);
+        }
+    }
+
+    let mut queue: VecDeque<T> = nodes.iter()
+        .filter(|node| in_degree[*node] == 0)
+        .cloned()
+        .collect();
+    let mut order = Vec::with_capacity(nodes.len());
+
+    while let Some(current) = queue.pop_front() {
+        for neighbor in adjacency.get(&current).into_iter().flatten() {
+            let degree = in_degree.get_mut(neighbor).expect("edge targets are known nodes");
+            *degree -= 1;
+            if *degree == 0 {
+                queue.push_back(neighbor.clone());
+            }
+        }
+        order.push(current);
+    }
+
+    (order.len() == in_degree.len()).then_some(order)
+}
@@ -239,6 +239,16 @@ impl LogicGraph {
         &self.registry
     }
 
+    /// Flag every node for re-execution
+    ///
+    /// Path bindings do not track registry writes, so callers that change
+    /// registry values a graph reads use this before executing it.
+    pub fn mark_all_dirty(&mut self) {
+        for node in self.nodes.values_mut() {
+            node.mark_dirty();
+        }
+    }
+
     /// Get all node IDs
     pub fn node_ids(&self) -> Vec<NodeId> {
         self.nodes.keys().cloned().collect()
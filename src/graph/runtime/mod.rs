@@ -6,17 +6,22 @@
 mod types;
 mod graph;
 mod executor;
+mod simulation;
+mod sweep;
 
 pub use types::{NodeId, NodeConnection, GraphNode, GraphValidation};
 pub use graph::{LogicGraph, GraphError, SerializableGraph};
-pub use executor::{GraphExecutor, NodeExecutionResult, GraphExecutionStats};
+pub use executor::{GraphExecutor, NodeExecutionResult, GraphExecutionStats, ExecutionOutcome, PausedExecution, topological_sort};
+pub use simulation::{SimulationRecording, SimulationFrame};
+pub use sweep::{SweepResult, SweepRow, DEFAULT_MAX_SWEEP_COMBINATIONS};
 
-use crate::core::logic::Evaluatable;
+use crate::core::logic::{DeterministicContext, EvalContext, Evaluatable, NodeStateStore, ProbeChannels};
 use crate::core::registry::MetaRegistry;
-use crate::core::types::{MetaValue, ScopeId};
-use std::collections::HashMap;
+use crate::core::types::{DotPath, MetaValue, ScopeId};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tracing::{debug, info};
+use std::time::Duration;
+use tracing::{debug, info, warn};
 
 /// High-level graph runtime that manages multiple graphs and execution
 #[derive(Debug)]
@@ -27,6 +32,10 @@ pub struct GraphRuntime {
     registry: Arc<MetaRegistry>,
     /// Runtime configuration
     config: RuntimeConfig,
+    /// Probe channels shared by every graph this runtime executes
+    probes: ProbeChannels,
+    /// State stateful nodes (timers) keep between passes
+    node_state: NodeStateStore,
 }
 
 /// Configuration for the graph runtime
@@ -40,6 +49,11 @@ pub struct RuntimeConfig {
     pub max_expression_depth: usize,
     /// Whether to enable execution tracing
     pub enable_tracing: bool,
+    /// Most parameter combinations a single `sweep` may evaluate
+    pub max_sweep_combinations: usize,
+    /// Evaluate full-graph passes by pulling from sinks, so `branch` nodes
+    /// only run the upstream nodes of the arm they take
+    pub lazy_branches: bool,
 }
 
 impl Default for RuntimeConfig {
@@ -49,6 +63,25 @@ impl Default for RuntimeConfig {
             auto_incremental: true,
             max_expression_depth: 100,
             enable_tracing: true,
+            max_sweep_combinations: DEFAULT_MAX_SWEEP_COMBINATIONS,
+            lazy_branches: false,
+        }
+    }
+}
+
+impl RuntimeConfig {
+    /// Per-node time limit, or None when `max_node_execution_time_ms` is not positive
+    pub fn max_node_execution_time(&self) -> Option<Duration> {
+        (self.max_node_execution_time_ms > 0.0)
+            .then(|| Duration::from_secs_f64(self.max_node_execution_time_ms / 1000.0))
+    }
+
+    /// Execute a whole graph the way this configuration asks for
+    fn execute(&self, graph: &mut LogicGraph, ctx: &EvalContext) -> Result<GraphExecutionStats, GraphError> {
+        if self.lazy_branches {
+            GraphExecutor::execute_graph_lazy(graph, ctx, self.max_node_execution_time())
+        } else {
+            GraphExecutor::execute_graph_with_limit(graph, ctx, self.max_node_execution_time())
         }
     }
 }
@@ -61,6 +94,8 @@ impl GraphRuntime {
             graphs: HashMap::new(),
             registry,
             config: RuntimeConfig::default(),
+            probes: ProbeChannels::default(),
+            node_state: NodeStateStore::default(),
         }
     }
 
@@ -71,6 +106,8 @@ impl GraphRuntime {
             graphs: HashMap::new(),
             registry,
             config,
+            probes: ProbeChannels::default(),
+            node_state: NodeStateStore::default(),
         }
     }
 
@@ -107,7 +144,51 @@ impl GraphRuntime {
     pub fn execute_graph(&mut self, scope_id: &ScopeId) -> Result<GraphExecutionStats, GraphError> {
         if let Some(graph) = self.graphs.get_mut(scope_id) {
             info!("Executing graph for scope: {}", scope_id.to_string());
-            GraphExecutor::execute_graph(graph)
+            let ctx = EvalContext::new(self.registry.clone(), scope_id.clone())
+                .with_probes(self.probes.clone())
+                .with_node_state(self.node_state.clone());
+            self.config.execute(graph, &ctx)
+        } else {
+            Err(GraphError::ValidationError(
+                format!("No graph found for scope: {}", scope_id.to_string())
+            ))
+        }
+    }
+
+    /// Execute a specific graph, pausing before any node in `breakpoints`
+    ///
+    /// Continue a paused pass with the graph from [`get_graph_mut`](Self::get_graph_mut).
+    pub fn execute_graph_with_breakpoints(
+        &mut self,
+        scope_id: &ScopeId,
+        breakpoints: &HashSet<NodeId>,
+    ) -> Result<ExecutionOutcome, GraphError> {
+        if let Some(graph) = self.graphs.get_mut(scope_id) {
+            info!("Executing graph for scope {} with breakpoints", scope_id.to_string());
+            let ctx = EvalContext::new(self.registry.clone(), scope_id.clone())
+                .with_probes(self.probes.clone())
+                .with_node_state(self.node_state.clone());
+            GraphExecutor::execute_graph_with_breakpoints(graph, &ctx, breakpoints, self.config.max_node_execution_time())
+        } else {
+            Err(GraphError::ValidationError(
+                format!("No graph found for scope: {}", scope_id.to_string())
+            ))
+        }
+    }
+
+    /// Execute a specific graph with a caller-provided node context
+    ///
+    /// Used with [`DeterministicContext`](crate::core::logic::DeterministicContext)
+    /// so time and randomness come from the context rather than the wall clock.
+    pub fn execute_graph_with_context(
+        &mut self,
+        scope_id: &ScopeId,
+        ctx: &EvalContext,
+    ) -> Result<GraphExecutionStats, GraphError> {
+        if let Some(graph) = self.graphs.get_mut(scope_id) {
+            info!("Executing graph for scope {} with provided context", scope_id.to_string());
+            let ctx = ctx.clone().with_node_state(self.node_state.clone());
+            self.config.execute(graph, &ctx)
         } else {
             Err(GraphError::ValidationError(
                 format!("No graph found for scope: {}", scope_id.to_string())
@@ -115,13 +196,57 @@ impl GraphRuntime {
         }
     }
 
+    /// Run a graph headlessly from initial inputs and return every node output
+    ///
+    /// Each input is written to the registry at its path in `scope_id`, where
+    /// nodes read it through `NodeInputBinding::Path`. The whole graph is then
+    /// re-executed. The result holds one entry per output of every node,
+    /// keyed by `<node id>.<output name>` as built by [`NodeId::output_path`],
+    /// e.g. `"6f1c…e2.result"` for an add node's sum.
+    pub fn evaluate_scope(
+        &mut self,
+        scope_id: &ScopeId,
+        inputs: HashMap<DotPath, MetaValue>,
+    ) -> Result<HashMap<DotPath, MetaValue>, GraphError> {
+        if !self.graphs.contains_key(scope_id) {
+            return Err(GraphError::ValidationError(
+                format!("No graph found for scope: {}", scope_id.to_string())
+            ));
+        }
+        for (path, value) in inputs {
+            self.registry.set_scoped(scope_id, &path, value)
+                .map_err(|e| GraphError::EvaluationError(format!("Failed to set input {}: {}", path, e)))?;
+        }
+
+        let graph = self.graphs.get_mut(scope_id).expect("graph checked above");
+        graph.mark_all_dirty();
+        let ctx = EvalContext::new(self.registry.clone(), scope_id.clone())
+            .with_probes(self.probes.clone())
+            .with_node_state(self.node_state.clone());
+        self.config.execute(graph, &ctx)?;
+
+        let mut outputs = HashMap::new();
+        for node_id in graph.node_ids() {
+            let Some(cached) = graph.get_node(&node_id).and_then(|node| node.cached_outputs.as_ref()) else {
+                continue;
+            };
+            for (name, value) in cached {
+                outputs.insert(node_id.output_path(name), value.clone());
+            }
+        }
+        Ok(outputs)
+    }
+
     /// Execute all graphs
     pub fn execute_all_graphs(&mut self) -> Vec<(ScopeId, Result<GraphExecutionStats, GraphError>)> {
         info!("Executing all graphs ({} total)", self.graphs.len());
         let mut results = Vec::new();
         
         for (scope_id, graph) in &mut self.graphs {
-            let result = GraphExecutor::execute_graph(graph);
+            let ctx = EvalContext::new(self.registry.clone(), scope_id.clone())
+                .with_probes(self.probes.clone())
+                .with_node_state(self.node_state.clone());
+            let result = self.config.execute(graph, &ctx);
             results.push((scope_id.clone(), result));
         }
         
@@ -132,9 +257,13 @@ impl GraphRuntime {
     pub fn execute_incremental(&mut self) -> Vec<(ScopeId, Result<GraphExecutionStats, GraphError>)> {
         info!("Executing incremental updates across all graphs");
         let mut results = Vec::new();
+        let limit = self.config.max_node_execution_time();
         
         for (scope_id, graph) in &mut self.graphs {
-            let result = GraphExecutor::execute_dirty_nodes(graph);
+            let ctx = EvalContext::new(self.registry.clone(), scope_id.clone())
+                .with_probes(self.probes.clone())
+                .with_node_state(self.node_state.clone());
+            let result = GraphExecutor::execute_dirty_nodes_with_limit(graph, &ctx, limit);
             results.push((scope_id.clone(), result));
         }
         
@@ -180,10 +309,151 @@ impl GraphRuntime {
         self.config = config;
     }
 
+    /// Probe channels fed by probe nodes during `execute_graph`
+    ///
+    /// Register a probe name here to receive its values.
+    pub fn probes(&self) -> &ProbeChannels {
+        &self.probes
+    }
+
     /// Get the shared registry
     pub fn registry(&self) -> &MetaRegistry {
         &self.registry
     }
+
+    /// Tick every graph on a deterministic clock and record node outputs
+    ///
+    /// Steps run from `t = 0` to `duration_s` inclusive, `step_s` apart,
+    /// with a fixed RNG seed so the recording is reproducible. Each frame holds
+    /// every node's cached outputs after that step; failures are logged.
+    pub fn record_simulation(&mut self, duration_s: f64, step_s: f64) -> SimulationRecording {
+        let steps = if step_s > 0.0 && duration_s > 0.0 {
+            (duration_s / step_s + 1e-9).floor() as usize
+        } else {
+            0
+        };
+        info!("Recording simulation: {} steps of {}s", steps + 1, step_s);
+
+        // Fresh node state so timers start at t = 0 in every recording
+        let node_state = NodeStateStore::new();
+        let mut frames = Vec::with_capacity(steps + 1);
+        for step in 0..=steps {
+            let time_s = step as f64 * step_s;
+            let mut outputs = HashMap::new();
+
+            for (scope_id, graph) in &mut self.graphs {
+                let ctx = DeterministicContext::new()
+                    .with_registry(self.registry.clone(), scope_id.clone())
+                    .with_time(time_s)
+                    .build()
+                    .with_node_state(node_state.clone());
+
+                if let Err(e) = GraphExecutor::execute_graph_with_context(graph, &ctx) {
+                    warn!("Simulation step at {}s failed for scope {}: {}", time_s, scope_id.to_string(), e);
+                }
+
+                for node_id in graph.node_ids() {
+                    if let Some(cached) = graph.get_node(&node_id).and_then(|node| node.cached_outputs.clone()) {
+                        outputs.insert(node_id, cached);
+                    }
+                }
+            }
+
+            frames.push(SimulationFrame { time_s, outputs });
+        }
+
+        SimulationRecording::new(step_s, frames)
+    }
+}
+
+impl GraphRuntime {
+    /// Evaluate a graph over every combination of parameter values
+    ///
+    /// Each parameter is a registry path in `scope` that nodes read through
+    /// path bindings. For every combination the values are written, the whole
+    /// graph is re-evaluated and `outputs` are collected into one row. An
+    /// output path is either a node output (see [`NodeId::output_path`]) or a
+    /// registry path. Parameters that existed beforehand get their original
+    /// values back afterwards.
+    ///
+    /// Fails without evaluating anything when the scope has no graph or the
+    /// number of combinations exceeds `config.max_sweep_combinations`.
+    pub fn sweep(
+        &mut self,
+        scope_id: &ScopeId,
+        params: &[(DotPath, Vec<MetaValue>)],
+        outputs: &[DotPath],
+    ) -> Result<SweepResult, GraphError> {
+        if !self.graphs.contains_key(scope_id) {
+            return Err(GraphError::ValidationError(
+                format!("No graph found for scope: {}", scope_id.to_string())
+            ));
+        }
+        let count = sweep::combination_count(params)
+            .filter(|count| *count <= self.config.max_sweep_combinations)
+            .ok_or_else(|| GraphError::ValidationError(format!(
+                "Sweep exceeds the limit of {} combinations",
+                self.config.max_sweep_combinations
+            )))?;
+        info!("Sweeping {} parameters over {} combinations", params.len(), count);
+
+        let originals: Vec<Option<MetaValue>> = params.iter()
+            .map(|(path, _)| self.registry.get_scoped(scope_id, path).ok())
+            .collect();
+
+        let mut rows = Vec::with_capacity(count);
+        for index in 0..count {
+            let inputs = sweep::combination(params, index);
+            let mut error = None;
+            for ((path, _), value) in params.iter().zip(&inputs) {
+                if let Err(e) = self.registry.set_scoped(scope_id, path, value.clone()) {
+                    error = Some(format!("Failed to set {}: {}", path, e));
+                }
+            }
+
+            let graph = self.graphs.get_mut(scope_id).expect("graph checked above");
+            graph.mark_all_dirty();
+            if error.is_none() {
+                let ctx = EvalContext::new(self.registry.clone(), scope_id.clone())
+                    .with_probes(self.probes.clone())
+                    .with_node_state(self.node_state.clone());
+                if let Err(e) = self.config.execute(graph, &ctx) {
+                    error = Some(e.to_string());
+                }
+            }
+
+            let values = outputs.iter().map(|path| self.sweep_output(scope_id, path)).collect();
+            rows.push(SweepRow { inputs, outputs: values, error });
+        }
+
+        for ((path, _), original) in params.iter().zip(originals) {
+            if let Some(value) = original {
+                if let Err(e) = self.registry.set_scoped(scope_id, path, value) {
+                    warn!("Failed to restore {} after sweep: {}", path, e);
+                }
+            }
+        }
+
+        Ok(SweepResult {
+            params: params.iter().map(|(path, _)| path.clone()).collect(),
+            outputs: outputs.to_vec(),
+            rows,
+        })
+    }
+
+    /// Resolve a sweep output path to a node output or registry value
+    fn sweep_output(&self, scope_id: &ScopeId, path: &DotPath) -> Option<MetaValue> {
+        let graph = self.graphs.get(scope_id)?;
+        if let [node, output] = path.segments() {
+            let node_output = uuid::Uuid::parse_str(node).ok()
+                .and_then(|uuid| graph.get_node(&NodeId::from_uuid(uuid)))
+                .map(|node| node.cached_outputs.as_ref().and_then(|outputs| outputs.get(output)).cloned());
+            if let Some(value) = node_output {
+                return value;
+            }
+        }
+        self.registry.get_scoped(scope_id, path).ok()
+    }
 }
 
 /// Runtime statistics
@@ -194,3 +464,174 @@ pub struct RuntimeStats {
     pub total_connections: usize,
     pub active_scopes: Vec<ScopeId>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::logic::nodes::{AddNode, BranchNode};
+    use crate::core::logic::{InputMap, NodeInputBinding, OutputMap};
+    use crate::core::types::error::MarcoError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Node that sleeps before answering, for timing tests
+    #[derive(Debug)]
+    struct SlowNode(Duration);
+
+    impl Evaluatable for SlowNode {
+        fn evaluate(&self, _inputs: &InputMap, _ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
+            std::thread::sleep(self.0);
+            Ok(OutputMap::new())
+        }
+    }
+
+    /// Node that counts its evaluations and outputs a fixed value
+    #[derive(Debug)]
+    struct CountingNode(f64, Arc<AtomicUsize>);
+
+    impl Evaluatable for CountingNode {
+        fn evaluate(&self, _inputs: &InputMap, _ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
+            self.1.fetch_add(1, Ordering::SeqCst);
+            Ok(OutputMap::from([("result".to_string(), MetaValue::Scalar(self.0))]))
+        }
+    }
+
+    #[test]
+    fn test_lazy_branches_skip_the_untaken_arm() {
+        let scope = ScopeId::new();
+        let mut runtime = GraphRuntime::new(Arc::new(MetaRegistry::new()));
+        let taken_runs = Arc::new(AtomicUsize::new(0));
+        let skipped_runs = Arc::new(AtomicUsize::new(0));
+        let taken = runtime.add_node_to_graph(&scope, Arc::new(CountingNode(1.0, taken_runs.clone())));
+        let skipped = runtime.add_node_to_graph(&scope, Arc::new(CountingNode(2.0, skipped_runs.clone())));
+        let branch = runtime.add_node_to_graph(&scope, Arc::new(BranchNode));
+
+        let graph = runtime.get_graph_mut(&scope).unwrap();
+        graph.get_node_mut(&branch).unwrap().set_input("condition", NodeInputBinding::literal(true));
+        graph.connect_nodes(&taken, "result", &branch, "true_value").unwrap();
+        graph.connect_nodes(&skipped, "result", &branch, "false_value").unwrap();
+
+        runtime.set_config(RuntimeConfig { lazy_branches: true, ..RuntimeConfig::default() });
+        let stats = runtime.execute_graph(&scope).unwrap();
+        assert_eq!(stats.execution_order, vec![taken.clone(), branch.clone()]);
+        assert_eq!(stats.nodes_skipped, 1);
+        assert_eq!((taken_runs.load(Ordering::SeqCst), skipped_runs.load(Ordering::SeqCst)), (1, 0));
+        let outputs = runtime.get_graph(&scope).unwrap().get_node(&branch).unwrap().cached_outputs.clone().unwrap();
+        assert_eq!(outputs["result"], MetaValue::Scalar(1.0));
+
+        // Eager execution still runs both arms
+        runtime.set_config(RuntimeConfig::default());
+        runtime.get_graph_mut(&scope).unwrap().mark_all_dirty();
+        runtime.execute_graph(&scope).unwrap();
+        assert_eq!((taken_runs.load(Ordering::SeqCst), skipped_runs.load(Ordering::SeqCst)), (2, 1));
+    }
+
+    #[test]
+    fn test_breakpoint_pauses_before_the_middle_of_a_chain() {
+        let scope = ScopeId::new();
+        let mut runtime = GraphRuntime::new(Arc::new(MetaRegistry::new()));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let first = runtime.add_node_to_graph(&scope, Arc::new(CountingNode(2.0, runs.clone())));
+        let middle = runtime.add_node_to_graph(&scope, Arc::new(AddNode));
+        let last = runtime.add_node_to_graph(&scope, Arc::new(AddNode));
+        let graph = runtime.get_graph_mut(&scope).unwrap();
+        graph.connect_nodes(&first, "result", &middle, "a").unwrap();
+        graph.connect_nodes(&middle, "result", &last, "a").unwrap();
+        for node in [&middle, &last] {
+            graph.get_node_mut(node).unwrap().set_input("b", NodeInputBinding::literal(1.0));
+        }
+
+        let breakpoints = HashSet::from([middle.clone()]);
+        let paused = match runtime.execute_graph_with_breakpoints(&scope, &breakpoints).unwrap() {
+            ExecutionOutcome::Paused(paused) => paused,
+            other => panic!("expected a pause, got {:?}", other),
+        };
+        assert_eq!(paused.node_id(), &middle);
+        assert_eq!(paused.remaining(), &[middle.clone(), last.clone()]);
+        assert_eq!(paused.inputs().get("a"), Some(&MetaValue::Scalar(2.0)));
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        let graph = runtime.get_graph_mut(&scope).unwrap();
+        assert!(graph.get_node(&middle).unwrap().cached_outputs.is_none());
+        assert!(graph.get_node(&last).unwrap().cached_outputs.is_none());
+
+        // Stepping runs only the paused node
+        let paused = match paused.step(graph).unwrap() {
+            ExecutionOutcome::Paused(paused) => paused,
+            other => panic!("expected a pause, got {:?}", other),
+        };
+        assert_eq!(paused.node_id(), &last);
+        assert!(graph.get_node(&middle).unwrap().cached_outputs.is_some());
+        assert!(graph.get_node(&last).unwrap().cached_outputs.is_none());
+
+        let stats = match paused.resume(graph).unwrap() {
+            ExecutionOutcome::Completed(stats) => stats,
+            other => panic!("expected completion, got {:?}", other),
+        };
+        assert_eq!(stats.nodes_executed, 3);
+        assert_eq!(graph.get_node(&last).unwrap().cached_outputs.as_ref().unwrap()["result"], MetaValue::Scalar(4.0));
+    }
+
+    #[test]
+    fn test_evaluate_scope_runs_add_graph_headlessly() {
+        let mut registry = MetaRegistry::new();
+        let scope = registry.create_scope();
+        let mut runtime = GraphRuntime::new(Arc::new(registry));
+
+        let add = runtime.add_node_to_graph(&scope, Arc::new(AddNode));
+        let node = runtime.get_graph_mut(&scope).unwrap().get_node_mut(&add).unwrap();
+        node.set_input("a", NodeInputBinding::Path(DotPath::new("inputs.a")));
+        node.set_input("b", NodeInputBinding::Path(DotPath::new("inputs.b")));
+
+        let inputs = HashMap::from([
+            (DotPath::new("inputs.a"), MetaValue::Scalar(2.0)),
+            (DotPath::new("inputs.b"), MetaValue::Scalar(3.5)),
+        ]);
+        let outputs = runtime.evaluate_scope(&scope, inputs).unwrap();
+        assert_eq!(outputs.get(&add.output_path("result")), Some(&MetaValue::Scalar(5.5)));
+
+        // New inputs re-run the graph even though no binding changed
+        let inputs = HashMap::from([(DotPath::new("inputs.a"), MetaValue::Scalar(10.0))]);
+        let outputs = runtime.evaluate_scope(&scope, inputs).unwrap();
+        assert_eq!(outputs.get(&add.output_path("result")), Some(&MetaValue::Scalar(13.5)));
+
+        assert!(runtime.evaluate_scope(&ScopeId::new(), HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_stats_time_each_node_and_enforce_limit() {
+        let scope = ScopeId::new();
+        let mut runtime = GraphRuntime::new(Arc::new(MetaRegistry::new()));
+        let fast = runtime.add_node_to_graph(&scope, Arc::new(AddNode));
+        let slow = runtime.add_node_to_graph(&scope, Arc::new(SlowNode(Duration::from_millis(30))));
+
+        let stats = runtime.execute_graph(&scope).unwrap();
+        assert_eq!(stats.per_node_times.len(), 2);
+        assert!(stats.per_node_times.contains_key(&fast));
+        let (slowest, time) = stats.slowest_node().unwrap();
+        assert_eq!(slowest, &slow);
+        assert!(time >= Duration::from_millis(30));
+
+        runtime.set_config(RuntimeConfig { max_node_execution_time_ms: 10.0, ..RuntimeConfig::default() });
+        runtime.get_graph_mut(&scope).unwrap().mark_all_dirty();
+        match runtime.execute_graph(&scope) {
+            Err(GraphError::EvaluationError(message)) => assert!(message.contains(&slow.to_string()), "{}", message),
+            other => panic!("expected a time limit error, got {:?}", other),
+        }
+
+        // Passes with breakpoints honour the same limit
+        runtime.get_graph_mut(&scope).unwrap().mark_all_dirty();
+        match runtime.execute_graph_with_breakpoints(&scope, &HashSet::new()) {
+            Err(GraphError::EvaluationError(message)) => assert!(message.contains(&slow.to_string()), "{}", message),
+            other => panic!("expected a time limit error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_topological_sort_orders_edges_and_rejects_cycles() {
+        let order = topological_sort(["c", "b", "a", "d"], [("a", "b"), ("b", "c"), ("x", "a")]).unwrap();
+        let position = |node| order.iter().position(|n| *n == node).unwrap();
+        assert_eq!(order.len(), 4);
+        assert!(position("a") < position("b") && position("b") < position("c"));
+
+        assert!(topological_sort(["a", "b"], [("a", "b"), ("b", "a")]).is_none());
+    }
+}
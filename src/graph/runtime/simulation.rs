@@ -0,0 +1,113 @@
+use super::types::NodeId;
+use crate::core::logic::OutputMap;
+use std::collections::HashMap;
+
+/// Captured outputs of every node at one simulation step
+#[derive(Debug, Clone)]
+pub struct SimulationFrame {
+    /// Clock time of this step in seconds
+    pub time_s: f64,
+    /// Cached outputs of every node after this step
+    pub outputs: HashMap<NodeId, OutputMap>,
+}
+
+/// Output-over-time recording of a deterministic graph simulation
+///
+/// Produced by `GraphRuntime::record_simulation`. Frames are evenly spaced
+/// by `step_s`, starting at `t = 0`, so scrubbing is a constant-time lookup.
+#[derive(Debug, Clone)]
+pub struct SimulationRecording {
+    step_s: f64,
+    frames: Vec<SimulationFrame>,
+}
+
+impl SimulationRecording {
+    /// Create a recording from frames captured at a fixed step
+    ///
+    /// `frames` must hold at least the `t = 0` frame.
+    pub fn new(step_s: f64, frames: Vec<SimulationFrame>) -> Self {
+        debug_assert!(!frames.is_empty(), "a recording always contains the t = 0 frame");
+        Self { step_s, frames }
+    }
+
+    /// Node outputs at time `t`, using the last step at or before `t`
+    ///
+    /// Times before the start or after the end clamp to the first/last frame.
+    pub fn output_at(&self, t: f64) -> &HashMap<NodeId, OutputMap> {
+        &self.frame_at(t).outputs
+    }
+
+    /// Frame at time `t` (see [`output_at`](Self::output_at))
+    pub fn frame_at(&self, t: f64) -> &SimulationFrame {
+        let last = self.frames.len() - 1;
+        let index = if t <= 0.0 || self.step_s <= 0.0 {
+            0
+        } else {
+            // Small epsilon so exact step times don't round down a frame
+            ((t / self.step_s + 1e-9).floor() as usize).min(last)
+        };
+        &self.frames[index]
+    }
+
+    /// All recorded frames in time order
+    pub fn frames(&self) -> &[SimulationFrame] {
+        &self.frames
+    }
+
+    /// Step between frames in seconds
+    pub fn step_s(&self) -> f64 {
+        self.step_s
+    }
+
+    /// Time of the last recorded frame
+    pub fn duration_s(&self) -> f64 {
+        self.frames.last().map(|frame| frame.time_s).unwrap_or(0.0)
+    }
+
+    /// Values of one node output across all frames
+    pub fn series(&self, node_id: &NodeId, output: &str) -> Vec<(f64, crate::core::types::MetaValue)> {
+        self.frames.iter()
+            .filter_map(|frame| {
+                let value = frame.outputs.get(node_id)?.get(output)?;
+                Some((frame.time_s, value.clone()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::logic::nodes::TimerNode;
+    use crate::core::logic::NodeInputBinding;
+    use crate::core::registry::MetaRegistry;
+    use crate::core::types::ScopeId;
+    use crate::graph::runtime::GraphRuntime;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_record_timer_progress_is_monotonic() {
+        let mut runtime = GraphRuntime::new(Arc::new(MetaRegistry::new()));
+        let scope = ScopeId::new();
+        let timer_id = runtime.add_node_to_graph(&scope, Arc::new(TimerNode));
+        runtime.get_graph_mut(&scope).unwrap()
+            .get_node_mut(&timer_id).unwrap()
+            .set_input("duration", NodeInputBinding::literal(1.0));
+
+        let recording = runtime.record_simulation(1.0, 0.1);
+        assert_eq!(recording.frames().len(), 11);
+
+        let progress: Vec<f64> = recording.series(&timer_id, "progress")
+            .into_iter()
+            .map(|(_, value)| value.as_scalar().unwrap())
+            .collect();
+        assert_eq!(progress.len(), 11);
+        assert!(progress.windows(2).all(|w| w[1] > w[0]), "progress not increasing: {:?}", progress);
+        assert!((progress[progress.len() - 1] - 1.0).abs() < 1e-9);
+
+        // Scrubbing picks the last step at or before t
+        let mid = recording.output_at(0.55)[&timer_id]["progress"].as_scalar().unwrap();
+        assert!((mid - 0.5).abs() < 1e-9);
+        let end = recording.output_at(5.0)[&timer_id]["progress"].as_scalar().unwrap();
+        assert!((end - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::core::types::{DotPath, MetaValue};
+
+/// Default cap on the number of combinations one sweep may evaluate
+pub const DEFAULT_MAX_SWEEP_COMBINATIONS: usize = 10_000;
+
+/// Outputs of one parameter combination in a sweep
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepRow {
+    /// Parameter values, in the order of [`SweepResult::params`]
+    pub inputs: Vec<MetaValue>,
+    /// Output values, in the order of [`SweepResult::outputs`]; `None` when
+    /// the output was not produced
+    pub outputs: Vec<Option<MetaValue>>,
+    /// Why the graph failed for this combination, if it did
+    pub error: Option<String>,
+}
+
+/// Table of outputs over the cartesian product of parameter values
+///
+/// Produced by `GraphRuntime::sweep`. Rows are ordered with the last
+/// parameter varying fastest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepResult {
+    pub params: Vec<DotPath>,
+    pub outputs: Vec<DotPath>,
+    pub rows: Vec<SweepRow>,
+}
+
+impl SweepResult {
+    /// Values of one output column across all rows
+    pub fn column(&self, output: &DotPath) -> Option<Vec<Option<&MetaValue>>> {
+        let index = self.outputs.iter().position(|path| path == output)?;
+        Some(self.rows.iter().map(|row| row.outputs[index].as_ref()).collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+/// Number of combinations in the cartesian product, or None on overflow
+pub(crate) fn combination_count(params: &[(DotPath, Vec<MetaValue>)]) -> Option<usize> {
+    params.iter().try_fold(1usize, |count, (_, values)| count.checked_mul(values.len()))
+}
+
+/// Parameter values of combination `index`, last parameter varying fastest
+pub(crate) fn combination(params: &[(DotPath, Vec<MetaValue>)], mut index: usize) -> Vec<MetaValue> {
+    let mut values = vec![MetaValue::default(); params.len()];
+    for (slot, (_, options)) in values.iter_mut().zip(params).rev() {
+        *slot = options[index % options.len()].clone();
+        index /= options.len();
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::logic::nodes::MultiplyNode;
+    use crate::core::logic::NodeInputBinding;
+    use crate::core::registry::MetaRegistry;
+    use crate::core::types::{DotPath, MetaValue};
+    use crate::graph::runtime::{GraphError, GraphRuntime, RuntimeConfig};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_sweep_doubles_each_parameter_value() {
+        let mut registry = MetaRegistry::new();
+        let scope = registry.create_scope();
+        let mut runtime = GraphRuntime::new(Arc::new(registry));
+
+        let double = runtime.add_node_to_graph(&scope, Arc::new(MultiplyNode));
+        let node = runtime.get_graph_mut(&scope).unwrap().get_node_mut(&double).unwrap();
+        node.set_input("a", NodeInputBinding::Path(DotPath::new("params.x")));
+        node.set_input("b", NodeInputBinding::literal(2.0));
+
+        let x = DotPath::new("params.x");
+        let result_path = double.output_path("result");
+        let values = vec![MetaValue::Scalar(1.0), MetaValue::Scalar(2.0), MetaValue::Scalar(3.0)];
+        let result = runtime.sweep(&scope, &[(x.clone(), values)], &[result_path.clone()]).unwrap();
+
+        assert_eq!(result.len(), 3);
+        assert!(result.rows.iter().all(|row| row.error.is_none()));
+        let doubled: Vec<f64> = result.column(&result_path).unwrap()
+            .into_iter()
+            .map(|value| value.and_then(|v| v.as_scalar()).unwrap())
+            .collect();
+        assert_eq!(doubled, vec![2.0, 4.0, 6.0]);
+        assert_eq!(result.rows[2].inputs, vec![MetaValue::Scalar(3.0)]);
+    }
+
+    #[test]
+    fn test_sweep_rejects_too_many_combinations() {
+        let mut registry = MetaRegistry::new();
+        let scope = registry.create_scope();
+        let config = RuntimeConfig { max_sweep_combinations: 8, ..RuntimeConfig::default() };
+        let mut runtime = GraphRuntime::with_config(Arc::new(registry), config);
+        runtime.add_node_to_graph(&scope, Arc::new(MultiplyNode));
+
+        let values: Vec<MetaValue> = (0..3).map(|i| MetaValue::Scalar(i as f64)).collect();
+        let params = vec![
+            (DotPath::new("params.x"), values.clone()),
+            (DotPath::new("params.y"), values),
+        ];
+        assert!(matches!(runtime.sweep(&scope, &params, &[]), Err(GraphError::ValidationError(_))));
+    }
+}
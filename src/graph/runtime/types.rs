@@ -30,6 +30,11 @@ impl NodeId {
     pub fn as_str(&self) -> String {
         self.0.to_string()
     }
+
+    /// Path naming one of this node's outputs, `<node id>.<output>`
+    pub fn output_path(&self, output: &str) -> DotPath {
+        DotPath::new(&format!("{}.{}", self.0, output))
+    }
 }
 
 impl Default for NodeId {
@@ -12,6 +12,7 @@ pub mod devtools;
 pub mod render;
 pub mod system;
 pub mod demos;
+pub mod web;
 
 // Re-export commonly used types for convenience
 pub use core::types::{MetaValue, DotPath};
@@ -150,6 +150,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     
                     WindowEvent::MouseInput { button, state, .. } => {
                         // Handle mouse input for UI interactions
+                        app_state.frame_pacer.request_redraw();
                         if state.is_pressed() {
                             info!("Mouse button {:?} pressed", button);
                             
@@ -174,6 +175,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
                     
                     WindowEvent::RedrawRequested => {
+                        app_state.frame_pacer.begin_frame();
+                        
                         // Calculate delta time
                         let now = std::time::Instant::now();
                         let dt = now.duration_since(last_frame_time).as_secs_f32();
@@ -216,6 +219,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         if app_state.frame_count % 60 == 0 {
                             info!("Performance: {:.1} FPS, {} errors", perf.fps, perf.error_count);
                         }
+                        
+                        // Wait out the rest of the frame budget
+                        app_state.frame_pacer.end_frame();
                     }
                     
                     _ => {}
@@ -223,8 +229,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
             
             Event::AboutToWait => {
-                // Request redraw for continuous rendering
-                window.request_redraw();
+                // Redraw continuously, or only after a change when pacing on demand
+                if app_state.frame_pacer.should_redraw() {
+                    window.request_redraw();
+                } else {
+                    target.set_control_flow(ControlFlow::Wait);
+                }
             }
             
             _ => {}
@@ -2,6 +2,8 @@
 use crate::core::types::error::MarcoError;
 // Template imports temporarily disabled for build compatibility
 // use crate::project::template::TemplateDefinition;
+// Native dialogs only; browsers have no blocking file picker
+#[cfg(not(target_arch = "wasm32"))]
 use rfd::FileDialog;
 use serde_json;
 use std::path::{Path, PathBuf};
@@ -34,6 +36,7 @@ impl ProjectManager {
     }
     
     // Project creation temporarily simplified for build compatibility
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn new_project(&mut self, template_name: &str) -> Result<(), MarcoError> {
         info!("Creating new project from template: {}", template_name);
         
@@ -55,6 +58,7 @@ impl ProjectManager {
         Ok(())
     }
     
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn open_project(&mut self) -> Result<(), MarcoError> {
         let file_path = FileDialog::new()
             .set_title("Open Marco 2.0 Project")
@@ -73,11 +77,15 @@ impl ProjectManager {
             self.save_to_path(path)?;
             info!("Project saved to: {:?}", path);
         } else {
+            #[cfg(not(target_arch = "wasm32"))]
             return self.save_project_as();
+            #[cfg(target_arch = "wasm32")]
+            return Err(MarcoError::Persistence("Project has no save location".to_string()));
         }
         Ok(())
     }
     
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn save_project_as(&self) -> Result<(), MarcoError> {
         let file_path = FileDialog::new()
             .set_title("Save Marco 2.0 Project")
@@ -0,0 +1,214 @@
+//! Connection routing around node bodies
+//! Pure curve/rect geometry used to keep bezier wires from crossing nodes
+use glam::Vec2;
+
+/// Horizontal pull of the default control points from each socket
+pub const DEFAULT_CONTROL_OFFSET: f32 = 50.0;
+
+/// Samples used when testing a curve against a rect
+const INTERSECTION_SAMPLES: usize = 32;
+
+/// Axis-aligned rectangle in world space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Rect {
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        Self { min: min.min(max), max: min.max(max) }
+    }
+
+    /// Rect of the given size centered on `center`
+    pub fn from_center_size(center: Vec2, size: Vec2) -> Self {
+        Self::new(center - size / 2.0, center + size / 2.0)
+    }
+
+    /// Grow by `amount` on every side
+    pub fn inflate(&self, amount: f32) -> Self {
+        Self::new(self.min - Vec2::splat(amount), self.max + Vec2::splat(amount))
+    }
+
+    pub fn union(&self, other: &Rect) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x && point.y >= self.min.y && point.y <= self.max.y
+    }
+
+    /// Whether the rects overlap; touching edges count
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+}
+
+/// Settings for routing connections around node bodies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoutingConfig {
+    /// Route around obstacles; when false curves use the default shape
+    pub enabled: bool,
+    /// Upper bound on control-point adjustments per connection
+    pub max_iterations: usize,
+    /// Clearance kept between a routed curve and an obstacle
+    pub margin: f32,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_iterations: 8,
+            margin: 12.0,
+        }
+    }
+}
+
+/// Point on a cubic bezier at parameter `t`
+pub fn cubic_bezier(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, t: f32) -> Vec2 {
+    let u = 1.0 - t;
+    let tt = t * t;
+    let uu = u * u;
+    let uuu = uu * u;
+    let ttt = tt * t;
+
+    p0 * uuu + p1 * (3.0 * uu * t) + p2 * (3.0 * u * tt) + p3 * ttt
+}
+
+/// Whether the segment `a`-`b` touches `rect` (Liang–Barsky clipping)
+pub fn segment_intersects_rect(a: Vec2, b: Vec2, rect: &Rect) -> bool {
+    let delta = b - a;
+    let mut t0 = 0.0f32;
+    let mut t1 = 1.0f32;
+
+    for (p, q) in [
+        (-delta.x, a.x - rect.min.x),
+        (delta.x, rect.max.x - a.x),
+        (-delta.y, a.y - rect.min.y),
+        (delta.y, rect.max.y - a.y),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return false;
+            }
+        } else {
+            let r = q / p;
+            if p < 0.0 {
+                t0 = t0.max(r);
+            } else {
+                t1 = t1.min(r);
+            }
+            if t0 > t1 {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Whether the cubic bezier through `controls` crosses `rect`
+///
+/// The curve is flattened into short segments, so grazing contacts thinner
+/// than a segment's sagitta may be missed.
+pub fn curve_intersects_rect(controls: &[Vec2; 4], rect: &Rect) -> bool {
+    let [p0, p1, p2, p3] = *controls;
+    let mut previous = p0;
+    for i in 1..=INTERSECTION_SAMPLES {
+        let t = i as f32 / INTERSECTION_SAMPLES as f32;
+        let point = cubic_bezier(p0, p1, p2, p3, t);
+        if segment_intersects_rect(previous, point, rect) {
+            return true;
+        }
+        previous = point;
+    }
+    false
+}
+
+/// Default S-curve control points between an output and an input socket
+pub fn default_controls(start: Vec2, end: Vec2) -> [Vec2; 4] {
+    [
+        start,
+        start + Vec2::new(DEFAULT_CONTROL_OFFSET, 0.0),
+        end - Vec2::new(DEFAULT_CONTROL_OFFSET, 0.0),
+        end,
+    ]
+}
+
+/// Control points for a curve from `start` to `end` that avoids `obstacles`
+///
+/// Starts from the default curve and, while it crosses an obstacle, lifts
+/// both inner control points above or below every obstacle hit so far
+/// (whichever side is closer), widening the clearance on each pass. Stops
+/// after `config.max_iterations` adjustments even if a crossing remains.
+pub fn route_controls(start: Vec2, end: Vec2, obstacles: &[Rect], config: &RoutingConfig) -> [Vec2; 4] {
+    let mut controls = default_controls(start, end);
+    if !config.enabled || obstacles.is_empty() {
+        return controls;
+    }
+
+    let mut blocked: Option<Rect> = None;
+    for iteration in 0..config.max_iterations {
+        let hits = obstacles.iter()
+            .filter(|rect| curve_intersects_rect(&controls, rect))
+            .fold(None, |acc: Option<Rect>, rect| Some(acc.map_or(*rect, |r| r.union(rect))));
+        let Some(hit) = hits else {
+            break;
+        };
+        let region = blocked.map_or(hit, |r| r.union(&hit));
+        blocked = Some(region);
+
+        let clearance = config.margin * (iteration + 1) as f32;
+        let region = region.inflate(clearance);
+        let mid_y = (start.y + end.y) / 2.0;
+        let target_y = if mid_y - region.min.y <= region.max.y - mid_y {
+            region.min.y
+        } else {
+            region.max.y
+        };
+
+        // With both inner controls at height h the curve's midpoint is at
+        // (start.y + end.y) / 8 + 3h / 4; solve for the midpoint to reach target
+        let height = (target_y - (start.y + end.y) / 8.0) / 0.75;
+        let reach = ((end.x - start.x).abs() / 3.0).max(DEFAULT_CONTROL_OFFSET);
+        controls[1] = Vec2::new(start.x + reach, height);
+        controls[2] = Vec2::new(end.x - reach, height);
+    }
+
+    controls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_rect_intersection() {
+        let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0));
+        assert!(segment_intersects_rect(Vec2::new(-5.0, 5.0), Vec2::new(15.0, 5.0), &rect));
+        assert!(!segment_intersects_rect(Vec2::new(-5.0, 15.0), Vec2::new(15.0, 15.0), &rect));
+        assert!(segment_intersects_rect(Vec2::new(5.0, 5.0), Vec2::new(6.0, 6.0), &rect));
+    }
+
+    #[test]
+    fn test_routed_curve_clears_obstacle() {
+        let start = Vec2::new(60.0, 0.0);
+        let end = Vec2::new(540.0, 0.0);
+        let obstacle = Rect::from_center_size(Vec2::new(300.0, 0.0), Vec2::new(120.0, 80.0));
+        assert!(curve_intersects_rect(&default_controls(start, end), &obstacle));
+
+        let config = RoutingConfig { enabled: true, ..Default::default() };
+        let routed = route_controls(start, end, &[obstacle], &config);
+        assert!(!curve_intersects_rect(&routed, &obstacle.inflate(1.0)));
+        assert_eq!((routed[0], routed[3]), (start, end));
+    }
+
+    #[test]
+    fn test_disabled_routing_keeps_default_curve() {
+        let start = Vec2::new(0.0, 0.0);
+        let end = Vec2::new(400.0, 0.0);
+        let obstacle = Rect::from_center_size(Vec2::new(200.0, 0.0), Vec2::new(50.0, 50.0));
+        let routed = route_controls(start, end, &[obstacle], &RoutingConfig::default());
+        assert_eq!(routed, default_controls(start, end));
+    }
+}
@@ -1,10 +1,17 @@
 //! Render module for Marco 2.0
 pub mod wgpu_renderer;
 pub mod node_renderer;
+pub mod connection_routing;
 pub mod wgpu_visual_editor;
 pub mod wgpu_test;
+pub mod thumbnail;
 pub mod shaders;
 
 pub use wgpu_renderer::WGPURenderer;
-pub use node_renderer::{NodeRenderer, NodeVertex, ConnectionVertex, CameraUniforms, ThemeUniforms};
+pub use node_renderer::{
+    NodeRenderer, DEPTH_FORMAT, NodeVertex, ConnectionInstance, ConnectionGeometry, CameraUniforms, ThemeUniforms,
+    BufferLimits, BufferCapacity, CapacityChange, NodeInstanceBatch,
+};
+pub use connection_routing::{RoutingConfig, Rect};
 pub use wgpu_visual_editor::WGPUVisualNodeEditor;
+pub use thumbnail::{render_template_thumbnail, render_template_thumbnail_with};
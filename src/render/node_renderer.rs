@@ -1,16 +1,36 @@
 //! WGPU-based Node Renderer for Visual Programming Interface
 //! Handles efficient rendering of logic nodes, connections, and UI elements
 
-use wgpu::{Device, Queue, RenderPass, BindGroup, Buffer, RenderPipeline};
+use wgpu::{Device, Queue, RenderPass, BindGroup, BindGroupLayout, Buffer, RenderPipeline};
 use wgpu::util::DeviceExt;
 use glam::{Vec2, Vec3, Mat4};
 use bytemuck::{Pod, Zeroable};
 use uuid::Uuid;
 use std::collections::HashMap;
+use std::ops::Range;
 
-use crate::ui::visual_node_editor::{VisualNode, NodeConnection};
+use crate::ui::visual_node_editor::{VisualNode, NodeConnection, NodeConnectionId};
 use crate::ui::theme::Marco2Theme;
 use crate::core::types::error::MarcoError;
+use crate::render::connection_routing::{self, cubic_bezier, Rect, RoutingConfig};
+use tracing::{debug, warn};
+
+/// Depth attachment format every pass drawing a `NodeRenderer` must use
+pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Screen pixels per world unit below which nodes and connections are drawn
+/// in low detail; matches `Marco2Theme::lod_zoom_threshold`
+pub const DEFAULT_LOD_THRESHOLD: f32 = 0.4;
+
+/// Triangle strip vertices per connection in low detail: one straight quad
+pub const LOW_DETAIL_VERTICES_PER_CONNECTION: usize = 4;
+
+/// World units of off-screen border kept when culling nodes
+pub const DEFAULT_CULL_MARGIN: f32 = 200.0;
+
+/// Camera-space depth span shared by the node stack, in front of the z = 0
+/// plane connections are drawn on
+const NODE_DEPTH_RANGE: f32 = 400.0;
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
@@ -21,16 +41,27 @@ pub struct NodeVertex {
     node_id: u32, // Packed node identifier for instancing
 }
 
+/// One connection curve; the vertex shader evaluates the bezier and expands
+/// it into a `VERTICES_PER_CONNECTION` triangle strip
 #[repr(C)]
-#[derive(Copy, Clone, Debug, Pod, Zeroable)]
-pub struct ConnectionVertex {
-    position: [f32; 3],
+#[derive(Copy, Clone, Debug, Pod, Zeroable, PartialEq)]
+pub struct ConnectionInstance {
+    /// Start, first control, second control and end point
+    controls: [[f32; 2]; 4],
     color: [f32; 4],
     thickness: f32,
     connection_id: u32,
     _padding: [f32; 2],
 }
 
+impl ConnectionInstance {
+    /// Point on the curve at `t` in 0..=1, as the vertex shader computes it
+    pub fn curve_point(&self, t: f32) -> Vec2 {
+        let [start, control1, control2, end] = self.controls.map(Vec2::from);
+        cubic_bezier(start, control1, control2, end, t)
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct NodeInstance {
@@ -48,18 +79,29 @@ pub struct NodeRenderer {
     connection_pipeline: RenderPipeline,
     ui_pipeline: RenderPipeline,
     
+    // Pipeline configuration, kept to rebuild for a new sample count
+    surface_format: wgpu::TextureFormat,
+    sample_count: u32,
+    camera_bind_group_layout: BindGroupLayout,
+    theme_bind_group_layout: BindGroupLayout,
+    
     // Vertex buffers
     node_vertex_buffer: Buffer,
-    connection_vertex_buffer: Buffer,
+    connection_instance_buffer: Buffer,
     index_buffer: Buffer,
     
     // Instance data
     node_instance_buffer: Buffer,
-    node_instances: Vec<NodeInstance>,
+    node_batch: NodeInstanceBatch,
     
     // Connection data
-    connection_vertices: Vec<ConnectionVertex>,
-    max_connections: usize,
+    connection_geometry: ConnectionGeometry,
+    connection_capacity: BufferCapacity,
+    
+    // Last camera uploaded, for culling
+    camera: CameraUniforms,
+    cull_margin: f32,
+    lod_threshold: f32,
     
     // Uniform buffers
     camera_buffer: Buffer,
@@ -68,10 +110,13 @@ pub struct NodeRenderer {
     // Bind groups
     camera_bind_group: BindGroup,
     theme_bind_group: BindGroup,
-    
-    // Node type registry for efficient rendering
-    node_type_cache: HashMap<String, u32>,
-    next_node_type_id: u32,
+}
+
+/// Pipelines that depend on the render target's sample count
+struct RenderPipelines {
+    node: RenderPipeline,
+    connection: RenderPipeline,
+    ui: RenderPipeline,
 }
 
 #[repr(C)]
@@ -82,7 +127,45 @@ pub struct CameraUniforms {
     pub zoom_level: f32,
     pub viewport_size: [f32; 2],
     pub time: f32,
-    pub _padding: f32,
+    /// 1.0 when zoomed out past the LOD threshold, else 0.0
+    pub low_detail: f32,
+}
+
+impl CameraUniforms {
+    /// Orthographic camera centered on `camera_pos`, `zoom` world units per
+    /// screen pixel, in low detail when that leaves fewer than
+    /// `lod_threshold` pixels per world unit
+    pub fn new(camera_pos: Vec3, zoom: f32, viewport_size: Vec2, time: f32, lod_threshold: f32) -> Self {
+        let view = Mat4::from_translation(-camera_pos);
+        let proj = Mat4::orthographic_lh(-viewport_size.x / 2.0 * zoom, viewport_size.x / 2.0 * zoom, 
+                                         -viewport_size.y / 2.0 * zoom, viewport_size.y / 2.0 * zoom, 
+                                         -1000.0, 1000.0);
+        let low_detail = zoom > 0.0 && 1.0 / zoom < lod_threshold;
+        
+        Self {
+            view_proj: (proj * view).to_cols_array_2d(),
+            camera_pos: camera_pos.to_array(),
+            zoom_level: zoom,
+            viewport_size: viewport_size.to_array(),
+            time,
+            low_detail: if low_detail { 1.0 } else { 0.0 },
+        }
+    }
+    
+    pub fn is_low_detail(&self) -> bool {
+        self.low_detail > 0.5
+    }
+    
+    /// Strip vertices drawn per connection at this camera
+    pub fn vertices_per_connection(&self) -> usize {
+        if self.is_low_detail() { LOW_DETAIL_VERTICES_PER_CONNECTION } else { VERTICES_PER_CONNECTION }
+    }
+    
+    /// World-space area the projection shows
+    pub fn visible_rect(&self) -> Rect {
+        let [x, y, _] = self.camera_pos;
+        Rect::from_center_size(Vec2::new(x, y), Vec2::from(self.viewport_size) * self.zoom_level)
+    }
 }
 
 #[repr(C)]
@@ -96,21 +179,24 @@ pub struct ThemeUniforms {
     grid_color: [f32; 4],
     selection_color: [f32; 4],
     connection_colors: [[f32; 4]; 8], // Support for 8 different connection types
+    /// Narrowest connection stroke, in world units
+    connection_thickness: f32,
+    /// Node border width in world units
+    node_border_width: f32,
+    _padding: [f32; 2],
 }
 
 impl NodeRenderer {
     pub fn new(device: &Device, surface_format: wgpu::TextureFormat) -> Result<Self, MarcoError> {
-        // Create shader modules
-        let node_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Node Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/node.wgsl").into()),
-        });
-        
-        let connection_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Connection Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/connection.wgsl").into()),
-        });
-        
+        Self::with_limits(device, surface_format, BufferLimits::default())
+    }
+    
+    /// Create a renderer with custom initial and maximum buffer sizes
+    pub fn with_limits(
+        device: &Device,
+        surface_format: wgpu::TextureFormat,
+        limits: BufferLimits,
+    ) -> Result<Self, MarcoError> {
         // Create bind group layouts
         let camera_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             entries: &[
@@ -144,10 +230,150 @@ impl NodeRenderer {
             label: Some("theme_bind_group_layout"),
         });
         
+        let pipelines = Self::create_pipelines(
+            device, surface_format, 1, &camera_bind_group_layout, &theme_bind_group_layout,
+        );
+        
+        // Initialize buffers
+        let node_vertices = Self::create_node_geometry();
+        let node_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Node Vertex Buffer"),
+            contents: bytemuck::cast_slice(&node_vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        
+        let connection_capacity = BufferCapacity::new(
+            limits.initial_connections,
+            limits.max_connections,
+        );
+        let connection_instance_buffer = Self::create_connection_buffer(device, connection_capacity.capacity());
+        
+        let indices: &[u16] = &[0, 1, 2, 2, 3, 0]; // Quad indices
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Index Buffer"),
+            contents: bytemuck::cast_slice(indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        
+        let node_batch = NodeInstanceBatch::new(BufferCapacity::new(limits.initial_nodes, limits.max_nodes));
+        let node_instance_buffer = Self::create_instance_buffer(device, node_batch.capacity().capacity());
+        
+        // Create uniform buffers
+        let camera_uniforms = CameraUniforms {
+            view_proj: Mat4::IDENTITY.to_cols_array_2d(),
+            camera_pos: [0.0, 0.0, 0.0],
+            zoom_level: 1.0,
+            viewport_size: [1920.0, 1080.0],
+            time: 0.0,
+            low_detail: 0.0,
+        };
+        
+        let camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Camera Buffer"),
+            contents: bytemuck::cast_slice(&[camera_uniforms]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        
+        let theme_uniforms = ThemeUniforms::default();
+        let theme_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Theme Buffer"),
+            contents: bytemuck::cast_slice(&[theme_uniforms]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        
+        // Create bind groups
+        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &camera_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: camera_buffer.as_entire_binding(),
+                }
+            ],
+            label: Some("camera_bind_group"),
+        });
+        
+        let theme_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &theme_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: theme_buffer.as_entire_binding(),
+                }
+            ],
+            label: Some("theme_bind_group"),
+        });
+        
+        Ok(Self {
+            node_pipeline: pipelines.node,
+            connection_pipeline: pipelines.connection,
+            ui_pipeline: pipelines.ui,
+            surface_format,
+            sample_count: 1,
+            camera_bind_group_layout,
+            theme_bind_group_layout,
+            node_vertex_buffer,
+            connection_instance_buffer,
+            index_buffer,
+            node_instance_buffer,
+            node_batch,
+            connection_geometry: ConnectionGeometry::new(),
+            connection_capacity,
+            camera: camera_uniforms,
+            cull_margin: DEFAULT_CULL_MARGIN,
+            lod_threshold: DEFAULT_LOD_THRESHOLD,
+            camera_buffer,
+            theme_buffer,
+            camera_bind_group,
+            theme_bind_group,
+        })
+    }
+    
+    /// MSAA samples per pixel the pipelines render with
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+    
+    /// Rebuild every pipeline for render passes with `sample_count` samples
+    ///
+    /// The count must be one the device supports for the surface format.
+    pub fn set_sample_count(&mut self, device: &Device, sample_count: u32) {
+        if sample_count == self.sample_count {
+            return;
+        }
+        let pipelines = Self::create_pipelines(
+            device, self.surface_format, sample_count, &self.camera_bind_group_layout, &self.theme_bind_group_layout,
+        );
+        self.node_pipeline = pipelines.node;
+        self.connection_pipeline = pipelines.connection;
+        self.ui_pipeline = pipelines.ui;
+        self.sample_count = sample_count;
+        debug!("Node renderer pipelines rebuilt for {}x MSAA", sample_count);
+    }
+    
+    /// Build the node, connection and UI pipelines for `sample_count` MSAA
+    fn create_pipelines(
+        device: &Device,
+        surface_format: wgpu::TextureFormat,
+        sample_count: u32,
+        camera_bind_group_layout: &BindGroupLayout,
+        theme_bind_group_layout: &BindGroupLayout,
+    ) -> RenderPipelines {
+        // Create shader modules
+        let node_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Node Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/node.wgsl").into()),
+        });
+        
+        let connection_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Connection Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/connection.wgsl").into()),
+        });
+        
         // Create render pipelines
         let node_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Node Pipeline Layout"),
-            bind_group_layouts: &[&camera_bind_group_layout, &theme_bind_group_layout],
+            bind_group_layouts: &[camera_bind_group_layout, theme_bind_group_layout],
             push_constant_ranges: &[],
         });
         
@@ -234,9 +460,9 @@ impl NodeRenderer {
                 unclipped_depth: false,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(Self::depth_state(true)),
             multisample: wgpu::MultisampleState {
-                count: 1,
+                count: sample_count,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
@@ -251,23 +477,29 @@ impl NodeRenderer {
                 module: &connection_shader,
                 entry_point: "vs_main",
                 buffers: &[
+                    // One instance per connection; no per-vertex data
                     wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<ConnectionVertex>() as wgpu::BufferAddress,
-                        step_mode: wgpu::VertexStepMode::Vertex,
+                        array_stride: std::mem::size_of::<ConnectionInstance>() as wgpu::BufferAddress,
+                        step_mode: wgpu::VertexStepMode::Instance,
                         attributes: &[
                             wgpu::VertexAttribute {
                                 offset: 0,
                                 shader_location: 0,
-                                format: wgpu::VertexFormat::Float32x3,
+                                format: wgpu::VertexFormat::Float32x4,
                             },
                             wgpu::VertexAttribute {
-                                offset: std::mem::size_of::<[f32; 3]>() as wgpu::BufferAddress,
+                                offset: std::mem::size_of::<[f32; 4]>() as wgpu::BufferAddress,
                                 shader_location: 1,
                                 format: wgpu::VertexFormat::Float32x4,
                             },
                             wgpu::VertexAttribute {
-                                offset: std::mem::size_of::<[f32; 7]>() as wgpu::BufferAddress,
+                                offset: std::mem::size_of::<[f32; 8]>() as wgpu::BufferAddress,
                                 shader_location: 2,
+                                format: wgpu::VertexFormat::Float32x4,
+                            },
+                            wgpu::VertexAttribute {
+                                offset: std::mem::size_of::<[f32; 12]>() as wgpu::BufferAddress,
+                                shader_location: 3,
                                 format: wgpu::VertexFormat::Float32,
                             },
                         ],
@@ -292,94 +524,19 @@ impl NodeRenderer {
                 unclipped_depth: false,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(Self::depth_state(false)),
             multisample: wgpu::MultisampleState {
-                count: 1,
+                count: sample_count,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
             multiview: None,
         });
         
-        // Initialize buffers
-        let node_vertices = Self::create_node_geometry();
-        let node_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Node Vertex Buffer"),
-            contents: bytemuck::cast_slice(&node_vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-        
-        let connection_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Connection Vertex Buffer"),
-            size: std::mem::size_of::<ConnectionVertex>() as u64 * 10000, // Support for many connections
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-        
-        let indices: &[u16] = &[0, 1, 2, 2, 3, 0]; // Quad indices
-        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Index Buffer"),
-            contents: bytemuck::cast_slice(indices),
-            usage: wgpu::BufferUsages::INDEX,
-        });
-        
-        let node_instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Node Instance Buffer"),
-            size: std::mem::size_of::<NodeInstance>() as u64 * 1000, // Support for 1000 nodes
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-        
-        // Create uniform buffers
-        let camera_uniforms = CameraUniforms {
-            view_proj: Mat4::IDENTITY.to_cols_array_2d(),
-            camera_pos: [0.0, 0.0, 0.0],
-            zoom_level: 1.0,
-            viewport_size: [1920.0, 1080.0],
-            time: 0.0,
-            _padding: 0.0,
-        };
-        
-        let camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Camera Buffer"),
-            contents: bytemuck::cast_slice(&[camera_uniforms]),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
-        
-        let theme_uniforms = ThemeUniforms::default();
-        let theme_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Theme Buffer"),
-            contents: bytemuck::cast_slice(&[theme_uniforms]),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
-        
-        // Create bind groups
-        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &camera_bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: camera_buffer.as_entire_binding(),
-                }
-            ],
-            label: Some("camera_bind_group"),
-        });
-        
-        let theme_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &theme_bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: theme_buffer.as_entire_binding(),
-                }
-            ],
-            label: Some("theme_bind_group"),
-        });
-        
         // Create UI pipeline (using connection shader as base)
         let ui_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("UI Pipeline Layout"),
-            bind_group_layouts: &[&camera_bind_group_layout, &theme_bind_group_layout],
+            bind_group_layouts: &[camera_bind_group_layout, theme_bind_group_layout],
             push_constant_ranges: &[],
         });
         
@@ -390,23 +547,29 @@ impl NodeRenderer {
                 module: &connection_shader,
                 entry_point: "vs_main",
                 buffers: &[
+                    // One instance per connection; no per-vertex data
                     wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<ConnectionVertex>() as wgpu::BufferAddress,
-                        step_mode: wgpu::VertexStepMode::Vertex,
+                        array_stride: std::mem::size_of::<ConnectionInstance>() as wgpu::BufferAddress,
+                        step_mode: wgpu::VertexStepMode::Instance,
                         attributes: &[
                             wgpu::VertexAttribute {
                                 offset: 0,
                                 shader_location: 0,
-                                format: wgpu::VertexFormat::Float32x3,
+                                format: wgpu::VertexFormat::Float32x4,
                             },
                             wgpu::VertexAttribute {
-                                offset: std::mem::size_of::<[f32; 3]>() as wgpu::BufferAddress,
+                                offset: std::mem::size_of::<[f32; 4]>() as wgpu::BufferAddress,
                                 shader_location: 1,
                                 format: wgpu::VertexFormat::Float32x4,
                             },
                             wgpu::VertexAttribute {
-                                offset: std::mem::size_of::<[f32; 7]>() as wgpu::BufferAddress,
+                                offset: std::mem::size_of::<[f32; 8]>() as wgpu::BufferAddress,
                                 shader_location: 2,
+                                format: wgpu::VertexFormat::Float32x4,
+                            },
+                            wgpu::VertexAttribute {
+                                offset: std::mem::size_of::<[f32; 12]>() as wgpu::BufferAddress,
+                                shader_location: 3,
                                 format: wgpu::VertexFormat::Float32,
                             },
                         ],
@@ -431,32 +594,71 @@ impl NodeRenderer {
                 unclipped_depth: false,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(Self::depth_state(false)),
             multisample: wgpu::MultisampleState {
-                count: 1,
+                count: sample_count,
                 mask: !0,
                 alpha_to_coverage_enabled: false,
             },
             multiview: None,
         });
         
-        Ok(Self {
-            node_pipeline,
-            connection_pipeline,
-            ui_pipeline,
-            node_vertex_buffer,
-            connection_vertex_buffer,
-            index_buffer,
-            node_instance_buffer,
-            node_instances: Vec::new(),
-            connection_vertices: Vec::new(),
-            max_connections: 10000,
-            camera_buffer,
-            theme_buffer,
-            camera_bind_group,
-            theme_bind_group,
-            node_type_cache: HashMap::new(),
-            next_node_type_id: 0,
+        RenderPipelines { node: node_pipeline, connection: connection_pipeline, ui: ui_pipeline }
+    }
+    
+    /// Depth testing for a pipeline; nodes write depth so the stack resolves
+    /// by z, while connections only test so they stay behind node bodies
+    fn depth_state(write: bool) -> wgpu::DepthStencilState {
+        wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: write,
+            depth_compare: if write { wgpu::CompareFunction::Less } else { wgpu::CompareFunction::LessEqual },
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }
+    }
+    
+    /// Depth target matching a `size` color target with `sample_count` samples
+    pub fn create_depth_view(device: &Device, size: (u32, u32), sample_count: u32) -> wgpu::TextureView {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Node Depth Texture"),
+            size: wgpu::Extent3d { width: size.0.max(1), height: size.1.max(1), depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        }).create_view(&wgpu::TextureViewDescriptor::default())
+    }
+    
+    /// Depth attachment clearing `view` to the far plane
+    pub fn depth_attachment(view: &wgpu::TextureView) -> wgpu::RenderPassDepthStencilAttachment<'_> {
+        wgpu::RenderPassDepthStencilAttachment {
+            view,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(1.0),
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        }
+    }
+    
+    fn create_instance_buffer(device: &Device, capacity: usize) -> Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Node Instance Buffer"),
+            size: (std::mem::size_of::<NodeInstance>() * capacity) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+    
+    fn create_connection_buffer(device: &Device, capacity: usize) -> Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Connection Instance Buffer"),
+            size: (std::mem::size_of::<ConnectionInstance>() * capacity) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
         })
     }
     
@@ -493,113 +695,347 @@ impl NodeRenderer {
     
     /// Update camera matrices and viewport
     pub fn update_camera(&mut self, queue: &Queue, camera_pos: Vec3, zoom: f32, viewport_size: Vec2, time: f32) {
-        let view = Mat4::from_translation(-camera_pos);
-        let proj = Mat4::orthographic_lh(-viewport_size.x / 2.0 * zoom, viewport_size.x / 2.0 * zoom, 
-                                         -viewport_size.y / 2.0 * zoom, viewport_size.y / 2.0 * zoom, 
-                                         -1000.0, 1000.0);
-        
-        let camera_uniforms = CameraUniforms {
-            view_proj: (proj * view).to_cols_array_2d(),
-            camera_pos: camera_pos.to_array(),
-            zoom_level: zoom,
-            viewport_size: viewport_size.to_array(),
-            time,
-            _padding: 0.0,
-        };
-        
+        let camera_uniforms = CameraUniforms::new(camera_pos, zoom, viewport_size, time, self.lod_threshold);
         queue.write_buffer(&self.camera_buffer, 0, bytemuck::cast_slice(&[camera_uniforms]));
+        self.camera = camera_uniforms;
+    }
+    
+    /// World-space area shown by the last `update_camera`
+    pub fn view_rect(&self) -> Rect {
+        self.camera.visible_rect()
     }
     
-    /// Update theme colors
+    /// Draw nodes as plain rectangles and connections as straight lines when
+    /// fewer than `threshold` screen pixels show one world unit
+    ///
+    /// Takes effect at the next `update_camera`.
+    pub fn set_lod_threshold(&mut self, threshold: f32) {
+        self.lod_threshold = threshold.max(0.0);
+    }
+    
+    /// Connection vertices the next `render` draws
+    pub fn connection_vertex_count(&self) -> usize {
+        let connections = self.connection_geometry.instances().len().min(self.connection_capacity.capacity());
+        connections * self.camera.vertices_per_connection()
+    }
+    
+    /// Keep nodes within `margin` world units outside the view instanced,
+    /// so they are ready when panning brings them on screen
+    pub fn set_cull_margin(&mut self, margin: f32) {
+        self.cull_margin = margin.max(0.0);
+    }
+    
+    /// Update theme colors, line widths and the LOD threshold
+    ///
+    /// Writes the theme buffer right away, so the next frame drawn uses the
+    /// new theme.
     pub fn update_theme(&mut self, queue: &Queue, theme: &Marco2Theme) {
         let theme_uniforms = ThemeUniforms::from_theme(theme);
         queue.write_buffer(&self.theme_buffer, 0, bytemuck::cast_slice(&[theme_uniforms]));
+        self.set_lod_threshold(theme.lod_zoom_threshold);
     }
     
     /// Update node instances for rendering
-    pub fn update_nodes(&mut self, queue: &Queue, nodes: &HashMap<Uuid, VisualNode>) -> Result<(), MarcoError> {
-        self.node_instances.clear();
-        
-        for (_, node) in nodes {
-            let transform = Mat4::from_scale_rotation_translation(
-                Vec3::new(node.size.x / 2.0, node.size.y / 2.0, 1.0),
-                glam::Quat::IDENTITY,
-                Vec3::new(node.position.x, node.position.y, 0.0),
-            );
-            
-            let node_type_id = self.get_or_register_node_type(&node.node_type);
-            
-            let instance = NodeInstance {
-                transform: transform.to_cols_array_2d(),
-                color: if node.selected { [0.2, 0.6, 1.0, 1.0] } else { [0.8, 0.8, 0.8, 1.0] },
-                node_type: node_type_id,
-                selected: if node.selected { 1 } else { 0 },
-                _padding: [0.0; 2],
-            };
-            
-            self.node_instances.push(instance);
+    ///
+    /// Nodes entirely outside `view` (usually `view_rect()`) grown by the
+    /// cull margin are skipped. Grows the instance buffer when the node
+    /// count exceeds its capacity. Past the configured cap, only the first
+    /// `max_nodes` instances are drawn.
+    pub fn update_nodes(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        nodes: &HashMap<Uuid, VisualNode>,
+        view: Rect,
+    ) -> Result<(), MarcoError> {
+        let change = self.node_batch.update(nodes, Some(view.inflate(self.cull_margin)));
+        if change.needs_realloc() {
+            self.node_instance_buffer = Self::create_instance_buffer(device, self.node_batch.capacity().capacity());
         }
         
         // Upload instance data to GPU
-        if !self.node_instances.is_empty() {
-            queue.write_buffer(&self.node_instance_buffer, 0, bytemuck::cast_slice(&self.node_instances));
+        let uploaded = self.node_batch.uploaded();
+        if !uploaded.is_empty() {
+            queue.write_buffer(&self.node_instance_buffer, 0, bytemuck::cast_slice(uploaded));
         }
         
         Ok(())
     }
     
-    /// Update connection geometry for rendering
-    pub fn update_connections(&mut self, queue: &Queue, connections: &[NodeConnection]) -> Result<(), MarcoError> {
-        self.connection_vertices.clear();
+    /// Update connection instances for rendering
+    ///
+    /// Only connections whose curve or style changed since the last call are
+    /// uploaded; a frame where nothing moved writes nothing. The instance
+    /// buffer grows when the connection count exceeds its capacity.
+    pub fn update_connections(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        nodes: &HashMap<Uuid, VisualNode>,
+        connections: &[NodeConnection],
+    ) -> Result<(), MarcoError> {
+        let mut dirty_ranges = self.connection_geometry.update(nodes, connections);
+        let instances = self.connection_geometry.instances();
+        let stride = std::mem::size_of::<ConnectionInstance>() as u64;
         
-        for (index, connection) in connections.iter().enumerate() {
-            // Generate bezier curve vertices for smooth connections
-            let bezier_points = self.generate_bezier_curve(&connection);
-            
-            for (i, point) in bezier_points.iter().enumerate() {
-                let t = i as f32 / (bezier_points.len() - 1) as f32;
-                let vertex = ConnectionVertex {
-                    position: [point.x, point.y, 0.0],
-                    color: connection.color,
-                    thickness: connection.thickness,
-                    connection_id: index as u32,
-                    _padding: [t, 0.0], // Store curve parameter for shader effects
-                };
-                self.connection_vertices.push(vertex);
-            }
+        let was_capped = self.connection_capacity.is_capped();
+        let change = self.connection_capacity.reserve(instances.len());
+        change.log("connection instance", was_capped);
+        if change.needs_realloc() {
+            // A fresh buffer has none of the previous contents
+            self.connection_instance_buffer = Self::create_connection_buffer(device, self.connection_capacity.capacity());
+            dirty_ranges = std::iter::once(0..instances.len()).collect();
         }
         
-        // Upload connection data to GPU
-        if !self.connection_vertices.is_empty() {
-            queue.write_buffer(&self.connection_vertex_buffer, 0, bytemuck::cast_slice(&self.connection_vertices));
+        // Upload only the rewritten ranges that fit in the buffer
+        let capacity = self.connection_capacity.capacity();
+        for range in dirty_ranges {
+            let range = range.start.min(capacity)..range.end.min(capacity);
+            if range.is_empty() {
+                continue;
+            }
+            queue.write_buffer(
+                &self.connection_instance_buffer,
+                range.start as u64 * stride,
+                bytemuck::cast_slice(&instances[range]),
+            );
         }
         
         Ok(())
     }
     
+    /// Configure routing of connections around node bodies
+    pub fn set_connection_routing(&mut self, routing: RoutingConfig) {
+        self.connection_geometry.set_routing(routing);
+    }
+    
     /// Render all nodes and connections
     pub fn render<'a>(&'a self, render_pass: &mut RenderPass<'a>) {
         // Render connections first (behind nodes)
-        if !self.connection_vertices.is_empty() {
+        let connection_count = self.connection_geometry.instances().len()
+            .min(self.connection_capacity.capacity());
+        if connection_count > 0 {
             render_pass.set_pipeline(&self.connection_pipeline);
             render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
             render_pass.set_bind_group(1, &self.theme_bind_group, &[]);
-            render_pass.set_vertex_buffer(0, self.connection_vertex_buffer.slice(..));
-            render_pass.draw(0..self.connection_vertices.len() as u32, 0..1);
+            render_pass.set_vertex_buffer(0, self.connection_instance_buffer.slice(..));
+            render_pass.draw(0..self.camera.vertices_per_connection() as u32, 0..connection_count as u32);
         }
         
         // Render nodes
-        if !self.node_instances.is_empty() {
+        let instance_count = self.node_batch.uploaded().len();
+        if instance_count > 0 {
             render_pass.set_pipeline(&self.node_pipeline);
             render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
             render_pass.set_bind_group(1, &self.theme_bind_group, &[]);
             render_pass.set_vertex_buffer(0, self.node_vertex_buffer.slice(..));
             render_pass.set_vertex_buffer(1, self.node_instance_buffer.slice(..));
             render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-            render_pass.draw_indexed(0..6, 0, 0..self.node_instances.len() as u32);
+            render_pass.draw_indexed(0..6, 0, 0..instance_count as u32);
+        }
+    }
+}
+
+/// Initial and maximum GPU buffer sizes for the node renderer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferLimits {
+    /// Node instances the instance buffer starts with
+    pub initial_nodes: usize,
+    /// Largest number of node instances the buffer may grow to
+    pub max_nodes: usize,
+    /// Connection instances the connection buffer starts with
+    pub initial_connections: usize,
+    /// Largest number of connection instances the buffer may grow to
+    pub max_connections: usize,
+}
+
+impl Default for BufferLimits {
+    fn default() -> Self {
+        Self {
+            initial_nodes: 1000,
+            max_nodes: 64_000,
+            initial_connections: 500,
+            max_connections: 64_000,
+        }
+    }
+}
+
+/// Outcome of reserving space in a [`BufferCapacity`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapacityChange {
+    /// The current buffer already fits
+    Unchanged,
+    /// The buffer grew and now fits the request
+    Grew { from: usize, to: usize },
+    /// The request exceeds the cap; the buffer is at the cap and truncates
+    Capped { from: usize, to: usize, required: usize },
+}
+
+impl CapacityChange {
+    /// Whether the GPU buffer has to be reallocated
+    pub fn needs_realloc(&self) -> bool {
+        match self {
+            CapacityChange::Unchanged => false,
+            CapacityChange::Grew { .. } => true,
+            CapacityChange::Capped { from, to, .. } => from != to,
+        }
+    }
+    
+    /// Whether the request was cut short by the cap
+    pub fn is_capped(&self) -> bool {
+        matches!(self, CapacityChange::Capped { .. })
+    }
+    
+    /// Log the change; the cap warning fires only on entering the capped state
+    fn log(&self, buffer: &str, was_capped: bool) {
+        match self {
+            CapacityChange::Unchanged => {
+                if was_capped {
+                    debug!("{} buffer no longer capped", buffer);
+                }
+            }
+            CapacityChange::Grew { from, to } => {
+                debug!("Growing {} buffer from {} to {}", buffer, from, to);
+            }
+            CapacityChange::Capped { to, required, .. } if !was_capped => {
+                warn!("{} buffer capped at {} (need {}); extra items will not be drawn", buffer, to, required);
+            }
+            CapacityChange::Capped { .. } => {}
+        }
+    }
+}
+
+/// Element capacity of a growable GPU buffer
+///
+/// Grows by doubling until the request fits or `max_capacity` is reached.
+/// Kept separate from the buffer itself so sizing decisions are testable
+/// without a GPU device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferCapacity {
+    capacity: usize,
+    max_capacity: usize,
+    capped: bool,
+}
+
+impl BufferCapacity {
+    pub fn new(initial: usize, max_capacity: usize) -> Self {
+        let max_capacity = max_capacity.max(1);
+        Self {
+            capacity: initial.clamp(1, max_capacity),
+            max_capacity,
+            capped: false,
+        }
+    }
+    
+    /// Current element capacity
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    
+    /// Largest capacity this buffer may grow to
+    pub fn max_capacity(&self) -> usize {
+        self.max_capacity
+    }
+    
+    /// Whether the last `reserve` was cut short by the cap
+    pub fn is_capped(&self) -> bool {
+        self.capped
+    }
+    
+    /// Make room for `required` elements, growing if needed
+    pub fn reserve(&mut self, required: usize) -> CapacityChange {
+        self.capped = required > self.max_capacity;
+        if required <= self.capacity {
+            return CapacityChange::Unchanged;
+        }
+        
+        let from = self.capacity;
+        let mut to = from;
+        while to < required && to < self.max_capacity {
+            to = to.saturating_mul(2).min(self.max_capacity);
+        }
+        self.capacity = to;
+        
+        if to >= required {
+            CapacityChange::Grew { from, to }
+        } else {
+            CapacityChange::Capped { from, to, required }
+        }
+    }
+}
+
+/// CPU-side node instance list sized against a growable buffer
+#[derive(Debug)]
+pub struct NodeInstanceBatch {
+    instances: Vec<NodeInstance>,
+    capacity: BufferCapacity,
+    // Node type registry for efficient rendering
+    node_type_cache: HashMap<String, u32>,
+    next_node_type_id: u32,
+}
+
+impl NodeInstanceBatch {
+    pub fn new(capacity: BufferCapacity) -> Self {
+        Self {
+            instances: Vec::new(),
+            capacity,
+            node_type_cache: HashMap::new(),
+            next_node_type_id: 0,
         }
     }
     
+    /// Rebuild instances for `nodes` and reserve buffer space for them
+    ///
+    /// With `visible`, nodes whose bounds miss it are left out. Instances
+    /// are ordered back to front by `VisualNode::stacking_key` and each steps
+    /// nearer the camera, so depth testing and blending agree.
+    pub fn update(&mut self, nodes: &HashMap<Uuid, VisualNode>, visible: Option<Rect>) -> CapacityChange {
+        self.instances.clear();
+        
+        let mut stacked: Vec<&VisualNode> = nodes.values()
+            .filter(|node| visible.is_none_or(|view| view.intersects(&Rect::from_center_size(node.position, node.size))))
+            .collect();
+        stacked.sort_by_key(|node| node.stacking_key());
+        let depth_step = NODE_DEPTH_RANGE / (stacked.len() + 1) as f32;
+        
+        for (rank, node) in stacked.into_iter().enumerate() {
+            let transform = Mat4::from_scale_rotation_translation(
+                Vec3::new(node.size.x / 2.0, node.size.y / 2.0, 1.0),
+                glam::Quat::IDENTITY,
+                Vec3::new(node.position.x, node.position.y, -depth_step * (rank + 1) as f32),
+            );
+            
+            let node_type_id = self.get_or_register_node_type(&node.node_type);
+            
+            self.instances.push(NodeInstance {
+                transform: transform.to_cols_array_2d(),
+                color: if node.selected { [0.2, 0.6, 1.0, 1.0] } else { [0.8, 0.8, 0.8, 1.0] },
+                node_type: node_type_id,
+                selected: if node.selected { 1 } else { 0 },
+                _padding: [0.0; 2],
+            });
+        }
+        
+        let was_capped = self.capacity.is_capped();
+        let change = self.capacity.reserve(self.instances.len());
+        change.log("node instance", was_capped);
+        change
+    }
+    
+    /// All instances built by the last update
+    pub fn instances(&self) -> &[NodeInstance] {
+        &self.instances
+    }
+    
+    /// Instances that fit in the buffer and are uploaded
+    pub fn uploaded(&self) -> &[NodeInstance] {
+        &self.instances[..self.instances.len().min(self.capacity.capacity())]
+    }
+    
+    pub fn capacity(&self) -> &BufferCapacity {
+        &self.capacity
+    }
+    
     fn get_or_register_node_type(&mut self, node_type: &str) -> u32 {
         if let Some(&id) = self.node_type_cache.get(node_type) {
             id
@@ -610,37 +1046,138 @@ impl NodeRenderer {
             id
         }
     }
+}
+
+/// Number of line segments a connection curve is drawn with; must match
+/// `SEGMENTS` in `shaders/connection.wgsl`
+pub const CONNECTION_SEGMENTS: usize = 20;
+
+/// Triangle strip vertices drawn per connection instance, two per curve point
+pub const VERTICES_PER_CONNECTION: usize = (CONNECTION_SEGMENTS + 1) * 2;
+
+/// Cached inputs that determine a connection's instance
+#[derive(Debug, Clone, PartialEq)]
+struct ConnectionCacheEntry {
+    id: NodeConnectionId,
+    controls: [Vec2; 4],
+    color: [f32; 4],
+    thickness: f32,
+}
+
+/// CPU-side connection instance store with per-connection dirty tracking
+///
+/// Keeps one instance per connection, in connection order, and remembers the
+/// control points each was built from, so a frame where one node moves only
+/// rewrites the instances attached to it.
+#[derive(Debug, Default)]
+pub struct ConnectionGeometry {
+    instances: Vec<ConnectionInstance>,
+    cache: Vec<ConnectionCacheEntry>,
+    routing: RoutingConfig,
+}
+
+impl ConnectionGeometry {
+    pub fn new() -> Self {
+        Self::default()
+    }
     
-    fn generate_bezier_curve(&self, connection: &NodeConnection) -> Vec<Vec2> {
-        // Generate smooth bezier curve between connection points
-        // This is a simplified implementation - in practice, you'd calculate
-        // actual node socket positions
-        let start = Vec2::new(0.0, 0.0); // Would get from actual node output position
-        let end = Vec2::new(100.0, 100.0); // Would get from actual node input position
-        
-        let control1 = start + Vec2::new(50.0, 0.0);
-        let control2 = end + Vec2::new(-50.0, 0.0);
+    /// Create geometry that routes curves per `routing`
+    pub fn with_routing(routing: RoutingConfig) -> Self {
+        Self { routing, ..Self::default() }
+    }
+    
+    pub fn routing(&self) -> &RoutingConfig {
+        &self.routing
+    }
+    
+    /// Change routing; every curve is rebuilt on the next update
+    pub fn set_routing(&mut self, routing: RoutingConfig) {
+        if self.routing != routing {
+            self.routing = routing;
+            self.cache.clear();
+        }
+    }
+    
+    /// All instances in connection order
+    pub fn instances(&self) -> &[ConnectionInstance] {
+        &self.instances
+    }
+    
+    /// Rebuild instances of connections whose endpoints or style changed
+    ///
+    /// Returns the merged instance ranges that were rewritten and need
+    /// uploading; empty when nothing changed.
+    pub fn update(
+        &mut self,
+        nodes: &HashMap<Uuid, VisualNode>,
+        connections: &[NodeConnection],
+    ) -> Vec<Range<usize>> {
+        self.instances.resize(connections.len(), ConnectionInstance::zeroed());
+        self.cache.truncate(connections.len());
         
-        let mut points = Vec::new();
-        let steps = 20;
+        // Node bodies curves may need to avoid; moving any of them can reroute
+        // other connections, which the control-point comparison below catches
+        let bodies: Vec<(Uuid, Rect)> = if self.routing.enabled {
+            nodes.values().map(|node| (node.id, Rect::from_center_size(node.position, node.size))).collect()
+        } else {
+            Vec::new()
+        };
         
-        for i in 0..=steps {
-            let t = i as f32 / steps as f32;
-            let point = Self::cubic_bezier(start, control1, control2, end, t);
-            points.push(point);
+        let mut dirty: Vec<Range<usize>> = Vec::new();
+        for (index, connection) in connections.iter().enumerate() {
+            let (start, end) = Self::connection_endpoints(nodes, &connection.id);
+            let obstacles: Vec<Rect> = bodies.iter()
+                .filter(|(id, _)| *id != connection.id.from_node && *id != connection.id.to_node)
+                .map(|(_, rect)| *rect)
+                .collect();
+            let entry = ConnectionCacheEntry {
+                id: connection.id.clone(),
+                controls: connection_routing::route_controls(start, end, &obstacles, &self.routing),
+                color: connection.color,
+                thickness: connection.thickness,
+            };
+            
+            if self.cache.get(index) == Some(&entry) {
+                continue;
+            }
+            
+            self.instances[index] = Self::instance(&entry, index as u32);
+            let range = index..index + 1;
+            if index < self.cache.len() {
+                self.cache[index] = entry;
+            } else {
+                self.cache.push(entry);
+            }
+            
+            // Merge with the previous range when contiguous
+            match dirty.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => dirty.push(range),
+            }
         }
         
-        points
+        dirty
     }
     
-    fn cubic_bezier(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, t: f32) -> Vec2 {
-        let u = 1.0 - t;
-        let tt = t * t;
-        let uu = u * u;
-        let uuu = uu * u;
-        let ttt = tt * t;
-        
-        p0 * uuu + p1 * (3.0 * uu * t) + p2 * (3.0 * u * tt) + p3 * ttt
+    /// World positions of the output and input sockets a connection joins
+    fn connection_endpoints(nodes: &HashMap<Uuid, VisualNode>, id: &NodeConnectionId) -> (Vec2, Vec2) {
+        let start = nodes.get(&id.from_node)
+            .and_then(|node| node.output_socket_position(&id.from_output))
+            .unwrap_or(Vec2::ZERO);
+        let end = nodes.get(&id.to_node)
+            .and_then(|node| node.input_socket_position(&id.to_input))
+            .unwrap_or(Vec2::ZERO);
+        (start, end)
+    }
+    
+    fn instance(entry: &ConnectionCacheEntry, connection_id: u32) -> ConnectionInstance {
+        ConnectionInstance {
+            controls: entry.controls.map(|point| point.to_array()),
+            color: entry.color,
+            thickness: entry.thickness,
+            connection_id,
+            _padding: [0.0; 2],
+        }
     }
 }
 
@@ -664,6 +1201,9 @@ impl Default for ThemeUniforms {
                 [1.0, 0.5, 0.0, 1.0], // Orange
                 [0.5, 0.0, 1.0, 1.0], // Purple
             ],
+            connection_thickness: 2.0,
+            node_border_width: 2.0,
+            _padding: [0.0; 2],
         }
     }
 }
@@ -682,6 +1222,244 @@ impl ThemeUniforms {
                 [1.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0], [1.0, 1.0, 0.0, 1.0],
                 [1.0, 0.0, 1.0, 1.0], [0.0, 1.0, 1.0, 1.0], [1.0, 0.5, 0.0, 1.0], [0.5, 0.0, 1.0, 1.0],
             ],
+            connection_thickness: theme.connection_thickness.max(0.0),
+            node_border_width: theme.node_border_width.max(0.0),
+            _padding: [0.0; 2],
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::visual_node_editor::VisualNodeEditor;
+
+    fn chained_editor(pairs: usize) -> (VisualNodeEditor, Vec<Uuid>) {
+        let mut editor = VisualNodeEditor::new();
+        let mut sources = Vec::new();
+        for i in 0..pairs {
+            let y = i as f32 * 200.0;
+            let from = editor.add_node("add", Vec2::new(0.0, y)).unwrap();
+            let to = editor.add_node("add", Vec2::new(300.0, y)).unwrap();
+            editor.connect_nodes(from, "result", to, "a").unwrap();
+            sources.push(from);
+        }
+        (editor, sources)
+    }
+
+    #[test]
+    fn test_first_update_tessellates_everything() {
+        let (editor, _) = chained_editor(5);
+        let mut geometry = ConnectionGeometry::new();
+
+        let dirty = geometry.update(&editor.nodes, &editor.connections);
+        assert_eq!(dirty, vec![0..5]);
+        assert_eq!(geometry.instances().len(), 5);
+
+        // Nothing moved, nothing to rewrite
+        assert!(geometry.update(&editor.nodes, &editor.connections).is_empty());
+    }
+
+    #[test]
+    fn test_moving_one_node_rewrites_only_its_connection() {
+        let (mut editor, sources) = chained_editor(8);
+        let mut geometry = ConnectionGeometry::new();
+        geometry.update(&editor.nodes, &editor.connections);
+        let before = geometry.instances().to_vec();
+
+        let moved = sources[3];
+        editor.nodes.get_mut(&moved).unwrap().position += Vec2::new(40.0, 20.0);
+        let index = editor.connections.iter().position(|c| c.id.from_node == moved).unwrap();
+
+        let dirty = geometry.update(&editor.nodes, &editor.connections);
+        assert_eq!(dirty, vec![index..index + 1]);
+
+        for (i, instance) in geometry.instances().iter().enumerate() {
+            if i == index {
+                continue;
+            }
+            assert_eq!(*instance, before[i], "instance {} of an unmoved connection changed", i);
+        }
+        assert_ne!(geometry.instances()[index].controls, before[index].controls);
+    }
+
+    #[test]
+    fn test_removed_connection_shrinks_buffer() {
+        let (mut editor, _) = chained_editor(4);
+        let mut geometry = ConnectionGeometry::new();
+        geometry.update(&editor.nodes, &editor.connections);
+
+        editor.connections.pop();
+        let dirty = geometry.update(&editor.nodes, &editor.connections);
+        assert!(dirty.is_empty());
+        assert_eq!(geometry.instances().len(), 3);
+    }
+
+    #[test]
+    fn test_routing_avoids_obstructing_node() {
+        let mut editor = VisualNodeEditor::new();
+        editor.snap_to_grid = false;
+        let from = editor.add_node("add", Vec2::new(0.0, 0.0)).unwrap();
+        let to = editor.add_node("add", Vec2::new(600.0, 0.0)).unwrap();
+        let blocker = editor.add_node("add", Vec2::new(300.0, 0.0)).unwrap();
+        editor.connect_nodes(from, "result", to, "a").unwrap();
+
+        let blocker_node = &editor.nodes[&blocker];
+        let obstacle = Rect::from_center_size(blocker_node.position, blocker_node.size);
+        let crosses = |geometry: &ConnectionGeometry| {
+            let instance = &geometry.instances()[0];
+            (0..CONNECTION_SEGMENTS).any(|i| {
+                let a = instance.curve_point(i as f32 / CONNECTION_SEGMENTS as f32);
+                let b = instance.curve_point((i + 1) as f32 / CONNECTION_SEGMENTS as f32);
+                connection_routing::segment_intersects_rect(a, b, &obstacle)
+            })
+        };
+
+        let mut geometry = ConnectionGeometry::new();
+        geometry.update(&editor.nodes, &editor.connections);
+        assert!(crosses(&geometry));
+
+        geometry.set_routing(RoutingConfig { enabled: true, ..Default::default() });
+        let dirty = geometry.update(&editor.nodes, &editor.connections);
+        assert_eq!(dirty, vec![0..1]);
+        assert!(!crosses(&geometry));
+    }
+
+    #[test]
+    fn test_selected_and_front_nodes_are_nearer_the_camera() {
+        let mut editor = VisualNodeEditor::new();
+        let back = editor.add_node("add", Vec2::ZERO).unwrap();
+        let middle = editor.add_node("add", Vec2::new(40.0, 0.0)).unwrap();
+        let front = editor.add_node("add", Vec2::new(80.0, 0.0)).unwrap();
+        let depth_of = |batch: &NodeInstanceBatch, nodes: &HashMap<Uuid, VisualNode>, id: Uuid| {
+            let x = nodes[&id].position.x;
+            batch.instances().iter().find(|instance| instance.transform[3][0] == x).unwrap().transform[3][2]
+        };
+
+        let mut batch = NodeInstanceBatch::new(BufferCapacity::new(16, 16));
+        batch.update(&editor.nodes, None);
+        assert!(depth_of(&batch, &editor.nodes, front) < depth_of(&batch, &editor.nodes, middle));
+        assert!(depth_of(&batch, &editor.nodes, middle) < depth_of(&batch, &editor.nodes, back));
+        assert!(depth_of(&batch, &editor.nodes, back) < 0.0);
+
+        editor.bring_to_front(back);
+        editor.send_to_back(front);
+        batch.update(&editor.nodes, None);
+        assert!(depth_of(&batch, &editor.nodes, back) < depth_of(&batch, &editor.nodes, middle));
+        assert!(depth_of(&batch, &editor.nodes, middle) < depth_of(&batch, &editor.nodes, front));
+        assert_eq!(editor.node_at_position(Vec2::new(40.0, 0.0)), Some(back));
+
+        // Selection lifts a node above higher z_order
+        editor.nodes.get_mut(&front).unwrap().selected = true;
+        batch.update(&editor.nodes, None);
+        assert!(depth_of(&batch, &editor.nodes, front) < depth_of(&batch, &editor.nodes, back));
+    }
+
+    #[test]
+    fn test_only_nodes_in_view_are_instanced() {
+        let nodes = many_nodes(1000);
+        let mut batch = NodeInstanceBatch::new(BufferCapacity::new(1000, 64_000));
+        let camera = CameraUniforms {
+            view_proj: Mat4::IDENTITY.to_cols_array_2d(),
+            camera_pos: [1000.0, 500.0, 0.0],
+            zoom_level: 0.5,
+            viewport_size: [800.0, 600.0],
+            time: 0.0,
+            low_detail: 0.0,
+        };
+        let view = camera.visible_rect();
+        assert_eq!(view, Rect::new(Vec2::new(800.0, 350.0), Vec2::new(1200.0, 650.0)));
+
+        batch.update(&nodes, Some(view));
+        let on_screen = nodes.values()
+            .filter(|node| view.intersects(&Rect::from_center_size(node.position, node.size)))
+            .count();
+        assert!(on_screen > 0 && on_screen < 20);
+        assert_eq!(batch.instances().len(), on_screen);
+
+        // A margin pulls in the neighbours just off screen
+        batch.update(&nodes, Some(view.inflate(DEFAULT_CULL_MARGIN)));
+        assert!(batch.instances().len() > on_screen);
+
+        batch.update(&nodes, None);
+        assert_eq!(batch.instances().len(), 1000);
+    }
+
+    #[test]
+    fn test_zoomed_out_camera_draws_low_detail() {
+        let viewport = Vec2::new(800.0, 600.0);
+        let close = CameraUniforms::new(Vec3::ZERO, 1.0, viewport, 0.0, DEFAULT_LOD_THRESHOLD);
+        assert!(!close.is_low_detail());
+        assert_eq!(close.vertices_per_connection(), VERTICES_PER_CONNECTION);
+
+        // 0.25 screen pixels per world unit
+        let far = CameraUniforms::new(Vec3::ZERO, 4.0, viewport, 0.0, DEFAULT_LOD_THRESHOLD);
+        assert!(far.is_low_detail());
+        assert!(far.vertices_per_connection() * 10 < close.vertices_per_connection());
+
+        let no_lod = CameraUniforms::new(Vec3::ZERO, 4.0, viewport, 0.0, 0.0);
+        assert!(!no_lod.is_low_detail());
+    }
+
+    fn many_nodes(count: usize) -> HashMap<Uuid, VisualNode> {
+        let mut editor = VisualNodeEditor::new();
+        for i in 0..count {
+            editor.add_node("add", Vec2::new((i % 50) as f32 * 200.0, (i / 50) as f32 * 150.0)).unwrap();
+        }
+        editor.nodes
+    }
+
+    #[test]
+    fn test_node_buffer_grows_past_initial_capacity() {
+        let nodes = many_nodes(1500);
+        let mut batch = NodeInstanceBatch::new(BufferCapacity::new(1000, 64_000));
+
+        let change = batch.update(&nodes, None);
+        assert_eq!(change, CapacityChange::Grew { from: 1000, to: 2000 });
+        assert!(change.needs_realloc());
+        assert_eq!(batch.capacity().capacity(), 2000);
+        assert_eq!(batch.uploaded().len(), 1500);
+
+        // Same count again fits without another reallocation
+        assert_eq!(batch.update(&nodes, None), CapacityChange::Unchanged);
+    }
+
+    #[test]
+    fn test_node_buffer_stops_growing_at_cap() {
+        let nodes = many_nodes(1500);
+        let mut batch = NodeInstanceBatch::new(BufferCapacity::new(1000, 1200));
+
+        let change = batch.update(&nodes, None);
+        assert_eq!(change, CapacityChange::Capped { from: 1000, to: 1200, required: 1500 });
+        assert!(change.is_capped());
+        assert_eq!(batch.instances().len(), 1500);
+        assert_eq!(batch.uploaded().len(), 1200);
+
+        // Already at the cap: still capped, but nothing to reallocate
+        let again = batch.update(&nodes, None);
+        assert!(again.is_capped());
+        assert!(!again.needs_realloc());
+    }
+
+    #[test]
+    fn test_capacity_doubles_until_request_fits() {
+        let mut capacity = BufferCapacity::new(10, 1000);
+        assert_eq!(capacity.reserve(75), CapacityChange::Grew { from: 10, to: 80 });
+        assert_eq!(capacity.reserve(80), CapacityChange::Unchanged);
+    }
+
+    #[test]
+    fn test_capacity_tracks_capped_state() {
+        let mut capacity = BufferCapacity::new(10, 20);
+        assert!(!capacity.is_capped());
+
+        capacity.reserve(30);
+        assert!(capacity.is_capped());
+        capacity.reserve(30);
+        assert!(capacity.is_capped());
+
+        // Fitting again clears the capped state
+        capacity.reserve(15);
+        assert!(!capacity.is_capped());
+    }
+}
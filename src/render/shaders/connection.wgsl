@@ -1,10 +1,18 @@
 // Connection Rendering Shader for Marco 2.0 Visual Programming Interface
 // Optimized for smooth bezier curves with varying thickness and animated flow effects
 
-struct VertexInput {
-    @location(0) position: vec3<f32>,
-    @location(1) color: vec4<f32>,
-    @location(2) thickness: f32,
+// Line segments per curve; must match CONNECTION_SEGMENTS in node_renderer.rs
+const SEGMENTS: u32 = 20u;
+
+// Extra half-width past the stroke for the anti-aliased edge and glow
+const EDGE_FRINGE: f32 = 1.0;
+
+// One instance per connection
+struct InstanceInput {
+    @location(0) start_control1: vec4<f32>,
+    @location(1) control2_end: vec4<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) thickness: f32,
 }
 
 struct VertexOutput {
@@ -13,7 +21,7 @@ struct VertexOutput {
     @location(1) thickness: f32,
     @location(2) world_pos: vec3<f32>,
     @location(3) curve_t: f32, // Parameter along the curve (0 to 1)
-    @location(4) distance_from_center: f32, // Distance from curve centerline
+    @location(4) distance_from_center: f32, // Signed distance from curve centerline
 }
 
 struct CameraUniforms {
@@ -22,6 +30,7 @@ struct CameraUniforms {
     zoom_level: f32,
     viewport_size: vec2<f32>,
     time: f32,
+    low_detail: f32, // 1.0 when zoomed out past the LOD threshold
 }
 
 struct ThemeUniforms {
@@ -33,33 +42,62 @@ struct ThemeUniforms {
     grid_color: vec4<f32>,
     selection_color: vec4<f32>,
     connection_colors: array<vec4<f32>, 8>,
+    connection_thickness: f32, // Minimum stroke width, world units
+    node_border_width: f32, // World units
 }
 
 @group(0) @binding(0) var<uniform> camera: CameraUniforms;
 @group(1) @binding(0) var<uniform> theme: ThemeUniforms;
 
+fn bezier_point(p0: vec2<f32>, p1: vec2<f32>, p2: vec2<f32>, p3: vec2<f32>, t: f32) -> vec2<f32> {
+    let u = 1.0 - t;
+    return u * u * u * p0 + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t * p3;
+}
+
+fn bezier_tangent(p0: vec2<f32>, p1: vec2<f32>, p2: vec2<f32>, p3: vec2<f32>, t: f32) -> vec2<f32> {
+    let u = 1.0 - t;
+    return 3.0 * u * u * (p1 - p0) + 6.0 * u * t * (p2 - p1) + 3.0 * t * t * (p3 - p2);
+}
+
 @vertex
-fn vs_main(vertex: VertexInput, @builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+fn vs_main(instance: InstanceInput, @builtin(vertex_index) vertex_index: u32) -> VertexOutput {
     var out: VertexOutput;
     
-    // Calculate curve parameter and perpendicular offset for line thickness
-    let segment_length = 2u; // Two vertices per line segment (triangle strip)
-    let segment_index = vertex_index / segment_length;
-    let is_top_vertex = (vertex_index % segment_length) == 0u;
-    
-    // For proper thickness, we need to expand vertices perpendicular to the curve direction
-    // This is a simplified implementation - in practice, you'd calculate proper normals
-    let thickness_offset = select(-vertex.thickness * 0.5, vertex.thickness * 0.5, is_top_vertex);
-    
-    let world_pos = vertex.position + vec3<f32>(0.0, thickness_offset, 0.0);
+    // Zoomed out, each connection is one straight segment
+    let low_detail = camera.low_detail > 0.5;
+    let segments = select(SEGMENTS, 1u, low_detail);
+    
+    // Two strip vertices per curve point, one either side of the centerline
+    let t = f32(vertex_index / 2u) / f32(segments);
+    let side = select(-1.0, 1.0, (vertex_index % 2u) == 0u);
+    
+    let p0 = instance.start_control1.xy;
+    let p1 = instance.start_control1.zw;
+    let p2 = instance.control2_end.xy;
+    let p3 = instance.control2_end.zw;
+    
+    // Control points can coincide with the ends, where the tangent vanishes
+    var tangent = select(bezier_tangent(p0, p1, p2, p3, t), p3 - p0, low_detail);
+    if (dot(tangent, tangent) < 1e-8) {
+        tangent = p3 - p0;
+    }
+    if (dot(tangent, tangent) < 1e-8) {
+        tangent = vec2<f32>(1.0, 0.0);
+    }
+    let normal = normalize(vec2<f32>(-tangent.y, tangent.x));
+    let thickness = max(instance.thickness, theme.connection_thickness);
+    let offset = side * (thickness * 0.5 + EDGE_FRINGE);
+    
+    let center = select(bezier_point(p0, p1, p2, p3, t), mix(p0, p3, t), low_detail);
+    let world_pos = vec3<f32>(center + normal * offset, 0.0);
     out.clip_position = camera.view_proj * vec4<f32>(world_pos, 1.0);
     out.world_pos = world_pos;
     
     // Pass through attributes
-    out.color = vertex.color;
-    out.thickness = vertex.thickness;
-    out.curve_t = f32(segment_index) / 20.0; // Approximate curve parameter
-    out.distance_from_center = abs(thickness_offset);
+    out.color = instance.color;
+    out.thickness = thickness;
+    out.curve_t = t;
+    out.distance_from_center = offset;
     
     return out;
 }
@@ -77,7 +115,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let edge_softness = 0.5;
     
     // Create smooth anti-aliased edges
-    let alpha = 1.0 - smoothstep(half_thickness - edge_softness, half_thickness + edge_softness, in.distance_from_center);
+    let alpha = 1.0 - smoothstep(half_thickness - edge_softness, half_thickness + edge_softness, abs(in.distance_from_center));
     
     // Create animated flow effect along the connection
     let flow_speed = 2.0;
@@ -98,12 +136,12 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let gradient_color = base_color * gradient_factor;
     
     // Add center highlight for active connections
-    let center_highlight = smoothstep(half_thickness * 0.7, half_thickness * 0.3, in.distance_from_center) * 0.4;
+    let center_highlight = smoothstep(half_thickness * 0.7, half_thickness * 0.3, abs(in.distance_from_center)) * 0.4;
     let highlighted_color = gradient_color + vec4<f32>(center_highlight, center_highlight, center_highlight, 0.0);
     
     // Add subtle glow effect around the connection
     let glow_size = half_thickness * 1.5;
-    let glow_alpha = smoothstep(glow_size, half_thickness, in.distance_from_center) * 0.2;
+    let glow_alpha = smoothstep(glow_size, half_thickness, abs(in.distance_from_center)) * 0.2;
     let glow_color = highlighted_color * 1.5; // Brighter glow
     
     // Combine main connection with glow
@@ -116,7 +154,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 @fragment
 fn fs_data_flow(in: VertexOutput) -> @location(0) vec4<f32> {
     let half_thickness = in.thickness * 0.5;
-    let alpha = 1.0 - smoothstep(half_thickness - 0.5, half_thickness + 0.5, in.distance_from_center);
+    let alpha = 1.0 - smoothstep(half_thickness - 0.5, half_thickness + 0.5, abs(in.distance_from_center));
     
     // Create moving data packets
     let packet_speed = 4.0;
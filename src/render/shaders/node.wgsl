@@ -31,6 +31,7 @@ struct CameraUniforms {
     zoom_level: f32,
     viewport_size: vec2<f32>,
     time: f32,
+    low_detail: f32, // 1.0 when zoomed out past the LOD threshold
 }
 
 struct ThemeUniforms {
@@ -42,6 +43,8 @@ struct ThemeUniforms {
     grid_color: vec4<f32>,
     selection_color: vec4<f32>,
     connection_colors: array<vec4<f32>, 8>,
+    connection_thickness: f32, // Minimum stroke width, world units
+    node_border_width: f32, // World units
 }
 
 @group(0) @binding(0) var<uniform> camera: CameraUniforms;
@@ -92,6 +95,15 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // Convert UV coordinates to local space (-1 to 1)
     let local_uv = (in.uv - 0.5) * 2.0;
     
+    // Local units per world unit along each axis, taken before any branch so
+    // the derivatives stay in uniform control flow
+    let uv_per_world = fwidth(local_uv) / max(fwidth(in.world_pos.xy), vec2<f32>(1e-6));
+    
+    // Zoomed out: a flat rectangle, no border, gradient or highlight
+    if (camera.low_detail > 0.5) {
+        return in.node_color;
+    }
+    
     // Calculate distance to rounded rectangle border
     let corner_radius = 0.1;
     let rect_size = vec2<f32>(0.8, 0.8); // Slightly smaller than full quad for border
@@ -101,8 +113,15 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let edge_width = 0.02;
     let alpha = 1.0 - smoothstep(-edge_width, edge_width, distance);
     
-    // Calculate border effect
-    let border_width = 0.05;
+    // Keep the transparent corners out of the depth buffer
+    if (alpha <= 0.0) {
+        discard;
+    }
+    
+    // Calculate border effect, scaled by the axis of the nearest edge so the
+    // border is the theme's width in world units on every side
+    let edge_scale = select(uv_per_world.y, uv_per_world.x, abs(local_uv.x) > abs(local_uv.y));
+    let border_width = theme.node_border_width * edge_scale;
     let border_alpha = smoothstep(-border_width - edge_width, -border_width + edge_width, distance);
     
     // Create animated glow effect
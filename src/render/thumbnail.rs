@@ -0,0 +1,285 @@
+//! Template Thumbnails
+//! Renders a template's nodes and GUI elements offscreen and encodes the result as PNG
+
+use wgpu::{Device, Queue};
+use glam::{Vec2, Vec3};
+use uuid::Uuid;
+use std::collections::HashMap;
+use tracing::warn;
+
+use crate::ui::template_creator::ProjectTemplate;
+use crate::ui::visual_node_editor::{VisualNode, VisualNodeEditor};
+use crate::ui::theme::Marco2Theme;
+use crate::render::node_renderer::NodeRenderer;
+use crate::core::types::error::MarcoError;
+
+/// Offscreen target format; read back as plain RGBA8
+const THUMBNAIL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// Bytes per RGBA8 pixel
+const BYTES_PER_PIXEL: u32 = 4;
+
+/// Extra room around the template's contents, as a fraction of their extent
+const FIT_MARGIN: f32 = 0.1;
+
+/// Error message when neither a hardware nor a fallback adapter exists
+const NO_ADAPTER: &str = "No graphics adapter available for thumbnail rendering";
+
+/// Render `template` to a `size` PNG on a headless device
+///
+/// Fails when no adapter is available, e.g. on machines without a GPU or
+/// software rasterizer.
+pub fn render_template_thumbnail(template: &ProjectTemplate, size: (u32, u32)) -> Result<Vec<u8>, MarcoError> {
+    let (device, queue) = pollster::block_on(request_headless_device())?;
+    render_template_thumbnail_with(&device, &queue, template, size)
+}
+
+/// Render `template` to a `size` PNG on an existing device
+pub fn render_template_thumbnail_with(
+    device: &Device,
+    queue: &Queue,
+    template: &ProjectTemplate,
+    size: (u32, u32),
+) -> Result<Vec<u8>, MarcoError> {
+    let (width, height) = size;
+    if width == 0 || height == 0 {
+        return Err(MarcoError::InvalidOperation(format!("Thumbnail size {}x{} is empty", width, height)));
+    }
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Thumbnail Texture"),
+        size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: THUMBNAIL_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let depth_view = NodeRenderer::create_depth_view(device, size, 1);
+
+    // Upload the template contents, framed to fill the thumbnail
+    let (nodes, connections) = template_scene(template);
+    let (center, extent) = scene_bounds(&nodes);
+    let viewport = Vec2::new(width as f32, height as f32);
+    let zoom = (extent.x / viewport.x).max(extent.y / viewport.y) * (1.0 + FIT_MARGIN);
+
+    let mut node_renderer = NodeRenderer::new(device, THUMBNAIL_FORMAT)?;
+    node_renderer.update_camera(queue, Vec3::new(center.x, center.y, 0.0), zoom, viewport, 0.0);
+    node_renderer.update_theme(queue, &Marco2Theme::default());
+    node_renderer.update_nodes(device, queue, &nodes, node_renderer.view_rect())?;
+    node_renderer.update_connections(device, queue, &nodes, &connections)?;
+
+    let padded_row = padded_bytes_per_row(width);
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Thumbnail Readback Buffer"),
+        size: padded_row as u64 * height as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let [r, g, b, a] = template.canvas_settings.background_color;
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Thumbnail Encoder"),
+    });
+    {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Thumbnail Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color { r: r as f64, g: g as f64, b: b as f64, a: a as f64 }),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(NodeRenderer::depth_attachment(&depth_view)),
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        node_renderer.render(&mut render_pass);
+    }
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &readback,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row),
+                rows_per_image: Some(height),
+            },
+        },
+        wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    // Wait for the copy, then strip the row padding
+    let slice = readback.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device.poll(wgpu::Maintain::Wait);
+    receiver.recv()
+        .map_err(|e| MarcoError::Unknown(format!("Thumbnail readback was dropped: {}", e)))?
+        .map_err(|e| MarcoError::Unknown(format!("Failed to map thumbnail buffer: {}", e)))?;
+    let pixels = unpad_rows(&slice.get_mapped_range(), width, height);
+    readback.unmap();
+
+    encode_png(pixels, width, height)
+}
+
+async fn request_headless_device() -> Result<(Device, Queue), MarcoError> {
+    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::all(),
+        ..Default::default()
+    });
+
+    // Prefer real hardware, then a software rasterizer
+    let mut adapter = None;
+    for force_fallback_adapter in [false, true] {
+        adapter = instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            compatible_surface: None,
+            force_fallback_adapter,
+        }).await;
+        if adapter.is_some() {
+            break;
+        }
+    }
+    let adapter = adapter
+        .ok_or_else(|| MarcoError::Unknown(NO_ADAPTER.to_string()))?;
+
+    adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            label: Some("Thumbnail Device"),
+            required_features: wgpu::Features::empty(),
+            required_limits: wgpu::Limits::downlevel_defaults().using_resolution(adapter.limits()),
+        },
+        None,
+    ).await
+        .map_err(|e| MarcoError::Unknown(format!("Failed to create thumbnail device: {}", e)))
+}
+
+/// Nodes and connections of `template`, with GUI elements drawn as plain boxes
+fn template_scene(template: &ProjectTemplate) -> (HashMap<Uuid, VisualNode>, Vec<crate::ui::visual_node_editor::NodeConnection>) {
+    let mut editor = VisualNodeEditor::new();
+    let mut ids = HashMap::new();
+    for serialized in &template.initial_nodes {
+        match editor.add_node(&serialized.node_type, serialized.position) {
+            Ok(id) => {
+                ids.insert(serialized.id, id);
+            }
+            Err(e) => warn!("Thumbnail skips node '{}': {}", serialized.title, e),
+        }
+    }
+    for connection in &template.initial_connections {
+        let (Some(&from), Some(&to)) = (ids.get(&connection.from_node), ids.get(&connection.to_node)) else {
+            continue;
+        };
+        if let Err(e) = editor.connect_nodes(from, &connection.from_output, to, &connection.to_input) {
+            warn!("Thumbnail skips connection: {}", e);
+        }
+    }
+
+    let mut nodes = editor.rendered_nodes();
+    for element in &template.gui_elements {
+        nodes.insert(element.id, VisualNode {
+            id: element.id,
+            node_type: format!("gui_{:?}", element.element_type).to_lowercase(),
+            position: element.position,
+            size: element.size,
+            inputs: HashMap::new(),
+            outputs: HashMap::new(),
+            properties: HashMap::new(),
+            selected: false,
+            title: String::new(),
+            dirty: false,
+            z_order: 0,
+        });
+    }
+    (nodes, editor.rendered_connections())
+}
+
+/// Center and size of the area covered by `nodes`, or the unit box when empty
+fn scene_bounds(nodes: &HashMap<Uuid, VisualNode>) -> (Vec2, Vec2) {
+    let mut min = Vec2::splat(f32::INFINITY);
+    let mut max = Vec2::splat(f32::NEG_INFINITY);
+    for node in nodes.values() {
+        // NodeRenderer draws nodes centered on their position
+        min = min.min(node.position - node.size / 2.0);
+        max = max.max(node.position + node.size / 2.0);
+    }
+    if min.x > max.x {
+        return (Vec2::ZERO, Vec2::ONE);
+    }
+    ((min + max) / 2.0, (max - min).max(Vec2::ONE))
+}
+
+/// Row stride of a `width` RGBA8 readback, rounded up to the copy alignment
+fn padded_bytes_per_row(width: u32) -> u32 {
+    let unpadded = width * BYTES_PER_PIXEL;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    unpadded.div_ceil(align) * align
+}
+
+/// Tightly packed rows from a readback whose rows are `padded_bytes_per_row` apart
+fn unpad_rows(padded: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let row = (width * BYTES_PER_PIXEL) as usize;
+    padded.chunks(padded_bytes_per_row(width) as usize)
+        .take(height as usize)
+        .flat_map(|chunk| &chunk[..row])
+        .copied()
+        .collect()
+}
+
+fn encode_png(pixels: Vec<u8>, width: u32, height: u32) -> Result<Vec<u8>, MarcoError> {
+    let image = image::RgbaImage::from_raw(width, height, pixels)
+        .ok_or_else(|| MarcoError::Unknown("Thumbnail readback has the wrong size".to_string()))?;
+    let mut png = std::io::Cursor::new(Vec::new());
+    image.write_to(&mut png, image::ImageOutputFormat::Png)
+        .map_err(|e| MarcoError::Unknown(format!("Failed to encode thumbnail: {}", e)))?;
+    Ok(png.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::template_creator::TemplateCreator;
+
+    #[test]
+    fn test_unpad_rows_drops_alignment_bytes() {
+        // 3 pixels = 12 bytes of data in a 256 byte row
+        assert_eq!(padded_bytes_per_row(3), 256);
+        assert_eq!(padded_bytes_per_row(64), 256);
+        assert_eq!(padded_bytes_per_row(65), 512);
+
+        let mut padded = vec![0u8; 512];
+        padded[..12].fill(1);
+        padded[256..268].fill(2);
+        let pixels = unpad_rows(&padded, 3, 2);
+        assert_eq!(pixels.len(), 24);
+        assert!(pixels[..12].iter().all(|&b| b == 1));
+        assert!(pixels[12..].iter().all(|&b| b == 2));
+    }
+
+    #[test]
+    fn test_256_thumbnail_is_png() {
+        let creator = TemplateCreator::new();
+        let template = creator.templates.values()
+            .max_by_key(|template| template.initial_nodes.len() + template.gui_elements.len())
+            .unwrap();
+
+        let png = match render_template_thumbnail(template, (256, 256)) {
+            Ok(png) => png,
+            // Headless CI machines may have no adapter at all
+            Err(MarcoError::Unknown(message)) if message == NO_ADAPTER => return,
+            Err(e) => panic!("thumbnail render failed: {}", e),
+        };
+        assert!(!png.is_empty());
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        let decoded = image::load_from_memory(&png).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (256, 256));
+    }
+}
@@ -13,6 +13,9 @@ use crate::core::types::error::MarcoError;
 pub struct WGPUVisualNodeEditor {
     visual_editor: VisualNodeEditor,
     node_renderer: NodeRenderer,
+    /// Depth target for the node stack and the viewport size it was made for
+    depth_view: wgpu::TextureView,
+    depth_size: Vec2,
     camera_position: Vec3,
     zoom_level: f32,
     viewport_size: Vec2,
@@ -28,26 +31,44 @@ impl WGPUVisualNodeEditor {
     ) -> Result<Self, MarcoError> {
         let visual_editor = VisualNodeEditor::new();
         let node_renderer = NodeRenderer::new(device, surface_format)?;
+        let viewport_size = Vec2::new(800.0, 600.0);
+        let depth_view = NodeRenderer::create_depth_view(device, (viewport_size.x as u32, viewport_size.y as u32), 1);
         
         Ok(Self {
             visual_editor,
             node_renderer,
+            depth_view,
+            depth_size: viewport_size,
             camera_position: Vec3::new(0.0, 0.0, 1.0),
             zoom_level: 1.0,
-            viewport_size: Vec2::new(800.0, 600.0),
+            viewport_size,
             time: 0.0,
             theme,
         })
     }
     
     /// Update the editor state and prepare for rendering
-    pub fn update(&mut self, dt: f32, _device: &Device, queue: &Queue) -> Result<(), MarcoError> {
+    pub fn update(&mut self, dt: f32, device: &Device, queue: &Queue) -> Result<(), MarcoError> {
         // Update time
         self.time += dt;
         
+        // Keep the depth target the size of the viewport
+        if self.depth_size != self.viewport_size {
+            let size = (self.viewport_size.x as u32, self.viewport_size.y as u32);
+            self.depth_view = NodeRenderer::create_depth_view(device, size, self.node_renderer.sample_count());
+            self.depth_size = self.viewport_size;
+        }
+        
         // Update renderer with current camera data
         self.node_renderer.update_camera(queue, self.camera_position, self.zoom_level, self.viewport_size, self.time);
         
+        // Upload nodes in view, with collapsed groups drawn as one box, and
+        // the connections that pass the editor's filter
+        let nodes = self.visual_editor.rendered_nodes();
+        let visible = self.visual_editor.rendered_connections();
+        self.node_renderer.update_nodes(device, queue, &nodes, self.node_renderer.view_rect())?;
+        self.node_renderer.update_connections(device, queue, &nodes, &visible)?;
+        
         Ok(())
     }
     
@@ -64,7 +85,7 @@ impl WGPUVisualNodeEditor {
                     store: wgpu::StoreOp::Store,
                 },
             })],
-            depth_stencil_attachment: None,
+            depth_stencil_attachment: Some(NodeRenderer::depth_attachment(&self.depth_view)),
             occlusion_query_set: None,
             timestamp_writes: None,
         });
@@ -118,9 +139,10 @@ impl WGPUVisualNodeEditor {
         self.viewport_size = new_size;
     }
     
-    /// Set theme for visual styling
-    pub fn set_theme(&mut self, theme: &Marco2Theme) {
+    /// Switch theme; the GPU theme buffer is updated immediately
+    pub fn set_theme(&mut self, queue: &Queue, theme: &Marco2Theme) {
         self.theme = theme.clone();
         self.visual_editor.set_theme(&self.theme);
+        self.node_renderer.update_theme(queue, &self.theme);
     }
 }
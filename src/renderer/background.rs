@@ -0,0 +1,381 @@
+//! Canvas Background
+//!
+//! Configurable canvas backgrounds: solid color, vertical gradient, dotted
+//! grid or a tiled image. Solid backgrounds only set the clear color; the
+//! other modes draw a fullscreen pass before nodes and connections.
+
+use bytemuck::{Pod, Zeroable};
+use std::path::PathBuf;
+use wgpu::util::DeviceExt;
+
+/// Clear color used before the background was configurable
+const DEFAULT_BACKGROUND: [f32; 4] = [0.1, 0.1, 0.12, 1.0];
+
+/// Background drawn behind the canvas contents
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanvasBackground {
+    /// Flat color
+    Solid([f32; 4]),
+    /// Vertical gradient from the top color to the bottom color
+    Gradient { top: [f32; 4], bottom: [f32; 4] },
+    /// Dots on a regular grid over a base color
+    Dotted { base: [f32; 4], dot: [f32; 4], spacing: f32, radius: f32 },
+    /// Image tiled across the canvas, `tile_size` pixels per repeat
+    Image { path: PathBuf, tile_size: f32 },
+}
+
+impl Default for CanvasBackground {
+    fn default() -> Self {
+        Self::Solid(DEFAULT_BACKGROUND)
+    }
+}
+
+/// Background shader modes, matching `BackgroundUniforms::mode`
+const MODE_SOLID: u32 = 0;
+const MODE_GRADIENT: u32 = 1;
+const MODE_DOTTED: u32 = 2;
+const MODE_IMAGE: u32 = 3;
+
+/// GPU-side background parameters
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable, PartialEq)]
+pub struct BackgroundUniforms {
+    /// Solid/top/base color
+    pub color_a: [f32; 4],
+    /// Bottom/dot color
+    pub color_b: [f32; 4],
+    /// x: dot spacing or tile size, y: dot radius
+    pub params: [f32; 4],
+    pub viewport_size: [f32; 2],
+    pub mode: u32,
+    pub _padding: u32,
+}
+
+impl CanvasBackground {
+    /// Solid background from a template's canvas settings
+    pub fn from_canvas_settings(settings: &marco2::ui::template_creator::CanvasSettings) -> Self {
+        Self::Solid(settings.background_color)
+    }
+
+    /// Solid background from the theme's canvas color
+    pub fn from_theme(theme: &marco2::Marco2Theme) -> Self {
+        Self::Solid(theme.canvas_background.as_array())
+    }
+
+    /// Color the render pass clears to
+    ///
+    /// For image backgrounds this shows only where the image is transparent.
+    pub fn clear_color(&self) -> wgpu::Color {
+        let [r, g, b, a] = match self {
+            Self::Solid(color) => *color,
+            Self::Gradient { bottom, .. } => *bottom,
+            Self::Dotted { base, .. } => *base,
+            Self::Image { .. } => DEFAULT_BACKGROUND,
+        };
+        wgpu::Color { r: r as f64, g: g as f64, b: b as f64, a: a as f64 }
+    }
+
+    /// Whether a fullscreen background pass is needed beyond the clear
+    pub fn needs_pass(&self) -> bool {
+        !matches!(self, Self::Solid(_))
+    }
+
+    /// Shader parameters for this background at the given viewport size
+    pub fn uniforms(&self, viewport_size: [f32; 2]) -> BackgroundUniforms {
+        let (mode, color_a, color_b, params) = match self {
+            Self::Solid(color) => (MODE_SOLID, *color, *color, [0.0; 4]),
+            Self::Gradient { top, bottom } => (MODE_GRADIENT, *top, *bottom, [0.0; 4]),
+            Self::Dotted { base, dot, spacing, radius } => {
+                (MODE_DOTTED, *base, *dot, [spacing.max(1.0), *radius, 0.0, 0.0])
+            }
+            Self::Image { tile_size, .. } => {
+                (MODE_IMAGE, [1.0; 4], [1.0; 4], [tile_size.max(1.0), 0.0, 0.0, 0.0])
+            }
+        };
+        BackgroundUniforms { color_a, color_b, params, viewport_size, mode, _padding: 0 }
+    }
+}
+
+const BACKGROUND_SHADER: &str = r#"
+struct Background {
+    color_a: vec4<f32>,
+    color_b: vec4<f32>,
+    params: vec4<f32>,
+    viewport_size: vec2<f32>,
+    mode: u32,
+};
+
+@group(0) @binding(0) var<uniform> bg: Background;
+@group(0) @binding(1) var tile: texture_2d<f32>;
+@group(0) @binding(2) var tile_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    // Fullscreen triangle
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let pixel = in.uv * bg.viewport_size;
+    if (bg.mode == 1u) {
+        return mix(bg.color_a, bg.color_b, clamp(in.uv.y, 0.0, 1.0));
+    }
+    if (bg.mode == 2u) {
+        let cell = pixel - floor(pixel / bg.params.x) * bg.params.x - vec2<f32>(bg.params.x * 0.5);
+        let coverage = 1.0 - smoothstep(bg.params.y - 0.5, bg.params.y + 0.5, length(cell));
+        return mix(bg.color_a, bg.color_b, coverage);
+    }
+    if (bg.mode == 3u) {
+        return textureSample(tile, tile_sampler, pixel / bg.params.x);
+    }
+    return bg.color_a;
+}
+"#;
+
+/// Draws non-solid canvas backgrounds
+pub struct BackgroundRenderer {
+    pipeline: wgpu::RenderPipeline,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    background: CanvasBackground,
+}
+
+impl BackgroundRenderer {
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue, format: wgpu::TextureFormat, sample_count: u32) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Background Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline = Self::create_pipeline(device, &bind_group_layout, format, sample_count);
+
+        let background = CanvasBackground::default();
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Background Uniforms"),
+            contents: bytemuck::cast_slice(&[background.uniforms([1.0, 1.0])]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Background Sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let placeholder = Self::create_texture(device, queue, 1, 1, &[255, 255, 255, 255]);
+        let bind_group = Self::create_bind_group(device, &bind_group_layout, &uniform_buffer, &placeholder, &sampler);
+
+        Self { pipeline, format, sample_count, bind_group_layout, bind_group, uniform_buffer, sampler, background }
+    }
+
+    /// Rebuild the pipeline for passes with `sample_count` MSAA samples
+    pub fn set_sample_count(&mut self, device: &wgpu::Device, sample_count: u32) {
+        if sample_count != self.sample_count {
+            self.pipeline = Self::create_pipeline(device, &self.bind_group_layout, self.format, sample_count);
+            self.sample_count = sample_count;
+        }
+    }
+
+    pub fn background(&self) -> &CanvasBackground {
+        &self.background
+    }
+
+    /// Switch backgrounds, loading the tile texture for image mode
+    ///
+    /// An image that fails to load falls back to the default solid color.
+    pub fn set_background(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, background: CanvasBackground) {
+        if let CanvasBackground::Image { path, .. } = &background {
+            match image::open(path) {
+                Ok(img) => {
+                    let rgba = img.to_rgba8();
+                    let texture = Self::create_texture(device, queue, rgba.width(), rgba.height(), &rgba);
+                    self.bind_group = Self::create_bind_group(
+                        device, &self.bind_group_layout, &self.uniform_buffer, &texture, &self.sampler,
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load background image {}: {}", path.display(), e);
+                    self.background = CanvasBackground::default();
+                    return;
+                }
+            }
+        }
+        self.background = background;
+    }
+
+    /// Upload uniforms for the current background and viewport
+    pub fn update(&self, queue: &wgpu::Queue, viewport_size: [f32; 2]) {
+        let uniforms = self.background.uniforms(viewport_size);
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Draw the background; call first in the pass
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if self.background.needs_pass() {
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+    }
+
+    fn create_pipeline(
+        device: &wgpu::Device,
+        bind_group_layout: &wgpu::BindGroupLayout,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> wgpu::RenderPipeline {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Background Shader"),
+            source: wgpu::ShaderSource::Wgsl(BACKGROUND_SHADER.into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Background Pipeline Layout"),
+            bind_group_layouts: &[bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Background Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            // Drawn at the far plane without writing, under everything else
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: marco2::render::DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+        })
+    }
+
+    fn create_texture(device: &wgpu::Device, queue: &wgpu::Queue, width: u32, height: u32, rgba: &[u8]) -> wgpu::Texture {
+        device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("Background Texture"),
+                size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            rgba,
+        )
+    }
+
+    fn create_bind_group(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        uniforms: &wgpu::Buffer,
+        texture: &wgpu::Texture,
+        sampler: &wgpu::Sampler,
+    ) -> wgpu::BindGroup {
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Background Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: uniforms.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(&view) },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::Sampler(sampler) },
+            ],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clear_color_matches_solid_background() {
+        let background = CanvasBackground::Solid([0.2, 0.3, 0.4, 1.0]);
+        let clear = background.clear_color();
+        assert!((clear.r - 0.2).abs() < 1e-6);
+        assert!((clear.g - 0.3).abs() < 1e-6);
+        assert!((clear.b - 0.4).abs() < 1e-6);
+        assert!(!background.needs_pass());
+    }
+
+    #[test]
+    fn test_gradient_uniforms_carry_both_endpoints() {
+        let top = [1.0, 0.0, 0.0, 1.0];
+        let bottom = [0.0, 0.0, 1.0, 1.0];
+        let background = CanvasBackground::Gradient { top, bottom };
+
+        let uniforms = background.uniforms([800.0, 600.0]);
+        assert_eq!(uniforms.mode, MODE_GRADIENT);
+        assert_eq!(uniforms.color_a, top);
+        assert_eq!(uniforms.color_b, bottom);
+        assert_eq!(uniforms.viewport_size, [800.0, 600.0]);
+        assert!(background.needs_pass());
+    }
+}
@@ -3,16 +3,217 @@
 //! Mode-specific canvases for LogicCanvas and GuiCanvas modes.
 
 use super::context::WgpuContext;
+use super::input::EditorAction;
+use super::text::{TextAlign, TextLabel, TextRenderer};
+use glam::Vec2;
+use marco2::ui::minimap::{CanvasViewport, Minimap};
+use marco2::ui::visual_node_editor::{NodeClipboard, SocketKind, VisualNodeEditor};
+use marco2::Marco2Theme;
+
+/// Zoom factor applied per wheel line
+const ZOOM_STEP: f32 = 1.1;
+
+/// Pixels of touchpad scroll treated as one wheel line
+const PIXELS_PER_LINE: f32 = 40.0;
+
+/// Height of the strip at the top of a node holding its title, in canvas units
+const TITLE_HEADER_HEIGHT: f32 = 24.0;
+
+/// Gap between a label and the node edge or socket it belongs to, in canvas units
+const LABEL_PADDING: f32 = 8.0;
+
+/// Port names relative to the title size
+const PORT_FONT_RATIO: f32 = 0.85;
 
 /// Logic Canvas - Visual programming interface
 pub struct LogicCanvas {
-    // Placeholder for now
+    pub editor: VisualNodeEditor,
+    clipboard: NodeClipboard,
+    /// Last pointer position in canvas space, where pastes land
+    pub pointer: Option<Vec2>,
+    /// Overview in the window corner; presses inside it navigate
+    pub minimap: Minimap,
 }
 
 impl LogicCanvas {
-    pub fn new(_context: &WgpuContext) -> Self {
+    pub fn new(context: &WgpuContext) -> Self {
         tracing::info!("LogicCanvas initialized");
-        Self {}
+        let mut editor = VisualNodeEditor::new();
+        editor.viewport_size = Vec2::new(context.config.width as f32, context.config.height as f32);
+        Self {
+            editor,
+            clipboard: NodeClipboard::default(),
+            pointer: None,
+            minimap: Minimap::new(),
+        }
+    }
+    
+    /// Track the window size, which bounds what gets labelled
+    pub fn resize(&mut self, size: Vec2) {
+        self.editor.viewport_size = size;
+    }
+    
+    /// Forward a mouse button to the editor; the middle button pans
+    ///
+    /// Left presses inside the minimap recenter the view instead and keep
+    /// doing so while dragged.
+    pub fn handle_mouse_input(&mut self, button: winit::event::MouseButton, state: winit::event::ElementState, position: Vec2) -> bool {
+        if button == winit::event::MouseButton::Left {
+            match state {
+                winit::event::ElementState::Pressed => {
+                    if let Some(target) = self.minimap.handle_mouse_press(position) {
+                        self.editor.center_view_on(target);
+                        return true;
+                    }
+                }
+                winit::event::ElementState::Released => {
+                    if self.minimap.handle_mouse_release() {
+                        return true;
+                    }
+                }
+            }
+        }
+        let button = match button {
+            winit::event::MouseButton::Left => 0,
+            winit::event::MouseButton::Middle => 1,
+            winit::event::MouseButton::Right => 2,
+            _ => return false,
+        };
+        match state {
+            winit::event::ElementState::Pressed => self.editor.handle_mouse_press(position, button),
+            winit::event::ElementState::Released => self.editor.handle_mouse_release(position, button),
+        }
+        true
+    }
+    
+    pub fn handle_cursor_moved(&mut self, position: Vec2) -> bool {
+        if let Some(target) = self.minimap.handle_mouse_move(position) {
+            self.editor.center_view_on(target);
+            return true;
+        }
+        self.editor.handle_mouse_move(position);
+        self.pointer = Some(self.editor.screen_to_canvas(position));
+        true
+    }
+    
+    /// Zoom around the cursor; scrolling up zooms in
+    pub fn handle_mouse_wheel(&mut self, delta: winit::event::MouseScrollDelta, position: Vec2) -> bool {
+        let lines = match delta {
+            winit::event::MouseScrollDelta::LineDelta(_, y) => y,
+            winit::event::MouseScrollDelta::PixelDelta(pixels) => pixels.y as f32 / PIXELS_PER_LINE,
+        };
+        if lines == 0.0 {
+            return false;
+        }
+        self.editor.zoom_canvas_at(position, ZOOM_STEP.powf(lines));
+        self.pointer = Some(self.editor.screen_to_canvas(position));
+        true
+    }
+    
+    /// Apply a shortcut action to the node editor
+    ///
+    /// Returns true when the action changed the editor or clipboard.
+    pub fn handle_action(&mut self, action: EditorAction) -> bool {
+        match action {
+            EditorAction::Undo => self.editor.undo().is_ok(),
+            EditorAction::Redo => self.editor.redo().is_ok(),
+            EditorAction::Copy => {
+                self.clipboard = self.editor.copy_selection();
+                !self.clipboard.is_empty()
+            }
+            EditorAction::Paste => {
+                // Without a pointer, offset from the originals like a duplicate
+                let Some(at) = self.pointer.or_else(|| {
+                    self.clipboard.centroid().map(|centroid| centroid + self.editor.duplicate_offset)
+                }) else {
+                    return false;
+                };
+                match self.editor.paste(&self.clipboard, at) {
+                    Ok(pasted) => !pasted.is_empty(),
+                    Err(e) => {
+                        tracing::warn!("Paste failed: {}", e);
+                        false
+                    }
+                }
+            }
+            EditorAction::Delete => {
+                let nodes = self.editor.delete_selected_nodes();
+                let connections = self.editor.delete_selected_connections();
+                nodes + connections > 0
+            }
+            EditorAction::Duplicate => match self.editor.duplicate_selected_nodes() {
+                Ok(duplicated) => !duplicated.is_empty(),
+                Err(e) => {
+                    tracing::warn!("Duplicate failed: {}", e);
+                    false
+                }
+            },
+        }
+    }
+    
+    /// Refit the minimap to the current nodes and view
+    pub fn update_minimap(&mut self) {
+        let viewport = CanvasViewport::from_editor(&self.editor);
+        self.minimap.update(&self.editor.rendered_nodes(), &viewport);
+    }
+    
+    /// Queue node titles and port names for this frame
+    ///
+    /// Titles are centered in each node's header and ports sit just inside
+    /// their sockets, inputs left-aligned and outputs right-aligned. Nodes
+    /// entirely off screen are skipped; front nodes are queued last so their
+    /// labels draw over the ones behind.
+    pub fn queue_labels(&self, text: &mut TextRenderer, theme: &Marco2Theme) {
+        let scale = self.editor.canvas_scale;
+        let title_px = text.screen_px(scale);
+        let port_px = text.screen_px(scale * PORT_FONT_RATIO);
+        let padding = LABEL_PADDING * scale;
+        let viewport = self.editor.viewport_size;
+
+        let nodes = self.editor.rendered_nodes();
+        let mut nodes: Vec<_> = nodes.values().collect();
+        nodes.sort_by_key(|node| node.stacking_key());
+
+        for node in nodes {
+            let min = self.editor.canvas_to_screen(node.position - node.size / 2.0);
+            let max = self.editor.canvas_to_screen(node.position + node.size / 2.0);
+            if max.x < 0.0 || max.y < 0.0 || min.x > viewport.x || min.y > viewport.y {
+                continue;
+            }
+
+            let title = if node.title.is_empty() { &node.node_type } else { &node.title };
+            text.queue(TextLabel {
+                text: title.clone(),
+                anchor: Vec2::new((min.x + max.x) / 2.0, min.y + TITLE_HEADER_HEIGHT * scale / 2.0),
+                align: TextAlign::Center,
+                max_width: max.x - min.x - 2.0 * padding,
+                px: title_px,
+                color: theme.text_color.as_array(),
+            });
+
+            // Each side gets half the node, less the padding on both ends
+            let port_width = (max.x - min.x) / 2.0 - 2.0 * padding;
+            let ports = node.inputs.keys().map(|name| (SocketKind::Input, name))
+                .chain(node.outputs.keys().map(|name| (SocketKind::Output, name)));
+            for (kind, name) in ports {
+                let Some(socket) = node.socket_position(kind, name) else {
+                    continue;
+                };
+                let socket = self.editor.canvas_to_screen(socket);
+                let (offset, align) = match kind {
+                    SocketKind::Input => (padding, TextAlign::Left),
+                    SocketKind::Output => (-padding, TextAlign::Right),
+                };
+                text.queue(TextLabel {
+                    text: name.clone(),
+                    anchor: Vec2::new(socket.x + offset, socket.y),
+                    align,
+                    max_width: port_width,
+                    px: port_px,
+                    color: theme.secondary_text_color.as_array(),
+                });
+            }
+        }
     }
     
     pub fn render(&self, _render_pass: &mut wgpu::RenderPass, _context: &WgpuContext) {
@@ -31,6 +232,13 @@ impl GuiCanvas {
         Self {}
     }
     
+    /// Apply a shortcut action to the design surface
+    pub fn handle_action(&mut self, action: EditorAction) -> bool {
+        // Placeholder until GUI elements are editable
+        tracing::debug!("GuiCanvas ignoring {:?}", action);
+        false
+    }
+    
     pub fn render(&self, _render_pass: &mut wgpu::RenderPass, _context: &WgpuContext) {
         // Placeholder - will implement professional GUI design tools
     }
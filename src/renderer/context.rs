@@ -3,9 +3,16 @@
 //! Handles WGPU device initialization, surface management, and 
 //! cross-platform rendering setup.
 
+use marco2::render::NodeRenderer;
 use wgpu::{Device, Queue, Surface, SurfaceConfiguration};
 use winit::window::Window;
 
+/// Sample counts WebGPU guarantees for every renderable format
+const GUARANTEED_SAMPLE_COUNTS: [u32; 2] = [1, 4];
+
+/// Sample counts worth asking an adapter about
+const CANDIDATE_SAMPLE_COUNTS: [u32; 4] = [1, 2, 4, 8];
+
 /// WGPU rendering context
 pub struct WgpuContext<'window> {
     pub surface: Surface<'window>,
@@ -13,6 +20,14 @@ pub struct WgpuContext<'window> {
     pub queue: Queue,
     pub config: SurfaceConfiguration,
     pub size: winit::dpi::PhysicalSize<u32>,
+    /// MSAA samples per pixel; 1 renders straight to the surface
+    pub msaa_samples: u32,
+    /// Sample counts the device supports for the surface format, ascending
+    supported_msaa: Vec<u32>,
+    /// Multisampled color target resolved into the surface texture
+    msaa_view: Option<wgpu::TextureView>,
+    /// Depth target sized and sampled like the color target
+    depth_view: wgpu::TextureView,
 }
 
 impl<'window> WgpuContext<'window> {
@@ -39,12 +54,31 @@ impl<'window> WgpuContext<'window> {
             .await
             .unwrap();
         
+        // Configure surface
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = surface_caps
+            .formats
+            .iter()
+            .copied()
+            .find(|f| f.is_srgb())
+            .unwrap_or(surface_caps.formats[0]);
+        
+        // Counts beyond 1x/4x need adapter-specific format features
+        let adapter_specific = wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
+        let required_features = adapter.features() & adapter_specific;
+        let supported_msaa = if required_features.contains(adapter_specific) {
+            let flags = adapter.get_texture_format_features(surface_format).flags;
+            CANDIDATE_SAMPLE_COUNTS.into_iter().filter(|&count| flags.sample_count_supported(count)).collect()
+        } else {
+            GUARANTEED_SAMPLE_COUNTS.to_vec()
+        };
+        
         // Request device and queue
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
                     label: None,
-                    required_features: wgpu::Features::empty(),
+                    required_features,
                     required_limits: if cfg!(target_arch = "wasm32") {
                         wgpu::Limits::downlevel_webgl2_defaults()
                     } else {
@@ -56,15 +90,6 @@ impl<'window> WgpuContext<'window> {
             .await
             .unwrap();
         
-        // Configure surface
-        let surface_caps = surface.get_capabilities(&adapter);
-        let surface_format = surface_caps
-            .formats
-            .iter()
-            .copied()
-            .find(|f| f.is_srgb())
-            .unwrap_or(surface_caps.formats[0]);
-        
         let config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             format: surface_format,
@@ -78,8 +103,10 @@ impl<'window> WgpuContext<'window> {
         
         surface.configure(&device, &config);
         
-        tracing::info!("WGPU Context initialized: {}x{}, format: {:?}", 
-            size.width, size.height, surface_format);
+        tracing::info!("WGPU Context initialized: {}x{}, format: {:?}, MSAA: {:?}", 
+            size.width, size.height, surface_format, supported_msaa);
+        
+        let depth_view = NodeRenderer::create_depth_view(&device, (config.width, config.height), 1);
         
         Self {
             surface,
@@ -87,7 +114,81 @@ impl<'window> WgpuContext<'window> {
             queue,
             config,
             size,
+            msaa_samples: 1,
+            supported_msaa,
+            msaa_view: None,
+            depth_view,
+        }
+    }
+    
+    /// Sample counts available to `set_msaa`, ascending
+    pub fn supported_msaa(&self) -> &[u32] {
+        &self.supported_msaa
+    }
+    
+    /// Switch to `samples`x MSAA, returning the count actually used
+    ///
+    /// Unsupported counts fall back to the highest supported count below
+    /// them. Pipelines drawing into the pass must be rebuilt with the
+    /// returned count.
+    pub fn set_msaa(&mut self, samples: u32) -> u32 {
+        let samples = fallback_sample_count(samples, &self.supported_msaa);
+        if samples != self.msaa_samples {
+            tracing::info!("MSAA set to {}x", samples);
+        }
+        self.msaa_samples = samples;
+        self.recreate_targets();
+        samples
+    }
+    
+    /// Color attachment for a pass that ends up in `frame`
+    ///
+    /// With MSAA the pass draws into the multisampled target and resolves
+    /// into `frame`; the multisampled contents are discarded afterwards.
+    pub fn color_attachment<'a>(&'a self, frame: &'a wgpu::TextureView, load: wgpu::LoadOp<wgpu::Color>) -> wgpu::RenderPassColorAttachment<'a> {
+        match &self.msaa_view {
+            Some(msaa_view) => wgpu::RenderPassColorAttachment {
+                view: msaa_view,
+                resolve_target: Some(frame),
+                ops: wgpu::Operations { load, store: wgpu::StoreOp::Discard },
+            },
+            None => wgpu::RenderPassColorAttachment {
+                view: frame,
+                resolve_target: None,
+                ops: wgpu::Operations { load, store: wgpu::StoreOp::Store },
+            },
+        }
+    }
+    
+    /// Depth attachment for the frame's pass, cleared to the far plane
+    pub fn depth_attachment(&self) -> wgpu::RenderPassDepthStencilAttachment<'_> {
+        NodeRenderer::depth_attachment(&self.depth_view)
+    }
+    
+    /// Rebuild the size- and sample-count-dependent targets
+    fn recreate_targets(&mut self) {
+        self.depth_view = NodeRenderer::create_depth_view(
+            &self.device, (self.config.width, self.config.height), self.msaa_samples,
+        );
+        if self.msaa_samples <= 1 {
+            self.msaa_view = None;
+            return;
         }
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("MSAA Color Target"),
+            size: wgpu::Extent3d {
+                width: self.config.width,
+                height: self.config.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: self.msaa_samples,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        self.msaa_view = Some(texture.create_view(&wgpu::TextureViewDescriptor::default()));
     }
     
     /// Resize surface
@@ -97,6 +198,7 @@ impl<'window> WgpuContext<'window> {
             self.config.width = new_size.width;
             self.config.height = new_size.height;
             self.surface.configure(&self.device, &self.config);
+            self.recreate_targets();
             
             tracing::info!("WGPU Context resized: {}x{}", new_size.width, new_size.height);
         }
@@ -119,3 +221,25 @@ impl<'window> WgpuContext<'window> {
         )
     }
 }
+
+/// Highest count in `supported` that does not exceed `requested`, or 1
+pub fn fallback_sample_count(requested: u32, supported: &[u32]) -> u32 {
+    supported.iter()
+        .copied()
+        .filter(|&count| count <= requested)
+        .max()
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_msaa_falls_back_to_highest_supported_count() {
+        assert_eq!(fallback_sample_count(8, &[1, 2, 4, 8]), 8);
+        assert_eq!(fallback_sample_count(8, &GUARANTEED_SAMPLE_COUNTS), 4);
+        assert_eq!(fallback_sample_count(2, &GUARANTEED_SAMPLE_COUNTS), 1);
+        assert_eq!(fallback_sample_count(0, &[1, 4]), 1);
+    }
+}
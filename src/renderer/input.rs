@@ -0,0 +1,158 @@
+//! Keyboard Shortcuts
+//!
+//! Tracks the held modifier keys and maps key chords to editor actions.
+//! The command modifier is Ctrl, or Cmd (the super key) on macOS keyboards.
+
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+
+/// Modifier keys currently held, updated from `ModifiersChanged` events
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyModifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+impl KeyModifiers {
+    pub fn update(&mut self, state: ModifiersState) {
+        *self = Self::from(state);
+    }
+
+    /// Ctrl or Cmd is held
+    pub fn command(&self) -> bool {
+        self.ctrl || self.logo
+    }
+}
+
+impl From<ModifiersState> for KeyModifiers {
+    fn from(state: ModifiersState) -> Self {
+        Self {
+            ctrl: state.control_key(),
+            shift: state.shift_key(),
+            alt: state.alt_key(),
+            logo: state.super_key(),
+        }
+    }
+}
+
+/// Editor operation triggered by a shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorAction {
+    Undo,
+    Redo,
+    Copy,
+    Paste,
+    Delete,
+    Duplicate,
+}
+
+/// Key of a chord, compared case-insensitively for characters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutKey {
+    Character(char),
+    Named(NamedKey),
+}
+
+impl ShortcutKey {
+    fn matches(&self, key: &Key) -> bool {
+        match (self, key) {
+            (Self::Character(expected), Key::Character(text)) => {
+                let mut chars = text.chars();
+                matches!((chars.next(), chars.next()), (Some(c), None) if c.eq_ignore_ascii_case(expected))
+            }
+            (Self::Named(expected), Key::Named(named)) => expected == named,
+            _ => false,
+        }
+    }
+}
+
+/// Key plus the modifiers that must be held with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChord {
+    pub key: ShortcutKey,
+    pub command: bool,
+    pub shift: bool,
+}
+
+impl KeyChord {
+    pub fn command(c: char) -> Self {
+        Self { key: ShortcutKey::Character(c), command: true, shift: false }
+    }
+
+    pub fn command_shift(c: char) -> Self {
+        Self { key: ShortcutKey::Character(c), command: true, shift: true }
+    }
+
+    pub fn named(key: NamedKey) -> Self {
+        Self { key: ShortcutKey::Named(key), command: false, shift: false }
+    }
+
+    /// Exact match: extra held modifiers make a different chord
+    pub fn matches(&self, key: &Key, modifiers: KeyModifiers) -> bool {
+        self.command == modifiers.command()
+            && self.shift == modifiers.shift
+            && !modifiers.alt
+            && self.key.matches(key)
+    }
+}
+
+/// Dispatch table from key chords to editor actions
+#[derive(Debug, Clone)]
+pub struct ShortcutMap {
+    bindings: Vec<(KeyChord, EditorAction)>,
+}
+
+impl Default for ShortcutMap {
+    fn default() -> Self {
+        Self {
+            bindings: vec![
+                (KeyChord::command('z'), EditorAction::Undo),
+                (KeyChord::command_shift('z'), EditorAction::Redo),
+                (KeyChord::command('c'), EditorAction::Copy),
+                (KeyChord::command('v'), EditorAction::Paste),
+                (KeyChord::named(NamedKey::Delete), EditorAction::Delete),
+                (KeyChord::command('d'), EditorAction::Duplicate),
+            ],
+        }
+    }
+}
+
+impl ShortcutMap {
+    /// Bind `chord` to `action`, replacing any existing binding for the chord
+    pub fn bind(&mut self, chord: KeyChord, action: EditorAction) {
+        self.bindings.retain(|(existing, _)| existing != &chord);
+        self.bindings.push((chord, action));
+    }
+
+    pub fn action_for(&self, key: &Key, modifiers: KeyModifiers) -> Option<EditorAction> {
+        self.bindings.iter()
+            .find(|(chord, _)| chord.matches(key, modifiers))
+            .map(|(_, action)| *action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character(c: &str) -> Key {
+        Key::Character(c.into())
+    }
+
+    #[test]
+    fn test_chords_require_exact_modifiers() {
+        let shortcuts = ShortcutMap::default();
+        let ctrl = KeyModifiers { ctrl: true, ..Default::default() };
+        let ctrl_shift = KeyModifiers { shift: true, ..ctrl };
+
+        assert_eq!(shortcuts.action_for(&character("z"), ctrl), Some(EditorAction::Undo));
+        // Shift turns the character uppercase on most layouts
+        assert_eq!(shortcuts.action_for(&character("Z"), ctrl_shift), Some(EditorAction::Redo));
+        assert_eq!(shortcuts.action_for(&character("v"), KeyModifiers { logo: true, ..Default::default() }), Some(EditorAction::Paste));
+        assert_eq!(shortcuts.action_for(&Key::Named(NamedKey::Delete), KeyModifiers::default()), Some(EditorAction::Delete));
+
+        assert_eq!(shortcuts.action_for(&character("c"), KeyModifiers::default()), None);
+        assert_eq!(shortcuts.action_for(&character("d"), KeyModifiers { alt: true, ..ctrl }), None);
+    }
+}
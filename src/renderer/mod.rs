@@ -8,17 +8,24 @@ pub mod primitives;
 pub mod text;
 pub mod canvas;
 pub mod platform;
+pub mod background;
+pub mod input;
 
 pub use context::WgpuContext;
 pub use primitives::*;
 pub use text::TextRenderer;
 pub use canvas::{LogicCanvas, GuiCanvas};
 pub use platform::PlatformAdapter;
+pub use background::{BackgroundRenderer, CanvasBackground};
+pub use input::{EditorAction, KeyChord, KeyModifiers, ShortcutMap};
 
 use std::sync::Arc;
 use crate::core::registry::MetaRegistry;
 use crate::graph::runtime::GraphRuntime;
 
+/// MSAA requested at startup; every adapter supports 4x
+const DEFAULT_MSAA_SAMPLES: u32 = 4;
+
 /// Main application using WGPU rendering
 pub struct Marco2App<'window> {
     /// Core business logic (preserved from original architecture)
@@ -32,11 +39,28 @@ pub struct Marco2App<'window> {
     logic_canvas: LogicCanvas,
     gui_canvas: GuiCanvas,
     
+    /// Canvas background drawn before nodes and connections
+    background_renderer: BackgroundRenderer,
+    
+    /// Node titles and port names, drawn over the canvas
+    text_renderer: TextRenderer,
+    theme: marco2::Marco2Theme,
+    
     /// Cross-platform abstractions
     platform: PlatformAdapter,
     
     /// Current application mode
     mode: AppMode,
+    
+    /// Held modifier keys and the shortcuts they unlock
+    modifiers: KeyModifiers,
+    shortcuts: ShortcutMap,
+    
+    /// A text field owns keyboard input; shortcuts and mode keys are suppressed
+    text_input_focused: bool,
+    
+    /// Last cursor position in window pixels
+    cursor_position: glam::Vec2,
 }
 
 /// Application modes for dual-interface system
@@ -62,12 +86,23 @@ impl<'window> Marco2App<'window> {
         let graph_runtime = GraphRuntime::new(registry.clone());
         
         // Initialize WGPU rendering context
-        let context = WgpuContext::new(window).await;
+        let mut context = WgpuContext::new(window).await;
+        context.set_msaa(DEFAULT_MSAA_SAMPLES);
         
         // Create mode-specific canvases
         let logic_canvas = LogicCanvas::new(&context);
         let gui_canvas = GuiCanvas::new(&context);
         
+        let mut background_renderer = BackgroundRenderer::new(&context.device, &context.queue, context.config.format, context.msaa_samples);
+        let theme = marco2::Marco2Theme::default();
+        background_renderer.set_background(&context.device, &context.queue, CanvasBackground::from_theme(&theme));
+        
+        let mut text_renderer = TextRenderer::new();
+        if let Err(e) = text_renderer.load_default_font() {
+            tracing::warn!("Node labels disabled: {}", e);
+        }
+        text_renderer.init_gpu(&context.device, context.config.format, context.msaa_samples);
+        
         // Platform adapter for cross-platform features
         let platform = PlatformAdapter::new();
         
@@ -77,8 +112,15 @@ impl<'window> Marco2App<'window> {
             context,
             logic_canvas,
             gui_canvas,
+            background_renderer,
+            text_renderer,
+            theme,
             platform,
             mode: AppMode::default(),
+            modifiers: KeyModifiers::default(),
+            shortcuts: ShortcutMap::default(),
+            text_input_focused: false,
+            cursor_position: glam::Vec2::ZERO,
         }
     }
     
@@ -90,6 +132,32 @@ impl<'window> Marco2App<'window> {
     /// Resize the rendering context
     pub fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
         self.context.resize(new_size);
+        self.logic_canvas.resize(glam::Vec2::new(new_size.width as f32, new_size.height as f32));
+    }
+    
+    /// Change MSAA, falling back to the highest count the adapter supports
+    ///
+    /// Returns the sample count in use.
+    pub fn set_msaa(&mut self, samples: u32) -> u32 {
+        let samples = self.context.set_msaa(samples);
+        self.background_renderer.set_sample_count(&self.context.device, samples);
+        self.text_renderer.set_sample_count(&self.context.device, samples);
+        samples
+    }
+    
+    /// Current canvas background
+    pub fn background(&self) -> &CanvasBackground {
+        self.background_renderer.background()
+    }
+    
+    /// Change the canvas background
+    pub fn set_background(&mut self, background: CanvasBackground) {
+        self.background_renderer.set_background(&self.context.device, &self.context.queue, background);
+    }
+    
+    /// Tell the app whether a text field has keyboard focus
+    pub fn set_text_input_focused(&mut self, focused: bool) {
+        self.text_input_focused = focused;
     }
     
     /// Handle window events
@@ -98,12 +166,19 @@ impl<'window> Marco2App<'window> {
             winit::event::WindowEvent::KeyboardInput { event: key_event, .. } => {
                 self.handle_keyboard_input(key_event)
             }
+            winit::event::WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers.update(modifiers.state());
+                false
+            }
             winit::event::WindowEvent::MouseInput { button, state, .. } => {
                 self.handle_mouse_input(*button, *state)
             }
             winit::event::WindowEvent::CursorMoved { position, .. } => {
                 self.handle_cursor_moved(*position)
             }
+            winit::event::WindowEvent::MouseWheel { delta, .. } => {
+                self.handle_mouse_wheel(*delta)
+            }
             winit::event::WindowEvent::Resized(new_size) => {
                 self.resize(*new_size);
                 true
@@ -123,28 +198,31 @@ impl<'window> Marco2App<'window> {
             }
         );
         
+        let viewport_size = [self.context.config.width as f32, self.context.config.height as f32];
+        self.background_renderer.update(&self.context.queue, viewport_size);
+        
+        self.text_renderer.begin_frame();
+        if self.mode == AppMode::LogicCanvas {
+            self.logic_canvas.update_minimap();
+            self.logic_canvas.queue_labels(&mut self.text_renderer, &self.theme);
+        }
+        self.text_renderer.upload(&self.context.device, &self.context.queue, viewport_size);
+        
         // Begin render pass
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Marco2 Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.1,
-                            b: 0.12,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
+                color_attachments: &[Some(self.context.color_attachment(
+                    &view,
+                    wgpu::LoadOp::Clear(self.background_renderer.background().clear_color()),
+                ))],
+                depth_stencil_attachment: Some(self.context.depth_attachment()),
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
             
+            self.background_renderer.render(&mut render_pass);
+            
             // Render current mode
             match self.mode {
                 AppMode::LogicCanvas => {
@@ -154,6 +232,8 @@ impl<'window> Marco2App<'window> {
                     self.gui_canvas.render(&mut render_pass, &self.context);
                 }
             }
+            
+            self.text_renderer.render(&mut render_pass);
         }
         
         // Submit commands and present
@@ -181,8 +261,13 @@ impl<'window> Marco2App<'window> {
     
     /// Handle keyboard input
     fn handle_keyboard_input(&mut self, key_event: &winit::event::KeyEvent) -> bool {
+        // Focused text fields get every key, including mode keys and chords
+        if self.text_input_focused || key_event.state != winit::event::ElementState::Pressed {
+            return false;
+        }
+        
         // Mode switching
-        if key_event.state == winit::event::ElementState::Pressed {
+        if !self.modifiers.command() {
             match key_event.logical_key {
                 winit::keyboard::Key::Character(ref c) if c == "1" => {
                     self.mode = AppMode::LogicCanvas;
@@ -198,28 +283,39 @@ impl<'window> Marco2App<'window> {
             }
         }
         
-        // Forward to active canvas (placeholder for now)
+        // Forward shortcuts to the active canvas
+        let Some(action) = self.shortcuts.action_for(&key_event.logical_key, self.modifiers) else {
+            return false;
+        };
+        tracing::debug!("Shortcut {:?} in {:?}", action, self.mode);
         match self.mode {
-            AppMode::LogicCanvas => false, // self.logic_canvas.handle_keyboard_input(key_event),
-            AppMode::GuiCanvas => false,   // self.gui_canvas.handle_keyboard_input(key_event),
+            AppMode::LogicCanvas => self.logic_canvas.handle_action(action),
+            AppMode::GuiCanvas => self.gui_canvas.handle_action(action),
         }
     }
     
     /// Handle mouse input
-    fn handle_mouse_input(&mut self, _button: winit::event::MouseButton, _state: winit::event::ElementState) -> bool {
-        // Placeholder for now
+    fn handle_mouse_input(&mut self, button: winit::event::MouseButton, state: winit::event::ElementState) -> bool {
         match self.mode {
-            AppMode::LogicCanvas => false, // self.logic_canvas.handle_mouse_input(button, state),
+            AppMode::LogicCanvas => self.logic_canvas.handle_mouse_input(button, state, self.cursor_position),
             AppMode::GuiCanvas => false,   // self.gui_canvas.handle_mouse_input(button, state),
         }
     }
     
     /// Handle cursor movement
-    fn handle_cursor_moved(&mut self, _position: winit::dpi::PhysicalPosition<f64>) -> bool {
-        // Placeholder for now
+    fn handle_cursor_moved(&mut self, position: winit::dpi::PhysicalPosition<f64>) -> bool {
+        self.cursor_position = glam::Vec2::new(position.x as f32, position.y as f32);
         match self.mode {
-            AppMode::LogicCanvas => false, // self.logic_canvas.handle_cursor_moved(position),
+            AppMode::LogicCanvas => self.logic_canvas.handle_cursor_moved(self.cursor_position),
             AppMode::GuiCanvas => false,   // self.gui_canvas.handle_cursor_moved(position),
         }
     }
+    
+    /// Handle scroll wheel and touchpad scrolling
+    fn handle_mouse_wheel(&mut self, delta: winit::event::MouseScrollDelta) -> bool {
+        match self.mode {
+            AppMode::LogicCanvas => self.logic_canvas.handle_mouse_wheel(delta, self.cursor_position),
+            AppMode::GuiCanvas => false,
+        }
+    }
 }
@@ -1,14 +1,997 @@
 //! Text Rendering
-//! 
+//!
 //! Advanced text rendering with professional typography support.
+//!
+//! Glyphs are rasterized on demand into a single-channel atlas of fixed-size
+//! cells. When the atlas fills up it doubles in size until it reaches its
+//! cap, after which the least recently used glyphs are evicted and
+//! re-rasterized the next time they are needed. Glyphs that cannot be placed
+//! in the current frame are skipped rather than failing the whole draw.
+//!
+//! Labels are queued each frame in screen pixels, laid out on a single line
+//! and clipped with an ellipsis, then drawn as one instanced batch of glyph
+//! quads sampling the atlas.
+
+use bytemuck::{Pod, Zeroable};
+use glam::Vec2;
+use std::collections::HashMap;
+
+/// Text size in canvas units at 100% zoom
+pub const DEFAULT_FONT_SIZE: f32 = 14.0;
+
+/// Smallest on-screen size text is drawn at; zooming out further stops
+/// shrinking it
+pub const MIN_READABLE_PX: f32 = 9.0;
+
+/// Largest glyph size as a fraction of the atlas cell, leaving room for
+/// ascenders and descenders
+const MAX_CELL_FILL: f32 = 0.75;
+
+/// Advance per pixel of size assumed when no font is loaded
+const FALLBACK_ADVANCE: f32 = 0.6;
+
+/// Appended to text cut short by `max_width`
+const ELLIPSIS: char = '\u{2026}';
+
+/// Font bundled with egui, used when no other font is set
+const DEFAULT_FONT_NAME: &str = "Ubuntu-Light";
+
+/// A glyph at a specific pixel size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlyphKey {
+    pub ch: char,
+    pub px: u16,
+}
+
+/// Coverage bitmap produced by a rasterizer, one byte per pixel
+#[derive(Debug, Clone, Default)]
+pub struct GlyphBitmap {
+    pub width: u32,
+    pub height: u32,
+    pub coverage: Vec<u8>,
+}
+
+/// Location of a glyph inside the atlas texture, in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtlasRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Atlas sizing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasConfig {
+    /// Starting texture edge length in pixels
+    pub initial_size: u32,
+    /// Largest texture edge length the atlas may grow to
+    pub max_size: u32,
+    /// Edge length of one glyph cell; larger glyphs are clipped
+    pub cell_size: u32,
+}
+
+impl Default for AtlasConfig {
+    fn default() -> Self {
+        Self {
+            initial_size: 512,
+            max_size: 2048,
+            cell_size: 32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AtlasEntry {
+    cell: (u32, u32),
+    region: AtlasRegion,
+    last_used: u64,
+}
+
+/// CPU-side glyph atlas with LRU eviction
+///
+/// `pixels` mirrors the GPU texture; callers upload `take_dirty_regions`
+/// each frame and recreate the texture when `take_resized` reports growth.
+#[derive(Debug)]
+pub struct GlyphAtlas {
+    config: AtlasConfig,
+    size: u32,
+    pixels: Vec<u8>,
+    entries: HashMap<GlyphKey, AtlasEntry>,
+    free_cells: Vec<(u32, u32)>,
+    frame: u64,
+    dirty_regions: Vec<AtlasRegion>,
+    resized: bool,
+    cap_warned: bool,
+}
+
+impl GlyphAtlas {
+    pub fn new(config: AtlasConfig) -> Self {
+        let size = config.initial_size.min(config.max_size).max(config.cell_size);
+        let mut atlas = Self {
+            config,
+            size,
+            pixels: vec![0; (size * size) as usize],
+            entries: HashMap::new(),
+            free_cells: Vec::new(),
+            frame: 0,
+            dirty_regions: Vec::new(),
+            resized: false,
+            cap_warned: false,
+        };
+        atlas.add_free_cells(0);
+        atlas
+    }
+
+    /// Start a new frame; glyphs used in earlier frames become evictable
+    pub fn begin_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Edge length of one glyph cell
+    pub fn cell_size(&self) -> u32 {
+        self.config.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, key: &GlyphKey) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Texture contents, `size * size` coverage bytes
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Regions written since the last call
+    pub fn take_dirty_regions(&mut self) -> Vec<AtlasRegion> {
+        std::mem::take(&mut self.dirty_regions)
+    }
+
+    /// Whether the atlas grew since the last call (re-upload `pixels` whole)
+    pub fn take_resized(&mut self) -> bool {
+        std::mem::replace(&mut self.resized, false)
+    }
+
+    /// Region of a cached glyph, rasterizing and inserting it if missing
+    ///
+    /// Returns None when the atlas is at its cap and every cell holds a glyph
+    /// already used this frame; the glyph is simply not drawn.
+    pub fn get_or_insert<F>(&mut self, key: GlyphKey, rasterize: F) -> Option<AtlasRegion>
+    where
+        F: FnOnce(GlyphKey) -> GlyphBitmap,
+    {
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.last_used = self.frame;
+            return Some(entry.region);
+        }
+
+        let cell = self.allocate_cell()?;
+        let bitmap = rasterize(key);
+        let region = self.write_cell(cell, &bitmap);
+        self.entries.insert(key, AtlasEntry { cell, region, last_used: self.frame });
+        Some(region)
+    }
+
+    fn allocate_cell(&mut self) -> Option<(u32, u32)> {
+        if self.free_cells.is_empty() && self.size < self.config.max_size {
+            self.grow();
+        }
+        if let Some(cell) = self.free_cells.pop() {
+            return Some(cell);
+        }
+
+        if !self.cap_warned {
+            tracing::warn!(
+                "Glyph atlas reached its {}px cap with {} glyphs; evicting least recently used glyphs",
+                self.size, self.entries.len()
+            );
+            self.cap_warned = true;
+        }
+        self.evict_lru()
+    }
+
+    /// Free the least recently used glyph not used in the current frame
+    fn evict_lru(&mut self) -> Option<(u32, u32)> {
+        let (key, entry) = self.entries.iter()
+            .filter(|(_, entry)| entry.last_used < self.frame)
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, entry)| (*key, *entry))?;
+        self.entries.remove(&key);
+        Some(entry.cell)
+    }
+
+    /// Double the texture edge, keeping existing glyphs where they are
+    fn grow(&mut self) {
+        let old_size = self.size;
+        let new_size = (old_size * 2).min(self.config.max_size);
+        let mut pixels = vec![0; (new_size * new_size) as usize];
+        for row in 0..old_size {
+            let src = (row * old_size) as usize;
+            let dst = (row * new_size) as usize;
+            pixels[dst..dst + old_size as usize].copy_from_slice(&self.pixels[src..src + old_size as usize]);
+        }
+
+        self.pixels = pixels;
+        self.size = new_size;
+        self.resized = true;
+        self.add_free_cells(old_size);
+        tracing::debug!("Glyph atlas grew from {}px to {}px", old_size, new_size);
+    }
+
+    /// Add every cell lying outside the top-left `old_size` square
+    fn add_free_cells(&mut self, old_size: u32) {
+        let cells = self.size / self.config.cell_size;
+        let old_cells = old_size / self.config.cell_size;
+        for row in (0..cells).rev() {
+            for col in (0..cells).rev() {
+                if row >= old_cells || col >= old_cells {
+                    self.free_cells.push((col, row));
+                }
+            }
+        }
+    }
+
+    fn write_cell(&mut self, cell: (u32, u32), bitmap: &GlyphBitmap) -> AtlasRegion {
+        let cell_size = self.config.cell_size;
+        let (x, y) = (cell.0 * cell_size, cell.1 * cell_size);
+        let width = bitmap.width.min(cell_size);
+        let height = bitmap.height.min(cell_size);
+
+        for row in 0..cell_size {
+            let dst = ((y + row) * self.size + x) as usize;
+            self.pixels[dst..dst + cell_size as usize].fill(0);
+            if row < height {
+                let src = (row * bitmap.width) as usize;
+                let available = bitmap.coverage.len().saturating_sub(src).min(width as usize);
+                self.pixels[dst..dst + available].copy_from_slice(&bitmap.coverage[src..src + available]);
+            }
+        }
+
+        let cell_region = AtlasRegion { x, y, width: cell_size, height: cell_size };
+        self.dirty_regions.push(cell_region);
+        AtlasRegion { x, y, width, height }
+    }
+}
+
+/// A glyph on a laid out line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedGlyph {
+    pub ch: char,
+    /// Pen position from the start of the line, in pixels
+    pub x: f32,
+    pub advance: f32,
+}
+
+/// A single line of glyphs, possibly cut short with an ellipsis
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextLine {
+    pub glyphs: Vec<PositionedGlyph>,
+    /// Sum of the advances of `glyphs`
+    pub width: f32,
+    /// Whether the text was cut to fit
+    pub clipped: bool,
+}
+
+impl TextLine {
+    /// The characters actually drawn, including any ellipsis
+    pub fn text(&self) -> String {
+        self.glyphs.iter().map(|glyph| glyph.ch).collect()
+    }
+}
+
+/// Lay `text` out on one line no wider than `max_width`
+///
+/// Text that does not fit keeps as many leading characters as leave room
+/// for an ellipsis; when not even the ellipsis fits the line is empty.
+pub fn layout_line_with<F>(text: &str, max_width: f32, mut advance: F) -> TextLine
+where
+    F: FnMut(char) -> f32,
+{
+    let mut line = TextLine::default();
+    for ch in text.chars() {
+        let width = advance(ch);
+        line.glyphs.push(PositionedGlyph { ch, x: line.width, advance: width });
+        line.width += width;
+    }
+    if line.width <= max_width {
+        return line;
+    }
+
+    let ellipsis = advance(ELLIPSIS);
+    let keep = line.glyphs.iter()
+        .take_while(|glyph| glyph.x + glyph.advance + ellipsis <= max_width)
+        .count();
+    line.glyphs.truncate(keep);
+    line.width = line.glyphs.last().map_or(0.0, |glyph| glyph.x + glyph.advance);
+    line.clipped = true;
+    if line.width + ellipsis <= max_width {
+        line.glyphs.push(PositionedGlyph { ch: ELLIPSIS, x: line.width, advance: ellipsis });
+        line.width += ellipsis;
+    }
+    line
+}
+
+/// Which point of a label its anchor marks horizontally
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+}
+
+/// A line of text queued for the current frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLabel {
+    pub text: String,
+    /// Screen position of the aligned edge, at the line's vertical center
+    pub anchor: Vec2,
+    pub align: TextAlign,
+    /// Width in pixels past which the text is cut with an ellipsis
+    pub max_width: f32,
+    pub px: u16,
+    pub color: [f32; 4],
+}
+
+/// One glyph quad as uploaded to the GPU
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable, PartialEq)]
+pub struct GlyphInstance {
+    /// Top-left corner and size in screen pixels
+    pub rect: [f32; 4],
+    /// Atlas texture coordinates of the top-left and bottom-right corners
+    pub uv: [f32; 4],
+    pub color: [f32; 4],
+}
 
 pub struct TextRenderer {
-    // Placeholder for now
+    atlas: GlyphAtlas,
+    font: Option<fontdue::Font>,
+    font_size: f32,
+    labels: Vec<TextLabel>,
+    gpu: Option<TextPipeline>,
 }
 
 impl TextRenderer {
     pub fn new() -> Self {
         tracing::info!("TextRenderer initialized");
-        Self {}
+        Self::with_atlas(AtlasConfig::default())
+    }
+
+    pub fn with_atlas(config: AtlasConfig) -> Self {
+        Self {
+            atlas: GlyphAtlas::new(config),
+            font: None,
+            font_size: DEFAULT_FONT_SIZE,
+            labels: Vec::new(),
+            gpu: None,
+        }
+    }
+
+    /// Load the font used by `prepare_text`
+    pub fn set_font(&mut self, bytes: &[u8]) -> Result<(), String> {
+        let font = fontdue::Font::from_bytes(bytes, fontdue::FontSettings::default())?;
+        self.font = Some(font);
+        Ok(())
+    }
+
+    /// Load one of the fonts bundled with egui, e.g. "Ubuntu-Light" or "Hack"
+    pub fn set_bundled_font(&mut self, name: &str) -> Result<(), String> {
+        let fonts = egui::FontDefinitions::default();
+        let data = fonts.font_data.get(name).ok_or_else(|| format!("No bundled font named '{}'", name))?;
+        self.set_font(&data.font)
+    }
+
+    /// Load the default UI font
+    pub fn load_default_font(&mut self) -> Result<(), String> {
+        self.set_bundled_font(DEFAULT_FONT_NAME)
+    }
+
+    pub fn has_font(&self) -> bool {
+        self.font.is_some()
+    }
+
+    /// Text size in canvas units at 100% zoom
+    pub fn font_size(&self) -> f32 {
+        self.font_size
+    }
+
+    /// Change the text size in canvas units at 100% zoom
+    pub fn set_font_size(&mut self, size: f32) {
+        if size.is_finite() && size > 0.0 {
+            self.font_size = size;
+        }
+    }
+
+    /// On-screen pixel size for `font_size` at `canvas_scale`
+    ///
+    /// Follows the zoom but never drops below `MIN_READABLE_PX`, and stops
+    /// growing once glyphs would no longer fit an atlas cell.
+    pub fn screen_px(&self, canvas_scale: f32) -> u16 {
+        let max = (self.atlas.cell_size() as f32 * MAX_CELL_FILL).max(MIN_READABLE_PX);
+        (self.font_size * canvas_scale).clamp(MIN_READABLE_PX, max).round() as u16
+    }
+
+    /// Horizontal advance of `ch` at `px`
+    pub fn advance(&self, ch: char, px: u16) -> f32 {
+        match self.font.as_ref() {
+            Some(font) => font.metrics(ch, px as f32).advance_width,
+            None => px as f32 * FALLBACK_ADVANCE,
+        }
+    }
+
+    /// Lay `text` out at `px`, clipped to `max_width` with an ellipsis
+    pub fn layout_line(&self, text: &str, px: u16, max_width: f32) -> TextLine {
+        layout_line_with(text, max_width, |ch| self.advance(ch, px))
+    }
+
+    /// Queue a label for the next `upload`
+    pub fn queue(&mut self, label: TextLabel) {
+        self.labels.push(label);
+    }
+
+    pub fn queued(&self) -> &[TextLabel] {
+        &self.labels
+    }
+
+    pub fn atlas(&self) -> &GlyphAtlas {
+        &self.atlas
+    }
+
+    pub fn atlas_mut(&mut self) -> &mut GlyphAtlas {
+        &mut self.atlas
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.atlas.begin_frame();
+    }
+
+    /// Atlas regions for each character of `text`, rasterized with the loaded font
+    ///
+    /// Entries are None for glyphs that could not be placed this frame, or
+    /// for every glyph when no font is loaded.
+    pub fn prepare_text(&mut self, text: &str, px: u16) -> Vec<Option<AtlasRegion>> {
+        let Some(font) = self.font.as_ref() else {
+            return vec![None; text.chars().count()];
+        };
+        let atlas = &mut self.atlas;
+        text.chars()
+            .map(|ch| {
+                atlas.get_or_insert(GlyphKey { ch, px }, |key| {
+                    let (metrics, coverage) = font.rasterize(key.ch, key.px as f32);
+                    GlyphBitmap { width: metrics.width as u32, height: metrics.height as u32, coverage }
+                })
+            })
+            .collect()
+    }
+
+    /// Like `prepare_text` but with a caller-supplied rasterizer
+    pub fn prepare_text_with<F>(&mut self, text: &str, px: u16, mut rasterize: F) -> Vec<Option<AtlasRegion>>
+    where
+        F: FnMut(GlyphKey) -> GlyphBitmap,
+    {
+        text.chars()
+            .map(|ch| self.atlas.get_or_insert(GlyphKey { ch, px }, &mut rasterize))
+            .collect()
+    }
+
+    /// Lay out and rasterize every queued label, emptying the queue
+    ///
+    /// Without a font nothing can be rasterized and no glyphs are returned.
+    pub fn prepare(&mut self) -> Vec<GlyphInstance> {
+        let labels = std::mem::take(&mut self.labels);
+        let Some(font) = self.font.as_ref() else {
+            return Vec::new();
+        };
+
+        let mut glyphs = Vec::new();
+        for label in &labels {
+            let px = label.px as f32;
+            let line = layout_line_with(&label.text, label.max_width, |ch| font.metrics(ch, px).advance_width);
+            let left = match label.align {
+                TextAlign::Left => label.anchor.x,
+                TextAlign::Center => label.anchor.x - line.width / 2.0,
+                TextAlign::Right => label.anchor.x - line.width,
+            };
+            let baseline = match font.horizontal_line_metrics(px) {
+                Some(metrics) => label.anchor.y + (metrics.ascent + metrics.descent) / 2.0,
+                None => label.anchor.y + px * 0.35,
+            };
+
+            for glyph in &line.glyphs {
+                let metrics = font.metrics(glyph.ch, px);
+                if metrics.width == 0 || metrics.height == 0 {
+                    continue;
+                }
+                let key = GlyphKey { ch: glyph.ch, px: label.px };
+                let Some(region) = self.atlas.get_or_insert(key, |key| {
+                    let (metrics, coverage) = font.rasterize(key.ch, key.px as f32);
+                    GlyphBitmap { width: metrics.width as u32, height: metrics.height as u32, coverage }
+                }) else {
+                    continue;
+                };
+                // Whole pixels keep the nearest-sampled atlas crisp
+                let x = (left + glyph.x + metrics.xmin as f32).round();
+                let y = (baseline - (metrics.ymin + metrics.height as i32) as f32).round();
+                glyphs.push(GlyphInstance {
+                    rect: [x, y, region.width as f32, region.height as f32],
+                    uv: [region.x as f32, region.y as f32, (region.x + region.width) as f32, (region.y + region.height) as f32],
+                    color: label.color,
+                });
+            }
+        }
+
+        // The atlas may have grown while rasterizing, so normalize last
+        let size = self.atlas.size() as f32;
+        for glyph in &mut glyphs {
+            glyph.uv = glyph.uv.map(|texel| texel / size);
+        }
+        glyphs
+    }
+
+    /// Create the GPU resources used by `upload` and `render`
+    pub fn init_gpu(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat, sample_count: u32) {
+        self.gpu = Some(TextPipeline::new(device, format, sample_count, self.atlas.size()));
+    }
+
+    /// Rebuild the pipeline for passes with `sample_count` MSAA samples
+    pub fn set_sample_count(&mut self, device: &wgpu::Device, sample_count: u32) {
+        if let Some(gpu) = self.gpu.as_mut() {
+            gpu.set_sample_count(device, sample_count);
+        }
+    }
+
+    /// Prepare the queued labels and upload them with any new glyphs
+    ///
+    /// Does nothing but empty the queue before `init_gpu`.
+    pub fn upload(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, viewport_size: [f32; 2]) {
+        let glyphs = self.prepare();
+        let Some(gpu) = self.gpu.as_mut() else {
+            return;
+        };
+
+        if self.atlas.take_resized() || gpu.atlas_size != self.atlas.size() {
+            gpu.resize_atlas(device, self.atlas.size());
+            self.atlas.take_dirty_regions();
+            gpu.write_atlas(queue, self.atlas.pixels(), self.atlas.size(), AtlasRegion {
+                x: 0,
+                y: 0,
+                width: self.atlas.size(),
+                height: self.atlas.size(),
+            });
+        }
+        for region in self.atlas.take_dirty_regions() {
+            gpu.write_atlas(queue, self.atlas.pixels(), self.atlas.size(), region);
+        }
+        gpu.write_glyphs(device, queue, &glyphs, viewport_size);
+    }
+
+    /// Draw the glyphs from the last `upload`; call after everything the
+    /// text sits on
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if let Some(gpu) = self.gpu.as_ref() {
+            gpu.render(render_pass);
+        }
+    }
+}
+
+const TEXT_SHADER: &str = r#"
+struct Screen {
+    size: vec2<f32>,
+};
+
+@group(0) @binding(0) var<uniform> screen: Screen;
+@group(0) @binding(1) var atlas: texture_2d<f32>;
+@group(0) @binding(2) var atlas_sampler: sampler;
+
+struct GlyphInput {
+    @location(0) rect: vec4<f32>,
+    @location(1) uv: vec4<f32>,
+    @location(2) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32, glyph: GlyphInput) -> VertexOutput {
+    // Triangle strip over the glyph quad
+    let corner = vec2<f32>(f32(index & 1u), f32((index >> 1u) & 1u));
+    let pixel = glyph.rect.xy + corner * glyph.rect.zw;
+    var out: VertexOutput;
+    out.position = vec4<f32>(pixel / screen.size * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+    out.uv = mix(glyph.uv.xy, glyph.uv.zw, corner);
+    out.color = glyph.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let coverage = textureSample(atlas, atlas_sampler, in.uv).r;
+    return vec4<f32>(in.color.rgb, in.color.a * coverage);
+}
+"#;
+
+/// Glyphs a new instance buffer has room for
+const INITIAL_GLYPH_CAPACITY: usize = 1024;
+
+/// GPU side of the text renderer: atlas texture and glyph batch
+struct TextPipeline {
+    pipeline: wgpu::RenderPipeline,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    atlas_texture: wgpu::Texture,
+    atlas_size: u32,
+    instance_buffer: wgpu::Buffer,
+    instance_capacity: usize,
+    instance_count: u32,
+}
+
+impl TextPipeline {
+    fn new(device: &wgpu::Device, format: wgpu::TextureFormat, sample_count: u32, atlas_size: u32) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Text Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline = Self::create_pipeline(device, &bind_group_layout, format, sample_count);
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Text Uniforms"),
+            // vec2 viewport size, padded to the minimum uniform size
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Glyphs are drawn at whole pixels and their native size
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Text Sampler"),
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let atlas_texture = Self::create_atlas_texture(device, atlas_size);
+        let bind_group = Self::create_bind_group(device, &bind_group_layout, &uniform_buffer, &atlas_texture, &sampler);
+        let instance_buffer = Self::create_instance_buffer(device, INITIAL_GLYPH_CAPACITY);
+
+        Self {
+            pipeline,
+            format,
+            sample_count,
+            bind_group_layout,
+            bind_group,
+            uniform_buffer,
+            sampler,
+            atlas_texture,
+            atlas_size,
+            instance_buffer,
+            instance_capacity: INITIAL_GLYPH_CAPACITY,
+            instance_count: 0,
+        }
+    }
+
+    fn set_sample_count(&mut self, device: &wgpu::Device, sample_count: u32) {
+        if sample_count != self.sample_count {
+            self.pipeline = Self::create_pipeline(device, &self.bind_group_layout, self.format, sample_count);
+            self.sample_count = sample_count;
+        }
+    }
+
+    /// Replace the atlas texture with an empty one of `size`
+    fn resize_atlas(&mut self, device: &wgpu::Device, size: u32) {
+        self.atlas_texture = Self::create_atlas_texture(device, size);
+        self.atlas_size = size;
+        self.bind_group = Self::create_bind_group(
+            device, &self.bind_group_layout, &self.uniform_buffer, &self.atlas_texture, &self.sampler,
+        );
+    }
+
+    /// Copy `region` of the CPU atlas, `size` pixels square, to the texture
+    fn write_atlas(&self, queue: &wgpu::Queue, pixels: &[u8], size: u32, region: AtlasRegion) {
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &self.atlas_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x: region.x, y: region.y, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+            },
+            pixels,
+            wgpu::ImageDataLayout {
+                offset: (region.y * size + region.x) as u64,
+                bytes_per_row: Some(size),
+                rows_per_image: Some(size),
+            },
+            wgpu::Extent3d { width: region.width, height: region.height, depth_or_array_layers: 1 },
+        );
+    }
+
+    fn write_glyphs(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, glyphs: &[GlyphInstance], viewport_size: [f32; 2]) {
+        if glyphs.len() > self.instance_capacity {
+            self.instance_capacity = glyphs.len().next_power_of_two();
+            self.instance_buffer = Self::create_instance_buffer(device, self.instance_capacity);
+        }
+        if !glyphs.is_empty() {
+            queue.write_buffer(&self.instance_buffer, 0, bytemuck::cast_slice(glyphs));
+        }
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[viewport_size[0], viewport_size[1], 0.0, 0.0]));
+        self.instance_count = glyphs.len() as u32;
+    }
+
+    fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        if self.instance_count == 0 {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.instance_buffer.slice(..));
+        render_pass.draw(0..4, 0..self.instance_count);
+    }
+
+    fn create_pipeline(
+        device: &wgpu::Device,
+        bind_group_layout: &wgpu::BindGroupLayout,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> wgpu::RenderPipeline {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Text Shader"),
+            source: wgpu::ShaderSource::Wgsl(TEXT_SHADER.into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Text Pipeline Layout"),
+            bind_group_layouts: &[bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Text Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<GlyphInstance>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x4, 1 => Float32x4, 2 => Float32x4],
+                }],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            // Labels sit on top of the nodes they annotate
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: marco2::render::DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+        })
+    }
+
+    fn create_atlas_texture(device: &wgpu::Device, size: u32) -> wgpu::Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Glyph Atlas Texture"),
+            size: wgpu::Extent3d { width: size, height: size, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        })
+    }
+
+    fn create_instance_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Glyph Instance Buffer"),
+            size: (capacity * std::mem::size_of::<GlyphInstance>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn create_bind_group(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        uniforms: &wgpu::Buffer,
+        texture: &wgpu::Texture,
+        sampler: &wgpu::Sampler,
+    ) -> wgpu::BindGroup {
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Text Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: uniforms.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(&view) },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::Sampler(sampler) },
+            ],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(key: GlyphKey) -> GlyphBitmap {
+        let side = key.px as u32;
+        GlyphBitmap { width: side, height: side, coverage: vec![key.ch as u8; (side * side) as usize] }
+    }
+
+    fn small_atlas() -> TextRenderer {
+        // 4x4 cells that can grow once to 8x8
+        TextRenderer::with_atlas(AtlasConfig { initial_size: 32, max_size: 64, cell_size: 8 })
+    }
+
+    #[test]
+    fn test_atlas_grows_then_evicts_to_render_every_glyph() {
+        let mut text = small_atlas();
+        let glyphs: String = (0..200u32).filter_map(|i| char::from_u32('!' as u32 + i)).collect();
+        let chars: Vec<char> = glyphs.chars().collect();
+
+        // More unique glyphs than the 64-cell cap, drawn a line at a time
+        for line in chars.chunks(20) {
+            text.begin_frame();
+            let line: String = line.iter().collect();
+            let regions = text.prepare_text_with(&line, 6, block);
+            assert!(regions.iter().all(Option::is_some));
+        }
+
+        let atlas = text.atlas();
+        assert_eq!(atlas.size(), 64);
+        assert_eq!(atlas.len(), 64);
+        assert!(atlas.contains(&GlyphKey { ch: *chars.last().unwrap(), px: 6 }));
+        assert!(!atlas.contains(&GlyphKey { ch: chars[0], px: 6 }));
+    }
+
+    #[test]
+    fn test_full_frame_skips_overflow_then_recovers() {
+        let mut text = small_atlas();
+        let glyphs: String = (0..80u32).filter_map(|i| char::from_u32('0' as u32 + i)).collect();
+
+        text.begin_frame();
+        let regions = text.prepare_text_with(&glyphs, 6, block);
+        assert_eq!(regions.iter().filter(|r| r.is_some()).count(), 64);
+
+        // The skipped tail renders next frame by evicting older glyphs
+        text.begin_frame();
+        let tail: String = glyphs.chars().skip(64).collect();
+        let regions = text.prepare_text_with(&tail, 6, block);
+        assert!(regions.iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn test_growth_keeps_existing_glyph_pixels() {
+        let mut atlas = GlyphAtlas::new(AtlasConfig { initial_size: 8, max_size: 16, cell_size: 8 });
+        let a = atlas.get_or_insert(GlyphKey { ch: 'a', px: 4 }, block).unwrap();
+        let b = atlas.get_or_insert(GlyphKey { ch: 'b', px: 4 }, block).unwrap();
+
+        assert!(atlas.take_resized());
+        assert_ne!((a.x, a.y), (b.x, b.y));
+        let size = atlas.size() as usize;
+        assert_eq!(atlas.pixels()[a.y as usize * size + a.x as usize], b'a');
+        assert_eq!(atlas.pixels()[b.y as usize * size + b.x as usize], b'b');
+    }
+
+    #[test]
+    fn test_layout_advances_match_font_metrics() {
+        // Hack is monospaced, so every advance is the same
+        let mut text = TextRenderer::new();
+        text.set_bundled_font("Hack").unwrap();
+        let px = 14;
+        let em = text.advance('m', px);
+        assert!(em > 0.0);
+        assert_eq!(text.advance('i', px), em);
+
+        let line = text.layout_line("Add node", px, f32::INFINITY);
+        assert_eq!(line.text(), "Add node");
+        assert!(!line.clipped);
+        for (i, glyph) in line.glyphs.iter().enumerate() {
+            assert_eq!(glyph.advance, em);
+            assert!((glyph.x - em * i as f32).abs() < 1e-3);
+        }
+        assert!((line.width - em * 8.0).abs() < 1e-3);
+
+        // A proportional font still places each glyph after the previous one
+        text.load_default_font().unwrap();
+        let line = text.layout_line("Wil", px, f32::INFINITY);
+        assert!(text.advance('W', px) > text.advance('i', px));
+        assert_eq!(line.glyphs[1].x, line.glyphs[0].advance);
+        assert_eq!(line.width, line.glyphs.iter().map(|glyph| glyph.advance).sum::<f32>());
+    }
+
+    #[test]
+    fn test_long_text_is_clipped_with_an_ellipsis() {
+        let advance = |ch: char| if ch == ELLIPSIS { 6.0 } else { 10.0 };
+
+        let line = layout_line_with("Multiply", 80.0, advance);
+        assert_eq!(line.text(), "Multiply");
+        assert!(!line.clipped);
+
+        // Five glyphs plus the ellipsis fit in 56 pixels
+        let line = layout_line_with("Multiply", 56.0, advance);
+        assert_eq!(line.text(), "Multi\u{2026}");
+        assert!(line.clipped);
+        assert_eq!(line.width, 56.0);
+
+        assert_eq!(layout_line_with("Multiply", 5.0, advance).glyphs.len(), 0);
+    }
+
+    #[test]
+    fn test_screen_px_follows_zoom_within_readable_limits() {
+        let mut text = TextRenderer::new();
+        assert_eq!(text.screen_px(1.0), DEFAULT_FONT_SIZE as u16);
+        assert_eq!(text.screen_px(0.1), MIN_READABLE_PX as u16);
+        assert_eq!(text.screen_px(5.0), 24);
+
+        text.set_font_size(10.0);
+        assert_eq!(text.screen_px(1.5), 15);
+        text.set_font_size(-1.0);
+        assert_eq!(text.font_size(), 10.0);
     }
 }
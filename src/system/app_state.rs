@@ -4,6 +4,7 @@ use crate::ui::theme::Marco2Theme;
 // use crate::project::manager::ProjectManager;
 // use crate::devtools::error_log::ErrorLog;
 use crate::system::test_harness::TestHarness;
+use crate::system::frame_pacer::FramePacer;
 // use crate::render::wgpu_renderer::WGPURenderer; // Disabled for build compatibility
 use std::sync::Arc;
 use tracing::info;
@@ -47,6 +48,7 @@ pub struct ApplicationState {
     pub frame_count: u64,
     pub last_fps_update: std::time::Instant,
     pub current_fps: f32,
+    pub frame_pacer: FramePacer,
 }
 
 impl ApplicationState {
@@ -66,6 +68,7 @@ impl ApplicationState {
             frame_count: 0,
             last_fps_update: std::time::Instant::now(),
             current_fps: 0.0,
+            frame_pacer: FramePacer::default(),
         }
     }
     
@@ -133,6 +136,7 @@ impl ApplicationState {
     }
     
     pub fn handle_window_resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
+        self.frame_pacer.request_redraw();
         if let Some(ref mut renderer) = self.renderer {
             // TODO: Implement resize method when WGPU renderer is ready
             // renderer.resize(new_size);
@@ -141,21 +145,25 @@ impl ApplicationState {
     }
     
     pub fn toggle_error_panel(&mut self) {
+        self.frame_pacer.request_redraw();
         self.show_error_panel = !self.show_error_panel;
         info!("Error panel toggled: {}", if self.show_error_panel { "shown" } else { "hidden" });
     }
     
     pub fn toggle_node_library(&mut self) {
+        self.frame_pacer.request_redraw();
         self.show_node_library = !self.show_node_library;
         info!("Node library toggled: {}", if self.show_node_library { "shown" } else { "hidden" });
     }
     
     pub fn toggle_properties_panel(&mut self) {
+        self.frame_pacer.request_redraw();
         self.show_properties_panel = !self.show_properties_panel;
         info!("Properties panel toggled: {}", if self.show_properties_panel { "shown" } else { "hidden" });
     }
     
     pub fn toggle_debug_tools(&mut self) {
+        self.frame_pacer.request_redraw();
         self.show_debug_tools = !self.show_debug_tools;
         if self.show_debug_tools {
             info!("Running comprehensive test suite");
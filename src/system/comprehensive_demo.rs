@@ -2,7 +2,7 @@
 //! Demonstrates the visual integration of all 13 comprehensive nodes
 
 use crate::core::logic::node_registry::NodeRegistry;
-use crate::core::logic::{EvalContext, InputMap, OutputMap};
+use crate::core::logic::{EvalContext, EvalRng, InputMap, NodeStateStore, OutputMap, ProbeChannels};
 use crate::core::registry::MetaRegistry;
 use crate::core::types::{MetaValue, ScopeId, DotPath};
 use crate::core::types::error::MarcoError;
@@ -73,7 +73,7 @@ impl ComprehensiveDemo {
         
         // Connect the pipeline
         self.visual_editor.connect_nodes(db_node, "result", transform_node, "data")?;
-        self.visual_editor.connect_nodes(transform_node, "result", validation_node, "input_value")?;
+        self.visual_editor.connect_nodes(transform_node, "result", validation_node, "value")?;
         self.visual_editor.connect_nodes(validation_node, "is_valid", api_node, "body")?;
         
         info!("Data processing pipeline created!");
@@ -115,7 +115,7 @@ impl ComprehensiveDemo {
         let calc_node = self.visual_editor.add_node("calculator", Vec2::new(450.0, 500.0))?;
         
         // Connect validation workflow
-        self.visual_editor.connect_nodes(string_node, "result", validation_node, "input_value")?;
+        self.visual_editor.connect_nodes(string_node, "result", validation_node, "value")?;
         self.visual_editor.connect_nodes(validation_node, "is_valid", calc_node, "variables")?;
         
         info!("Validation demo created!");
@@ -188,6 +188,12 @@ impl ComprehensiveDemo {
             registry: Arc::new(self.meta_registry.clone()),
             scope_id: self.scope_id.clone(),
             parent: None,
+            time_seconds: None,
+            rng: EvalRng::from_entropy(),
+            probes: ProbeChannels::default(),
+            elapsed_time: 0.0,
+            node_state: NodeStateStore::default(),
+            current_node: None,
         };
         
         // Create a node instance
@@ -318,7 +324,7 @@ mod tests {
     #[test]
     fn test_node_category_filtering() {
         let mut demo = ComprehensiveDemo::new();
-        let math_nodes = demo.test_node_category(NodeCategory::Math).unwrap();
+        let math_nodes = demo.test_node_category(&NodeCategory::Math).unwrap();
         assert!(math_nodes.len() > 0);
         assert!(math_nodes.iter().any(|name| name.contains("Math")));
     }
@@ -0,0 +1,157 @@
+//! Frame pacing for the desktop render loop
+//!
+//! Caps the frame rate by sleeping out the rest of each frame's budget and
+//! optionally skips redraws entirely until something marks the frame dirty.
+use std::time::{Duration, Instant};
+
+/// Portion of the wait spun instead of slept on platforms with coarse sleeps
+#[cfg(windows)]
+const DEFAULT_SPIN_MARGIN: Duration = Duration::from_micros(1500);
+#[cfg(not(windows))]
+const DEFAULT_SPIN_MARGIN: Duration = Duration::ZERO;
+
+/// Frame pacing settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct FramePacingConfig {
+    /// Frames per second to pace to, or None for uncapped
+    pub target_fps: Option<f64>,
+    /// Only redraw when `request_redraw` was called since the last frame
+    pub redraw_on_change_only: bool,
+    /// Final part of each wait that is spun rather than slept for accuracy
+    pub spin_margin: Duration,
+}
+
+impl Default for FramePacingConfig {
+    fn default() -> Self {
+        Self {
+            target_fps: Some(60.0),
+            redraw_on_change_only: false,
+            spin_margin: DEFAULT_SPIN_MARGIN,
+        }
+    }
+}
+
+/// Time left to wait in a frame, zero when already over budget
+pub fn sleep_duration(target_frame_time: Duration, elapsed: Duration) -> Duration {
+    target_frame_time.saturating_sub(elapsed)
+}
+
+/// Sleep/spin limiter driven from the main loop
+#[derive(Debug)]
+pub struct FramePacer {
+    config: FramePacingConfig,
+    frame_start: Instant,
+    needs_redraw: bool,
+}
+
+impl FramePacer {
+    pub fn new(config: FramePacingConfig) -> Self {
+        Self {
+            config,
+            frame_start: Instant::now(),
+            // Always draw the first frame
+            needs_redraw: true,
+        }
+    }
+
+    pub fn config(&self) -> &FramePacingConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: FramePacingConfig) {
+        self.config = config;
+    }
+
+    /// Duration of one frame at the target rate, if capped
+    pub fn target_frame_time(&self) -> Option<Duration> {
+        self.config.target_fps
+            .filter(|fps| *fps > 0.0)
+            .map(|fps| Duration::from_secs_f64(1.0 / fps))
+    }
+
+    /// Mark the scene as changed so the next frame is drawn
+    pub fn request_redraw(&mut self) {
+        self.needs_redraw = true;
+    }
+
+    pub fn needs_redraw(&self) -> bool {
+        self.needs_redraw
+    }
+
+    /// Whether the loop should draw a frame now
+    pub fn should_redraw(&self) -> bool {
+        !self.config.redraw_on_change_only || self.needs_redraw
+    }
+
+    /// Call at the start of a frame
+    pub fn begin_frame(&mut self) {
+        self.frame_start = Instant::now();
+        self.needs_redraw = false;
+    }
+
+    /// Call at the end of a frame; waits out the remaining budget
+    ///
+    /// Returns how long it waited.
+    pub fn end_frame(&mut self) -> Duration {
+        let Some(target) = self.target_frame_time() else {
+            return Duration::ZERO;
+        };
+
+        let wait = sleep_duration(target, self.frame_start.elapsed());
+        if wait.is_zero() {
+            return wait;
+        }
+
+        let deadline = self.frame_start + target;
+        let sleep = wait.saturating_sub(self.config.spin_margin);
+        if !sleep.is_zero() {
+            std::thread::sleep(sleep);
+        }
+        while Instant::now() < deadline {
+            std::hint::spin_loop();
+        }
+        wait
+    }
+}
+
+impl Default for FramePacer {
+    fn default() -> Self {
+        Self::new(FramePacingConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sleep_fills_remaining_budget() {
+        let target = Duration::from_secs_f64(1.0 / 60.0);
+        let elapsed = Duration::from_millis(10);
+        assert_eq!(sleep_duration(target, elapsed), target - elapsed);
+    }
+
+    #[test]
+    fn test_no_sleep_when_over_budget() {
+        let target = Duration::from_millis(16);
+        assert_eq!(sleep_duration(target, Duration::from_millis(16)), Duration::ZERO);
+        assert_eq!(sleep_duration(target, Duration::from_millis(40)), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_redraw_on_change_only() {
+        let mut pacer = FramePacer::new(FramePacingConfig {
+            target_fps: None,
+            redraw_on_change_only: true,
+            ..Default::default()
+        });
+        assert!(pacer.should_redraw());
+
+        pacer.begin_frame();
+        assert_eq!(pacer.end_frame(), Duration::ZERO);
+        assert!(!pacer.should_redraw());
+
+        pacer.request_redraw();
+        assert!(pacer.should_redraw());
+    }
+}
@@ -1,10 +1,12 @@
 //! System-level modules for Marco 2.0
 pub mod app_state;
+pub mod frame_pacer;
 pub mod test_harness;
 pub mod comprehensive_demo;
 pub mod integration_tests;
 
 pub use app_state::*;
+pub use frame_pacer::{FramePacer, FramePacingConfig};
 pub use test_harness::*;
 pub use comprehensive_demo::*;
 pub use integration_tests::*;
@@ -69,6 +69,7 @@ impl Marco2App {
                 nodes_skipped: 0,
                 nodes_failed: 0,
                 execution_order: Vec::new(),
+                per_node_times: std::collections::HashMap::new(),
             },
         }
     }
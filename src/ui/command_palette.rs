@@ -0,0 +1,208 @@
+//! Command Palette
+//!
+//! Keyboard-driven access to IDE actions. Commands are looked up by id or
+//! filtered by a fuzzy query whose characters must appear in the title in
+//! order; matches at word starts and in runs rank higher.
+use crate::core::logic::node_registry::NodeRegistry;
+use crate::ui::integrated_ide::{IDEMode, IntegratedIDE};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// What a command does when executed
+pub type CommandAction = Arc<dyn Fn(&mut IntegratedIDE) + Send + Sync>;
+
+/// Bonus for a query character matching the first letter of a word
+const WORD_START_BONUS: i32 = 8;
+/// Bonus for a query character directly following the previous match
+const CONSECUTIVE_BONUS: i32 = 4;
+/// Penalty per title character skipped between two matches
+const GAP_PENALTY: i32 = 1;
+
+#[derive(Clone)]
+pub struct Command {
+    pub id: String,
+    pub title: String,
+    pub action: CommandAction,
+}
+
+impl Command {
+    pub fn new(id: &str, title: &str, action: impl Fn(&mut IntegratedIDE) + Send + Sync + 'static) -> Self {
+        Self {
+            id: id.to_string(),
+            title: title.to_string(),
+            action: Arc::new(action),
+        }
+    }
+}
+
+impl std::fmt::Debug for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Command")
+            .field("id", &self.id)
+            .field("title", &self.title)
+            .finish()
+    }
+}
+
+/// Registry of commands offered by the palette
+#[derive(Debug, Clone, Default)]
+pub struct CommandPalette {
+    commands: Vec<Command>,
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Palette with the IDE's built-in commands and a create command for
+    /// every node type in `node_registry`
+    pub fn with_builtin_commands(node_registry: &NodeRegistry) -> Self {
+        let mut palette = Self::new();
+
+        palette.register(Command::new("mode.node_editor", "Switch to Node Editor", |ide| ide.set_mode(IDEMode::NodeEditor)));
+        palette.register(Command::new("mode.template_design", "Switch to Template Design", |ide| ide.set_mode(IDEMode::TemplateDesign)));
+        palette.register(Command::new("mode.code_editor", "Switch to Code Editor", |ide| ide.set_mode(IDEMode::CodeEditor)));
+        palette.register(Command::new("mode.preview", "Switch to Preview", |ide| ide.set_mode(IDEMode::Preview)));
+
+        palette.register(Command::new("panel.sidebar", "Toggle Sidebar", |ide| ide.toggle_sidebar()));
+        palette.register(Command::new("panel.bottom", "Toggle Bottom Panel", |ide| ide.toggle_bottom_panel()));
+        palette.register(Command::new("panel.template_gallery", "Toggle Template Gallery", |ide| ide.open_template_gallery()));
+
+        palette.register(Command::new("graph.run", "Run Graph", |ide| {
+            match ide.node_editor.evaluate_graph() {
+                Ok(results) => {
+                    info!("Evaluated {} nodes", results.len());
+                    ide.debug_overlay.set_node_values(results);
+                }
+                Err(e) => {
+                    error!("Graph evaluation failed: {}", e);
+                    ide.debug_overlay.set_node_values(HashMap::new());
+                }
+            }
+        }));
+        palette.register(Command::new("debug.values", "Toggle Live Values", |ide| {
+            ide.debug_overlay.show_values = !ide.debug_overlay.show_values;
+        }));
+
+        let mut node_types = node_registry.list_available_nodes();
+        node_types.sort();
+        for node_type in node_types {
+            let title = node_registry.specification(&node_type)
+                .map(|spec| spec.title)
+                .unwrap_or_else(|| node_type.clone());
+            let created = node_type.clone();
+            palette.register(Command::new(&format!("node.create.{}", node_type), &format!("Create Node: {}", title), move |ide| {
+                if let Err(e) = ide.create_node_at_view_center(&created) {
+                    error!("Could not create '{}' node: {}", created, e);
+                }
+            }));
+        }
+
+        palette
+    }
+
+    /// Add a command, replacing any command with the same id
+    pub fn register(&mut self, command: Command) {
+        self.commands.retain(|existing| existing.id != command.id);
+        self.commands.push(command);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Command> {
+        self.commands.iter().find(|command| command.id == id)
+    }
+
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Commands whose title contains the query's characters in order, best
+    /// match first
+    ///
+    /// Whitespace in the query is ignored and case does not matter. Ties
+    /// go to the shorter title. An empty query matches every command.
+    pub fn fuzzy_filter(&self, query: &str) -> Vec<&Command> {
+        let mut matches: Vec<(i32, &Command)> = self.commands.iter()
+            .filter_map(|command| fuzzy_score(query, &command.title).map(|score| (score, command)))
+            .collect();
+        matches.sort_by(|(a_score, a), (b_score, b)| {
+            b_score.cmp(a_score)
+                .then(a.title.len().cmp(&b.title.len()))
+                .then(a.title.cmp(&b.title))
+        });
+        matches.into_iter().map(|(_, command)| command).collect()
+    }
+}
+
+/// Subsequence match score of `query` against `text`, None if some query
+/// character is missing
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let index = (next..text.len()).find(|&i| text[i] == wanted)?;
+        score += 1;
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += WORD_START_BONUS;
+        }
+        match previous {
+            Some(p) if p + 1 == index => score += CONSECUTIVE_BONUS,
+            Some(p) => score -= (index - p - 1) as i32 * GAP_PENALTY,
+            None => {}
+        }
+        previous = Some(index);
+        next = index + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titled(id: &str, title: &str) -> Command {
+        Command::new(id, title, |_| {})
+    }
+
+    #[test]
+    fn test_fuzzy_filter_ranks_word_start_subsequences_first() {
+        let mut palette = CommandPalette::new();
+        palette.register(titled("json", "Copy Node Properties Json"));
+        palette.register(titled("open", "Open Project"));
+        palette.register(titled("gallery", "Open Template Gallery"));
+        palette.register(titled("sidebar", "Toggle Sidebar"));
+
+        let ids: Vec<&str> = palette.fuzzy_filter("opn prj").iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["open", "json"]);
+        assert_eq!(palette.fuzzy_filter("OTG")[0].id, "gallery");
+        assert!(palette.fuzzy_filter("xyz").is_empty());
+        assert_eq!(palette.fuzzy_filter("").len(), 4);
+    }
+
+    #[test]
+    fn test_builtin_commands_drive_the_ide() {
+        let mut ide = IntegratedIDE::new();
+        assert!(ide.command_palette.get("node.create.math").is_some());
+
+        ide.execute_command("mode.preview").unwrap();
+        assert_eq!(ide.mode, IDEMode::Preview);
+
+        ide.execute_command("panel.sidebar").unwrap();
+        assert!(ide.sidebar_collapsed);
+
+        ide.execute_command("node.create.math").unwrap();
+        assert_eq!(ide.node_editor.nodes.len(), 1);
+        assert_eq!(ide.node_editor.nodes.values().next().unwrap().node_type, "math");
+
+        ide.execute_command("debug.values").unwrap();
+        assert!(ide.debug_overlay.show_values);
+        ide.execute_command("graph.run").unwrap();
+        assert_eq!(ide.debug_overlay.node_values().len(), 1);
+
+        assert!(ide.execute_command("no.such.command").is_err());
+    }
+}
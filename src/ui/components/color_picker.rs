@@ -0,0 +1,196 @@
+//! Color Picker
+//!
+//! Edits a `ColorRGBA` through RGB or HSV sliders and a hex field. Every
+//! edit returns the updated color as a `MetaValue::Color`, ready to store
+//! on a node or GUI element property.
+use crate::core::types::error::MarcoError;
+use crate::core::types::{ColorRGBA, MetaValue};
+
+/// Which set of sliders the picker shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorPickerMode {
+    Rgb,
+    Hsv,
+}
+
+/// A slider of the picker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChannel {
+    Red,
+    Green,
+    Blue,
+    /// Degrees, 0 to 360
+    Hue,
+    Saturation,
+    Value,
+    Alpha,
+}
+
+impl ColorChannel {
+    /// Largest slider value; every channel starts at 0
+    pub fn max(&self) -> f32 {
+        match self {
+            ColorChannel::Hue => 360.0,
+            _ => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ColorPicker {
+    color: ColorRGBA,
+    mode: ColorPickerMode,
+    /// HSV kept separately so hue and saturation survive passing through
+    /// grays and black, where RGB no longer determines them
+    hsv: (f32, f32, f32),
+    /// Text in the hex field, which may be mid-edit and unparsable
+    hex_input: String,
+}
+
+impl ColorPicker {
+    pub fn new(color: ColorRGBA) -> Self {
+        Self {
+            hsv: color.to_hsv(),
+            hex_input: color.to_hex(),
+            color,
+            mode: ColorPickerMode::Rgb,
+        }
+    }
+
+    /// Picker for a color property value; None if `value` is not a color
+    pub fn from_value(value: &MetaValue) -> Option<Self> {
+        match value {
+            MetaValue::Color(color) => Some(Self::new(color.clone())),
+            _ => None,
+        }
+    }
+
+    pub fn color(&self) -> &ColorRGBA {
+        &self.color
+    }
+
+    pub fn value(&self) -> MetaValue {
+        MetaValue::Color(self.color.clone())
+    }
+
+    pub fn mode(&self) -> ColorPickerMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: ColorPickerMode) {
+        self.mode = mode;
+    }
+
+    /// Sliders for the current mode with their values, alpha last
+    pub fn sliders(&self) -> Vec<(ColorChannel, f32)> {
+        let (h, s, v) = self.hsv;
+        match self.mode {
+            ColorPickerMode::Rgb => vec![
+                (ColorChannel::Red, self.color.r),
+                (ColorChannel::Green, self.color.g),
+                (ColorChannel::Blue, self.color.b),
+                (ColorChannel::Alpha, self.color.a),
+            ],
+            ColorPickerMode::Hsv => vec![
+                (ColorChannel::Hue, h),
+                (ColorChannel::Saturation, s),
+                (ColorChannel::Value, v),
+                (ColorChannel::Alpha, self.color.a),
+            ],
+        }
+    }
+
+    /// Move one slider, clamped to its range, and return the new color
+    ///
+    /// Any channel can be set regardless of the mode shown.
+    pub fn set_channel(&mut self, channel: ColorChannel, value: f32) -> MetaValue {
+        let value = if value.is_finite() { value.clamp(0.0, channel.max()) } else { 0.0 };
+        let ColorRGBA { r, g, b, a } = self.color;
+        let (h, s, v) = self.hsv;
+        match channel {
+            ColorChannel::Red => self.set_rgb(ColorRGBA::new(value, g, b, a)),
+            ColorChannel::Green => self.set_rgb(ColorRGBA::new(r, value, b, a)),
+            ColorChannel::Blue => self.set_rgb(ColorRGBA::new(r, g, value, a)),
+            ColorChannel::Alpha => self.color.a = value,
+            ColorChannel::Hue => self.set_hsv((value % 360.0, s, v)),
+            ColorChannel::Saturation => self.set_hsv((h, value, v)),
+            ColorChannel::Value => self.set_hsv((h, s, value)),
+        }
+        self.hex_input = self.color.to_hex();
+        self.value()
+    }
+
+    pub fn hex_input(&self) -> &str {
+        &self.hex_input
+    }
+
+    /// Take text typed into the hex field
+    ///
+    /// The field keeps the text even when it does not parse, so the user
+    /// can finish typing; the color only changes once it does.
+    pub fn set_hex(&mut self, hex: &str) -> Result<MetaValue, MarcoError> {
+        self.hex_input = hex.to_string();
+        let color = ColorRGBA::from_hex(hex)?;
+        self.set_rgb(color);
+        Ok(self.value())
+    }
+
+    fn set_rgb(&mut self, color: ColorRGBA) {
+        let (h, s, v) = color.to_hsv();
+        // Keep the old hue and saturation where the new color has none
+        let h = if s <= f32::EPSILON || v <= f32::EPSILON { self.hsv.0 } else { h };
+        let s = if v <= f32::EPSILON { self.hsv.1 } else { s };
+        self.hsv = (h, s, v);
+        self.color = color;
+    }
+
+    fn set_hsv(&mut self, hsv: (f32, f32, f32)) {
+        let (h, s, v) = hsv;
+        self.color = ColorRGBA::from_hsv(h, s, v, self.color.a);
+        self.hsv = hsv;
+    }
+}
+
+impl Default for ColorPicker {
+    fn default() -> Self {
+        Self::new(ColorRGBA::white())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: &ColorRGBA, b: &ColorRGBA) -> bool {
+        [a.r - b.r, a.g - b.g, a.b - b.b, a.a - b.a].iter().all(|d| d.abs() < 1e-5)
+    }
+
+    #[test]
+    fn test_edits_emit_colors_and_keep_hue_through_black() {
+        let mut picker = ColorPicker::new(ColorRGBA::red());
+        picker.set_mode(ColorPickerMode::Hsv);
+
+        let MetaValue::Color(color) = picker.set_channel(ColorChannel::Hue, 120.0) else {
+            panic!("expected a color");
+        };
+        assert!(close(&color, &ColorRGBA::green()));
+        assert_eq!(picker.hex_input(), "#00FF00");
+
+        // Dropping the value to black and back keeps the chosen hue
+        picker.set_channel(ColorChannel::Value, 0.0);
+        picker.set_channel(ColorChannel::Value, 1.0);
+        assert!(close(picker.color(), &ColorRGBA::green()));
+        assert_eq!(picker.sliders()[0], (ColorChannel::Hue, 120.0));
+
+        picker.set_channel(ColorChannel::Alpha, 2.0);
+        assert_eq!(picker.color().a, 1.0);
+
+        // A half-typed hex leaves the color alone
+        assert!(picker.set_hex("#12").is_err());
+        assert_eq!(picker.hex_input(), "#12");
+        assert!(close(picker.color(), &ColorRGBA::green()));
+        assert_eq!(picker.set_hex("#0000FF80").unwrap(), MetaValue::Color(ColorRGBA::from_u8(0, 0, 255, 128)));
+        picker.set_mode(ColorPickerMode::Rgb);
+        assert_eq!(picker.sliders()[2], (ColorChannel::Blue, 1.0));
+    }
+}
@@ -1,7 +1,14 @@
 //! UI Components - LEGACY MODULE
 //! 
 //! This module contained legacy egui-based components that have been
-//! replaced by the new WGPU-based rendering system.
+//! replaced by the new WGPU-based rendering system. New components built
+//! on plain data live in submodules.
+
+pub mod color_picker;
+pub mod theme_editor;
+
+pub use color_picker::{ColorChannel, ColorPicker, ColorPickerMode};
+pub use theme_editor::ThemeEditor;
 
 use crate::ui::theme::Marco2Theme;
 
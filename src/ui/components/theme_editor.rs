@@ -0,0 +1,163 @@
+//! Theme Editor
+//!
+//! Edits the colors of a `Marco2Theme` one field at a time through a
+//! `ColorPicker`. Changes reach the node renderer on the next `apply`, so
+//! calling it every frame previews edits live.
+use crate::core::types::error::MarcoError;
+use crate::render::node_renderer::NodeRenderer;
+use crate::ui::components::color_picker::{ColorChannel, ColorPicker};
+use crate::ui::theme::{Color, Marco2Theme};
+use wgpu::Queue;
+
+#[derive(Debug)]
+pub struct ThemeEditor {
+    theme: Marco2Theme,
+    /// Color field being edited, with the picker showing it
+    selected: Option<(&'static str, ColorPicker)>,
+    /// Theme changed since it was last applied to the renderer
+    dirty: bool,
+}
+
+impl ThemeEditor {
+    pub fn new(theme: Marco2Theme) -> Self {
+        Self {
+            theme,
+            selected: None,
+            dirty: true,
+        }
+    }
+
+    pub fn theme(&self) -> &Marco2Theme {
+        &self.theme
+    }
+
+    /// Replace the theme being edited, e.g. with a preset or a loaded file
+    pub fn set_theme(&mut self, theme: Marco2Theme) {
+        self.theme = theme;
+        let field = self.selected.take().map(|(name, _)| name);
+        if let Some(name) = field {
+            self.select_field(name);
+        }
+        self.dirty = true;
+    }
+
+    /// Switch to the built-in theme `name`; see [`Marco2Theme::preset`]
+    pub fn load_preset(&mut self, name: &str) -> Result<(), MarcoError> {
+        let theme = Marco2Theme::preset(name)
+            .ok_or_else(|| MarcoError::InvalidOperation(format!("Unknown theme preset: {}", name)))?;
+        self.set_theme(theme);
+        Ok(())
+    }
+
+    pub fn load_from_str(&mut self, json: &str) -> Result<(), MarcoError> {
+        self.set_theme(Marco2Theme::load_from_str(json)?);
+        Ok(())
+    }
+
+    /// Start editing a color field; false if the theme has no such field
+    pub fn select_field(&mut self, name: &str) -> bool {
+        let Some((name, color)) = self.theme.color_fields().into_iter().find(|(field, _)| *field == name) else {
+            return false;
+        };
+        self.selected = Some((name, ColorPicker::new(color.into())));
+        true
+    }
+
+    pub fn selected_field(&self) -> Option<&'static str> {
+        self.selected.as_ref().map(|(name, _)| *name)
+    }
+
+    /// Picker for the selected field, to read its sliders and hex text
+    pub fn picker(&self) -> Option<&ColorPicker> {
+        self.selected.as_ref().map(|(_, picker)| picker)
+    }
+
+    /// Move a slider of the selected field's picker
+    ///
+    /// Returns false when no field is selected.
+    pub fn set_channel(&mut self, channel: ColorChannel, value: f32) -> bool {
+        let Some((name, picker)) = &mut self.selected else {
+            return false;
+        };
+        picker.set_channel(channel, value);
+        let (name, color) = (*name, picker.color().clone().into());
+        self.set_color(name, color)
+    }
+
+    /// Type into the selected field's hex input
+    ///
+    /// Text that does not parse yet is kept in the picker and leaves the
+    /// theme unchanged.
+    pub fn set_hex(&mut self, hex: &str) -> Result<(), MarcoError> {
+        let Some((name, picker)) = &mut self.selected else {
+            return Err(MarcoError::InvalidOperation("No theme color selected".to_string()));
+        };
+        picker.set_hex(hex)?;
+        let (name, color) = (*name, picker.color().clone().into());
+        self.set_color(name, color);
+        Ok(())
+    }
+
+    /// Set a color field directly; false if the theme has no such field
+    pub fn set_color(&mut self, name: &str, color: Color) -> bool {
+        let Some(field) = self.theme.color_field_mut(name) else {
+            return false;
+        };
+        if *field != color {
+            *field = color;
+            self.dirty = true;
+        }
+        true
+    }
+
+    /// Theme has edits the renderer has not seen
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Push pending edits to the renderer; returns whether anything was sent
+    pub fn apply(&mut self, renderer: &mut NodeRenderer, queue: &Queue) -> bool {
+        if !self.dirty {
+            return false;
+        }
+        renderer.update_theme(queue, &self.theme);
+        self.dirty = false;
+        true
+    }
+}
+
+impl Default for ThemeEditor {
+    fn default() -> Self {
+        Self::new(Marco2Theme::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::ColorRGBA;
+
+    #[test]
+    fn test_picker_edits_update_the_selected_color() {
+        let mut editor = ThemeEditor::new(Marco2Theme::dark());
+        editor.dirty = false;
+        assert!(!editor.set_channel(ColorChannel::Red, 1.0));
+        assert!(!editor.select_field("font_size"));
+
+        assert!(editor.select_field("accent_color"));
+        assert!(editor.set_channel(ColorChannel::Value, 0.0));
+        assert_eq!(editor.theme().accent_color, Color::rgb(0, 0, 0));
+        assert!(editor.is_dirty());
+
+        assert!(editor.set_hex("#12").is_err());
+        editor.set_hex("#336699").unwrap();
+        assert_eq!(editor.theme().accent_color, Color::rgb(0x33, 0x66, 0x99));
+
+        // A preset keeps the selection and reloads the picker from it
+        editor.load_preset("light").unwrap();
+        assert_eq!(editor.selected_field(), Some("accent_color"));
+        let accent: ColorRGBA = Marco2Theme::light().accent_color.into();
+        assert_eq!(editor.picker().unwrap().hex_input(), accent.to_hex());
+        assert!(editor.load_preset("sepia").is_err());
+    }
+}
@@ -6,11 +6,15 @@
 use crate::ui::responsive::{ResponsiveLayout, ScreenSize, LayoutBreakpoint};
 use crate::ui::touch::{TouchHandler, TouchGesture, TouchTool, TouchId};
 use crate::ui::mobile_canvas::{MobileCanvasDesigner, ScreenOrientation};
+use crate::ui::metrics::UiMetrics;
 
 use serde::{Deserialize, Serialize};
 use glam::Vec2;
 use std::time::Instant;
 
+/// Zoom factor applied per scroll wheel line
+pub const WHEEL_ZOOM_STEP: f32 = 1.1;
+
 /// Cross-platform UI manager that orchestrates responsive design and touch input
 #[derive(Debug)]
 pub struct CrossPlatformUI {
@@ -31,6 +35,12 @@ pub struct CrossPlatformUI {
     
     /// Performance metrics
     performance: PerformanceMetrics,
+    
+    /// Telemetry counters and timings
+    metrics: UiMetrics,
+    
+    /// Arrival time of the oldest input not yet reflected in a frame
+    pending_input: Option<Instant>,
 }
 
 /// Platform detection and capabilities
@@ -203,7 +213,7 @@ impl PerformanceMetrics {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum PerformanceLevel {
     High,
     Medium,
@@ -233,6 +243,8 @@ impl CrossPlatformUI {
             platform_info,
             adaptation_config: UIAdaptationConfig::default(),
             performance: PerformanceMetrics::default(),
+            metrics: UiMetrics::default(),
+            pending_input: None,
         }
     }
     
@@ -240,6 +252,10 @@ impl CrossPlatformUI {
     pub fn update(&mut self, delta_time: f32, screen_bounds: Vec2) {
         // Update performance metrics
         self.performance.update(delta_time * 1000.0);
+        self.metrics.record_frame(delta_time * 1000.0);
+        if let Some(input_time) = self.pending_input.take() {
+            self.metrics.record_input_latency(input_time.elapsed().as_secs_f32() * 1000.0);
+        }
         
         // Update responsive layout
         self.layout.update_screen_bounds(screen_bounds);
@@ -247,6 +263,9 @@ impl CrossPlatformUI {
         // Update mobile systems if active
         if let Some(ref mut mobile_canvas) = self.mobile_canvas {
             mobile_canvas.update(delta_time, screen_bounds);
+            for kind in mobile_canvas.take_recognized_gestures() {
+                self.metrics.record_gesture(kind);
+            }
         }
         
         if let Some(ref mut touch_handler) = self.touch_handler {
@@ -263,6 +282,7 @@ impl CrossPlatformUI {
     pub fn handle_touch_down(&mut self, id: TouchId, position: Vec2, pressure: f32) -> bool {
         if let Some(ref mut mobile_canvas) = self.mobile_canvas {
             mobile_canvas.handle_touch_down(id, position, pressure);
+            self.record_touch();
             return true;
         }
         false
@@ -271,6 +291,7 @@ impl CrossPlatformUI {
     pub fn handle_touch_move(&mut self, id: TouchId, position: Vec2, pressure: f32) -> bool {
         if let Some(ref mut mobile_canvas) = self.mobile_canvas {
             mobile_canvas.handle_touch_move(id, position, pressure);
+            self.record_touch();
             return true;
         }
         false
@@ -279,11 +300,17 @@ impl CrossPlatformUI {
     pub fn handle_touch_up(&mut self, id: TouchId) -> bool {
         if let Some(ref mut mobile_canvas) = self.mobile_canvas {
             mobile_canvas.handle_touch_up(id);
+            self.record_touch();
             return true;
         }
         false
     }
     
+    fn record_touch(&mut self) {
+        self.metrics.record_touch();
+        self.pending_input.get_or_insert_with(Instant::now);
+    }
+    
     /// Handle mouse events for desktop platforms
     pub fn handle_mouse_down(&mut self, position: Vec2, button: MouseButton) -> bool {
         // If we have touch capabilities, treat mouse as single touch
@@ -292,6 +319,7 @@ impl CrossPlatformUI {
         }
         
         // Handle traditional mouse input
+        self.record_mouse_event();
         self.handle_desktop_mouse_down(position, button)
     }
     
@@ -300,6 +328,7 @@ impl CrossPlatformUI {
             return self.handle_touch_move(TouchId(0), position, 1.0);
         }
         
+        self.record_mouse_event();
         self.handle_desktop_mouse_move(position)
     }
     
@@ -308,9 +337,27 @@ impl CrossPlatformUI {
             return self.handle_touch_up(TouchId(0));
         }
         
+        self.record_mouse_event();
         self.handle_desktop_mouse_up(position, button)
     }
     
+    /// Handle scroll wheel input, in lines; positive zooms in around `position`
+    pub fn handle_mouse_wheel(&mut self, position: Vec2, lines: f32) -> bool {
+        self.record_mouse_event();
+        match self.mobile_canvas {
+            Some(ref mut mobile_canvas) if lines != 0.0 => {
+                mobile_canvas.zoom_at(position, WHEEL_ZOOM_STEP.powf(lines));
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    fn record_mouse_event(&mut self) {
+        self.metrics.record_mouse_event();
+        self.pending_input.get_or_insert_with(Instant::now);
+    }
+    
     /// Desktop mouse handling (placeholder)
     fn handle_desktop_mouse_down(&mut self, _position: Vec2, _button: MouseButton) -> bool {
         // TODO: Implement desktop mouse handling
@@ -377,6 +424,15 @@ impl CrossPlatformUI {
         &self.performance
     }
     
+    /// Telemetry collected by the UI pipeline
+    pub fn metrics(&self) -> &UiMetrics {
+        &self.metrics
+    }
+    
+    pub fn reset_metrics(&mut self) {
+        self.metrics.reset();
+    }
+    
     /// Get mobile canvas designer (if active)
     pub fn mobile_canvas(&self) -> Option<&MobileCanvasDesigner> {
         self.mobile_canvas.as_ref()
@@ -387,6 +443,10 @@ impl CrossPlatformUI {
         &self.layout
     }
     
+    pub fn adaptation_config(&self) -> &UIAdaptationConfig {
+        &self.adaptation_config
+    }
+    
     /// Update adaptation configuration
     pub fn set_adaptation_config(&mut self, config: UIAdaptationConfig) {
         let force_mobile = config.force_mobile_mode;
@@ -431,7 +491,7 @@ pub enum MouseButton {
 }
 
 /// UI rendering configuration derived from platform and performance
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct UIRenderConfig {
     pub screen_size: ScreenSize,
     pub breakpoint: LayoutBreakpoint,
@@ -478,3 +538,42 @@ impl UIRenderConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_count_frames_and_touches() {
+        let bounds = Vec2::new(390.0, 844.0);
+        let mut ui = CrossPlatformUI::new(bounds, true);
+
+        assert!(ui.handle_touch_down(TouchId(1), Vec2::new(100.0, 100.0), 1.0));
+        assert!(ui.handle_touch_move(TouchId(1), Vec2::new(110.0, 100.0), 1.0));
+        assert!(ui.handle_touch_up(TouchId(1)));
+        for _ in 0..3 {
+            ui.update(1.0 / 60.0, bounds);
+        }
+        ui.update(0.05, bounds);
+
+        let metrics = ui.metrics();
+        assert_eq!(metrics.frames_rendered, 4);
+        assert_eq!(metrics.frames_dropped, 1);
+        assert_eq!(metrics.touches_handled, 3);
+        assert_eq!(metrics.mouse_events_handled, 0);
+        assert_eq!(metrics.input_latency.count(), 1);
+    }
+
+    #[test]
+    fn test_wheel_zooms_mobile_canvas() {
+        let bounds = Vec2::new(390.0, 844.0);
+        let mut ui = CrossPlatformUI::new(bounds, true);
+        let center = bounds / 2.0;
+
+        assert!(ui.handle_mouse_wheel(center, 2.0));
+        let zoom = ui.mobile_canvas.as_ref().unwrap().viewport().zoom;
+        assert!((zoom - WHEEL_ZOOM_STEP * WHEEL_ZOOM_STEP).abs() < 1e-5);
+        assert!(!ui.handle_mouse_wheel(center, 0.0));
+        assert_eq!(ui.metrics().mouse_events_handled, 2);
+    }
+}
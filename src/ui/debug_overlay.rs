@@ -1,15 +1,105 @@
 //! Debug Overlay (WGPU Migration Stub)
-//! 
+//!
 //! Minimal stub for the debug overlay to enable compilation during WGPU migration.
+//!
+//! With `show_values` on, the overlay also labels each node's output ports
+//! with the values from the last graph evaluation. Nodes without results,
+//! because evaluation failed or never reached them, get a red "!" instead.
+//! Registry watches added to the overlay are plotted over time.
 
 use tracing::info;
+use glam::Vec2;
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::core::logic::OutputMap;
+use crate::core::registry::WatchHandle;
+use crate::core::types::{DotPath, MetaValue};
 use crate::graph::runtime::GraphExecutionStats;
+use crate::ui::visual_node_editor::{SocketKind, VisualNode};
+
+/// Gap between an output socket and its value label, in canvas units
+const VALUE_LABEL_OFFSET: f32 = 12.0;
+
+/// Longest string value shown before it is cut with an ellipsis
+const MAX_STRING_CHARS: usize = 24;
+
+/// Color of the badge on nodes without results
+const ERROR_BADGE_COLOR: [f32; 4] = [0.9, 0.2, 0.2, 1.0];
+
+/// How one output value is drawn
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueDisplay {
+    Text(String),
+    /// Drawn as a check mark or a cross
+    Check(bool),
+    /// A small square filled with the color, RGBA
+    Swatch([f32; 4]),
+    /// Red "!" for a node or port without a value
+    Error,
+}
+
+impl ValueDisplay {
+    /// Display for a single output value
+    pub fn from_value(value: &MetaValue) -> Self {
+        match value {
+            MetaValue::Scalar(v) => Self::Text(format!("{:.3}", v)),
+            MetaValue::Bool(b) => Self::Check(*b),
+            MetaValue::Color(c) => Self::Swatch([c.r, c.g, c.b, c.a]),
+            MetaValue::String(s) if s.chars().count() > MAX_STRING_CHARS => {
+                Self::Text(format!("\"{}\u{2026}\"", s.chars().take(MAX_STRING_CHARS).collect::<String>()))
+            }
+            MetaValue::String(s) => Self::Text(format!("\"{}\"", s)),
+            MetaValue::List(items) => Self::Text(format!("[{} items]", items.len())),
+            MetaValue::Object(fields) => Self::Text(format!("{{{} fields}}", fields.len())),
+            MetaValue::Image { width, height, .. } => Self::Text(format!("{}x{} image", width, height)),
+            MetaValue::Vec2(v) => Self::Text(format!("({:.3}, {:.3})", v.x, v.y)),
+            MetaValue::Vec3(v) => Self::Text(format!("({:.3}, {:.3}, {:.3})", v.x, v.y, v.z)),
+        }
+    }
+
+    /// Plain-text form: check marks, a swatch glyph, or "!"
+    pub fn text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Check(true) => "\u{2713}".to_string(),
+            Self::Check(false) => "\u{2717}".to_string(),
+            Self::Swatch(_) => "\u{25A0}".to_string(),
+            Self::Error => "!".to_string(),
+        }
+    }
+
+    /// Color to draw `text` in: the swatch's own color, red for errors,
+    /// `text_color` otherwise
+    pub fn color(&self, text_color: [f32; 4]) -> [f32; 4] {
+        match self {
+            Self::Swatch(color) => *color,
+            Self::Error => ERROR_BADGE_COLOR,
+            Self::Text(_) | Self::Check(_) => text_color,
+        }
+    }
+}
+
+/// A value drawn next to a node
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueLabel {
+    pub node_id: Uuid,
+    /// Output port the value belongs to; None for a node-wide error badge
+    pub port: Option<String>,
+    /// Canvas position of the label's left edge, vertically centered
+    pub position: Vec2,
+    pub display: ValueDisplay,
+}
 
 /// Debug overlay for performance monitoring
 #[derive(Debug)]
 pub struct DebugOverlay {
     visible: bool,
     stats: GraphExecutionStats,
+    /// Label output ports with their last evaluated values
+    pub show_values: bool,
+    node_values: HashMap<Uuid, OutputMap>,
+    /// Registry paths plotted over time
+    watches: Vec<WatchHandle>,
 }
 
 impl DebugOverlay {
@@ -23,25 +113,107 @@ impl DebugOverlay {
                 nodes_skipped: 0,
                 nodes_failed: 0,
                 execution_order: Vec::new(),
+                per_node_times: std::collections::HashMap::new(),
             },
+            show_values: false,
+            node_values: HashMap::new(),
+            watches: Vec::new(),
         }
     }
-    
+
     pub fn set_visible(&mut self, visible: bool) {
         self.visible = visible;
     }
-    
+
     pub fn is_visible(&self) -> bool {
         self.visible
     }
-    
+
     pub fn update_stats(&mut self, stats: GraphExecutionStats) {
         self.stats = stats;
     }
-    
+
+    /// Replace the values shown with the results of an evaluation
+    pub fn set_node_values(&mut self, values: HashMap<Uuid, OutputMap>) {
+        self.node_values = values;
+    }
+
+    pub fn node_values(&self) -> &HashMap<Uuid, OutputMap> {
+        &self.node_values
+    }
+
+    /// Value labels for `nodes`, empty unless `show_values` is on
+    ///
+    /// Each output port gets a label just right of its socket. A node with
+    /// no results gets one error badge at its top-right corner instead, and
+    /// a port missing from its node's results gets an error label.
+    pub fn value_labels(&self, nodes: &HashMap<Uuid, VisualNode>) -> Vec<ValueLabel> {
+        if !self.show_values {
+            return Vec::new();
+        }
+
+        let mut labels = Vec::new();
+        for node in nodes.values() {
+            let Some(outputs) = self.node_values.get(&node.id) else {
+                labels.push(ValueLabel {
+                    node_id: node.id,
+                    port: None,
+                    position: node.position + Vec2::new(node.size.x / 2.0 + VALUE_LABEL_OFFSET, -node.size.y / 2.0),
+                    display: ValueDisplay::Error,
+                });
+                continue;
+            };
+            for name in node.outputs.keys() {
+                let Some(socket) = node.socket_position(SocketKind::Output, name) else {
+                    continue;
+                };
+                labels.push(ValueLabel {
+                    node_id: node.id,
+                    port: Some(name.clone()),
+                    position: socket + Vec2::new(VALUE_LABEL_OFFSET, 0.0),
+                    display: outputs.get(name).map_or(ValueDisplay::Error, ValueDisplay::from_value),
+                });
+            }
+        }
+        labels
+    }
+
+    /// Plot the values recorded by `watch`
+    pub fn add_watch(&mut self, watch: WatchHandle) {
+        self.watches.push(watch);
+    }
+
+    /// Stop plotting `path`, handing its watch back
+    pub fn remove_watch(&mut self, path: &DotPath) -> Option<WatchHandle> {
+        let index = self.watches.iter().position(|watch| watch.path() == path)?;
+        Some(self.watches.remove(index))
+    }
+
+    /// Plot points per watched path: seconds since the oldest sample
+    /// against the value
+    ///
+    /// Only scalar samples are plotted; other values leave gaps.
+    pub fn plot_series(&mut self) -> Vec<(DotPath, Vec<Vec2>)> {
+        self.watches.iter_mut()
+            .map(|watch| {
+                let path = watch.path().clone();
+                let history = watch.history();
+                let points = history.first().map_or_else(Vec::new, |(start, _)| {
+                    history.iter()
+                        .filter_map(|(at, value)| {
+                            let seconds = at.duration_since(*start).as_secs_f32();
+                            value.as_scalar().map(|v| Vec2::new(seconds, v as f32))
+                        })
+                        .collect()
+                });
+                (path, points)
+            })
+            .collect()
+    }
+
     pub fn render(&mut self) {
         if self.visible {
-            info!("DebugOverlay render called - {} nodes executed in {:.2}ms", 
+            info!("DebugOverlay render called - {} nodes executed in {:.2}ms",
                   self.stats.nodes_executed, self.stats.total_execution_time_ms);
         }
     }
@@ -52,3 +224,77 @@ impl Default for DebugOverlay {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::ColorRGBA;
+    use crate::ui::visual_node_editor::VisualNodeEditor;
+
+    #[test]
+    fn test_values_format_per_type() {
+        assert_eq!(ValueDisplay::from_value(&MetaValue::Scalar(2.0 / 3.0)), ValueDisplay::Text("0.667".to_string()));
+        assert_eq!(ValueDisplay::from_value(&MetaValue::Bool(true)).text(), "\u{2713}");
+        assert_eq!(ValueDisplay::from_value(&MetaValue::Bool(false)).text(), "\u{2717}");
+        assert_eq!(
+            ValueDisplay::from_value(&MetaValue::Color(ColorRGBA::new(1.0, 0.5, 0.0, 1.0))),
+            ValueDisplay::Swatch([1.0, 0.5, 0.0, 1.0]),
+        );
+        assert_eq!(ValueDisplay::from_value(&MetaValue::Vec2(Vec2::new(1.0, 0.25))).text(), "(1.000, 0.250)");
+        assert_eq!(ValueDisplay::from_value(&MetaValue::List(vec![MetaValue::Bool(true); 3])).text(), "[3 items]");
+    }
+
+    #[test]
+    fn test_evaluated_outputs_label_ports_and_missing_nodes_show_errors() {
+        let mut editor = VisualNodeEditor::new();
+        let evaluated = editor.add_node("clamp", Vec2::ZERO).unwrap();
+        let missing = editor.add_node("clamp", Vec2::new(400.0, 0.0)).unwrap();
+
+        let mut overlay = DebugOverlay::new();
+        let outputs: OutputMap = [("result".to_string(), MetaValue::Scalar(1.5))].into_iter().collect();
+        overlay.set_node_values([(evaluated, outputs)].into_iter().collect());
+        let nodes = editor.rendered_nodes();
+        assert!(overlay.value_labels(&nodes).is_empty());
+
+        overlay.show_values = true;
+        let labels = overlay.value_labels(&nodes);
+        let node = &nodes[&evaluated];
+        let result: Vec<&ValueLabel> = labels.iter().filter(|label| label.node_id == evaluated).collect();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].port.as_deref(), Some("result"));
+        assert_eq!(result[0].display.text(), "1.500");
+        let socket = node.socket_position(SocketKind::Output, "result").unwrap();
+        assert_eq!(result[0].position, socket + Vec2::new(VALUE_LABEL_OFFSET, 0.0));
+
+        let badge: Vec<&ValueLabel> = labels.iter().filter(|label| label.node_id == missing).collect();
+        assert_eq!(badge.len(), 1);
+        assert_eq!(badge[0].port, None);
+        assert_eq!(badge[0].display.text(), "!");
+        assert_eq!(badge[0].display.color([1.0; 4]), ERROR_BADGE_COLOR);
+    }
+
+    #[test]
+    fn test_watched_scalars_are_plotted_in_order() {
+        let mut registry = crate::core::registry::MetaRegistry::new();
+        let scope = registry.create_scope();
+        let path = DotPath::from("anim.t");
+        let mut overlay = DebugOverlay::new();
+        overlay.add_watch(registry.watch(path.clone()));
+
+        for t in [0.0, 0.5, 1.0] {
+            registry.set_scoped(&scope, &path, MetaValue::Scalar(t)).unwrap();
+        }
+        registry.set_scoped(&scope, &path, MetaValue::String("done".to_string())).unwrap();
+
+        let series = overlay.plot_series();
+        assert_eq!(series.len(), 1);
+        let (plotted, points) = &series[0];
+        assert_eq!(plotted, &path);
+        assert_eq!(points.iter().map(|p| p.y).collect::<Vec<_>>(), vec![0.0, 0.5, 1.0]);
+        assert_eq!(points[0].x, 0.0);
+        assert!(points.windows(2).all(|pair| pair[0].x <= pair[1].x));
+
+        assert!(overlay.remove_watch(&path).is_some());
+        assert!(overlay.plot_series().is_empty());
+    }
+}
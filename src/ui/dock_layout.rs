@@ -0,0 +1,352 @@
+//! Dock Layout
+//!
+//! Divides the IDE window into regions separated by draggable dividers.
+//! Every region is either a leaf hosting one panel or a split sharing its
+//! area between two child regions. Layouts serialize, so a user's
+//! arrangement can be saved and restored.
+use crate::core::types::error::MarcoError;
+use crate::render::connection_routing::Rect;
+use crate::ui::responsive::PanelId;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Identifies a region within one `DockLayout`
+pub type RegionId = u32;
+
+/// Smallest share of a split either child can be given
+const MIN_RATIO: f32 = 0.05;
+
+/// Width of the grab area centered on a divider, in pixels
+pub const DIVIDER_GRAB_WIDTH: f32 = 8.0;
+
+/// Direction a split lays out its children
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Orientation {
+    /// Side by side, first child on the left, divided by a vertical line
+    Horizontal,
+    /// Stacked, first child on top, divided by a horizontal line
+    Vertical,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DockRegion {
+    /// A leaf showing one panel, or nothing yet
+    Panel(Option<PanelId>),
+    /// `ratio` is the first child's share of the region
+    Split {
+        orientation: Orientation,
+        ratio: f32,
+        first: RegionId,
+        second: RegionId,
+    },
+}
+
+/// Tree of splits and panels filling the window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockLayout {
+    root: RegionId,
+    regions: HashMap<RegionId, DockRegion>,
+    next_id: RegionId,
+    /// Split whose divider is being dragged
+    #[serde(skip)]
+    dragging: Option<RegionId>,
+}
+
+impl Default for DockLayout {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl DockLayout {
+    /// A layout with one region showing `panel`
+    pub fn new(panel: Option<PanelId>) -> Self {
+        Self {
+            root: 0,
+            regions: HashMap::from([(0, DockRegion::Panel(panel))]),
+            next_id: 1,
+            dragging: None,
+        }
+    }
+
+    /// The IDE's standard arrangement: node library on the left, the canvas
+    /// with the properties panel below it on the right
+    pub fn standard() -> Self {
+        let mut layout = Self::new(Some(PanelId::NodeLibrary));
+        let (_, main) = layout.split_region(layout.root, Orientation::Horizontal, 0.25)
+            .expect("a new layout's root is a leaf");
+        let (canvas, properties) = layout.split_region(main, Orientation::Vertical, 0.75)
+            .expect("a new region is a leaf");
+        layout.regions.insert(canvas, DockRegion::Panel(Some(PanelId::NodeCanvas)));
+        layout.regions.insert(properties, DockRegion::Panel(Some(PanelId::Properties)));
+        layout
+    }
+
+    pub fn root(&self) -> RegionId {
+        self.root
+    }
+
+    pub fn region(&self, region_id: RegionId) -> Option<&DockRegion> {
+        self.regions.get(&region_id)
+    }
+
+    /// Split a leaf in two, returning the ids of its new children
+    ///
+    /// The region's panel moves to the first child and the second starts
+    /// empty; `ratio` is the first child's share, held away from the edges.
+    pub fn split_region(&mut self, region_id: RegionId, orientation: Orientation, ratio: f32) -> Result<(RegionId, RegionId), MarcoError> {
+        let ratio = Self::checked_ratio(ratio)?;
+        let panel = match self.regions.get(&region_id) {
+            Some(DockRegion::Panel(panel)) => *panel,
+            Some(DockRegion::Split { .. }) => {
+                return Err(MarcoError::InvalidOperation(format!("Region {} is already split", region_id)));
+            }
+            None => return Err(MarcoError::InvalidOperation(format!("Unknown dock region: {}", region_id))),
+        };
+
+        let (first, second) = (self.next_id, self.next_id + 1);
+        self.next_id += 2;
+        self.regions.insert(first, DockRegion::Panel(panel));
+        self.regions.insert(second, DockRegion::Panel(None));
+        self.regions.insert(region_id, DockRegion::Split { orientation, ratio, first, second });
+        Ok((first, second))
+    }
+
+    /// Move the divider of a split so its first child gets `ratio`
+    pub fn set_divider(&mut self, region_id: RegionId, ratio: f32) -> Result<(), MarcoError> {
+        let new_ratio = Self::checked_ratio(ratio)?;
+        match self.regions.get_mut(&region_id) {
+            Some(DockRegion::Split { ratio, .. }) => {
+                *ratio = new_ratio;
+                Ok(())
+            }
+            Some(DockRegion::Panel(_)) => {
+                Err(MarcoError::InvalidOperation(format!("Region {} has no divider", region_id)))
+            }
+            None => Err(MarcoError::InvalidOperation(format!("Unknown dock region: {}", region_id))),
+        }
+    }
+
+    /// The children of a split with their shares of it
+    pub fn child_ratios(&self, region_id: RegionId) -> Option<[(RegionId, f32); 2]> {
+        match self.regions.get(&region_id)? {
+            DockRegion::Split { ratio, first, second, .. } => Some([(*first, *ratio), (*second, 1.0 - ratio)]),
+            DockRegion::Panel(_) => None,
+        }
+    }
+
+    /// Show `panel` in a leaf, replacing whatever it showed
+    pub fn set_panel(&mut self, region_id: RegionId, panel: Option<PanelId>) -> Result<(), MarcoError> {
+        match self.regions.get_mut(&region_id) {
+            Some(DockRegion::Panel(current)) => {
+                *current = panel;
+                Ok(())
+            }
+            Some(DockRegion::Split { .. }) => {
+                Err(MarcoError::InvalidOperation(format!("Region {} is split and cannot host a panel", region_id)))
+            }
+            None => Err(MarcoError::InvalidOperation(format!("Unknown dock region: {}", region_id))),
+        }
+    }
+
+    /// Leaf showing `panel`, if any
+    pub fn panel_region(&self, panel: PanelId) -> Option<RegionId> {
+        self.regions.iter()
+            .find(|(_, region)| **region == DockRegion::Panel(Some(panel)))
+            .map(|(id, _)| *id)
+    }
+
+    /// Screen rectangle of every region when the layout fills `bounds`
+    pub fn region_rects(&self, bounds: Rect) -> HashMap<RegionId, Rect> {
+        let mut rects = HashMap::new();
+        let mut pending = vec![(self.root, bounds)];
+        while let Some((region_id, rect)) = pending.pop() {
+            rects.insert(region_id, rect);
+            if let Some(DockRegion::Split { orientation, ratio, first, second }) = self.regions.get(&region_id) {
+                let split = Self::split_point(rect, *orientation, *ratio);
+                let (first_rect, second_rect) = match orientation {
+                    Orientation::Horizontal => (
+                        Rect::new(rect.min, Vec2::new(split, rect.max.y)),
+                        Rect::new(Vec2::new(split, rect.min.y), rect.max),
+                    ),
+                    Orientation::Vertical => (
+                        Rect::new(rect.min, Vec2::new(rect.max.x, split)),
+                        Rect::new(Vec2::new(rect.min.x, split), rect.max),
+                    ),
+                };
+                pending.push((*first, first_rect));
+                pending.push((*second, second_rect));
+            }
+        }
+        rects
+    }
+
+    /// Where each hosted panel is drawn when the layout fills `bounds`
+    pub fn panel_rects(&self, bounds: Rect) -> Vec<(PanelId, Rect)> {
+        self.region_rects(bounds).into_iter()
+            .filter_map(|(region_id, rect)| match self.regions.get(&region_id) {
+                Some(DockRegion::Panel(Some(panel))) => Some((*panel, rect)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Split whose divider grab area contains `position`
+    ///
+    /// Where a nested divider meets its parent's, the nested one wins.
+    pub fn divider_at(&self, bounds: Rect, position: Vec2) -> Option<RegionId> {
+        let half = DIVIDER_GRAB_WIDTH / 2.0;
+        let rects = self.region_rects(bounds);
+        let mut hits: Vec<(usize, RegionId)> = rects.iter()
+            .filter_map(|(region_id, rect)| {
+                let DockRegion::Split { orientation, ratio, .. } = self.regions.get(region_id)? else {
+                    return None;
+                };
+                let split = Self::split_point(*rect, *orientation, *ratio);
+                let grab = match orientation {
+                    Orientation::Horizontal => Rect::new(Vec2::new(split - half, rect.min.y), Vec2::new(split + half, rect.max.y)),
+                    Orientation::Vertical => Rect::new(Vec2::new(rect.min.x, split - half), Vec2::new(rect.max.x, split + half)),
+                };
+                grab.contains(position).then(|| (self.depth(*region_id), *region_id))
+            })
+            .collect();
+        hits.sort();
+        hits.last().map(|(_, region_id)| *region_id)
+    }
+
+    /// Start dragging the divider under `position`; false if there is none
+    pub fn begin_divider_drag(&mut self, bounds: Rect, position: Vec2) -> bool {
+        self.dragging = self.divider_at(bounds, position);
+        self.dragging.is_some()
+    }
+
+    /// Move the dragged divider to follow `position`; false when not dragging
+    pub fn drag_divider(&mut self, bounds: Rect, position: Vec2) -> bool {
+        let Some(region_id) = self.dragging else {
+            return false;
+        };
+        let Some(DockRegion::Split { orientation, .. }) = self.regions.get(&region_id) else {
+            self.dragging = None;
+            return false;
+        };
+        let Some(rect) = self.region_rects(bounds).get(&region_id).copied() else {
+            return false;
+        };
+        let extent = rect.max - rect.min;
+        let ratio = match orientation {
+            Orientation::Horizontal => (position.x - rect.min.x) / extent.x.max(1.0),
+            Orientation::Vertical => (position.y - rect.min.y) / extent.y.max(1.0),
+        };
+        self.set_divider(region_id, ratio).is_ok()
+    }
+
+    /// Stop dragging; returns true if a divider was being dragged
+    pub fn end_divider_drag(&mut self) -> bool {
+        self.dragging.take().is_some()
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragging.is_some()
+    }
+
+    pub fn to_json(&self) -> Result<String, MarcoError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| MarcoError::Persistence(format!("Failed to serialize dock layout: {}", e)))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, MarcoError> {
+        serde_json::from_str(json)
+            .map_err(|e| MarcoError::Persistence(format!("Failed to parse dock layout: {}", e)))
+    }
+
+    fn checked_ratio(ratio: f32) -> Result<f32, MarcoError> {
+        if !ratio.is_finite() {
+            return Err(MarcoError::InvalidOperation(format!("Invalid split ratio: {}", ratio)));
+        }
+        Ok(ratio.clamp(MIN_RATIO, 1.0 - MIN_RATIO))
+    }
+
+    /// Coordinate of a split's divider along its axis
+    fn split_point(rect: Rect, orientation: Orientation, ratio: f32) -> f32 {
+        match orientation {
+            Orientation::Horizontal => rect.min.x + (rect.max.x - rect.min.x) * ratio,
+            Orientation::Vertical => rect.min.y + (rect.max.y - rect.min.y) * ratio,
+        }
+    }
+
+    /// Number of splits above `region_id`
+    fn depth(&self, region_id: RegionId) -> usize {
+        let mut depth = 0;
+        let mut current = region_id;
+        while let Some(parent) = self.parent(current) {
+            depth += 1;
+            current = parent;
+        }
+        depth
+    }
+
+    fn parent(&self, region_id: RegionId) -> Option<RegionId> {
+        self.regions.iter()
+            .find(|(_, region)| matches!(region, DockRegion::Split { first, second, .. } if *first == region_id || *second == region_id))
+            .map(|(id, _)| *id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window() -> Rect {
+        Rect::new(Vec2::ZERO, Vec2::new(1200.0, 800.0))
+    }
+
+    #[test]
+    fn test_split_region_keeps_the_panel_and_ratios_sum_to_one() {
+        let mut layout = DockLayout::new(Some(PanelId::NodeLibrary));
+        let root = layout.root();
+        let (left, right) = layout.split_region(root, Orientation::Horizontal, 0.3).unwrap();
+        assert_eq!(layout.region(left), Some(&DockRegion::Panel(Some(PanelId::NodeLibrary))));
+        assert_eq!(layout.region(right), Some(&DockRegion::Panel(None)));
+        assert!(layout.split_region(root, Orientation::Vertical, 0.5).is_err());
+
+        let (top, _) = layout.split_region(right, Orientation::Vertical, 0.99).unwrap();
+        layout.set_panel(top, Some(PanelId::NodeCanvas)).unwrap();
+        for region_id in [root, right] {
+            let [(_, a), (_, b)] = layout.child_ratios(region_id).unwrap();
+            assert!((a + b - 1.0).abs() < 1e-6);
+        }
+        // Extreme ratios are held away from the edge
+        assert_eq!(layout.child_ratios(right).unwrap()[0].1, 1.0 - MIN_RATIO);
+        assert!(layout.set_divider(left, 0.5).is_err());
+        assert!(layout.set_divider(root, f32::NAN).is_err());
+
+        let rects = layout.region_rects(window());
+        assert_eq!(rects[&left], Rect::new(Vec2::ZERO, Vec2::new(360.0, 800.0)));
+        assert_eq!(layout.panel_region(PanelId::NodeCanvas), Some(top));
+
+        let restored = DockLayout::from_json(&layout.to_json().unwrap()).unwrap();
+        assert_eq!(restored.region_rects(window()), rects);
+    }
+
+    #[test]
+    fn test_dragging_a_divider_resizes_its_split() {
+        let mut layout = DockLayout::standard();
+        let root = layout.root();
+        let divider_x = 1200.0 * 0.25;
+        assert_eq!(layout.divider_at(window(), Vec2::new(divider_x + 2.0, 100.0)), Some(root));
+        assert_eq!(layout.divider_at(window(), Vec2::new(divider_x + 20.0, 100.0)), None);
+
+        assert!(layout.begin_divider_drag(window(), Vec2::new(divider_x, 400.0)));
+        assert!(layout.drag_divider(window(), Vec2::new(600.0, 380.0)));
+        assert!(layout.end_divider_drag());
+        assert!(!layout.drag_divider(window(), Vec2::new(900.0, 380.0)));
+        assert_eq!(layout.child_ratios(root).unwrap()[0].1, 0.5);
+
+        let canvas = layout.panel_rects(window()).into_iter()
+            .find(|(panel, _)| *panel == PanelId::NodeCanvas)
+            .map(|(_, rect)| rect)
+            .unwrap();
+        assert_eq!(canvas, Rect::new(Vec2::new(600.0, 0.0), Vec2::new(1200.0, 600.0)));
+    }
+}
@@ -0,0 +1,273 @@
+//! Import of node graphs from other tools
+//!
+//! External graphs are mapped onto Marco node types through a
+//! [`TypeMapping`] table. Nodes whose type has no mapping are imported as
+//! placeholders so their connections survive, and every such node is listed
+//! in the [`ImportReport`].
+use crate::core::types::error::MarcoError;
+use crate::core::types::MetaValue;
+use crate::ui::visual_node_editor::{SocketKind, VisualNodeEditor};
+use glam::Vec2;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Interchange formats understood by [`GraphImporter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// `{"nodes": [{"id", "type", "position"?, "properties"?}],
+    ///   "edges": [{"source", "source_port", "target", "target_port"}]}`
+    GenericJson,
+}
+
+/// Marco equivalent of one external node type
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeTypeMapping {
+    pub node_type: String,
+    /// External port name to Marco socket name; unlisted ports keep their name
+    pub ports: HashMap<String, String>,
+}
+
+/// Table translating external node types and ports to Marco ones
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TypeMapping {
+    types: HashMap<String, NodeTypeMapping>,
+}
+
+impl TypeMapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map an external node type onto a Marco node type
+    pub fn map_type(mut self, external: &str, node_type: &str) -> Self {
+        self.types.entry(external.to_string()).or_default().node_type = node_type.to_string();
+        self
+    }
+
+    /// Rename a port of an already mapped external type
+    pub fn map_port(mut self, external: &str, external_port: &str, port: &str) -> Self {
+        if let Some(mapping) = self.types.get_mut(external) {
+            mapping.ports.insert(external_port.to_string(), port.to_string());
+        }
+        self
+    }
+
+    pub fn get(&self, external: &str) -> Option<&NodeTypeMapping> {
+        self.types.get(external)
+    }
+}
+
+/// External node imported as a placeholder
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnmappedNode {
+    pub external_id: String,
+    pub external_type: String,
+    pub node: Uuid,
+}
+
+/// What an import could not carry over faithfully
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    pub unmapped: Vec<UnmappedNode>,
+    /// Edges that were dropped, with the reason
+    pub skipped_edges: Vec<String>,
+    /// External node id to the Marco node created for it
+    pub node_ids: HashMap<String, Uuid>,
+}
+
+impl ImportReport {
+    /// True when every node was mapped and every edge connected
+    pub fn is_clean(&self) -> bool {
+        self.unmapped.is_empty() && self.skipped_edges.is_empty()
+    }
+}
+
+#[derive(Deserialize)]
+struct GenericGraph {
+    nodes: Vec<GenericNode>,
+    #[serde(default)]
+    edges: Vec<GenericEdge>,
+}
+
+#[derive(Deserialize)]
+struct GenericNode {
+    id: String,
+    #[serde(rename = "type")]
+    node_type: String,
+    #[serde(default)]
+    position: Option<[f32; 2]>,
+    #[serde(default)]
+    properties: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct GenericEdge {
+    source: String,
+    source_port: String,
+    target: String,
+    target_port: String,
+}
+
+/// Builds editor graphs from external formats using a type mapping
+#[derive(Debug, Clone, Default)]
+pub struct GraphImporter {
+    pub mapping: TypeMapping,
+}
+
+impl GraphImporter {
+    pub fn new(mapping: TypeMapping) -> Self {
+        Self { mapping }
+    }
+
+    /// Import `data` into a new editor
+    ///
+    /// Fails only when the document cannot be read; unmapped nodes and
+    /// unconnectable edges are recorded in the report instead.
+    pub fn import_from_format(&self, data: &str, format: ImportFormat) -> Result<(VisualNodeEditor, ImportReport), MarcoError> {
+        match format {
+            ImportFormat::GenericJson => {
+                let graph: GenericGraph = serde_json::from_str(data)
+                    .map_err(|e| MarcoError::Persistence(format!("Invalid generic graph JSON: {}", e)))?;
+                self.import_generic(graph)
+            }
+        }
+    }
+
+    fn import_generic(&self, graph: GenericGraph) -> Result<(VisualNodeEditor, ImportReport), MarcoError> {
+        let mut editor = VisualNodeEditor::new();
+        editor.snap_to_grid = false;
+        let mut report = ImportReport::default();
+        let mut external_types = HashMap::new();
+
+        for (index, node) in graph.nodes.iter().enumerate() {
+            let position = node.position
+                .map(Vec2::from_array)
+                .unwrap_or_else(|| Vec2::new(index as f32 * 200.0, 0.0));
+
+            let mapped = self.mapping.get(&node.node_type)
+                .and_then(|mapping| editor.add_node(&mapping.node_type, position).ok());
+            let id = match mapped {
+                Some(id) => id,
+                None => {
+                    warn!("No Marco node type for '{}' ({}); importing a placeholder", node.node_type, node.id);
+                    let id = editor.add_placeholder_node(&node.node_type, position)?;
+                    report.unmapped.push(UnmappedNode {
+                        external_id: node.id.clone(),
+                        external_type: node.node_type.clone(),
+                        node: id,
+                    });
+                    id
+                }
+            };
+
+            if let Some(visual) = editor.nodes.get_mut(&id) {
+                for (name, value) in &node.properties {
+                    if let Some(value) = MetaValue::from_json(value) {
+                        visual.properties.insert(name.clone(), value);
+                    }
+                }
+            }
+            if report.node_ids.insert(node.id.clone(), id).is_some() {
+                warn!("Duplicate node id '{}' in imported graph; edges use the last one", node.id);
+            }
+            external_types.insert(node.id.clone(), node.node_type.clone());
+        }
+
+        for edge in &graph.edges {
+            let (Some(&from), Some(&to)) = (report.node_ids.get(&edge.source), report.node_ids.get(&edge.target)) else {
+                report.skipped_edges.push(format!(
+                    "{}.{} -> {}.{}: unknown node", edge.source, edge.source_port, edge.target, edge.target_port
+                ));
+                continue;
+            };
+            let from_port = self.port_name(external_types.get(&edge.source), &edge.source_port);
+            let to_port = self.port_name(external_types.get(&edge.target), &edge.target_port);
+            editor.ensure_placeholder_socket(from, SocketKind::Output, &from_port);
+            editor.ensure_placeholder_socket(to, SocketKind::Input, &to_port);
+
+            if let Err(e) = editor.connect_nodes(from, &from_port, to, &to_port) {
+                report.skipped_edges.push(format!(
+                    "{}.{} -> {}.{}: {}", edge.source, edge.source_port, edge.target, edge.target_port, e
+                ));
+            }
+        }
+
+        editor.snap_to_grid = true;
+        info!(
+            "Imported {} nodes and {} connections ({} placeholders, {} edges skipped)",
+            editor.nodes.len(), editor.connections.len(), report.unmapped.len(), report.skipped_edges.len()
+        );
+        Ok((editor, report))
+    }
+
+    fn port_name(&self, external_type: Option<&String>, port: &str) -> String {
+        external_type
+            .and_then(|t| self.mapping.get(t))
+            .and_then(|mapping| mapping.ports.get(port))
+            .cloned()
+            .unwrap_or_else(|| port.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRAPH: &str = r#"{
+        "nodes": [
+            { "id": "x", "type": "Sum", "position": [0, 0], "properties": { "label": "first" } },
+            { "id": "y", "type": "Sum", "position": [200, 0] },
+            { "id": "z", "type": "ShaderBlur" }
+        ],
+        "edges": [
+            { "source": "x", "source_port": "out", "target": "y", "target_port": "lhs" },
+            { "source": "y", "source_port": "out", "target": "z", "target_port": "image" },
+            { "source": "y", "source_port": "out", "target": "missing", "target_port": "in" }
+        ]
+    }"#;
+
+    fn mapping() -> TypeMapping {
+        TypeMapping::new()
+            .map_type("Sum", "add")
+            .map_port("Sum", "out", "result")
+            .map_port("Sum", "lhs", "a")
+    }
+
+    #[test]
+    fn test_generic_import_maps_nodes_and_edges() {
+        let importer = GraphImporter::new(mapping());
+        let (editor, report) = importer.import_from_format(GRAPH, ImportFormat::GenericJson).unwrap();
+
+        let x = report.node_ids["x"];
+        let y = report.node_ids["y"];
+        assert_eq!(editor.nodes[&x].node_type, "add");
+        assert_eq!(editor.nodes[&x].properties.get("label"), Some(&MetaValue::String("first".to_string())));
+        assert_eq!(editor.nodes[&y].position, Vec2::new(200.0, 0.0));
+        assert!(editor.connections.iter().any(|c| {
+            c.id.from_node == x && c.id.from_output == "result" && c.id.to_node == y && c.id.to_input == "a"
+        }));
+        assert_eq!(report.skipped_edges.len(), 1);
+    }
+
+    #[test]
+    fn test_unmapped_type_becomes_placeholder() {
+        let importer = GraphImporter::new(mapping());
+        let (editor, report) = importer.import_from_format(GRAPH, ImportFormat::GenericJson).unwrap();
+
+        assert_eq!(report.unmapped.len(), 1);
+        let unmapped = &report.unmapped[0];
+        assert_eq!((unmapped.external_id.as_str(), unmapped.external_type.as_str()), ("z", "ShaderBlur"));
+
+        let placeholder = &editor.nodes[&unmapped.node];
+        assert_eq!(placeholder.node_type, crate::ui::visual_node_editor::PLACEHOLDER_NODE_TYPE);
+        assert_eq!(placeholder.properties.get("original_type"), Some(&MetaValue::String("ShaderBlur".to_string())));
+        assert!(placeholder.inputs["image"].connected_output.is_some());
+
+        // Placeholders survive a save and reload with their connections
+        let restored = VisualNodeEditor::from_json(&editor.to_logic_json().unwrap()).unwrap();
+        assert_eq!(restored.connections.len(), editor.connections.len());
+        assert_eq!(restored.nodes[&unmapped.node].title, "Missing: ShaderBlur");
+    }
+}
@@ -2,13 +2,17 @@
 //! Combines all components into a cohesive visual development environment
 use crate::core::types::error::MarcoError;
 use crate::ui::theme::Marco2Theme;
-use crate::ui::visual_node_editor::VisualNodeEditor;
+use crate::ui::visual_node_editor::{NodeClipboard, VisualNodeEditor};
 use crate::ui::template_creator::TemplateCreator;
 use crate::ui::template_gallery::TemplateGallery;
 use crate::ui::node_library_panel::NodeLibraryPanel;
+use crate::ui::debug_overlay::DebugOverlay;
 use crate::ui::event::UIEvent;
+use crate::ui::command_palette::CommandPalette;
+use crate::ui::dock_layout::DockLayout;
+use crate::ui::responsive::PanelId;
+use crate::render::connection_routing::Rect;
 use glam::Vec2;
-use uuid::Uuid;
 use tracing::{info, warn, error};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -38,6 +42,7 @@ pub struct IntegratedIDE {
     pub template_creator: TemplateCreator,
     pub template_gallery: TemplateGallery,
     pub node_library: NodeLibraryPanel,
+    pub debug_overlay: DebugOverlay,
     
     // UI State
     pub sidebar_width: f32,
@@ -50,11 +55,14 @@ pub struct IntegratedIDE {
     pub main_area_rect: (Vec2, Vec2), // (position, size)
     pub sidebar_rect: (Vec2, Vec2),
     pub bottom_rect: (Vec2, Vec2),
+    /// Splitter arrangement of the panels within the window
+    pub dock_layout: DockLayout,
     
     // Interaction State
     pub mouse_position: Vec2,
     pub selected_tool: IDETool,
     pub clipboard_content: Option<ClipboardData>,
+    pub command_palette: CommandPalette,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -69,7 +77,7 @@ pub enum IDETool {
 
 #[derive(Debug, Clone)]
 pub enum ClipboardData {
-    Nodes(Vec<String>), // Node IDs
+    Nodes(NodeClipboard),
     GuiElements(Vec<String>), // Element IDs
     Code(String),
     Template(String), // Template ID
@@ -77,15 +85,18 @@ pub enum ClipboardData {
 
 impl IntegratedIDE {
     pub fn new() -> Self {
+        let node_editor = VisualNodeEditor::new();
+        let command_palette = CommandPalette::with_builtin_commands(&node_editor.node_registry);
         Self {
             mode: IDEMode::NodeEditor,
             layout: PanelLayout::Standard,
             theme: Marco2Theme::default(),
             
-            node_editor: VisualNodeEditor::new(),
+            node_editor,
             template_creator: TemplateCreator::new(),
             template_gallery: TemplateGallery::new(),
             node_library: NodeLibraryPanel::new(),
+            debug_overlay: DebugOverlay::new(),
             
             sidebar_width: 300.0,
             bottom_panel_height: 200.0,
@@ -96,10 +107,12 @@ impl IntegratedIDE {
             main_area_rect: (Vec2::ZERO, Vec2::ZERO),
             sidebar_rect: (Vec2::ZERO, Vec2::ZERO),
             bottom_rect: (Vec2::ZERO, Vec2::ZERO),
+            dock_layout: DockLayout::standard(),
             
             mouse_position: Vec2::ZERO,
             selected_tool: IDETool::Select,
             clipboard_content: None,
+            command_palette,
         }
     }
     
@@ -246,11 +259,9 @@ impl IntegratedIDE {
     pub fn copy_selection(&mut self) -> Result<(), MarcoError> {
         match self.mode {
             IDEMode::NodeEditor => {
-                let selected_nodes = self.node_editor.get_selected_node_ids();
-                if !selected_nodes.is_empty() {
-                    self.clipboard_content = Some(ClipboardData::Nodes(
-                    selected_nodes.into_iter().map(|id| id.to_string()).collect()
-                ));
+                let clipboard = self.node_editor.copy_selection();
+                if !clipboard.is_empty() {
+                    self.clipboard_content = Some(ClipboardData::Nodes(clipboard));
                     info!("Copied {} nodes to clipboard", self.clipboard_content.as_ref().unwrap().len());
                 }
             },
@@ -269,13 +280,10 @@ impl IntegratedIDE {
     pub fn paste_clipboard(&mut self) -> Result<(), MarcoError> {
         if let Some(ref clipboard_data) = self.clipboard_content.clone() {
             match (clipboard_data, &self.mode) {
-                (ClipboardData::Nodes(node_ids), IDEMode::NodeEditor) => {
-                    for node_id_str in node_ids {
-                        if let Ok(node_id) = Uuid::parse_str(&node_id_str) {
-                            self.node_editor.duplicate_node(node_id, Vec2::new(20.0, 20.0))?;
-                        }
-                    }
-                    info!("Pasted {} nodes", node_ids.len());
+                (ClipboardData::Nodes(clipboard), IDEMode::NodeEditor) => {
+                    let cursor = self.node_editor.screen_to_canvas(self.node_editor.mouse_position);
+                    let pasted = self.node_editor.paste(clipboard, cursor)?;
+                    info!("Pasted {} nodes", pasted.len());
                 },
                 (ClipboardData::GuiElements(element_ids), IDEMode::TemplateDesign) => {
                     for element_id in element_ids {
@@ -304,6 +312,26 @@ impl IntegratedIDE {
         Ok(())
     }
     
+    /// Run the palette command with the given id
+    pub fn execute_command(&mut self, id: &str) -> Result<(), MarcoError> {
+        let action = self.command_palette.get(id)
+            .map(|command| command.action.clone())
+            .ok_or_else(|| MarcoError::InvalidOperation(format!("Unknown command: {}", id)))?;
+        info!("Executing command: {}", id);
+        action(self);
+        Ok(())
+    }
+    
+    /// Add a node of `node_type` at the center of the main area and switch
+    /// to the node editor
+    pub fn create_node_at_view_center(&mut self, node_type: &str) -> Result<(), MarcoError> {
+        let (position, size) = self.main_area_rect;
+        let center = self.node_editor.screen_to_canvas(position + size * 0.5);
+        self.node_editor.add_node(node_type, center)?;
+        self.set_mode(IDEMode::NodeEditor);
+        Ok(())
+    }
+    
     pub fn open_template_gallery(&mut self) {
         self.template_gallery.toggle_visibility();
         if self.template_gallery.visible {
@@ -312,6 +340,10 @@ impl IntegratedIDE {
     }
     
     fn handle_mouse_move(&mut self, position: Vec2) -> Result<(), MarcoError> {
+        if self.dock_layout.drag_divider(self.window_bounds(), position) {
+            return Ok(());
+        }
+        
         // Update hover states and handle tool-specific behavior
         match self.selected_tool {
             IDETool::Pan => {
@@ -326,6 +358,11 @@ impl IntegratedIDE {
     }
     
     fn handle_mouse_click(&mut self, position: Vec2, button: u8) -> Result<(), MarcoError> {
+        // Pressing on a splitter divider starts resizing the regions beside it
+        if button == 1 && self.dock_layout.begin_divider_drag(self.window_bounds(), position) {
+            return Ok(());
+        }
+        
         // Handle click based on current tool and area
         if self.is_point_in_sidebar(position) {
             self.handle_sidebar_click(position, button)?;
@@ -337,6 +374,21 @@ impl IntegratedIDE {
         Ok(())
     }
     
+    /// Finish any divider drag; `UIEvent` has no release event, so the
+    /// windowing layer calls this directly
+    pub fn handle_mouse_release(&mut self, position: Vec2) {
+        if self.dock_layout.is_dragging() {
+            self.dock_layout.drag_divider(self.window_bounds(), position);
+            self.dock_layout.end_divider_drag();
+        }
+    }
+    
+    /// Where the dock layout places `panel`, if it is docked
+    pub fn panel_rect(&self, panel: PanelId) -> Option<Rect> {
+        let region = self.dock_layout.panel_region(panel)?;
+        self.dock_layout.region_rects(self.window_bounds()).get(&region).copied()
+    }
+    
     fn handle_key_press(&mut self, key: String) -> Result<(), MarcoError> {
         // Handle global hotkeys - simplified for now
         match key.as_str() {
@@ -382,6 +434,11 @@ impl IntegratedIDE {
         // Convert to main area local coordinates
         let local_pos = position - self.main_area_rect.0;
         info!("Main area click at {:?} (local: {:?})", position, local_pos);
+        
+        // Select tool: a left click on a connection curve removes it
+        if self.mode == IDEMode::NodeEditor && self.selected_tool == IDETool::Select && button == 1 {
+            self.node_editor.delete_connection_at(local_pos);
+        }
         Ok(())
     }
     
@@ -466,6 +523,10 @@ impl IntegratedIDE {
               self.main_area_rect, self.sidebar_rect, self.bottom_rect);
     }
     
+    fn window_bounds(&self) -> Rect {
+        Rect::new(Vec2::ZERO, self.window_size)
+    }
+    
     fn is_point_in_sidebar(&self, point: Vec2) -> bool {
         if self.sidebar_collapsed { return false; }
         let (pos, size) = self.sidebar_rect;
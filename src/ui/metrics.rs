@@ -0,0 +1,191 @@
+//! UI telemetry
+//!
+//! Counters and timing histograms collected by the cross-platform UI
+//! pipeline. Serializable so diagnostics and the web debug overlay can read
+//! a snapshot.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Frame-time bucket bounds in milliseconds
+const FRAME_TIME_BUCKETS_MS: [f32; 7] = [8.0, 12.0, 16.7, 20.0, 33.4, 50.0, 100.0];
+
+/// Input-latency bucket bounds in milliseconds
+const INPUT_LATENCY_BUCKETS_MS: [f32; 6] = [4.0, 8.0, 16.7, 33.4, 50.0, 100.0];
+
+/// Frames longer than this count as dropped (two frames at 60 FPS)
+pub const DEFAULT_DROP_THRESHOLD_MS: f32 = 33.4;
+
+/// Fixed-bucket histogram of durations in milliseconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimingHistogram {
+    /// Upper bound of each bucket; one extra overflow bucket follows
+    bounds_ms: Vec<f32>,
+    counts: Vec<u64>,
+    count: u64,
+    sum_ms: f64,
+    min_ms: f32,
+    max_ms: f32,
+}
+
+impl TimingHistogram {
+    pub fn new(bounds_ms: &[f32]) -> Self {
+        Self {
+            bounds_ms: bounds_ms.to_vec(),
+            counts: vec![0; bounds_ms.len() + 1],
+            count: 0,
+            sum_ms: 0.0,
+            min_ms: 0.0,
+            max_ms: 0.0,
+        }
+    }
+
+    pub fn record(&mut self, ms: f32) {
+        let ms = ms.max(0.0);
+        let bucket = self.bounds_ms.iter().position(|bound| ms <= *bound).unwrap_or(self.bounds_ms.len());
+        self.counts[bucket] += 1;
+        self.min_ms = if self.count == 0 { ms } else { self.min_ms.min(ms) };
+        self.max_ms = self.max_ms.max(ms);
+        self.count += 1;
+        self.sum_ms += ms as f64;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean_ms(&self) -> f32 {
+        if self.count == 0 { 0.0 } else { (self.sum_ms / self.count as f64) as f32 }
+    }
+
+    pub fn min_ms(&self) -> f32 {
+        self.min_ms
+    }
+
+    pub fn max_ms(&self) -> f32 {
+        self.max_ms
+    }
+
+    /// Bucket upper bounds paired with their counts; the last bound is infinite
+    pub fn buckets(&self) -> impl Iterator<Item = (f32, u64)> + '_ {
+        self.bounds_ms.iter().copied()
+            .chain(std::iter::once(f32::INFINITY))
+            .zip(self.counts.iter().copied())
+    }
+
+    /// Upper bound of the bucket holding the `p`th percentile (0-1)
+    pub fn percentile_ms(&self, p: f32) -> f32 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let target = ((p.clamp(0.0, 1.0) * self.count as f32).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bound, count) in self.buckets() {
+            seen += count;
+            if seen >= target {
+                return bound.min(self.max_ms);
+            }
+        }
+        self.max_ms
+    }
+}
+
+/// Telemetry for the cross-platform UI
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UiMetrics {
+    pub frames_rendered: u64,
+    /// Frames slower than `drop_threshold_ms`
+    pub frames_dropped: u64,
+    pub touches_handled: u64,
+    pub mouse_events_handled: u64,
+    /// Recognized gestures by kind ("tap", "pinch", ...)
+    pub gestures_recognized: BTreeMap<String, u64>,
+    pub frame_time: TimingHistogram,
+    /// Time from an input event to the next frame that reflects it
+    pub input_latency: TimingHistogram,
+    pub drop_threshold_ms: f32,
+}
+
+impl Default for UiMetrics {
+    fn default() -> Self {
+        Self {
+            frames_rendered: 0,
+            frames_dropped: 0,
+            touches_handled: 0,
+            mouse_events_handled: 0,
+            gestures_recognized: BTreeMap::new(),
+            frame_time: TimingHistogram::new(&FRAME_TIME_BUCKETS_MS),
+            input_latency: TimingHistogram::new(&INPUT_LATENCY_BUCKETS_MS),
+            drop_threshold_ms: DEFAULT_DROP_THRESHOLD_MS,
+        }
+    }
+}
+
+impl UiMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_frame(&mut self, frame_time_ms: f32) {
+        self.frames_rendered += 1;
+        if frame_time_ms > self.drop_threshold_ms {
+            self.frames_dropped += 1;
+        }
+        self.frame_time.record(frame_time_ms);
+    }
+
+    pub fn record_touch(&mut self) {
+        self.touches_handled += 1;
+    }
+
+    pub fn record_mouse_event(&mut self) {
+        self.mouse_events_handled += 1;
+    }
+
+    pub fn record_gesture(&mut self, kind: &str) {
+        *self.gestures_recognized.entry(kind.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn record_input_latency(&mut self, latency_ms: f32) {
+        self.input_latency.record(latency_ms);
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self { drop_threshold_ms: self.drop_threshold_ms, ..Self::default() };
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_and_percentiles() {
+        let mut histogram = TimingHistogram::new(&[10.0, 20.0]);
+        for ms in [5.0, 15.0, 15.0, 40.0] {
+            histogram.record(ms);
+        }
+
+        let counts: Vec<u64> = histogram.buckets().map(|(_, count)| count).collect();
+        assert_eq!(counts, vec![1, 2, 1]);
+        assert_eq!(histogram.mean_ms(), 18.75);
+        assert_eq!((histogram.min_ms(), histogram.max_ms()), (5.0, 40.0));
+        assert_eq!(histogram.percentile_ms(0.5), 20.0);
+        assert_eq!(histogram.percentile_ms(1.0), 40.0);
+    }
+
+    #[test]
+    fn test_slow_frames_count_as_dropped() {
+        let mut metrics = UiMetrics::new();
+        metrics.record_frame(16.0);
+        metrics.record_frame(50.0);
+        assert_eq!((metrics.frames_rendered, metrics.frames_dropped), (2, 1));
+
+        let json = metrics.to_json().unwrap();
+        let parsed: UiMetrics = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, metrics);
+    }
+}
@@ -0,0 +1,242 @@
+//! Minimap
+//!
+//! Scaled-down overview of the node canvas: every node's bounding box plus
+//! a rectangle for the part of the canvas currently on screen. The minimap
+//! sits in the bottom-right corner of the window, and clicking or dragging
+//! inside it recenters the main view on the canvas point under the cursor.
+use crate::render::connection_routing::Rect;
+use crate::ui::visual_node_editor::{VisualNode, VisualNodeEditor};
+use glam::Vec2;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Size of a new minimap in screen pixels
+pub const DEFAULT_MINIMAP_SIZE: Vec2 = Vec2::new(200.0, 150.0);
+
+/// Gap between the minimap and the window corner, in screen pixels
+const MINIMAP_MARGIN: f32 = 16.0;
+
+/// Canvas space shown around the nodes, as a fraction of their extent
+const CONTENT_PADDING: f32 = 0.1;
+
+/// The part of the canvas shown in the main view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CanvasViewport {
+    pub canvas_offset: Vec2,
+    pub canvas_scale: f32,
+    /// Window size in screen pixels
+    pub window_size: Vec2,
+}
+
+impl CanvasViewport {
+    pub fn from_editor(editor: &VisualNodeEditor) -> Self {
+        Self {
+            canvas_offset: editor.canvas_offset,
+            canvas_scale: editor.canvas_scale,
+            window_size: editor.viewport_size,
+        }
+    }
+
+    /// Canvas-space rectangle covered by the window
+    pub fn visible_rect(&self) -> Rect {
+        Rect::new(
+            -self.canvas_offset / self.canvas_scale,
+            (self.window_size - self.canvas_offset) / self.canvas_scale,
+        )
+    }
+}
+
+/// Overview of the whole graph with click-to-navigate
+#[derive(Debug, Clone)]
+pub struct Minimap {
+    pub visible: bool,
+    /// Size of the minimap in screen pixels
+    pub size: Vec2,
+    /// Screen rectangle the minimap occupies
+    frame: Rect,
+    /// Canvas-space area fitted into the frame
+    content: Rect,
+    /// Minimap pixels per canvas unit
+    scale: f32,
+    node_boxes: Vec<Rect>,
+    viewport_box: Option<Rect>,
+    /// A press inside the minimap is being dragged
+    dragging: bool,
+}
+
+impl Default for Minimap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Minimap {
+    pub fn new() -> Self {
+        Self {
+            visible: true,
+            size: DEFAULT_MINIMAP_SIZE,
+            frame: Rect::new(Vec2::ZERO, Vec2::ZERO),
+            content: Rect::new(Vec2::ZERO, Vec2::ONE),
+            scale: 1.0,
+            node_boxes: Vec::new(),
+            viewport_box: None,
+            dragging: false,
+        }
+    }
+
+    /// Refit the minimap to `nodes` and the current view
+    ///
+    /// The fitted area depends only on the nodes, so the mapping stays put
+    /// while a drag moves the view. With no nodes the current view is shown.
+    pub fn update(&mut self, nodes: &HashMap<Uuid, VisualNode>, viewport: &CanvasViewport) {
+        let corner = viewport.window_size - Vec2::splat(MINIMAP_MARGIN);
+        self.frame = Rect::new(corner - self.size, corner);
+
+        let mut nodes: Vec<&VisualNode> = nodes.values().collect();
+        nodes.sort_by_key(|node| node.stacking_key());
+        let bounds: Vec<Rect> = nodes.iter()
+            .map(|node| Rect::from_center_size(node.position, node.size))
+            .collect();
+        self.content = match bounds.iter().copied().reduce(|a, b| a.union(&b)) {
+            Some(content) => content.inflate((content.max - content.min).max_element() * CONTENT_PADDING),
+            None => viewport.visible_rect(),
+        };
+        let extent = (self.content.max - self.content.min).max(Vec2::ONE);
+        self.scale = (self.size.x / extent.x).min(self.size.y / extent.y);
+
+        self.node_boxes = bounds.iter().map(|rect| self.to_minimap_rect(rect)).collect();
+        let view = self.to_minimap_rect(&viewport.visible_rect());
+        self.viewport_box = view.intersects(&self.frame).then(|| {
+            Rect::new(view.min.max(self.frame.min), view.max.min(self.frame.max))
+        });
+    }
+
+    /// Screen rectangle the minimap occupies
+    pub fn frame(&self) -> Rect {
+        self.frame
+    }
+
+    /// Node bounding boxes in screen space, back to front
+    pub fn node_boxes(&self) -> &[Rect] {
+        &self.node_boxes
+    }
+
+    /// The main view in screen space, clipped to the frame; None when the
+    /// view is entirely outside the fitted area
+    pub fn viewport_box(&self) -> Option<Rect> {
+        self.viewport_box
+    }
+
+    /// Canvas position to center the main view on for a click at `pos`,
+    /// or None when `pos` is outside the minimap
+    pub fn hit_test(&self, pos: Vec2) -> Option<Vec2> {
+        if !self.visible || !self.frame.contains(pos) {
+            return None;
+        }
+        Some(self.to_canvas(pos))
+    }
+
+    /// Start a drag on a press inside the minimap
+    ///
+    /// Returns the canvas position to jump to, or None if the press missed.
+    pub fn handle_mouse_press(&mut self, pos: Vec2) -> Option<Vec2> {
+        let target = self.hit_test(pos)?;
+        self.dragging = true;
+        Some(target)
+    }
+
+    /// Canvas position to follow while dragging; the cursor is held to the
+    /// minimap edge when it leaves the frame
+    pub fn handle_mouse_move(&mut self, pos: Vec2) -> Option<Vec2> {
+        if !self.dragging {
+            return None;
+        }
+        Some(self.to_canvas(pos.clamp(self.frame.min, self.frame.max)))
+    }
+
+    /// End a drag; returns true if the minimap had the press
+    pub fn handle_mouse_release(&mut self) -> bool {
+        std::mem::replace(&mut self.dragging, false)
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Screen position of the fitted area's center, which maps to the frame's center
+    fn frame_center(&self) -> Vec2 {
+        (self.frame.min + self.frame.max) / 2.0
+    }
+
+    fn content_center(&self) -> Vec2 {
+        (self.content.min + self.content.max) / 2.0
+    }
+
+    fn to_minimap(&self, canvas: Vec2) -> Vec2 {
+        self.frame_center() + (canvas - self.content_center()) * self.scale
+    }
+
+    fn to_canvas(&self, screen: Vec2) -> Vec2 {
+        self.content_center() + (screen - self.frame_center()) / self.scale
+    }
+
+    fn to_minimap_rect(&self, rect: &Rect) -> Rect {
+        Rect::new(self.to_minimap(rect.min), self.to_minimap(rect.max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor_with_nodes() -> VisualNodeEditor {
+        let mut editor = VisualNodeEditor::new();
+        editor.viewport_size = Vec2::new(800.0, 600.0);
+        editor.add_node("math", Vec2::new(0.0, 0.0)).unwrap();
+        editor.add_node("math", Vec2::new(2000.0, 1000.0)).unwrap();
+        editor
+    }
+
+    #[test]
+    fn test_update_fits_nodes_and_view_into_the_corner() {
+        let editor = editor_with_nodes();
+        let mut minimap = Minimap::new();
+        minimap.update(&editor.rendered_nodes(), &CanvasViewport::from_editor(&editor));
+
+        let frame = minimap.frame();
+        assert_eq!(frame.max, Vec2::new(800.0 - MINIMAP_MARGIN, 600.0 - MINIMAP_MARGIN));
+        assert_eq!(frame.max - frame.min, DEFAULT_MINIMAP_SIZE);
+        assert_eq!(minimap.node_boxes().len(), 2);
+        assert!(minimap.node_boxes().iter().all(|rect| frame.contains(rect.min) && frame.contains(rect.max)));
+
+        // At 100% zoom with no offset the view starts at the canvas origin
+        let first = minimap.node_boxes().iter().map(|rect| rect.min.x).fold(f32::INFINITY, f32::min);
+        let view = minimap.viewport_box().unwrap();
+        assert!(view.min.x > first && view.max.x < frame.max.x);
+    }
+
+    #[test]
+    fn test_click_and_drag_recenter_the_main_view() {
+        let mut editor = editor_with_nodes();
+        let mut minimap = Minimap::new();
+        minimap.update(&editor.rendered_nodes(), &CanvasViewport::from_editor(&editor));
+        assert_eq!(minimap.hit_test(Vec2::new(10.0, 10.0)), None);
+
+        // Clicking where the second node is drawn centers the view on it
+        let target_box = minimap.node_boxes()[1];
+        let click = (target_box.min + target_box.max) / 2.0;
+        let target = minimap.handle_mouse_press(click).unwrap();
+        assert!(target.distance(Vec2::new(2000.0, 1000.0)) < 1.0);
+        editor.center_view_on(target);
+        assert!(editor.canvas_to_screen(target).distance(editor.viewport_size / 2.0) < 1e-3);
+
+        // Dragging keeps following the cursor until release
+        let before = minimap.frame();
+        minimap.update(&editor.rendered_nodes(), &CanvasViewport::from_editor(&editor));
+        assert_eq!(minimap.frame(), before);
+        let dragged = minimap.handle_mouse_move(minimap.frame().min - Vec2::splat(50.0)).unwrap();
+        assert_eq!(dragged, minimap.hit_test(minimap.frame().min).unwrap());
+        assert!(minimap.handle_mouse_release());
+        assert_eq!(minimap.handle_mouse_move(click), None);
+    }
+}
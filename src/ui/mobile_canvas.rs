@@ -5,9 +5,12 @@
 
 use crate::ui::responsive::{ResponsiveLayout, ScreenSize, LayoutBreakpoint};
 use crate::ui::touch::{TouchHandler, TouchGesture, TouchTool, TouchId};
+use crate::ui::visual_node_editor::{NodeClipboard, VisualNodeEditor};
 use serde::{Deserialize, Serialize};
 use glam::Vec2;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
 
 /// Mobile-optimized GUI canvas configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,8 +28,38 @@ pub struct MobileCanvasConfig {
     pub floating_toolbar: bool,
     pub edge_snap_distance: f32,
     pub auto_hide_ui_delay: f32,
+    
+    /// Node type the Draw and Shape tools create
+    #[serde(default = "default_node_type")]
+    pub default_node_type: String,
+    
+    /// How quickly a flung viewport slows down, per second; higher stops sooner
+    #[serde(default = "default_inertia_decay")]
+    pub inertia_decay: f32,
+    
+    /// Seconds the toolbar takes to move to its new layout when the screen turns
+    #[serde(default = "default_orientation_transition")]
+    pub orientation_transition: f32,
+}
+
+fn default_node_type() -> String {
+    "math".to_string()
+}
+
+fn default_inertia_decay() -> f32 {
+    DEFAULT_INERTIA_DECAY
+}
+
+fn default_orientation_transition() -> f32 {
+    0.3
 }
 
+/// Default `MobileCanvasConfig::inertia_decay`
+pub const DEFAULT_INERTIA_DECAY: f32 = 4.0;
+
+/// Fling speed, in screen pixels per second, below which the viewport stops
+const INERTIA_STOP_SPEED: f32 = 5.0;
+
 impl Default for MobileCanvasConfig {
     fn default() -> Self {
         Self {
@@ -38,6 +71,9 @@ impl Default for MobileCanvasConfig {
             floating_toolbar: true,
             edge_snap_distance: 20.0,
             auto_hide_ui_delay: 3.0,
+            default_node_type: default_node_type(),
+            inertia_decay: DEFAULT_INERTIA_DECAY,
+            orientation_transition: default_orientation_transition(),
         }
     }
 }
@@ -66,6 +102,40 @@ impl MobileToolItem {
     }
 }
 
+/// Seconds the selection highlight takes to fade out
+const SELECTION_PULSE_DURATION: f32 = 0.25;
+
+/// Extra scale of the selected item at the start of its highlight
+const SELECTION_PULSE_SCALE: f32 = 0.2;
+
+/// Toolbar changes the host can answer with haptics, sound or relayout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToolbarEvent {
+    ToolSelected(TouchTool),
+    /// The screen turned; the toolbar is animating to its new layout
+    OrientationChanged {
+        from: ScreenOrientation,
+        to: ScreenOrientation,
+    },
+}
+
+/// Toolbar moving between two layouts
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ToolbarTransition {
+    from_position: Vec2,
+    from_size: Vec2,
+    to_position: Vec2,
+    to_size: Vec2,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl ToolbarTransition {
+    fn progress(&self) -> f32 {
+        (self.elapsed / self.duration).min(1.0)
+    }
+}
+
 /// Mobile toolbar layout for different screen orientations
 #[derive(Debug, Clone)]
 pub struct MobileToolbar {
@@ -75,6 +145,12 @@ pub struct MobileToolbar {
     pub is_visible: bool,
     pub is_floating: bool,
     pub auto_hide: bool,
+    /// Highlight of the last selected item, 1.0 on selection fading to 0.0
+    selection_pulse: f32,
+    /// Events since the last `take_events`
+    events: Vec<ToolbarEvent>,
+    /// Layout animation in progress, if any
+    transition: Option<ToolbarTransition>,
 }
 
 impl MobileToolbar {
@@ -89,11 +165,7 @@ impl MobileToolbar {
             MobileToolItem::new(TouchTool::Shape),
         ];
         
-        let (size, is_floating) = match screen_size {
-            ScreenSize::Mobile => (Vec2::new(320.0, 64.0), true),
-            ScreenSize::Tablet => (Vec2::new(400.0, 72.0), false),
-            _ => (Vec2::new(480.0, 80.0), false),
-        };
+        let (size, is_floating) = Self::size_for(screen_size);
         
         Self {
             items,
@@ -102,24 +174,107 @@ impl MobileToolbar {
             is_visible: true,
             is_floating,
             auto_hide: screen_size == ScreenSize::Mobile,
+            selection_pulse: 0.0,
+            events: Vec::new(),
+            transition: None,
+        }
+    }
+    
+    /// Toolbar size for a screen size, and whether it floats
+    fn size_for(screen_size: ScreenSize) -> (Vec2, bool) {
+        match screen_size {
+            ScreenSize::Mobile => (Vec2::new(320.0, 64.0), true),
+            ScreenSize::Tablet => (Vec2::new(400.0, 72.0), false),
+            _ => (Vec2::new(480.0, 80.0), false),
         }
     }
     
+    /// Fade the selection highlight and advance any layout animation
+    pub fn update(&mut self, delta_time: f32) {
+        self.selection_pulse = (self.selection_pulse - delta_time / SELECTION_PULSE_DURATION).max(0.0);
+        
+        if let Some(transition) = &mut self.transition {
+            transition.elapsed += delta_time;
+            let t = transition.progress();
+            let eased = t * t * (3.0 - 2.0 * t);
+            self.position = transition.from_position.lerp(transition.to_position, eased);
+            self.size = transition.from_size.lerp(transition.to_size, eased);
+            if t >= 1.0 {
+                self.position = transition.to_position;
+                self.size = transition.to_size;
+                self.transition = None;
+            }
+        }
+    }
+    
+    /// Progress of the layout animation from 0.0 to 1.0; 1.0 when idle
+    pub fn layout_progress(&self) -> f32 {
+        self.transition.map_or(1.0, |transition| transition.progress())
+    }
+    
+    /// Animate from the current layout to the one for `orientation` over
+    /// `duration` seconds
+    ///
+    /// Starting again mid-animation continues from where the toolbar is
+    /// drawn now, so turning the screen twice never ends at a stale layout.
+    pub fn animate_layout(&mut self, screen_bounds: Vec2, orientation: ScreenOrientation, screen_size: ScreenSize, duration: f32) {
+        let (size, is_floating) = Self::size_for(screen_size);
+        self.is_floating = is_floating;
+        let to_position = Self::position_for(screen_bounds, size, orientation);
+        if duration <= 0.0 {
+            self.position = to_position;
+            self.size = size;
+            self.transition = None;
+            return;
+        }
+        self.transition = Some(ToolbarTransition {
+            from_position: self.position,
+            from_size: self.size,
+            to_position,
+            to_size: size,
+            elapsed: 0.0,
+            duration,
+        });
+    }
+    
+    pub fn selection_pulse(&self) -> f32 {
+        self.selection_pulse
+    }
+    
+    /// Scale to draw item `index` at: enlarged while the selected item pulses
+    pub fn item_scale(&self, index: usize) -> f32 {
+        match self.items.get(index) {
+            Some(item) if item.is_selected => 1.0 + SELECTION_PULSE_SCALE * self.selection_pulse,
+            _ => 1.0,
+        }
+    }
+    
+    /// Events since the last call, oldest first
+    pub fn take_events(&mut self) -> Vec<ToolbarEvent> {
+        std::mem::take(&mut self.events)
+    }
+    
+    /// Move straight to the layout for `orientation`, ending any animation
     pub fn update_layout(&mut self, screen_bounds: Vec2, orientation: ScreenOrientation) {
+        self.transition = None;
+        self.position = Self::position_for(screen_bounds, self.size, orientation);
+    }
+    
+    fn position_for(screen_bounds: Vec2, size: Vec2, orientation: ScreenOrientation) -> Vec2 {
         match orientation {
             ScreenOrientation::Portrait => {
                 // Position toolbar at bottom for portrait
-                self.position = Vec2::new(
-                    (screen_bounds.x - self.size.x) * 0.5,
-                    screen_bounds.y - self.size.y - 20.0
-                );
+                Vec2::new(
+                    (screen_bounds.x - size.x) * 0.5,
+                    screen_bounds.y - size.y - 20.0
+                )
             },
             ScreenOrientation::Landscape => {
                 // Position toolbar on side for landscape
-                self.position = Vec2::new(
+                Vec2::new(
                     20.0,
-                    (screen_bounds.y - self.size.y) * 0.5
-                );
+                    (screen_bounds.y - size.y) * 0.5
+                )
             },
         }
     }
@@ -154,8 +309,11 @@ impl MobileToolbar {
             }
             
             // Select the touched item
+            let tool = self.items[index].tool;
             self.items[index].is_selected = true;
-            Some(self.items[index].tool)
+            self.selection_pulse = 1.0;
+            self.events.push(ToolbarEvent::ToolSelected(tool));
+            Some(tool)
         } else {
             None
         }
@@ -187,6 +345,10 @@ pub struct MobileViewport {
     pub bounds: Vec2,
     pub min_zoom: f32,
     pub max_zoom: f32,
+    /// Fling velocity in screen pixels per second, applied by `apply_inertia`
+    pub velocity: Vec2,
+    /// Exponential decay rate of `velocity`, per second
+    pub inertia_decay: f32,
 }
 
 impl MobileViewport {
@@ -197,6 +359,8 @@ impl MobileViewport {
             bounds,
             min_zoom: 0.1,
             max_zoom: 5.0,
+            velocity: Vec2::ZERO,
+            inertia_decay: DEFAULT_INERTIA_DECAY,
         }
     }
     
@@ -217,6 +381,27 @@ impl MobileViewport {
         self.clamp_position();
     }
     
+    /// Keep panning with the fling velocity, decaying it exponentially
+    ///
+    /// The velocity stops below a small threshold, and along any axis where
+    /// the pan limits were reached. Returns true while still moving.
+    pub fn apply_inertia(&mut self, delta_time: f32) -> bool {
+        if self.velocity == Vec2::ZERO {
+            return false;
+        }
+        self.velocity *= (-self.inertia_decay * delta_time).exp();
+        if self.velocity.length() < INERTIA_STOP_SPEED {
+            self.velocity = Vec2::ZERO;
+            return false;
+        }
+        
+        let intended = self.position + self.velocity * delta_time / self.zoom;
+        self.pan(self.velocity * delta_time);
+        let blocked = (self.position - intended).abs().cmpgt(Vec2::splat(f32::EPSILON));
+        self.velocity = Vec2::select(blocked, Vec2::ZERO, self.velocity);
+        self.velocity != Vec2::ZERO
+    }
+    
     fn clamp_position(&mut self) {
         let max_offset = self.bounds * (self.zoom - 1.0) / (2.0 * self.zoom);
         self.position = self.position.clamp(-max_offset, max_offset);
@@ -326,6 +511,14 @@ impl MobileContextMenu {
     }
 }
 
+/// A node title being edited after a tap with the Text tool
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleEdit {
+    pub node_id: Uuid,
+    /// Title as typed so far
+    pub text: String,
+}
+
 /// Main mobile canvas designer
 #[derive(Debug)]
 pub struct MobileCanvasDesigner {
@@ -353,20 +546,45 @@ pub struct MobileCanvasDesigner {
     /// UI auto-hide timer
     ui_hide_timer: f32,
     
-    /// Selected elements for mobile interaction
-    selected_elements: Vec<u32>,
+    /// Node editor the canvas edits; world positions are its canvas positions
+    editor: Option<Arc<Mutex<VisualNodeEditor>>>,
+    
+    /// Nodes taken by the last cut or copy
+    clipboard: NodeClipboard,
+    
+    /// Title being edited, opened by tapping a node with the Text tool
+    title_edit: Option<TitleEdit>,
     
     /// Drag state for mobile interactions
     drag_state: Option<MobileDragState>,
+    
+    /// Selection being twisted by a two-finger rotate
+    rotate_state: Option<MobileRotateState>,
+    
+    /// Kinds of gestures handled since the last `take_recognized_gestures`
+    recognized_gestures: Vec<&'static str>,
 }
 
+/// A node being dragged; the selection moves with it
 #[derive(Debug, Clone)]
 struct MobileDragState {
-    pub element_id: u32,
+    pub node_id: Uuid,
+    /// World position of the node when the drag began
     pub start_position: Vec2,
+    /// World distance dragged so far
     pub offset: Vec2,
 }
 
+/// Selected nodes being rotated around their centroid
+#[derive(Debug, Clone)]
+struct MobileRotateState {
+    pub pivot: Vec2,
+    /// World positions of the nodes when the twist began
+    pub start_positions: Vec<(Uuid, Vec2)>,
+    /// Radians turned so far, clockwise on screen
+    pub angle: f32,
+}
+
 impl MobileCanvasDesigner {
     pub fn new(screen_bounds: Vec2) -> Self {
         let mut layout = ResponsiveLayout::new();
@@ -386,8 +604,12 @@ impl MobileCanvasDesigner {
             context_menu: MobileContextMenu::new(),
             orientation,
             ui_hide_timer: 0.0,
-            selected_elements: Vec::new(),
+            editor: None,
+            clipboard: NodeClipboard::default(),
+            title_edit: None,
             drag_state: None,
+            rotate_state: None,
+            recognized_gestures: Vec::new(),
         }
     }
     
@@ -399,8 +621,14 @@ impl MobileCanvasDesigner {
         // Update orientation
         let new_orientation = ScreenOrientation::from_dimensions(screen_bounds.x, screen_bounds.y);
         if new_orientation != self.orientation {
-            self.orientation = new_orientation;
-            self.toolbar.update_layout(screen_bounds, self.orientation);
+            let from = std::mem::replace(&mut self.orientation, new_orientation);
+            self.toolbar.animate_layout(
+                screen_bounds,
+                new_orientation,
+                self.layout.current_screen_size(),
+                self.config.orientation_transition,
+            );
+            self.toolbar.events.push(ToolbarEvent::OrientationChanged { from, to: new_orientation });
         }
         
         // Update viewport bounds
@@ -408,13 +636,22 @@ impl MobileCanvasDesigner {
         
         // Update touch handler
         self.touch_handler.update(delta_time);
+        self.toolbar.update(delta_time);
         
         // Handle pending gestures
         let gestures = self.touch_handler.drain_gestures();
         for gesture in gestures {
+            self.recognized_gestures.push(gesture.kind());
             self.handle_gesture(gesture);
         }
         
+        // A drag or twist ends once every finger is lifted, and a flung view coasts
+        if self.touch_handler.active_touch_count() == 0 {
+            self.end_drag();
+            self.rotate_state = None;
+            self.viewport.apply_inertia(delta_time);
+        }
+        
         // Update UI auto-hide timer
         if self.toolbar.auto_hide {
             if self.touch_handler.active_touch_count() > 0 {
@@ -429,10 +666,106 @@ impl MobileCanvasDesigner {
         }
     }
     
+    pub fn config(&self) -> &MobileCanvasConfig {
+        &self.config
+    }
+    
+    /// Replace the configuration, e.g. with user preferences
+    pub fn set_config(&mut self, config: MobileCanvasConfig) {
+        self.viewport.inertia_decay = config.inertia_decay;
+        self.config = config;
+    }
+    
+    /// Toolbar events since the last call, for haptics, sounds or relayout
+    pub fn take_toolbar_events(&mut self) -> Vec<ToolbarEvent> {
+        self.toolbar.take_events()
+    }
+    
+    /// Gesture kinds handled since the last call
+    pub fn take_recognized_gestures(&mut self) -> Vec<&'static str> {
+        std::mem::take(&mut self.recognized_gestures)
+    }
+
+    /// Edit `editor` through touch gestures
+    pub fn connect_editor(&mut self, editor: Arc<Mutex<VisualNodeEditor>>) {
+        self.end_drag();
+        self.editor = Some(editor);
+        self.title_edit = None;
+    }
+
+    /// The connected node editor, if any
+    pub fn editor(&self) -> Option<&Arc<Mutex<VisualNodeEditor>>> {
+        self.editor.as_ref()
+    }
+
+    /// Switch tools, highlighting the tool in the toolbar
+    pub fn set_tool(&mut self, tool: TouchTool) {
+        if let Some(index) = self.toolbar.items.iter().position(|item| item.tool == tool) {
+            self.toolbar.select_tool(index);
+        }
+        self.touch_handler.set_tool(tool);
+    }
+
+    pub fn current_tool(&self) -> TouchTool {
+        self.touch_handler.current_tool()
+    }
+
+    /// Title edit opened by the Text tool, if any
+    pub fn title_edit(&self) -> Option<&TitleEdit> {
+        self.title_edit.as_ref()
+    }
+
+    /// Replace the text of the open title edit
+    pub fn set_title_text(&mut self, text: &str) {
+        if let Some(edit) = &mut self.title_edit {
+            edit.text = text.to_string();
+        }
+    }
+
+    /// Write the edited title to its node and close the edit
+    ///
+    /// Returns false if no edit was open or the node is gone.
+    pub fn commit_title_edit(&mut self) -> bool {
+        let Some(edit) = self.title_edit.take() else {
+            return false;
+        };
+        self.with_editor(|editor| match editor.nodes.get_mut(&edit.node_id) {
+            Some(node) => {
+                node.title = edit.text;
+                true
+            }
+            None => false,
+        }).unwrap_or(false)
+    }
+
+    pub fn cancel_title_edit(&mut self) {
+        self.title_edit = None;
+    }
+
+    /// Topmost node under a screen position, if an editor is connected
+    pub fn node_at_screen_position(&self, screen_pos: Vec2) -> Option<Uuid> {
+        self.with_editor(|editor| self.node_under(editor, screen_pos)).flatten()
+    }
+
+    /// Ids of the editor's selected nodes, empty without an editor
+    pub fn selected_elements(&self) -> Vec<Uuid> {
+        self.with_editor(|editor| editor.selected_nodes.clone()).unwrap_or_default()
+    }
+
+    /// Run `f` on the connected editor; None when no editor is connected
+    fn with_editor<R>(&self, f: impl FnOnce(&mut VisualNodeEditor) -> R) -> Option<R> {
+        let editor = self.editor.as_ref()?;
+        let mut editor = editor.lock().unwrap();
+        Some(f(&mut editor))
+    }
+
     /// Handle touch input events
     pub fn handle_touch_down(&mut self, id: TouchId, position: Vec2, pressure: f32) {
         self.touch_handler.touch_down(id, position, pressure);
         
+        // Touching the canvas catches a coasting viewport
+        self.viewport.velocity = Vec2::ZERO;
+        
         // Reset UI hide timer on touch
         self.ui_hide_timer = 0.0;
         self.toolbar.is_visible = true;
@@ -451,6 +784,11 @@ impl MobileCanvasDesigner {
         self.touch_handler.touch_up(id);
     }
     
+    /// Zoom the viewport around a screen point, e.g. from a scroll wheel
+    pub fn zoom_at(&mut self, center: Vec2, scale_delta: f32) {
+        self.viewport.zoom_at(center, scale_delta);
+    }
+    
     /// Handle recognized gestures
     fn handle_gesture(&mut self, gesture: TouchGesture) {
         match gesture {
@@ -466,14 +804,18 @@ impl MobileCanvasDesigner {
                 self.handle_long_press(position);
             },
             
-            TouchGesture::Pan { delta, .. } => {
-                self.handle_pan(delta);
+            TouchGesture::Pan { start_position, delta, velocity, .. } => {
+                self.handle_pan(start_position, delta, velocity);
             },
             
             TouchGesture::Pinch { center, scale, .. } => {
                 self.handle_pinch(center, scale);
             },
             
+            TouchGesture::Rotate { angle_delta, .. } => {
+                self.handle_rotate(angle_delta);
+            },
+            
             TouchGesture::Swipe { direction, .. } => {
                 self.handle_swipe(direction);
             },
@@ -499,19 +841,15 @@ impl MobileCanvasDesigner {
         }
         
         // Handle canvas tap based on current tool
-        let world_pos = self.viewport.screen_to_world(position);
         match self.touch_handler.current_tool() {
             TouchTool::Select => {
-                self.handle_selection_tap(world_pos);
+                self.handle_selection_tap(position);
             },
-            TouchTool::Draw => {
-                self.start_drawing(world_pos);
+            TouchTool::Draw | TouchTool::Shape => {
+                self.create_node_at(self.viewport.screen_to_world(position));
             },
             TouchTool::Text => {
-                self.start_text_input(world_pos);
-            },
-            TouchTool::Shape => {
-                self.start_shape_creation(world_pos);
+                self.start_text_input(position);
             },
             _ => {}
         }
@@ -535,25 +873,38 @@ impl MobileCanvasDesigner {
             return;
         }
         
-        let world_pos = self.viewport.screen_to_world(position);
-        
-        // Check if long-pressing on an element
-        if self.is_element_at_position(world_pos) {
-            self.context_menu.show_at(position, world_pos);
+        // Long-pressing a node shows the menu for it, keeping any selection it is part of
+        let pressed = self.with_editor(|editor| {
+            let node_id = self.node_under(editor, position)?;
+            if !editor.selected_nodes.contains(&node_id) {
+                editor.select_node(node_id, false);
+            }
+            Some(node_id)
+        }).flatten();
+        if pressed.is_some() {
+            self.context_menu.show_at(position, self.viewport.screen_to_world(position));
         }
     }
     
     /// Handle pan gesture
-    fn handle_pan(&mut self, delta: Vec2) {
-        match self.touch_handler.current_tool() {
-            TouchTool::Pan => {
-                self.viewport.pan(delta * self.config.pan_sensitivity);
-            },
-            TouchTool::Select if !self.selected_elements.is_empty() => {
-                // Move selected elements
-                self.move_selected_elements(delta);
-            },
-            _ => {}
+    ///
+    /// With the Select or Pan tool, a pan that starts on a node drags it
+    /// along with the rest of the selection. With Select, a pan elsewhere
+    /// drags the selection; with Pan, it moves the viewport, which keeps
+    /// the finger's velocity to coast on after release.
+    fn handle_pan(&mut self, start_position: Vec2, delta: Vec2, velocity: Vec2) {
+        let tool = self.touch_handler.current_tool();
+        if self.drag_state.is_none() && matches!(tool, TouchTool::Select | TouchTool::Pan) {
+            self.drag_state = self.begin_drag(start_position, tool);
+        }
+        
+        if let Some(drag) = &mut self.drag_state {
+            drag.offset += delta / self.viewport.zoom;
+            let (node_id, target) = (drag.node_id, drag.start_position + drag.offset);
+            self.move_selected_elements(node_id, target);
+        } else if tool == TouchTool::Pan {
+            self.viewport.pan(delta * self.config.pan_sensitivity);
+            self.viewport.velocity = velocity * self.config.pan_sensitivity;
         }
     }
     
@@ -562,6 +913,39 @@ impl MobileCanvasDesigner {
         self.viewport.zoom_at(center, 1.0 + (scale - 1.0) * self.config.zoom_sensitivity);
     }
     
+    /// Handle rotate gesture: turn the selected nodes around their centroid
+    ///
+    /// Node boxes stay upright; only their positions turn.
+    fn handle_rotate(&mut self, angle_delta: f32) {
+        if self.rotate_state.is_none() {
+            self.rotate_state = self.with_editor(|editor| {
+                let start_positions: Vec<(Uuid, Vec2)> = editor.selected_nodes.iter()
+                    .filter_map(|id| editor.nodes.get(id).map(|node| (*id, node.position)))
+                    .collect();
+                if start_positions.is_empty() {
+                    return None;
+                }
+                let pivot = start_positions.iter().map(|(_, position)| *position).sum::<Vec2>()
+                    / start_positions.len() as f32;
+                Some(MobileRotateState { pivot, start_positions, angle: 0.0 })
+            }).flatten();
+        }
+        let Some(rotate) = &mut self.rotate_state else {
+            return;
+        };
+        rotate.angle += angle_delta;
+        
+        let rotate = rotate.clone();
+        let rotation = Vec2::from_angle(rotate.angle);
+        self.with_editor(|editor| {
+            for (node_id, start) in &rotate.start_positions {
+                if let Some(node) = editor.nodes.get_mut(node_id) {
+                    node.position = rotate.pivot + rotation.rotate(*start - rotate.pivot);
+                }
+            }
+        });
+    }
+    
     /// Handle swipe gesture
     fn handle_swipe(&mut self, direction: crate::ui::touch::SwipeDirection) {
         use crate::ui::touch::SwipeDirection;
@@ -581,41 +965,101 @@ impl MobileCanvasDesigner {
         }
     }
     
-    /// Handle selection tap
-    fn handle_selection_tap(&mut self, world_pos: Vec2) {
-        // TODO: Implement element selection logic
-        // This would integrate with the actual element system
-        tracing::debug!("Selection tap at world position: {:?}", world_pos);
+    /// Select the node under a tap, or clear the selection on empty canvas
+    fn handle_selection_tap(&mut self, screen_pos: Vec2) {
+        self.with_editor(|editor| match self.node_under(editor, screen_pos) {
+            Some(node_id) => editor.select_node(node_id, false),
+            None => editor.deselect_all(),
+        });
     }
     
-    /// Start drawing at position
-    fn start_drawing(&mut self, world_pos: Vec2) {
-        // TODO: Implement drawing start logic
-        tracing::debug!("Start drawing at world position: {:?}", world_pos);
+    /// Create a node of the configured default type centered on `world_pos`
+    /// and select it
+    fn create_node_at(&mut self, world_pos: Vec2) -> Option<Uuid> {
+        let node_type = self.config.default_node_type.clone();
+        let created = self.with_editor(|editor| {
+            let node_id = editor.add_node(&node_type, world_pos)?;
+            editor.select_node(node_id, false);
+            Ok::<_, crate::core::types::error::MarcoError>(node_id)
+        });
+        match created {
+            Some(Ok(node_id)) => Some(node_id),
+            Some(Err(e)) => {
+                tracing::warn!("Could not create '{}' node: {}", node_type, e);
+                None
+            },
+            None => {
+                tracing::debug!("No editor connected; ignoring node creation at {:?}", world_pos);
+                None
+            },
+        }
     }
     
-    /// Start text input at position
-    fn start_text_input(&mut self, world_pos: Vec2) {
-        // TODO: Implement text input logic
-        tracing::debug!("Start text input at world position: {:?}", world_pos);
+    /// Open a title edit for the node under a tap
+    fn start_text_input(&mut self, screen_pos: Vec2) {
+        self.title_edit = self.with_editor(|editor| {
+            let node_id = self.node_under(editor, screen_pos)?;
+            editor.select_node(node_id, false);
+            Some(TitleEdit { node_id, text: editor.nodes[&node_id].title.clone() })
+        }).flatten();
     }
     
-    /// Start shape creation at position
-    fn start_shape_creation(&mut self, world_pos: Vec2) {
-        // TODO: Implement shape creation logic
-        tracing::debug!("Start shape creation at world position: {:?}", world_pos);
+    /// Drag for a pan starting at `screen_pos`, selecting the node under it
+    ///
+    /// Off the nodes, the Select tool drags the existing selection.
+    fn begin_drag(&self, screen_pos: Vec2, tool: TouchTool) -> Option<MobileDragState> {
+        self.with_editor(|editor| {
+            let node_id = match self.node_under(editor, screen_pos) {
+                Some(node_id) => {
+                    if !editor.selected_nodes.contains(&node_id) {
+                        editor.select_node(node_id, false);
+                    }
+                    node_id
+                },
+                None if tool == TouchTool::Select => *editor.selected_nodes.first()?,
+                None => return None,
+            };
+            let start_position = editor.nodes.get(&node_id)?.position;
+            // Every step of the drag undoes as one move
+            editor.begin_edit_group();
+            Some(MobileDragState { node_id, start_position, offset: Vec2::ZERO })
+        }).flatten()
+    }
+    
+    /// Finish the current drag, closing its undo step
+    fn end_drag(&mut self) {
+        if self.drag_state.take().is_some() {
+            self.with_editor(|editor| editor.end_edit_group());
+        }
     }
     
-    /// Check if there's an element at the given position
-    fn is_element_at_position(&self, _world_pos: Vec2) -> bool {
-        // TODO: Implement element hit testing
-        false
+    /// Move the selection so the dragged node lands on `target`
+    ///
+    /// Moving by the total drag rather than each pan delta keeps grid
+    /// snapping from swallowing small finger movements.
+    fn move_selected_elements(&mut self, node_id: Uuid, target: Vec2) {
+        self.with_editor(|editor| {
+            if let Some(current) = editor.nodes.get(&node_id).map(|node| node.position) {
+                editor.move_selected_nodes(target - current);
+            }
+        });
     }
     
-    /// Move selected elements by delta
-    fn move_selected_elements(&mut self, _delta: Vec2) {
-        // TODO: Implement element movement
-        tracing::debug!("Moving {} selected elements", self.selected_elements.len());
+    /// Topmost node under `screen_pos` in `editor`
+    ///
+    /// Bounds are tested on screen, each grown to at least the minimum
+    /// touch size, so nodes stay easy to hit when zoomed out.
+    fn node_under(&self, editor: &VisualNodeEditor, screen_pos: Vec2) -> Option<Uuid> {
+        let min_size = Vec2::splat(self.config.min_touch_size);
+        editor.nodes.values()
+            .filter(|node| !editor.is_node_hidden(node.id))
+            .filter(|node| {
+                let center = self.viewport.world_to_screen(node.position);
+                let half = (node.size * self.viewport.zoom).max(min_size) / 2.0;
+                (screen_pos - center).abs().cmple(half).all()
+            })
+            .max_by_key(|node| node.stacking_key())
+            .map(|node| node.id)
     }
     
     /// Handle context menu action
@@ -635,38 +1079,60 @@ impl MobileCanvasDesigner {
         }
     }
     
-    /// Context menu actions (placeholder implementations)
+    /// Context menu actions, applied to the editor's selection
     fn cut_selected(&mut self) {
-        tracing::info!("Cut {} selected elements", self.selected_elements.len());
+        self.copy_selected();
+        self.delete_selected();
     }
     
     fn copy_selected(&mut self) {
-        tracing::info!("Copy {} selected elements", self.selected_elements.len());
+        if let Some(clipboard) = self.with_editor(|editor| editor.copy_selection()) {
+            tracing::info!("Copy {} selected nodes", clipboard.len());
+            self.clipboard = clipboard;
+        }
     }
     
     fn paste_at_position(&mut self) {
-        tracing::info!("Paste at position: {:?}", self.context_menu.target_position);
+        let target = self.context_menu.target_position;
+        let clipboard = &self.clipboard;
+        let Some(editor) = &self.editor else {
+            return;
+        };
+        if let Err(e) = editor.lock().unwrap().paste(clipboard, target) {
+            tracing::warn!("Paste failed: {}", e);
+        }
     }
     
     fn delete_selected(&mut self) {
-        tracing::info!("Delete {} selected elements", self.selected_elements.len());
-        self.selected_elements.clear();
+        if let Some(removed) = self.with_editor(|editor| editor.delete_selected_nodes()) {
+            tracing::info!("Delete {} selected nodes", removed);
+        }
     }
     
     fn duplicate_selected(&mut self) {
-        tracing::info!("Duplicate {} selected elements", self.selected_elements.len());
+        if let Some(Err(e)) = self.with_editor(|editor| editor.duplicate_selected_nodes()) {
+            tracing::warn!("Duplicate failed: {}", e);
+        }
     }
     
     fn bring_to_front(&mut self) {
-        tracing::info!("Bring {} elements to front", self.selected_elements.len());
+        self.with_editor(|editor| {
+            for node_id in editor.selected_nodes.clone() {
+                editor.bring_to_front(node_id);
+            }
+        });
     }
     
     fn send_to_back(&mut self) {
-        tracing::info!("Send {} elements to back", self.selected_elements.len());
+        self.with_editor(|editor| {
+            for node_id in editor.selected_nodes.clone() {
+                editor.send_to_back(node_id);
+            }
+        });
     }
     
     fn show_properties(&mut self) {
-        tracing::info!("Show properties for {} elements", self.selected_elements.len());
+        tracing::info!("Show properties for {} nodes", self.selected_elements().len());
     }
     
     /// Get current screen size
@@ -705,3 +1171,208 @@ impl MobileCanvasDesigner {
         &self.context_menu
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::touch::GestureThresholds;
+    use std::time::Duration;
+
+    const BOUNDS: Vec2 = Vec2::new(390.0, 844.0);
+
+    fn designer_with_editor() -> (MobileCanvasDesigner, Arc<Mutex<VisualNodeEditor>>) {
+        let editor = Arc::new(Mutex::new(VisualNodeEditor::new()));
+        let mut designer = MobileCanvasDesigner::new(BOUNDS);
+        designer.connect_editor(editor.clone());
+        (designer, editor)
+    }
+
+    /// Touch and release at `position`, then let the double-tap window pass
+    fn tap(designer: &mut MobileCanvasDesigner, position: Vec2) {
+        designer.handle_touch_down(TouchId(1), position, 1.0);
+        designer.handle_touch_up(TouchId(1));
+        std::thread::sleep(GestureThresholds::default().double_tap_max_interval + Duration::from_millis(20));
+        designer.update(1.0 / 60.0, BOUNDS);
+    }
+
+    #[test]
+    fn test_selecting_a_tool_deselects_the_others_and_pulses() {
+        let mut designer = MobileCanvasDesigner::new(BOUNDS);
+        designer.set_tool(TouchTool::Select);
+        designer.take_toolbar_events();
+        designer.update(1.0, BOUNDS);
+
+        let index = designer.toolbar().items.iter().position(|item| item.tool == TouchTool::Text).unwrap();
+        assert_eq!(designer.toolbar.select_tool(index), Some(TouchTool::Text));
+        let toolbar = designer.toolbar();
+        let selected: Vec<TouchTool> = toolbar.items.iter().filter(|item| item.is_selected).map(|item| item.tool).collect();
+        assert_eq!(selected, vec![TouchTool::Text]);
+        assert_eq!(toolbar.selection_pulse(), 1.0);
+        assert_eq!(toolbar.item_scale(index), 1.0 + SELECTION_PULSE_SCALE);
+        assert_eq!(toolbar.item_scale(0), 1.0);
+        assert_eq!(designer.take_toolbar_events(), vec![ToolbarEvent::ToolSelected(TouchTool::Text)]);
+
+        designer.update(SELECTION_PULSE_DURATION / 2.0, BOUNDS);
+        assert!((designer.toolbar().selection_pulse() - 0.5).abs() < 1e-5);
+        designer.update(SELECTION_PULSE_DURATION, BOUNDS);
+        assert_eq!(designer.toolbar().selection_pulse(), 0.0);
+        assert!(designer.take_toolbar_events().is_empty());
+    }
+
+    #[test]
+    fn test_turning_back_mid_animation_retargets_the_toolbar() {
+        let landscape = Vec2::new(BOUNDS.y, BOUNDS.x);
+        let mut designer = MobileCanvasDesigner::new(BOUNDS);
+        let (portrait_position, portrait_size) = (designer.toolbar().position, designer.toolbar().size);
+        let duration = designer.config().orientation_transition;
+
+        designer.update(duration / 3.0, landscape);
+        let progress = designer.toolbar().layout_progress();
+        assert!(progress > 0.0 && progress < 1.0);
+        let halfway = designer.toolbar().position;
+        assert_ne!(halfway, portrait_position);
+
+        // Turning back starts from where the toolbar is drawn, not from a jump
+        designer.update(0.0, BOUNDS);
+        assert_eq!(designer.toolbar().position, halfway);
+        assert_eq!(designer.toolbar().layout_progress(), 0.0);
+
+        designer.update(duration, BOUNDS);
+        assert_eq!(designer.toolbar().layout_progress(), 1.0);
+        assert_eq!(designer.toolbar().position, portrait_position);
+        assert_eq!(designer.toolbar().size, portrait_size);
+        assert_eq!(designer.take_toolbar_events(), vec![
+            ToolbarEvent::OrientationChanged { from: ScreenOrientation::Portrait, to: ScreenOrientation::Landscape },
+            ToolbarEvent::OrientationChanged { from: ScreenOrientation::Landscape, to: ScreenOrientation::Portrait },
+        ]);
+    }
+
+    #[test]
+    fn test_viewport_round_trips_screen_points() {
+        let mut viewport = MobileViewport::new(BOUNDS);
+        viewport.zoom_at(Vec2::new(100.0, 200.0), 2.5);
+        viewport.pan(Vec2::new(-40.0, 25.0));
+        for screen in [Vec2::ZERO, BOUNDS / 2.0, BOUNDS, Vec2::new(13.5, 700.25)] {
+            let round_trip = viewport.world_to_screen(viewport.screen_to_world(screen));
+            assert!(round_trip.distance(screen) < 1e-3, "{:?} came back as {:?}", screen, round_trip);
+        }
+    }
+
+    #[test]
+    fn test_fling_coasts_and_decays_to_a_stop() {
+        let mut designer = MobileCanvasDesigner::new(BOUNDS);
+        designer.set_tool(TouchTool::Pan);
+        let center = BOUNDS / 2.0;
+        designer.zoom_at(center, 5.0);
+
+        designer.handle_touch_down(TouchId(1), center, 1.0);
+        for step in 1..=5 {
+            std::thread::sleep(Duration::from_millis(16));
+            designer.handle_touch_move(TouchId(1), center - Vec2::new(step as f32 * 8.0, 0.0), 1.0);
+        }
+        designer.handle_touch_up(TouchId(1));
+        designer.update(1.0 / 60.0, BOUNDS);
+        let flung = designer.viewport().velocity;
+        assert!(flung.x < 0.0 && flung.y == 0.0, "fling velocity {:?}", flung);
+
+        let mut last = designer.viewport().clone();
+        for _ in 0..120 {
+            designer.update(1.0 / 20.0, BOUNDS);
+            let viewport = designer.viewport();
+            assert!(viewport.velocity.length() <= last.velocity.length());
+            assert!(viewport.position.x <= last.position.x);
+            last = viewport.clone();
+        }
+        assert_eq!(last.velocity, Vec2::ZERO);
+        assert!(last.position.x < 0.0);
+    }
+
+    #[test]
+    fn test_twist_rotates_the_selection_around_its_centroid() {
+        let (mut designer, editor) = designer_with_editor();
+        let (left, right) = {
+            let mut editor = editor.lock().unwrap();
+            let left = editor.add_node("math", Vec2::new(-100.0, 0.0)).unwrap();
+            let right = editor.add_node("math", Vec2::new(100.0, 0.0)).unwrap();
+            editor.select_node(left, false);
+            editor.select_node(right, true);
+            (left, right)
+        };
+
+        designer.handle_gesture(TouchGesture::Rotate { center: Vec2::ZERO, angle_delta: std::f32::consts::FRAC_PI_4 });
+        designer.handle_gesture(TouchGesture::Rotate { center: Vec2::ZERO, angle_delta: std::f32::consts::FRAC_PI_4 });
+
+        // A quarter turn clockwise on screen takes the left node to the top
+        let editor = editor.lock().unwrap();
+        assert!(editor.nodes[&left].position.distance(Vec2::new(0.0, -100.0)) < 1e-3);
+        assert!(editor.nodes[&right].position.distance(Vec2::new(0.0, 100.0)) < 1e-3);
+    }
+
+    #[test]
+    fn test_hit_testing_grows_small_nodes_to_the_touch_size() {
+        let (mut designer, editor) = designer_with_editor();
+        let node_id = editor.lock().unwrap().add_node("math", Vec2::ZERO).unwrap();
+        let center = BOUNDS / 2.0;
+        assert_eq!(designer.node_at_screen_position(center + Vec2::new(50.0, 0.0)), Some(node_id));
+
+        // At 10% the node is 12x8 pixels, but a touch-sized target around it still hits
+        designer.zoom_at(center, 0.1);
+        let node_center = designer.viewport().world_to_screen(Vec2::ZERO);
+        let reach = designer.config.min_touch_size / 2.0;
+        assert_eq!(designer.node_at_screen_position(node_center + Vec2::new(reach - 1.0, 0.0)), Some(node_id));
+        assert_eq!(designer.node_at_screen_position(node_center + Vec2::new(reach + 1.0, 0.0)), None);
+        assert!(designer.selected_elements().is_empty());
+    }
+
+    #[test]
+    fn test_draw_tap_creates_a_node_and_text_tap_renames_it() {
+        let (mut designer, editor) = designer_with_editor();
+        designer.set_tool(TouchTool::Draw);
+
+        // The screen center is the world origin at 100% zoom
+        let center = BOUNDS / 2.0;
+        tap(&mut designer, center);
+        assert_eq!(designer.take_recognized_gestures(), vec!["tap"]);
+        let node_id = {
+            let editor = editor.lock().unwrap();
+            assert_eq!(editor.nodes.len(), 1);
+            let node = editor.nodes.values().next().unwrap();
+            assert_eq!(node.node_type, "math");
+            assert_eq!(node.position, designer.viewport().screen_to_world(center));
+            assert_eq!(editor.selected_nodes, vec![node.id]);
+            node.id
+        };
+
+        designer.set_tool(TouchTool::Text);
+        tap(&mut designer, center);
+        assert_eq!(designer.title_edit().map(|edit| edit.node_id), Some(node_id));
+        designer.set_title_text("Gain");
+        assert!(designer.commit_title_edit());
+        assert_eq!(editor.lock().unwrap().nodes[&node_id].title, "Gain");
+        assert!(designer.title_edit().is_none());
+    }
+
+    #[test]
+    fn test_select_drag_moves_the_node_under_the_finger() {
+        let (mut designer, editor) = designer_with_editor();
+        let node_id = {
+            let mut editor = editor.lock().unwrap();
+            editor.snap_to_grid = false;
+            editor.add_node("math", Vec2::ZERO).unwrap()
+        };
+
+        let center = BOUNDS / 2.0;
+        designer.handle_touch_down(TouchId(1), center, 1.0);
+        for step in 1..=10 {
+            designer.handle_touch_move(TouchId(1), center + Vec2::new(step as f32 * 10.0, 0.0), 1.0);
+        }
+        designer.handle_touch_up(TouchId(1));
+        designer.update(1.0 / 60.0, BOUNDS);
+
+        // The first move only starts the pan; the other nine carry the node
+        let editor = editor.lock().unwrap();
+        assert_eq!(editor.nodes[&node_id].position, Vec2::new(90.0, 0.0));
+        assert_eq!(editor.selected_nodes, vec![node_id]);
+        assert_eq!(designer.viewport().position, Vec2::ZERO);
+    }
+}
@@ -20,31 +20,46 @@ pub mod nodes;
 pub mod visual_node_editor;
 pub mod template_creator;
 pub mod node_library_panel;
+pub mod node_search_index;
 pub mod template_gallery;
+pub mod template_marketplace;
 pub mod integrated_ide;
+pub mod command_palette;
+pub mod workspace;
+pub mod graph_import;
+pub mod minimap;
+pub mod dock_layout;
 
 // Phase 4 Sprint 1: Cross-Platform UI Systems
 pub mod responsive;
 pub mod touch;
 pub mod mobile_canvas;
 pub mod cross_platform;
+pub mod metrics;
 
 // Re-export core UI types that exist
 pub use app::{Marco2App, AppMode};
 pub use node_editor::{NodePos, NodeLayout, NodeType, VisualConnection, NodeEditorEvent, NodeEditorResponse};
-pub use debug_overlay::DebugOverlay;
-pub use property_panel::{PropertyPanel, PropertyPanelResponse};
+pub use debug_overlay::{DebugOverlay, ValueDisplay, ValueLabel};
+pub use property_panel::{PropertyPanel, PropertyPanelResponse, PropertyField, PropertyWidget};
 pub use gui_canvas::{GuiCanvasDesigner, GuiCanvasEvent, GuiCanvasResponse, CanvasTool, GuiElementType};
 pub use gui_canvas_simple::SimpleGuiCanvas;
 pub use theme::Marco2Theme;
-pub use visual_node_editor::VisualNodeEditor;
+pub use visual_node_editor::{VisualNodeEditor, SocketKind, BulkPropertyEdit, EditorEdit, CommandStack, ConnectionFilter, ConnectionStyle, ConnectionRouting, EditorSnapshot, EditorSession, EditorUiState, NodeGroup, NodeClipboard, LayoutDirection, ConnectionResult, GridSpacing};
 pub use template_creator::{TemplateCreator, TemplateCategory, GuiElement};
 pub use node_library_panel::NodeLibraryPanel;
 pub use template_gallery::TemplateGallery;
+pub use template_marketplace::MarketplaceManifest;
 pub use integrated_ide::{IntegratedIDE, IDEMode};
+pub use command_palette::{CommandPalette, Command, CommandAction};
+pub use workspace::{Workspace, WorkspaceTab, WorkspaceSnapshot};
+pub use graph_import::{GraphImporter, ImportFormat, ImportReport, TypeMapping};
+pub use minimap::{Minimap, CanvasViewport};
+pub use dock_layout::{DockLayout, DockRegion, Orientation, RegionId};
 
 // Phase 4 Sprint 1: Cross-Platform UI System Re-exports
 pub use responsive::{ResponsiveLayout, ScreenSize, LayoutBreakpoint};
 pub use touch::{TouchHandler, TouchId, TouchGesture, TouchTool};
-pub use mobile_canvas::{MobileCanvasDesigner, ScreenOrientation, MobileContextMenu};
+pub use mobile_canvas::{MobileCanvasDesigner, ScreenOrientation, MobileContextMenu, ToolbarEvent};
+pub use metrics::{UiMetrics, TimingHistogram};
 pub use cross_platform::{CrossPlatformUI, PlatformInfo, PlatformType, UIRenderConfig, MouseButton, PerformanceLevel};
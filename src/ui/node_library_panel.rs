@@ -3,7 +3,9 @@
 use crate::core::types::error::MarcoError;
 use crate::ui::theme::Marco2Theme;
 use crate::ui::template_creator::{TemplateCategory, GuiElementType};
+use crate::ui::node_search_index::NodeSearchIndex;
 use glam::Vec2;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{info, warn};
 
@@ -14,9 +16,13 @@ pub struct NodeLibraryPanel {
     pub selected_category: NodeCategory,
     pub node_definitions: HashMap<String, NodeDefinition>,
     pub gui_element_definitions: HashMap<String, GuiElementDefinition>,
+    /// Definition ids that were rejected because the id was already taken
+    pub rejected_definitions: Vec<String>,
+    /// Search index over `node_definitions`, kept in sync by add/remove
+    search_index: NodeSearchIndex,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NodeCategory {
     All,
     Logic,
@@ -57,11 +63,13 @@ pub struct GuiElementDefinition {
     pub example_use: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodePortDefinition {
     pub name: String,
     pub data_type: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub required: bool,
 }
 
@@ -102,6 +110,35 @@ pub struct PropertyConstraints {
     pub regex_pattern: Option<String>,
 }
 
+/// Node definition as written in a library data file
+///
+/// Properties are not part of the file format; loaded definitions start
+/// with none.
+#[derive(Debug, Deserialize)]
+struct NodeDefinitionEntry {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: String,
+    category: NodeCategory,
+    #[serde(default)]
+    icon: String,
+    #[serde(default)]
+    inputs: Vec<NodePortDefinition>,
+    #[serde(default)]
+    outputs: Vec<NodePortDefinition>,
+    #[serde(default)]
+    example_use: String,
+    #[serde(default)]
+    documentation_url: Option<String>,
+}
+
+/// Library data file with entries not yet validated
+#[derive(Deserialize)]
+struct NodeLibraryFile {
+    nodes: Vec<serde_json::Value>,
+}
+
 impl NodeLibraryPanel {
     pub fn new() -> Self {
         let mut panel = Self {
@@ -110,9 +147,12 @@ impl NodeLibraryPanel {
             selected_category: NodeCategory::All,
             node_definitions: HashMap::new(),
             gui_element_definitions: HashMap::new(),
+            rejected_definitions: Vec::new(),
+            search_index: NodeSearchIndex::new(),
         };
         
         panel.load_builtin_definitions();
+        panel.rebuild_search_index();
         panel
     }
     
@@ -131,12 +171,23 @@ impl NodeLibraryPanel {
         info!("Node library category: {:?}", self.selected_category);
     }
     
+    /// Nodes matching the current search and category, best matches first
     pub fn get_filtered_nodes(&self) -> Vec<&NodeDefinition> {
-        self.node_definitions.values()
+        self.search_index.search(&self.search_query)
+            .iter()
+            .filter_map(|key| self.node_definitions.get(key))
             .filter(|node| self.matches_filter(node))
             .collect()
     }
     
+    /// Rebuild the search index from scratch
+    ///
+    /// Only needed after mutating `node_definitions` directly; the add/remove
+    /// methods keep the index up to date incrementally.
+    pub fn rebuild_search_index(&mut self) {
+        self.search_index = NodeSearchIndex::build(&self.node_definitions);
+    }
+    
     pub fn get_filtered_gui_elements(&self) -> Vec<&GuiElementDefinition> {
         self.gui_element_definitions.values()
             .filter(|element| self.matches_gui_filter(element))
@@ -151,13 +202,83 @@ impl NodeLibraryPanel {
         self.gui_element_definitions.get(element_type)
     }
     
+    /// Replace the node definitions with those listed in a library data file
+    ///
+    /// The file is `{"nodes": [...]}`, each entry carrying an `id`, `name`,
+    /// `category`, ports and docs. Malformed or duplicate entries are skipped
+    /// with a warning. If the document is unreadable or holds no valid entry
+    /// the current definitions (the builtin set by default) are kept and an
+    /// error is returned. Returns the number of definitions loaded.
+    pub fn load_definitions_from_json(&mut self, json: &str) -> Result<usize, MarcoError> {
+        let file: NodeLibraryFile = serde_json::from_str(json)
+            .map_err(|e| MarcoError::Persistence(format!("Invalid node library file: {}", e)))?;
+
+        let mut definitions = HashMap::new();
+        for (index, value) in file.nodes.into_iter().enumerate() {
+            let entry = match serde_json::from_value::<NodeDefinitionEntry>(value) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    warn!("Skipping node library entry #{}: {}", index, e);
+                    continue;
+                }
+            };
+            if entry.id.trim().is_empty() || entry.name.trim().is_empty() {
+                warn!("Skipping node library entry #{}: id and name must not be empty", index);
+                continue;
+            }
+            if definitions.contains_key(&entry.id) {
+                warn!("Skipping node library entry #{}: duplicate id '{}'", index, entry.id);
+                continue;
+            }
+            definitions.insert(entry.id, NodeDefinition {
+                name: entry.name,
+                description: entry.description,
+                category: entry.category,
+                icon: entry.icon,
+                inputs: entry.inputs,
+                outputs: entry.outputs,
+                properties: Vec::new(),
+                example_use: entry.example_use,
+                documentation_url: entry.documentation_url,
+            });
+        }
+
+        if definitions.is_empty() {
+            return Err(MarcoError::Persistence(
+                "Node library file contains no valid definitions; keeping current library".to_string()
+            ));
+        }
+
+        let count = definitions.len();
+        self.node_definitions = definitions;
+        self.rebuild_search_index();
+        info!("Loaded {} node definitions from library file", count);
+        Ok(count)
+    }
+    
+    /// Add a custom definition keyed by its name
+    ///
+    /// An existing definition with the same name is replaced, so editing a
+    /// custom node re-adds it. Only builtin ids are protected from clobbering.
     pub fn add_custom_node(&mut self, definition: NodeDefinition) -> Result<(), MarcoError> {
         let name = definition.name.clone();
-        self.node_definitions.insert(name.clone(), definition);
-        info!("Added custom node definition: {}", name);
+        self.search_index.insert(&name, &definition);
+        if self.node_definitions.insert(name.clone(), definition).is_some() {
+            info!("Replaced custom node definition: {}", name);
+        } else {
+            info!("Added custom node definition: {}", name);
+        }
         Ok(())
     }
     
+    pub fn remove_custom_node(&mut self, name: &str) -> Result<NodeDefinition, MarcoError> {
+        let definition = self.node_definitions.remove(name)
+            .ok_or_else(|| MarcoError::UI(format!("Node definition '{}' not found", name)))?;
+        self.search_index.remove(name);
+        info!("Removed custom node definition: {}", name);
+        Ok(definition)
+    }
+    
     pub fn add_custom_gui_element(&mut self, definition: GuiElementDefinition) -> Result<(), MarcoError> {
         let name = definition.name.clone();
         self.gui_element_definitions.insert(name.clone(), definition);
@@ -233,9 +354,22 @@ impl NodeLibraryPanel {
         true
     }
     
+    /// Insert a builtin definition, refusing to overwrite an existing id
+    ///
+    /// Duplicates are logged and recorded in `rejected_definitions` instead of
+    /// silently replacing the definition that was registered first.
+    fn register_builtin(&mut self, id: &str, definition: NodeDefinition) {
+        if self.node_definitions.contains_key(id) {
+            warn!("Duplicate node definition '{}' ({}) rejected", id, definition.name);
+            self.rejected_definitions.push(id.to_string());
+            return;
+        }
+        self.node_definitions.insert(id.to_string(), definition);
+    }
+    
     fn load_builtin_definitions(&mut self) {
         // ===== COMPREHENSIVE MATH NODES =====
-        self.node_definitions.insert("math".to_string(), NodeDefinition {
+        self.register_builtin("math", NodeDefinition {
             name: "Math Operations".to_string(),
             description: "Comprehensive mathematical operations including trigonometry, powers, and rounding".to_string(),
             category: NodeCategory::Math,
@@ -274,7 +408,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== STRING PROCESSING NODES =====
-        self.node_definitions.insert("string".to_string(), NodeDefinition {
+        self.register_builtin("string", NodeDefinition {
             name: "String Operations".to_string(),
             description: "Comprehensive text processing including case conversion, analysis, and manipulation".to_string(),
             category: NodeCategory::Text,
@@ -319,7 +453,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== TIMER NODE (Enhanced) =====
-        self.node_definitions.insert("timer".to_string(), NodeDefinition {
+        self.register_builtin("timer", NodeDefinition {
             name: "Timer".to_string(),
             description: "Advanced timer with progress tracking, auto-reset, and multiple output formats".to_string(),
             category: NodeCategory::Time,
@@ -394,7 +528,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== CALCULATOR NODE =====
-        self.node_definitions.insert("calculator".to_string(), NodeDefinition {
+        self.register_builtin("calculator", NodeDefinition {
             name: "Expression Calculator".to_string(),
             description: "Evaluates mathematical expressions with variable substitution".to_string(),
             category: NodeCategory::Math,
@@ -433,7 +567,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== DATABASE NODE =====
-        self.node_definitions.insert("database".to_string(), NodeDefinition {
+        self.register_builtin("database", NodeDefinition {
             name: "Database Operations".to_string(),
             description: "CRUD operations with query building and data management".to_string(),
             category: NodeCategory::Data,
@@ -490,7 +624,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== VALIDATION NODE =====
-        self.node_definitions.insert("validation".to_string(), NodeDefinition {
+        self.register_builtin("validation", NodeDefinition {
             name: "Data Validation".to_string(),
             description: "Form validation with type checking and constraint validation".to_string(),
             category: NodeCategory::Data,
@@ -535,7 +669,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== API NODE =====
-        self.node_definitions.insert("api".to_string(), NodeDefinition {
+        self.register_builtin("api", NodeDefinition {
             name: "API Request".to_string(),
             description: "HTTP API simulation with request building and response handling".to_string(),
             category: NodeCategory::Network,
@@ -592,7 +726,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== DATA TRANSFORM NODE =====
-        self.node_definitions.insert("data_transform".to_string(), NodeDefinition {
+        self.register_builtin("data_transform", NodeDefinition {
             name: "Data Transform".to_string(),
             description: "Data processing pipelines with filter, map, sort, and aggregate operations".to_string(),
             category: NodeCategory::Data,
@@ -637,7 +771,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== AUDIO NODE =====
-        self.node_definitions.insert("audio".to_string(), NodeDefinition {
+        self.register_builtin("audio", NodeDefinition {
             name: "Audio Synthesis".to_string(),
             description: "Audio generation with waveforms, effects, and sound design tools".to_string(),
             category: NodeCategory::Audio,
@@ -688,7 +822,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== ANIMATION NODE =====
-        self.node_definitions.insert("animation".to_string(), NodeDefinition {
+        self.register_builtin("animation", NodeDefinition {
             name: "Animation Controller".to_string(),
             description: "Easing functions, keyframe animation, and timeline management".to_string(),
             category: NodeCategory::Animation,
@@ -739,7 +873,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== FILE SYSTEM NODE =====
-        self.node_definitions.insert("filesystem".to_string(), NodeDefinition {
+        self.register_builtin("filesystem", NodeDefinition {
             name: "File Operations".to_string(),
             description: "File and directory operations including read, write, and processing".to_string(),
             category: NodeCategory::Files,
@@ -790,7 +924,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== NETWORK NODE =====
-        self.node_definitions.insert("network".to_string(), NodeDefinition {
+        self.register_builtin("network", NodeDefinition {
             name: "Network Utilities".to_string(),
             description: "Network operations including ping, bandwidth monitoring, and connectivity tests".to_string(),
             category: NodeCategory::Network,
@@ -835,7 +969,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== COLOR PROCESSING NODE =====
-        self.node_definitions.insert("color".to_string(), NodeDefinition {
+        self.register_builtin("color", NodeDefinition {
             name: "Color Processing".to_string(),
             description: "Color space conversions, effects, and analysis tools".to_string(),
             category: NodeCategory::Color,
@@ -892,7 +1026,7 @@ impl NodeLibraryPanel {
         });
 
         // ===== LEGACY NODES (for compatibility) =====
-        self.node_definitions.insert("add".to_string(), NodeDefinition {
+        self.register_builtin("add", NodeDefinition {
             name: "Add (Legacy)".to_string(),
             description: "Simple addition - use Math Operations for comprehensive functionality".to_string(),
             category: NodeCategory::Math,
@@ -924,7 +1058,7 @@ impl NodeLibraryPanel {
             documentation_url: Some("https://docs.marco2.dev/nodes/math/add".to_string()),
         });
         
-        self.node_definitions.insert("branch".to_string(), NodeDefinition {
+        self.register_builtin("branch", NodeDefinition {
             name: "Branch".to_string(),
             description: "Conditional logic - outputs different values based on a condition".to_string(),
             category: NodeCategory::Logic,
@@ -962,7 +1096,7 @@ impl NodeLibraryPanel {
             documentation_url: Some("https://docs.marco2.dev/nodes/logic/branch".to_string()),
         });
         
-        self.node_definitions.insert("multiply".to_string(), NodeDefinition {
+        self.register_builtin("multiply", NodeDefinition {
             name: "Multiply".to_string(),
             description: "Multiplies two numbers".to_string(),
             category: NodeCategory::Math,
@@ -994,8 +1128,8 @@ impl NodeLibraryPanel {
             documentation_url: Some("https://docs.marco2.dev/nodes/math/multiply".to_string()),
         });
         
-        self.node_definitions.insert("timer".to_string(), NodeDefinition {
-            name: "Timer".to_string(),
+        self.register_builtin("timer_legacy", NodeDefinition {
+            name: "Timer (Legacy)".to_string(),
             description: "Generates time-based values for animations".to_string(),
             category: NodeCategory::Animation,
             icon: "⏰".to_string(),
@@ -1174,3 +1308,157 @@ impl NodeLibraryPanel {
               self.node_definitions.len(), self.gui_element_definitions.len());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn custom_definition(name: &str, description: &str) -> NodeDefinition {
+        NodeDefinition {
+            name: name.to_string(),
+            description: description.to_string(),
+            category: NodeCategory::Custom,
+            icon: "*".to_string(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            properties: Vec::new(),
+            example_use: format!("Use {} in a graph", name),
+            documentation_url: None,
+        }
+    }
+
+    fn brute_force(panel: &NodeLibraryPanel) -> HashSet<String> {
+        panel.node_definitions.values()
+            .filter(|node| panel.matches_filter(node))
+            .map(|node| node.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_timer_definitions_are_not_clobbered() {
+        let panel = NodeLibraryPanel::new();
+        assert!(panel.rejected_definitions.is_empty(), "rejected: {:?}", panel.rejected_definitions);
+
+        let timer = panel.get_node_definition("timer").unwrap();
+        let outputs: Vec<&str> = timer.outputs.iter().map(|p| p.name.as_str()).collect();
+        assert!(outputs.contains(&"progress"));
+        assert!(outputs.contains(&"finished"));
+        assert_eq!(timer.category, NodeCategory::Time);
+
+        let legacy = panel.get_node_definition("timer_legacy").unwrap();
+        assert_eq!(legacy.category, NodeCategory::Animation);
+        assert!(legacy.outputs.iter().any(|p| p.name == "Sine"));
+    }
+
+    #[test]
+    fn test_custom_node_with_same_name_replaces() {
+        let mut panel = NodeLibraryPanel::new();
+        panel.add_custom_node(custom_definition("Blur", "First pass")).unwrap();
+        panel.add_custom_node(custom_definition("Blur", "Second pass")).unwrap();
+        assert_eq!(panel.get_node_definition("Blur").unwrap().description, "Second pass");
+        assert!(panel.rejected_definitions.is_empty());
+
+        // The search index follows the replacement
+        panel.set_search_query("second".to_string());
+        assert!(panel.get_filtered_nodes().iter().any(|n| n.name == "Blur"));
+        panel.set_search_query("first".to_string());
+        assert!(!panel.get_filtered_nodes().iter().any(|n| n.name == "Blur"));
+    }
+
+    #[test]
+    fn test_indexed_search_matches_brute_force() {
+        let mut panel = NodeLibraryPanel::new();
+        let words = ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot"];
+        for i in 0..300 {
+            let name = format!("Plugin {} {}", words[i % words.len()], i);
+            let description = format!("Custom {} processor number {}", words[(i * 7) % words.len()], i);
+            panel.add_custom_node(custom_definition(&name, &description)).unwrap();
+        }
+
+        for query in ["delta", "ho", "processor number 1", "math", "x", "plugin echo 4", "zzz"] {
+            panel.set_search_query(query.to_string());
+            let indexed: HashSet<String> = panel.get_filtered_nodes().iter().map(|n| n.name.clone()).collect();
+            assert_eq!(indexed, brute_force(&panel), "mismatch for query '{}'", query);
+        }
+    }
+
+    #[test]
+    fn test_search_results_are_ranked() {
+        let mut panel = NodeLibraryPanel::new();
+        panel.add_custom_node(custom_definition("Noise", "Generates gradient values")).unwrap();
+        panel.add_custom_node(custom_definition("Gradient", "Linear color ramp")).unwrap();
+
+        panel.set_search_query("gradient".to_string());
+        let results = panel.get_filtered_nodes();
+        assert_eq!(results[0].name, "Gradient");
+        assert!(results.iter().any(|n| n.name == "Noise"));
+    }
+
+    #[test]
+    fn test_index_tracks_added_and_removed_nodes() {
+        let mut panel = NodeLibraryPanel::new();
+        panel.set_search_query("quaternion".to_string());
+        assert!(panel.get_filtered_nodes().is_empty());
+
+        panel.add_custom_node(custom_definition("Quaternion Slerp", "Rotation blend")).unwrap();
+        let names: Vec<String> = panel.get_filtered_nodes().iter().map(|n| n.name.clone()).collect();
+        assert_eq!(names, vec!["Quaternion Slerp".to_string()]);
+
+        panel.remove_custom_node("Quaternion Slerp").unwrap();
+        assert!(panel.get_filtered_nodes().is_empty());
+        assert!(panel.remove_custom_node("Quaternion Slerp").is_err());
+    }
+
+    const LIBRARY_JSON: &str = r#"{
+        "nodes": [
+            {
+                "id": "lerp",
+                "name": "Lerp",
+                "description": "Blend between two numbers",
+                "category": "Math",
+                "inputs": [
+                    { "name": "a", "data_type": "Number", "required": true },
+                    { "name": "b", "data_type": "Number", "required": true },
+                    { "name": "t", "data_type": "Number" }
+                ],
+                "outputs": [{ "name": "result", "data_type": "Number" }],
+                "documentation_url": "https://docs.marco2.dev/nodes/math/lerp"
+            },
+            { "id": "upper", "name": "Uppercase", "category": "Text" },
+            { "id": "broken", "name": "Broken", "category": "NotACategory" },
+            { "name": "No Id", "category": "Logic" }
+        ]
+    }"#;
+
+    #[test]
+    fn test_load_definitions_from_json_replaces_builtins() {
+        let mut panel = NodeLibraryPanel::new();
+        assert_eq!(panel.load_definitions_from_json(LIBRARY_JSON).unwrap(), 2);
+
+        let mut ids: Vec<&str> = panel.node_definitions.keys().map(String::as_str).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["lerp", "upper"]);
+
+        let lerp = panel.get_node_definition("lerp").unwrap();
+        assert_eq!(lerp.category, NodeCategory::Math);
+        let inputs: Vec<(&str, bool)> = lerp.inputs.iter().map(|p| (p.name.as_str(), p.required)).collect();
+        assert_eq!(inputs, vec![("a", true), ("b", true), ("t", false)]);
+        assert_eq!(lerp.documentation_url.as_deref(), Some("https://docs.marco2.dev/nodes/math/lerp"));
+
+        panel.set_search_query("upper".to_string());
+        let names: Vec<&str> = panel.get_filtered_nodes().iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["Uppercase"]);
+    }
+
+    #[test]
+    fn test_unusable_library_keeps_builtin_definitions() {
+        let mut panel = NodeLibraryPanel::new();
+        let builtin_count = panel.node_definitions.len();
+
+        assert!(panel.load_definitions_from_json("not json").is_err());
+        assert!(panel.load_definitions_from_json(r#"{"nodes": [{"id": "x"}]}"#).is_err());
+        assert_eq!(panel.node_definitions.len(), builtin_count);
+        assert!(panel.get_node_definition("math").is_some());
+    }
+}
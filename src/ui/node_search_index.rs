@@ -0,0 +1,186 @@
+//! Incremental search index for the node library
+//! Inverted n-gram index over node names, descriptions and examples
+use std::collections::{HashMap, HashSet};
+
+use crate::ui::node_library_panel::NodeDefinition;
+
+/// Longest n-gram stored in the index; longer queries intersect these
+const MAX_GRAM: usize = 3;
+
+/// Lowercased searchable text for one node definition
+#[derive(Debug, Clone)]
+struct IndexedDocument {
+    name: String,
+    description: String,
+    example_use: String,
+}
+
+impl IndexedDocument {
+    fn from_definition(definition: &NodeDefinition) -> Self {
+        Self {
+            name: definition.name.to_lowercase(),
+            description: definition.description.to_lowercase(),
+            example_use: definition.example_use.to_lowercase(),
+        }
+    }
+
+    fn fields(&self) -> [&str; 3] {
+        [&self.name, &self.description, &self.example_use]
+    }
+
+    /// Relevance score for a lowercase query, or None if it does not match
+    fn score(&self, query: &str) -> Option<u32> {
+        if self.name == query {
+            Some(100)
+        } else if self.name.starts_with(query) {
+            Some(80)
+        } else if self.name.contains(query) {
+            Some(60)
+        } else if self.description.contains(query) {
+            Some(30)
+        } else if self.example_use.contains(query) {
+            Some(10)
+        } else {
+            None
+        }
+    }
+}
+
+/// Inverted index from character n-grams to node definition keys
+///
+/// Candidates are found by intersecting the posting lists of a query's
+/// grams and then verified with a substring check, so results are exactly
+/// those of a full scan while only touching matching definitions.
+#[derive(Debug, Clone, Default)]
+pub struct NodeSearchIndex {
+    postings: HashMap<String, HashSet<String>>,
+    documents: HashMap<String, IndexedDocument>,
+}
+
+impl NodeSearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build an index over a full set of definitions
+    pub fn build(definitions: &HashMap<String, NodeDefinition>) -> Self {
+        let mut index = Self::new();
+        for (key, definition) in definitions {
+            index.insert(key, definition);
+        }
+        index
+    }
+
+    /// Add or replace the entry for `key`
+    pub fn insert(&mut self, key: &str, definition: &NodeDefinition) {
+        self.remove(key);
+
+        let document = IndexedDocument::from_definition(definition);
+        for gram in Self::document_grams(&document) {
+            self.postings.entry(gram).or_default().insert(key.to_string());
+        }
+        self.documents.insert(key.to_string(), document);
+    }
+
+    /// Remove the entry for `key`, returning whether it was present
+    pub fn remove(&mut self, key: &str) -> bool {
+        let Some(document) = self.documents.remove(key) else {
+            return false;
+        };
+
+        for gram in Self::document_grams(&document) {
+            if let Some(keys) = self.postings.get_mut(&gram) {
+                keys.remove(key);
+                if keys.is_empty() {
+                    self.postings.remove(&gram);
+                }
+            }
+        }
+        true
+    }
+
+    /// Number of indexed definitions
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Whether `key` is indexed
+    pub fn contains(&self, key: &str) -> bool {
+        self.documents.contains_key(key)
+    }
+
+    /// Search for definitions matching `query`, best matches first
+    ///
+    /// Returns definition keys ranked by where the query matched (name
+    /// before description before example), ties broken by key.
+    pub fn search(&self, query: &str) -> Vec<String> {
+        let query = query.to_lowercase();
+        if query.is_empty() {
+            let mut all: Vec<String> = self.documents.keys().cloned().collect();
+            all.sort();
+            return all;
+        }
+
+        let mut ranked: Vec<(u32, &String)> = self.candidates(&query)
+            .into_iter()
+            .filter_map(|key| {
+                let document = self.documents.get(key)?;
+                document.score(&query).map(|score| (score, key))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        ranked.into_iter().map(|(_, key)| key.clone()).collect()
+    }
+
+    /// Keys containing every gram of the query
+    fn candidates(&self, query: &str) -> HashSet<&String> {
+        let grams = Self::query_grams(query);
+        let mut lists: Vec<&HashSet<String>> = Vec::with_capacity(grams.len());
+        for gram in &grams {
+            match self.postings.get(gram) {
+                Some(keys) => lists.push(keys),
+                None => return HashSet::new(),
+            }
+        }
+
+        // Intersect starting from the shortest posting list
+        lists.sort_by_key(|keys| keys.len());
+        let Some((first, rest)) = lists.split_first() else {
+            return HashSet::new();
+        };
+        first.iter()
+            .filter(|key| rest.iter().all(|keys| keys.contains(*key)))
+            .collect()
+    }
+
+    /// All 1..=MAX_GRAM character grams of a document's fields
+    fn document_grams(document: &IndexedDocument) -> HashSet<String> {
+        let mut grams = HashSet::new();
+        for field in document.fields() {
+            let chars: Vec<char> = field.chars().collect();
+            for size in 1..=MAX_GRAM {
+                for window in chars.windows(size) {
+                    grams.insert(window.iter().collect());
+                }
+            }
+        }
+        grams
+    }
+
+    /// Grams to look up for a query: the query itself if short, else its trigrams
+    fn query_grams(query: &str) -> Vec<String> {
+        let chars: Vec<char> = query.chars().collect();
+        if chars.len() <= MAX_GRAM {
+            return vec![query.to_string()];
+        }
+        let mut grams: Vec<String> = chars.windows(MAX_GRAM).map(|w| w.iter().collect()).collect();
+        grams.sort();
+        grams.dedup();
+        grams
+    }
+}
@@ -1,14 +1,98 @@
 //! Property Panel (WGPU Migration Stub)
 //! 
 //! Minimal stub for the property panel to enable compilation during WGPU migration.
+//!
+//! `build_for_node` lays out one typed widget per editable input of the
+//! selected node. Edits come back as `PropertyPanelResponse::PropertyChanged`
+//! for the host to apply with `VisualNodeEditor::update_node_property`.
 
-use tracing::info;
-use crate::core::types::MetaValue;
+use std::collections::HashMap;
+use tracing::{debug, info};
+use uuid::Uuid;
+use crate::core::logic::OutputMap;
+use crate::core::types::{ColorRGBA, MetaValue};
+use crate::ui::node_library_panel::NodeDefinition;
+use crate::ui::visual_node_editor::{NodeDataType, VisualNode};
+
+/// Editor for one input, chosen by its data type
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyWidget {
+    /// Bounds come from the property's constraints; None is unbounded
+    Slider { value: f64, min: Option<f64>, max: Option<f64> },
+    Checkbox(bool),
+    TextField(String),
+    /// RGBA
+    ColorPicker([f32; 4]),
+}
+
+impl PropertyWidget {
+    /// Widget for an input of `data_type` showing `value`
+    ///
+    /// Returns None for types without a widget, such as vectors and lists.
+    fn for_type(data_type: &NodeDataType, value: &MetaValue, min: Option<f64>, max: Option<f64>) -> Option<Self> {
+        match data_type {
+            NodeDataType::Scalar => Some(Self::Slider { value: value.as_scalar().unwrap_or(0.0), min, max }),
+            NodeDataType::Boolean => Some(Self::Checkbox(matches!(value, MetaValue::Bool(true)))),
+            NodeDataType::String => Some(Self::TextField(match value {
+                MetaValue::String(text) => text.clone(),
+                _ => String::new(),
+            })),
+            NodeDataType::Color => Some(Self::ColorPicker(match value {
+                MetaValue::Color(c) => [c.r, c.g, c.b, c.a],
+                _ => [0.0, 0.0, 0.0, 1.0],
+            })),
+            _ => None,
+        }
+    }
+
+    /// Take `value` into the widget, returning what the property becomes
+    ///
+    /// Scalars are clamped to the slider's bounds and color channels to
+    /// 0..=1. A value of the wrong type leaves the widget unchanged and
+    /// returns None.
+    fn edit(&mut self, value: MetaValue) -> Option<MetaValue> {
+        match (self, value) {
+            (Self::Slider { value, min, max }, MetaValue::Scalar(new)) if new.is_finite() => {
+                let new = max.map_or(new, |max| new.min(max));
+                *value = min.map_or(new, |min| new.max(min));
+                Some(MetaValue::Scalar(*value))
+            }
+            (Self::Checkbox(checked), MetaValue::Bool(new)) => {
+                *checked = new;
+                Some(MetaValue::Bool(new))
+            }
+            (Self::TextField(text), MetaValue::String(new)) => {
+                *text = new.clone();
+                Some(MetaValue::String(new))
+            }
+            (Self::ColorPicker(rgba), MetaValue::Color(c)) => {
+                *rgba = [c.r, c.g, c.b, c.a].map(|channel| channel.clamp(0.0, 1.0));
+                Some(MetaValue::Color(ColorRGBA::new(rgba[0], rgba[1], rgba[2], rgba[3])))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A labelled widget in the panel
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyField {
+    pub name: String,
+    pub widget: PropertyWidget,
+}
 
 /// Property panel for editing node properties
 #[derive(Debug)]
 pub struct PropertyPanel {
     visible: bool,
+    /// Node whose properties and outputs are shown
+    selected_node: Option<Uuid>,
+    /// Latest outputs of the selected node
+    preview_values: OutputMap,
+    /// Pinned nodes in pin order, with their latest outputs
+    pinned: Vec<(Uuid, OutputMap)>,
+    /// Widgets for the selected node's inputs, in name order
+    fields: Vec<PropertyField>,
 }
 
 impl PropertyPanel {
@@ -16,6 +100,10 @@ impl PropertyPanel {
         info!("Creating PropertyPanel stub for WGPU migration");
         Self {
             visible: false,
+            selected_node: None,
+            preview_values: OutputMap::new(),
+            pinned: Vec::new(),
+            fields: Vec::new(),
         }
     }
     
@@ -27,9 +115,138 @@ impl PropertyPanel {
         self.visible
     }
     
+    /// Change the node shown in the panel; pinned watches are unaffected
+    pub fn set_selected_node(&mut self, node_id: Option<Uuid>) {
+        if self.selected_node != node_id {
+            self.selected_node = node_id;
+            self.preview_values.clear();
+            self.fields.clear();
+        }
+    }
+    
+    pub fn selected_node(&self) -> Option<Uuid> {
+        self.selected_node
+    }
+    
+    /// Select `node` and lay out a widget for each of its editable inputs
+    ///
+    /// Connected inputs are driven by the graph and get no widget. Each
+    /// widget shows the node's current property value, falling back to the
+    /// input's default. Slider bounds are taken from the matching property
+    /// constraints in `definition`, when one is given.
+    pub fn build_for_node(&mut self, node: &VisualNode, definition: Option<&NodeDefinition>) -> &[PropertyField] {
+        self.set_selected_node(Some(node.id));
+
+        let mut inputs: Vec<_> = node.inputs.iter()
+            .filter(|(_, input)| input.connected_output.is_none())
+            .collect();
+        inputs.sort_by(|a, b| a.0.cmp(b.0));
+
+        self.fields = inputs.into_iter()
+            .filter_map(|(name, input)| {
+                let value = node.properties.get(name).cloned()
+                    .or_else(|| input.default_value.clone())
+                    .unwrap_or_else(|| input.data_type.zero_value());
+                let constraints = definition
+                    .and_then(|definition| definition.properties.iter().find(|property| &property.name == name))
+                    .map(|property| &property.constraints);
+                let (min, max) = constraints.map_or((None, None), |c| (c.min_value, c.max_value));
+                let widget = PropertyWidget::for_type(&input.data_type, &value, min, max)?;
+                Some(PropertyField { name: name.clone(), widget })
+            })
+            .collect();
+        debug!("Built {} property widgets for node {}", self.fields.len(), node.id);
+        &self.fields
+    }
+    
+    /// Widgets built for the selected node
+    pub fn fields(&self) -> &[PropertyField] {
+        &self.fields
+    }
+    
+    /// Apply an edit from the widget for `name`
+    ///
+    /// The value is coerced by the widget, so scalars come back clamped to
+    /// their constraints. Nothing changes for an unknown property or a value
+    /// of the wrong type.
+    pub fn edit_property(&mut self, name: &str, value: MetaValue) -> PropertyPanelResponse {
+        let Some(node_id) = self.selected_node else {
+            return PropertyPanelResponse::None;
+        };
+        let Some(field) = self.fields.iter_mut().find(|field| field.name == name) else {
+            return PropertyPanelResponse::None;
+        };
+        match field.widget.edit(value) {
+            Some(value) => PropertyPanelResponse::PropertyChanged { node_id, name: name.to_string(), value },
+            None => PropertyPanelResponse::None,
+        }
+    }
+    
+    /// Outputs of the selected node from the last evaluation
+    pub fn preview_values(&self) -> &OutputMap {
+        &self.preview_values
+    }
+    
+    /// Feed the results of a graph evaluation into the panel
+    ///
+    /// Refreshes the selected node's preview and every pinned watch. Nodes
+    /// missing from `results` keep their previous values.
+    pub fn update_preview(&mut self, results: &HashMap<Uuid, OutputMap>) {
+        if let Some(outputs) = self.selected_node.and_then(|id| results.get(&id)) {
+            self.preview_values = outputs.clone();
+        }
+        for (node_id, values) in &mut self.pinned {
+            if let Some(outputs) = results.get(node_id) {
+                *values = outputs.clone();
+            }
+        }
+    }
+    
+    /// Pin a node's outputs into the watch section
+    ///
+    /// Returns false if the node was already pinned. The watch starts with
+    /// the current preview when pinning the selected node.
+    pub fn pin_node(&mut self, node_id: Uuid) -> bool {
+        if self.is_pinned(node_id) {
+            return false;
+        }
+        let initial = if self.selected_node == Some(node_id) {
+            self.preview_values.clone()
+        } else {
+            OutputMap::new()
+        };
+        self.pinned.push((node_id, initial));
+        debug!("Pinned node {} in property panel", node_id);
+        true
+    }
+    
+    /// Remove a node from the watch section, returning whether it was pinned
+    pub fn unpin_node(&mut self, node_id: Uuid) -> bool {
+        let before = self.pinned.len();
+        self.pinned.retain(|(id, _)| *id != node_id);
+        self.pinned.len() != before
+    }
+    
+    pub fn is_pinned(&self, node_id: Uuid) -> bool {
+        self.pinned.iter().any(|(id, _)| *id == node_id)
+    }
+    
+    /// Pinned node ids in pin order
+    pub fn pinned_nodes(&self) -> Vec<Uuid> {
+        self.pinned.iter().map(|(id, _)| *id).collect()
+    }
+    
+    /// Latest outputs of a pinned node
+    pub fn pinned_values(&self, node_id: Uuid) -> Option<&OutputMap> {
+        self.pinned.iter().find(|(id, _)| *id == node_id).map(|(_, values)| values)
+    }
+    
     pub fn render(&mut self) {
         if self.visible {
             info!("PropertyPanel render called (stub)");
+            for (node_id, values) in &self.pinned {
+                info!("Watch {}: {} outputs", node_id, values.len());
+            }
         }
     }
 }
@@ -41,15 +258,117 @@ impl Default for PropertyPanel {
 }
 
 /// Response from property panel
-#[derive(Debug)]
-pub struct PropertyPanelResponse {
-    pub property_changed: Option<(String, MetaValue)>,
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyPanelResponse {
+    None,
+    /// Apply with `VisualNodeEditor::update_node_property`
+    PropertyChanged { node_id: Uuid, name: String, value: MetaValue },
 }
 
-impl PropertyPanelResponse {
-    pub fn none() -> Self {
-        Self {
-            property_changed: None,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::node_library_panel::{NodeCategory, NodePropertyDefinition, PropertyConstraints, PropertyType};
+    use crate::ui::visual_node_editor::VisualNodeEditor;
+    use glam::Vec2;
+
+    fn result_of(values: &OutputMap) -> f64 {
+        values.get("result").and_then(|v| v.as_scalar()).unwrap()
+    }
+
+    #[test]
+    fn test_pinned_node_survives_selection_change() {
+        let mut editor = VisualNodeEditor::new();
+        let watched = editor.add_node("add", Vec2::new(0.0, 0.0)).unwrap();
+        let other = editor.add_node("add", Vec2::new(300.0, 0.0)).unwrap();
+        editor.update_node_property(watched, "a", MetaValue::Scalar(1.0)).unwrap();
+        editor.update_node_property(watched, "b", MetaValue::Scalar(2.0)).unwrap();
+
+        let mut panel = PropertyPanel::new();
+        panel.set_selected_node(Some(watched));
+        panel.update_preview(&editor.evaluate_graph().unwrap());
+        assert!(panel.pin_node(watched));
+        assert!(!panel.pin_node(watched));
+        assert_eq!(result_of(panel.pinned_values(watched).unwrap()), 3.0);
+
+        // Selecting another node keeps the watch
+        panel.set_selected_node(Some(other));
+        assert_eq!(panel.pinned_nodes(), vec![watched]);
+        assert_eq!(result_of(panel.pinned_values(watched).unwrap()), 3.0);
+
+        // Re-evaluation updates the pinned node even though it isn't selected
+        editor.update_node_property(watched, "a", MetaValue::Scalar(5.0)).unwrap();
+        panel.update_preview(&editor.evaluate_graph().unwrap());
+        assert_eq!(result_of(panel.pinned_values(watched).unwrap()), 7.0);
+        assert_eq!(result_of(panel.preview_values()), 0.0);
+
+        assert!(panel.unpin_node(watched));
+        assert!(panel.pinned_values(watched).is_none());
+    }
+
+    fn add_definition(min: f64, max: f64) -> NodeDefinition {
+        NodeDefinition {
+            name: "Add".to_string(),
+            description: String::new(),
+            category: NodeCategory::Math,
+            icon: String::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            properties: vec![NodePropertyDefinition {
+                name: "a".to_string(),
+                property_type: PropertyType::Number { min: Some(min), max: Some(max), step: None },
+                default_value: "0".to_string(),
+                description: String::new(),
+                constraints: PropertyConstraints {
+                    min_value: Some(min),
+                    max_value: Some(max),
+                    allowed_values: None,
+                    regex_pattern: None,
+                },
+            }],
+            example_use: String::new(),
+            documentation_url: None,
         }
     }
+
+    #[test]
+    fn test_scalar_edits_are_reported_and_clamped_to_constraints() {
+        let mut editor = VisualNodeEditor::new();
+        let node_id = editor.add_node("add", Vec2::ZERO).unwrap();
+        editor.update_node_property(node_id, "a", MetaValue::Scalar(2.0)).unwrap();
+        let node = editor.rendered_nodes()[&node_id].clone();
+
+        let mut panel = PropertyPanel::new();
+        let fields = panel.build_for_node(&node, Some(&add_definition(0.0, 10.0)));
+        assert_eq!(fields.iter().map(|field| field.name.as_str()).collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(fields[0].widget, PropertyWidget::Slider { value: 2.0, min: Some(0.0), max: Some(10.0) });
+        assert_eq!(fields[1].widget, PropertyWidget::Slider { value: 0.0, min: None, max: None });
+
+        let response = panel.edit_property("a", MetaValue::Scalar(4.5));
+        assert_eq!(response, PropertyPanelResponse::PropertyChanged {
+            node_id,
+            name: "a".to_string(),
+            value: MetaValue::Scalar(4.5),
+        });
+        if let PropertyPanelResponse::PropertyChanged { node_id, name, value } = response {
+            editor.update_node_property(node_id, &name, value).unwrap();
+        }
+        assert_eq!(editor.rendered_nodes()[&node_id].properties["a"], MetaValue::Scalar(4.5));
+
+        // Out of range is clamped, and the widget follows
+        let PropertyPanelResponse::PropertyChanged { value, .. } = panel.edit_property("a", MetaValue::Scalar(25.0)) else {
+            panic!("expected a property change");
+        };
+        assert_eq!(value, MetaValue::Scalar(10.0));
+        assert_eq!(panel.fields()[0].widget, PropertyWidget::Slider { value: 10.0, min: Some(0.0), max: Some(10.0) });
+        assert_eq!(panel.edit_property("b", MetaValue::Scalar(-3.0)), PropertyPanelResponse::PropertyChanged {
+            node_id,
+            name: "b".to_string(),
+            value: MetaValue::Scalar(-3.0),
+        });
+
+        // Wrong types and unknown names are ignored
+        assert_eq!(panel.edit_property("a", MetaValue::Bool(true)), PropertyPanelResponse::None);
+        assert_eq!(panel.edit_property("c", MetaValue::Scalar(1.0)), PropertyPanelResponse::None);
+    }
 }
@@ -52,8 +52,16 @@ pub struct LayoutBreakpoint {
     pub panel_layout: PanelLayout,
     pub tool_configuration: ToolConfiguration,
     pub grid_size: f32,
+    /// Once entered, this breakpoint is only left when the width passes
+    /// `min_width` or `max_width` by more than this many pixels, so resizing
+    /// right at a boundary does not flip-flop between layouts
+    #[serde(default)]
+    pub hysteresis: f32,
 }
 
+/// Default hysteresis margin for the built-in breakpoints, in pixels
+pub const DEFAULT_BREAKPOINT_HYSTERESIS: f32 = 16.0;
+
 /// Panel layout configurations for different screen sizes
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PanelLayout {
@@ -94,6 +102,7 @@ pub enum ToolLayout {
 /// Panel identifier for layout management
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PanelId {
+    NodeCanvas,
     NodeLibrary,
     Properties,
     Inspector,
@@ -101,6 +110,7 @@ pub enum PanelId {
     ComponentLibrary,
     Layers,
     Assets,
+    TemplateGallery,
 }
 
 /// Adaptive component that changes based on screen size
@@ -128,8 +138,9 @@ pub struct ComponentConfig {
     pub properties: HashMap<String, serde_json::Value>,
 }
 
+type BreakpointCallback = Box<dyn Fn(ScreenSize, ScreenSize) + Send + Sync>;
+
 /// Main responsive layout system
-#[derive(Debug)]
 pub struct ResponsiveLayout {
     /// Screen size breakpoints and their configurations
     breakpoints: HashMap<ScreenSize, LayoutBreakpoint>,
@@ -146,6 +157,25 @@ pub struct ResponsiveLayout {
     /// Layout transition animations
     transition_active: bool,
     transition_progress: f32,
+    
+    /// False until the first viewport update, which ignores hysteresis
+    size_resolved: bool,
+    
+    /// Called with the old and new size whenever the screen size changes
+    breakpoint_listeners: Vec<BreakpointCallback>,
+}
+
+impl std::fmt::Debug for ResponsiveLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponsiveLayout")
+            .field("current_size", &self.current_size)
+            .field("viewport_size", &self.viewport_size)
+            .field("adaptive_components", &self.adaptive_components.len())
+            .field("transition_active", &self.transition_active)
+            .field("transition_progress", &self.transition_progress)
+            .field("breakpoint_listeners", &self.breakpoint_listeners.len())
+            .finish()
+    }
 }
 
 impl Default for ResponsiveLayout {
@@ -163,6 +193,8 @@ impl ResponsiveLayout {
             adaptive_components: Vec::new(),
             transition_active: false,
             transition_progress: 0.0,
+            size_resolved: false,
+            breakpoint_listeners: Vec::new(),
         };
         
         layout.setup_default_breakpoints();
@@ -187,6 +219,7 @@ impl ResponsiveLayout {
                 touch_optimized: true,
             },
             grid_size: 16.0,
+            hysteresis: DEFAULT_BREAKPOINT_HYSTERESIS,
         });
         
         // Tablet configuration (768px - 1024px)
@@ -204,6 +237,7 @@ impl ResponsiveLayout {
                 touch_optimized: true,
             },
             grid_size: 20.0,
+            hysteresis: DEFAULT_BREAKPOINT_HYSTERESIS,
         });
         
         // Desktop configuration (1024px - 1920px)
@@ -223,6 +257,7 @@ impl ResponsiveLayout {
                 touch_optimized: false,
             },
             grid_size: 20.0,
+            hysteresis: DEFAULT_BREAKPOINT_HYSTERESIS,
         });
         
         // Ultrawide configuration (> 1920px)
@@ -242,18 +277,34 @@ impl ResponsiveLayout {
                 touch_optimized: false,
             },
             grid_size: 24.0,
+            hysteresis: DEFAULT_BREAKPOINT_HYSTERESIS,
         });
     }
     
     /// Update viewport size and recalculate layout
     pub fn update_viewport(&mut self, new_size: Vec2) {
-        let previous_size = self.current_size;
         self.viewport_size = new_size;
-        self.current_size = ScreenSize::from_width(new_size.x);
-        
-        // Trigger layout transition if screen size category changed
-        if previous_size != self.current_size {
+        let size = self.apply_hysteresis(new_size.x, ScreenSize::from_width(new_size.x));
+        self.set_current_size(size);
+    }
+    
+    /// Call `callback` with the old and new size each time a viewport or
+    /// screen bounds update crosses into another breakpoint
+    pub fn on_breakpoint_change<F>(&mut self, callback: F)
+    where
+        F: Fn(ScreenSize, ScreenSize) + Send + Sync + 'static,
+    {
+        self.breakpoint_listeners.push(Box::new(callback));
+    }
+    
+    /// Switch to `size`, animating and notifying listeners if it changed
+    fn set_current_size(&mut self, size: ScreenSize) {
+        let previous_size = std::mem::replace(&mut self.current_size, size);
+        if previous_size != size {
             self.start_transition();
+            for listener in &self.breakpoint_listeners {
+                listener(previous_size, size);
+            }
         }
     }
     
@@ -386,14 +437,39 @@ impl ResponsiveLayout {
     pub fn update_layout(&mut self, width: f32, height: f32) {
         self.viewport_size = Vec2::new(width, height);
         // Update breakpoint based on new dimensions
-        let new_size = if width < 768.0 {
-            ScreenBreakpoint::Mobile
-        } else if width < 1024.0 {
-            ScreenBreakpoint::Tablet
-        } else {
-            ScreenBreakpoint::Desktop
+        let size = self.apply_hysteresis(width, ScreenSize::from_width(width));
+        self.set_current_size(size);
+    }
+    
+    /// Set the hysteresis margin of one breakpoint; false if it doesn't exist
+    pub fn set_hysteresis(&mut self, size: ScreenSize, margin: f32) -> bool {
+        match self.breakpoints.get_mut(&size) {
+            Some(breakpoint) => {
+                breakpoint.hysteresis = margin.max(0.0);
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Keep the current size while `width` stays within its breakpoint's
+    /// bounds widened by the hysteresis margin, else switch to `candidate`
+    fn apply_hysteresis(&mut self, width: f32, candidate: ScreenSize) -> ScreenSize {
+        if !std::mem::replace(&mut self.size_resolved, true) || candidate == self.current_size {
+            return candidate;
+        }
+        let Some(breakpoint) = self.breakpoints.get(&self.current_size) else {
+            return candidate;
         };
-        self.current_size = new_size;
+        
+        let margin = breakpoint.hysteresis.max(0.0);
+        let above_min = width >= breakpoint.min_width - margin;
+        let below_max = breakpoint.max_width.is_none_or(|max| width < max + margin);
+        if above_min && below_max {
+            self.current_size
+        } else {
+            candidate
+        }
     }
     
     /// Update screen bounds and recalculate layout
@@ -417,3 +493,73 @@ impl ResponsiveLayout {
         self.transition_active
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hysteresis_prevents_flip_flop_at_boundary() {
+        let mut layout = ResponsiveLayout::new();
+        layout.set_hysteresis(ScreenSize::Tablet, 20.0);
+        layout.set_hysteresis(ScreenSize::Desktop, 20.0);
+        layout.update_viewport(Vec2::new(1000.0, 800.0));
+        assert_eq!(layout.current_screen_size(), ScreenSize::Tablet);
+
+        // Hovering around 1024 within the margin keeps the tablet layout
+        for width in [1025.0, 1020.0, 1040.0, 1010.0, 1043.0] {
+            layout.update_viewport(Vec2::new(width, 800.0));
+            assert_eq!(layout.current_screen_size(), ScreenSize::Tablet, "flipped at {}", width);
+        }
+
+        layout.update_viewport(Vec2::new(1045.0, 800.0));
+        assert_eq!(layout.current_screen_size(), ScreenSize::Desktop);
+
+        // Coming back down also needs to clear the margin
+        layout.update_viewport(Vec2::new(1010.0, 800.0));
+        assert_eq!(layout.current_screen_size(), ScreenSize::Desktop);
+        layout.update_viewport(Vec2::new(1000.0, 800.0));
+        assert_eq!(layout.current_screen_size(), ScreenSize::Tablet);
+    }
+
+    #[test]
+    fn test_breakpoint_callback_fires_once_per_boundary() {
+        use std::sync::{Arc, Mutex};
+
+        let mut layout = ResponsiveLayout::new();
+        for size in [ScreenSize::Mobile, ScreenSize::Tablet, ScreenSize::Desktop, ScreenSize::Ultrawide] {
+            layout.set_hysteresis(size, 0.0);
+        }
+        layout.update_screen_bounds(Vec2::new(1920.0, 1080.0));
+        assert_eq!(layout.current_screen_size(), ScreenSize::Ultrawide);
+
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let recorded = changes.clone();
+        layout.on_breakpoint_change(move |old, new| recorded.lock().unwrap().push((old, new)));
+
+        let mut crossings = Vec::new();
+        for width in (400..1920).rev() {
+            let before = changes.lock().unwrap().len();
+            layout.update_screen_bounds(Vec2::new(width as f32, 1080.0));
+            if changes.lock().unwrap().len() != before {
+                crossings.push(width);
+            }
+        }
+
+        assert_eq!(crossings, vec![1919, 1023, 767]);
+        assert_eq!(*changes.lock().unwrap(), vec![
+            (ScreenSize::Ultrawide, ScreenSize::Desktop),
+            (ScreenSize::Desktop, ScreenSize::Tablet),
+            (ScreenSize::Tablet, ScreenSize::Mobile),
+        ]);
+    }
+
+    #[test]
+    fn test_zero_hysteresis_switches_exactly_at_boundary() {
+        let mut layout = ResponsiveLayout::new();
+        layout.set_hysteresis(ScreenSize::Tablet, 0.0);
+        layout.update_viewport(Vec2::new(1023.0, 800.0));
+        layout.update_viewport(Vec2::new(1024.0, 800.0));
+        assert_eq!(layout.current_screen_size(), ScreenSize::Desktop);
+    }
+}
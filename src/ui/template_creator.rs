@@ -171,12 +171,20 @@ pub struct LayoutConstraints {
     pub aspect_ratio: Option<f32>,
 }
 
+/// Template `version` written by this build
+pub const TEMPLATE_VERSION: &str = "1.0.0";
+
+/// Rewrites an exported template from one version to the next
+pub type TemplateMigration = fn(serde_json::Value) -> serde_json::Value;
+
 pub struct TemplateCreator {
     pub templates: HashMap<Uuid, ProjectTemplate>,
     pub current_template: Option<Uuid>,
     pub node_editor: VisualNodeEditor,
     pub gui_elements: Vec<GuiElement>,
     pub preview_mode: bool,
+    /// Source version to target version and the migration between them
+    migrations: HashMap<String, (String, TemplateMigration)>,
 }
 
 impl std::fmt::Debug for TemplateCreator {
@@ -186,6 +194,7 @@ impl std::fmt::Debug for TemplateCreator {
             .field("current_template", &self.current_template)
             .field("gui_elements", &format!("{} gui elements", self.gui_elements.len()))
             .field("preview_mode", &self.preview_mode)
+            .field("migrations", &format!("{} migrations", self.migrations.len()))
             .finish()
     }
 }
@@ -198,10 +207,12 @@ impl TemplateCreator {
             node_editor: VisualNodeEditor::new(),
             gui_elements: Vec::new(),
             preview_mode: false,
+            migrations: HashMap::new(),
         };
         
         // Load built-in templates
         creator.load_builtin_templates();
+        creator.register_migration("0.9", TEMPLATE_VERSION, add_fixed_gui_layout);
         creator
     }
     
@@ -239,7 +250,7 @@ impl TemplateCreator {
             },
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
-            version: "1.0.0".to_string(),
+            version: TEMPLATE_VERSION.to_string(),
             author: "User".to_string(),
         };
         
@@ -458,9 +469,40 @@ impl TemplateCreator {
         }
     }
     
+    /// Run `migrate` on imported templates whose `version` is `from_version`
+    ///
+    /// Migrations chain: after one runs the template's version becomes
+    /// `to_version` and the migration registered for that version runs next.
+    pub fn register_migration(&mut self, from_version: &str, to_version: &str, migrate: TemplateMigration) {
+        self.migrations.insert(from_version.to_string(), (to_version.to_string(), migrate));
+    }
+    
+    /// Bring an exported template up to `TEMPLATE_VERSION`, stopping early
+    /// if no migration is registered for its version
+    fn migrate_template(&self, mut template: serde_json::Value) -> serde_json::Value {
+        let mut visited = std::collections::HashSet::new();
+        loop {
+            let version = template.get("version").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            if version == TEMPLATE_VERSION || !visited.insert(version.clone()) {
+                return template;
+            }
+            let Some((to_version, migrate)) = self.migrations.get(&version) else {
+                return template;
+            };
+            info!("Migrating template from version {} to {}", version, to_version);
+            template = migrate(template);
+            if let Some(object) = template.as_object_mut() {
+                object.insert("version".to_string(), serde_json::Value::String(to_version.clone()));
+            }
+        }
+    }
+    
     pub fn import_template(&mut self, template_json: &str) -> Result<Uuid, MarcoError> {
-        let template: ProjectTemplate = serde_json::from_str(template_json)
+        let value: serde_json::Value = serde_json::from_str(template_json)
             .map_err(|e| MarcoError::Persistence(format!("Failed to parse template: {}", e)))?;
+        let template: ProjectTemplate = serde_json::from_value(self.migrate_template(value))
+            .map_err(|e| MarcoError::Persistence(format!("Failed to parse template: {}", e)))?;
+        self.validate_template(&template)?;
         
         let template_id = template.id;
         self.templates.insert(template_id, template);
@@ -469,6 +511,45 @@ impl TemplateCreator {
         Ok(template_id)
     }
     
+    /// Check that a template can be loaded: connections reference its own
+    /// nodes, node types are known and GUI element positions are finite
+    ///
+    /// The error lists every problem found, not just the first.
+    pub fn validate_template(&self, template: &ProjectTemplate) -> Result<(), MarcoError> {
+        let mut problems = Vec::new();
+        let node_ids: std::collections::HashSet<Uuid> = template.initial_nodes.iter().map(|node| node.id).collect();
+        
+        for node in &template.initial_nodes {
+            if self.node_editor.node_registry.specification(&node.node_type).is_none() {
+                problems.push(format!("node {} has unknown type '{}'", node.id, node.node_type));
+            }
+        }
+        for (index, connection) in template.initial_connections.iter().enumerate() {
+            for (end, node_id) in [("source", connection.from_node), ("target", connection.to_node)] {
+                if !node_ids.contains(&node_id) {
+                    problems.push(format!(
+                        "connection {} ({}.{} -> {}.{}) has unknown {} node {}",
+                        index, connection.from_node, connection.from_output,
+                        connection.to_node, connection.to_input, end, node_id
+                    ));
+                }
+            }
+        }
+        for element in &template.gui_elements {
+            if !element.position.is_finite() {
+                problems.push(format!("GUI element {} has non-finite position {:?}", element.id, element.position));
+            }
+        }
+        
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(MarcoError::Persistence(format!(
+                "Invalid template '{}' ({} problems): {}", template.name, problems.len(), problems.join("; ")
+            )))
+        }
+    }
+    
     pub fn toggle_preview_mode(&mut self) {
         self.preview_mode = !self.preview_mode;
         info!("Preview mode: {}", if self.preview_mode { "enabled" } else { "disabled" });
@@ -698,3 +779,107 @@ impl TemplateCreator {
         }
     }
 }
+
+/// 0.9 templates predate `gui_layout`; they were laid out at fixed positions
+fn add_fixed_gui_layout(mut template: serde_json::Value) -> serde_json::Value {
+    if let Some(object) = template.as_object_mut() {
+        if !object.contains_key("gui_layout") {
+            let layout = GuiLayout {
+                layout_type: LayoutType::Fixed,
+                constraints: LayoutConstraints {
+                    min_width: None,
+                    max_width: None,
+                    min_height: None,
+                    max_height: None,
+                    aspect_ratio: None,
+                },
+            };
+            if let Ok(layout) = serde_json::to_value(layout) {
+                object.insert("gui_layout".to_string(), layout);
+            }
+        }
+    }
+    template
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_rejects_dangling_connections_listing_every_problem() {
+        let mut creator = TemplateCreator::new();
+        let template_id = creator.create_new_template("Broken".to_string(), TemplateCategory::Custom).unwrap();
+        let node_id = Uuid::new_v4();
+        let missing = Uuid::new_v4();
+        {
+            let template = creator.templates.get_mut(&template_id).unwrap();
+            template.initial_nodes.push(SerializedNode {
+                id: node_id,
+                node_type: "add".to_string(),
+                position: Vec2::ZERO,
+                properties: HashMap::new(),
+                title: "Add".to_string(),
+            });
+            template.initial_connections.push(SerializedConnection {
+                from_node: node_id,
+                from_output: "result".to_string(),
+                to_node: missing,
+                to_input: "a".to_string(),
+            });
+        }
+        let valid_json = creator.export_template(template_id).unwrap();
+
+        let mut template: ProjectTemplate = serde_json::from_str(&valid_json).unwrap();
+        template.initial_nodes[0].node_type = "warp_drive".to_string();
+        let json = serde_json::to_string(&template).unwrap();
+
+        let mut importer = TemplateCreator::new();
+        let before = importer.templates.len();
+        let Err(MarcoError::Persistence(message)) = importer.import_template(&json) else {
+            panic!("template with a dangling connection should be rejected");
+        };
+        assert!(message.contains(&missing.to_string()), "{}", message);
+        assert!(message.contains("warp_drive"), "{}", message);
+        assert_eq!(importer.templates.len(), before);
+
+        template.initial_nodes[0].node_type = "add".to_string();
+        template.initial_connections[0].to_node = node_id;
+        assert_eq!(importer.import_template(&serde_json::to_string(&template).unwrap()).unwrap(), template_id);
+    }
+
+    #[test]
+    fn test_v0_9_template_is_migrated_on_import_and_round_trips() {
+        let mut creator = TemplateCreator::new();
+        let template_id = creator.create_new_template("Legacy".to_string(), TemplateCategory::Prototype).unwrap();
+        let mut legacy: serde_json::Value = serde_json::from_str(&creator.export_template(template_id).unwrap()).unwrap();
+        let object = legacy.as_object_mut().unwrap();
+        object.remove("gui_layout");
+        object.insert("version".to_string(), serde_json::json!("0.9"));
+
+        let mut importer = TemplateCreator::new();
+        // Pre-1.0 dev builds wrote 0.8; chain it into the default migration
+        importer.register_migration("0.8", "0.9", |mut template| {
+            template["name"] = serde_json::json!("Renamed by 0.8 migration");
+            template
+        });
+        assert!(importer.import_template(&legacy.to_string()).is_ok());
+        let imported = &importer.templates[&template_id];
+        assert_eq!(imported.version, TEMPLATE_VERSION);
+        assert!(matches!(imported.gui_layout.layout_type, LayoutType::Fixed));
+        assert_eq!(imported.name, "Legacy");
+
+        let exported = importer.export_template(template_id).unwrap();
+        let mut again = TemplateCreator::new();
+        again.import_template(&exported).unwrap();
+        assert_eq!(again.export_template(template_id).unwrap(), exported);
+
+        legacy["version"] = serde_json::json!("0.8");
+        again.register_migration("0.8", "0.9", |mut template| {
+            template["name"] = serde_json::json!("Renamed by 0.8 migration");
+            template
+        });
+        again.import_template(&legacy.to_string()).unwrap();
+        assert_eq!(again.templates[&template_id].name, "Renamed by 0.8 migration");
+    }
+}
@@ -3,9 +3,11 @@
 use crate::core::types::error::MarcoError;
 use crate::ui::theme::Marco2Theme;
 use crate::ui::template_creator::{TemplateCategory, ProjectTemplate};
+use crate::ui::template_marketplace::{MarketplaceManifest, MarketplaceTemplate, ManifestEntryError};
+use crate::render::thumbnail::render_template_thumbnail;
 use glam::Vec2;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{info, warn, error};
 
 #[derive(Debug, Clone)]
@@ -71,6 +73,15 @@ pub struct TemplateEntry {
     pub metadata: TemplateMetadata,
     pub preview_data: PreviewData,
     pub statistics: TemplateStatistics,
+    /// Archive location for marketplace templates that are not installed yet
+    pub download: Option<TemplateDownload>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateDownload {
+    pub url: String,
+    /// Hex-encoded SHA-256 the archive must match
+    pub checksum: String,
 }
 
 #[derive(Debug, Clone)]
@@ -235,6 +246,7 @@ impl TemplateGallery {
                 user_ratings: Vec::new(),
                 feedback_comments: Vec::new(),
             },
+            download: None,
             template,
         };
         
@@ -274,6 +286,117 @@ impl TemplateGallery {
         Ok(())
     }
     
+    /// Render a PNG thumbnail of a template into `dir` and point its
+    /// preview at it
+    pub fn generate_thumbnail(&mut self, template_id: &str, dir: &Path, size: (u32, u32)) -> Result<PathBuf, MarcoError> {
+        let entry = self.templates.get_mut(template_id)
+            .ok_or_else(|| MarcoError::TemplateNotFound(template_id.to_string()))?;
+        
+        let png = render_template_thumbnail(&entry.template, size)?;
+        let path = dir.join(format!("{}.png", template_id));
+        std::fs::write(&path, png)
+            .map_err(|e| MarcoError::Persistence(format!("Failed to write thumbnail {}: {}", path.display(), e)))?;
+        
+        entry.preview_data.thumbnail_path = Some(path.clone());
+        info!("Generated thumbnail for '{}' at {}", template_id, path.display());
+        Ok(path)
+    }
+    
+    /// Add the templates listed in a marketplace manifest
+    ///
+    /// Valid listings become community entries (replacing earlier listings
+    /// with the same id); malformed ones are skipped and returned. Errors
+    /// only when the manifest document itself cannot be read.
+    pub fn load_manifest(&mut self, json: &str) -> Result<Vec<ManifestEntryError>, MarcoError> {
+        let (manifest, errors) = MarketplaceManifest::parse(json)?;
+        for error in &errors {
+            warn!("Skipping marketplace {}", error);
+        }
+        
+        for listing in manifest.templates {
+            if self.templates.get(&listing.id).is_some_and(|entry| entry.download.is_none()) {
+                warn!("Marketplace template '{}' clashes with an installed template", listing.id);
+                continue;
+            }
+            let id = listing.id.clone();
+            self.templates.insert(id, Self::marketplace_entry(listing));
+        }
+        
+        info!("Loaded marketplace manifest ({} rejected entries)", errors.len());
+        Ok(errors)
+    }
+    
+    fn marketplace_entry(listing: MarketplaceTemplate) -> TemplateEntry {
+        let author = if listing.author.is_empty() { "Community".to_string() } else { listing.author };
+        TemplateEntry {
+            template: ProjectTemplate {
+                id: uuid::Uuid::new_v4(),
+                name: listing.name,
+                description: listing.description,
+                category: listing.category,
+                preview_image: None,
+                tags: listing.tags.clone(),
+                difficulty: crate::ui::template_creator::TemplateDifficulty::Beginner,
+                initial_nodes: Vec::new(),
+                initial_connections: Vec::new(),
+                initial_properties: HashMap::new(),
+                canvas_settings: crate::ui::template_creator::CanvasSettings {
+                    background_color: [0.1, 0.1, 0.1, 1.0],
+                    grid_size: 20.0,
+                    snap_to_grid: true,
+                    show_grid: true,
+                    canvas_size: Vec2::new(800.0, 600.0),
+                },
+                gui_elements: Vec::new(),
+                gui_layout: crate::ui::template_creator::GuiLayout {
+                    layout_type: crate::ui::template_creator::LayoutType::Fixed,
+                    constraints: crate::ui::template_creator::LayoutConstraints {
+                        min_width: None,
+                        max_width: None,
+                        min_height: None,
+                        max_height: None,
+                        aspect_ratio: None,
+                    },
+                },
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                version: listing.version.clone(),
+                author: author.clone(),
+            },
+            metadata: TemplateMetadata {
+                id: listing.id,
+                author,
+                version: listing.version,
+                license: String::new(),
+                created_date: String::new(),
+                modified_date: String::new(),
+                tags: listing.tags,
+                complexity: ComplexityLevel::Beginner,
+                rating: listing.rating,
+                download_count: 0,
+                featured: false,
+            },
+            preview_data: PreviewData {
+                thumbnail_path: None,
+                screenshot_paths: Vec::new(),
+                demo_project_path: None,
+                preview_html: None,
+                code_samples: Vec::new(),
+            },
+            statistics: TemplateStatistics {
+                usage_count: 0,
+                success_rate: 0.0,
+                average_completion_time: 0.0,
+                user_ratings: Vec::new(),
+                feedback_comments: Vec::new(),
+            },
+            download: Some(TemplateDownload {
+                url: listing.download_url,
+                checksum: listing.checksum,
+            }),
+        }
+    }
+    
     pub fn render(&self, theme: &Marco2Theme) {
         if !self.visible {
             return;
@@ -507,6 +630,7 @@ impl TemplateGallery {
                     "Easy to understand and extend".to_string(),
                 ],
             },
+            download: None,
         };
         
         self.templates.insert("calculator_app".to_string(), calculator_entry);
@@ -590,6 +714,7 @@ impl TemplateGallery {
                     "Very professional looking".to_string(),
                 ],
             },
+            download: None,
         };
         
         self.templates.insert("analytics_dashboard".to_string(), dashboard_entry);
@@ -640,3 +765,49 @@ impl std::fmt::Display for TemplateCategory {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKSUM: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    #[test]
+    fn test_load_manifest_keeps_valid_entries_and_reports_invalid() {
+        let manifest = format!(r#"{{
+            "schema_version": 1,
+            "templates": [
+                {{ "id": "particle_sim", "name": "Particle Sim", "version": "1.2.0",
+                   "download_url": "https://example.com/particle_sim.zip", "checksum": "{0}",
+                   "category": "Simulation", "rating": 4.2 }},
+                {{ "id": "kanban", "name": "Kanban Board", "version": "0.3.1",
+                   "download_url": "https://example.com/kanban.zip", "checksum": "{0}",
+                   "category": "Dashboard", "rating": 3.9, "author": "jdoe" }},
+                {{ "id": "broken", "name": "Broken", "version": "1.0",
+                   "download_url": "http://example.com/broken.zip", "checksum": "{0}",
+                   "category": "Game" }}
+            ]
+        }}"#, CHECKSUM);
+
+        let mut gallery = TemplateGallery::new();
+        let builtin = gallery.templates.len();
+        let errors = gallery.load_manifest(&manifest).unwrap();
+
+        assert_eq!(gallery.templates.len(), builtin + 2);
+        let kanban = &gallery.templates["kanban"];
+        assert_eq!(kanban.metadata.author, "jdoe");
+        assert_eq!(kanban.template.category, TemplateCategory::Dashboard);
+        assert_eq!(kanban.download.as_ref().map(|d| d.checksum.as_str()), Some(CHECKSUM));
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].index, errors[0].id.as_deref()), (2, Some("broken")));
+        assert!(!gallery.templates.contains_key("broken"));
+    }
+
+    #[test]
+    fn test_load_manifest_rejects_unreadable_document() {
+        let mut gallery = TemplateGallery::new();
+        assert!(gallery.load_manifest("{ \"templates\": 3 }").is_err());
+        assert!(gallery.load_manifest(r#"{ "schema_version": 99, "templates": [] }"#).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+//! Template marketplace manifest
+//!
+//! A marketplace publishes a JSON manifest listing the community templates
+//! it offers. Entries are validated one at a time so a single bad listing
+//! does not hide the rest of the catalogue.
+use crate::core::types::error::MarcoError;
+use crate::ui::template_creator::TemplateCategory;
+use serde::{Deserialize, Serialize};
+
+/// Manifest schema version understood by this build
+pub const MANIFEST_SCHEMA_VERSION: u32 = 1;
+
+/// Catalogue of installable community templates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketplaceManifest {
+    pub schema_version: u32,
+    pub templates: Vec<MarketplaceTemplate>,
+}
+
+/// One template listing in a manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketplaceTemplate {
+    pub id: String,
+    pub name: String,
+    /// Semantic version, "major.minor.patch"
+    pub version: String,
+    /// HTTPS location of the template archive
+    pub download_url: String,
+    /// Hex-encoded SHA-256 of the archive
+    pub checksum: String,
+    pub category: TemplateCategory,
+    /// Average rating from 0 to 5
+    #[serde(default)]
+    pub rating: f32,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Why a manifest entry was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestEntryError {
+    /// Position of the entry in the manifest's `templates` array
+    pub index: usize,
+    /// The entry's id, when it had a readable one
+    pub id: Option<String>,
+    pub message: String,
+}
+
+impl std::fmt::Display for ManifestEntryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.id {
+            Some(id) => write!(f, "template #{} ('{}'): {}", self.index, id, self.message),
+            None => write!(f, "template #{}: {}", self.index, self.message),
+        }
+    }
+}
+
+/// Manifest document with entries not yet validated
+#[derive(Deserialize)]
+struct RawManifest {
+    schema_version: u32,
+    templates: Vec<serde_json::Value>,
+}
+
+impl MarketplaceManifest {
+    /// Parse a manifest, keeping the valid entries and reporting the rest
+    ///
+    /// Fails only when the document itself is unreadable or uses an
+    /// unsupported schema version.
+    pub fn parse(json: &str) -> Result<(Self, Vec<ManifestEntryError>), MarcoError> {
+        let raw: RawManifest = serde_json::from_str(json)
+            .map_err(|e| MarcoError::Persistence(format!("Invalid marketplace manifest: {}", e)))?;
+        if raw.schema_version != MANIFEST_SCHEMA_VERSION {
+            return Err(MarcoError::Persistence(format!(
+                "Unsupported marketplace manifest version {} (expected {})",
+                raw.schema_version, MANIFEST_SCHEMA_VERSION
+            )));
+        }
+
+        let mut templates = Vec::new();
+        let mut errors = Vec::new();
+        for (index, value) in raw.templates.into_iter().enumerate() {
+            let id = value.get("id").and_then(|id| id.as_str()).map(str::to_string);
+            let result = serde_json::from_value::<MarketplaceTemplate>(value)
+                .map_err(|e| e.to_string())
+                .and_then(|template| template.validate().map(|_| template));
+            match result {
+                Ok(template) => templates.push(template),
+                Err(message) => errors.push(ManifestEntryError { index, id, message }),
+            }
+        }
+
+        Ok((Self { schema_version: raw.schema_version, templates }, errors))
+    }
+}
+
+impl MarketplaceTemplate {
+    /// Check field formats beyond what deserialization enforces
+    pub fn validate(&self) -> Result<(), String> {
+        let id_ok = !self.id.is_empty()
+            && self.id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+        if !id_ok {
+            return Err("id must be non-empty lowercase letters, digits, '_' or '-'".to_string());
+        }
+        if self.name.trim().is_empty() {
+            return Err("name must not be empty".to_string());
+        }
+        let parts: Vec<&str> = self.version.split('.').collect();
+        if parts.len() != 3 || parts.iter().any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit())) {
+            return Err(format!("version '{}' is not major.minor.patch", self.version));
+        }
+        if !self.download_url.starts_with("https://") || self.download_url.len() <= "https://".len() {
+            return Err(format!("download_url '{}' must be an https URL", self.download_url));
+        }
+        if self.checksum.len() != 64 || !self.checksum.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("checksum must be a 64-character hex SHA-256".to_string());
+        }
+        if !(0.0..=5.0).contains(&self.rating) {
+            return Err(format!("rating {} is outside 0-5", self.rating));
+        }
+        if self.category == TemplateCategory::All {
+            return Err("category 'All' is a filter, not a template category".to_string());
+        }
+        Ok(())
+    }
+}
@@ -2,9 +2,15 @@
 //! 
 //! Consistent visual styling and theming for the IDE.
 //! Updated for WGPU rendering system
+//!
+//! Themes serialize to JSON so custom ones can be saved and shared.
+
+use crate::core::types::ColorRGBA;
+use crate::core::types::error::MarcoError;
+use serde::{Deserialize, Serialize};
 
 /// RGBA color representation for WGPU
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Color {
     pub r: f32,
     pub g: f32,
@@ -34,10 +40,37 @@ impl Color {
     pub fn as_array(&self) -> [f32; 4] {
         [self.r, self.g, self.b, self.a]
     }
+    
+    /// WCAG relative luminance of the sRGB channels, ignoring alpha
+    pub fn relative_luminance(&self) -> f32 {
+        let linear = |c: f32| if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) };
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+    
+    /// WCAG contrast ratio against `other`, from 1 (none) to 21
+    pub fn contrast_ratio(&self, other: &Color) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+}
+
+impl From<ColorRGBA> for Color {
+    fn from(color: ColorRGBA) -> Self {
+        Self { r: color.r, g: color.g, b: color.b, a: color.a }
+    }
+}
+
+impl From<Color> for ColorRGBA {
+    fn from(color: Color) -> Self {
+        ColorRGBA::new(color.r, color.g, color.b, color.a)
+    }
 }
 
 /// Marco 2.0 visual theme configuration
-#[derive(Debug, Clone)]
+///
+/// Fields missing from a saved theme take their default values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Marco2Theme {
     // Color palette
     pub primary_color: Color,
@@ -91,6 +124,14 @@ pub struct Marco2Theme {
     pub grid_color: Color,
     pub grid_size: f32,
     pub canvas_background: Color,
+    /// Narrowest connection line drawn, in world units; thicker
+    /// connections keep their own width
+    pub connection_thickness: f32,
+    /// Width of the node outline in world units
+    pub node_border_width: f32,
+    /// Screen pixels per world unit below which the node renderer drops to
+    /// plain rectangles and straight connections
+    pub lod_zoom_threshold: f32,
 }
 
 impl Default for Marco2Theme {
@@ -148,11 +189,103 @@ impl Default for Marco2Theme {
             grid_color: Color::rgb(40, 40, 50),
             grid_size: 20.0,
             canvas_background: Color::rgb(20, 20, 25),
+            connection_thickness: 2.0,
+            node_border_width: 2.0,
+            lod_zoom_threshold: 0.4,
         }
     }
 }
 
 impl Marco2Theme {
+    /// The default dark theme
+    pub fn dark() -> Self {
+        Self::default()
+    }
+    
+    /// Built-in theme by name: `dark`, `light` or `high_contrast`
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high_contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+    
+    pub fn to_json(&self) -> Result<String, MarcoError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| MarcoError::Persistence(format!("Failed to serialize theme: {}", e)))
+    }
+    
+    pub fn load_from_str(json: &str) -> Result<Self, MarcoError> {
+        serde_json::from_str(json)
+            .map_err(|e| MarcoError::Persistence(format!("Failed to parse theme: {}", e)))
+    }
+    
+    /// Every color field with its name, in declaration order
+    pub fn color_fields(&self) -> Vec<(&'static str, Color)> {
+        vec![
+            ("primary_color", self.primary_color),
+            ("secondary_color", self.secondary_color),
+            ("accent_color", self.accent_color),
+            ("background_color", self.background_color),
+            ("surface_color", self.surface_color),
+            ("text_color", self.text_color),
+            ("secondary_text_color", self.secondary_text_color),
+            ("error_color", self.error_color),
+            ("warning_color", self.warning_color),
+            ("success_color", self.success_color),
+            ("node_add_color", self.node_add_color),
+            ("node_multiply_color", self.node_multiply_color),
+            ("node_constant_color", self.node_constant_color),
+            ("node_logic_color", self.node_logic_color),
+            ("node_conditional_color", self.node_conditional_color),
+            ("node_button_color", self.node_button_color),
+            ("node_slider_color", self.node_slider_color),
+            ("border_color", self.border_color),
+            ("hover_color", self.hover_color),
+            ("selected_color", self.selected_color),
+            ("button_background", self.button_background),
+            ("card_background", self.card_background),
+            ("heading_color", self.heading_color),
+            ("grid_color", self.grid_color),
+            ("canvas_background", self.canvas_background),
+        ]
+    }
+    
+    /// Color field by the name `color_fields` gives it
+    pub fn color_field_mut(&mut self, name: &str) -> Option<&mut Color> {
+        let color = match name {
+            "primary_color" => &mut self.primary_color,
+            "secondary_color" => &mut self.secondary_color,
+            "accent_color" => &mut self.accent_color,
+            "background_color" => &mut self.background_color,
+            "surface_color" => &mut self.surface_color,
+            "text_color" => &mut self.text_color,
+            "secondary_text_color" => &mut self.secondary_text_color,
+            "error_color" => &mut self.error_color,
+            "warning_color" => &mut self.warning_color,
+            "success_color" => &mut self.success_color,
+            "node_add_color" => &mut self.node_add_color,
+            "node_multiply_color" => &mut self.node_multiply_color,
+            "node_constant_color" => &mut self.node_constant_color,
+            "node_logic_color" => &mut self.node_logic_color,
+            "node_conditional_color" => &mut self.node_conditional_color,
+            "node_button_color" => &mut self.node_button_color,
+            "node_slider_color" => &mut self.node_slider_color,
+            "border_color" => &mut self.border_color,
+            "hover_color" => &mut self.hover_color,
+            "selected_color" => &mut self.selected_color,
+            "button_background" => &mut self.button_background,
+            "card_background" => &mut self.card_background,
+            "heading_color" => &mut self.heading_color,
+            "grid_color" => &mut self.grid_color,
+            "canvas_background" => &mut self.canvas_background,
+            _ => return None,
+        };
+        Some(color)
+    }
+    
     /// Create a light theme variant
     pub fn light() -> Self {
         Self {
@@ -172,18 +305,25 @@ impl Marco2Theme {
     }
     
     /// Create a high contrast theme variant
+    ///
+    /// White on black with bright outlines and thicker lines, for low-vision
+    /// users.
     pub fn high_contrast() -> Self {
         Self {
+            accent_color: Color::rgb(255, 220, 0),
             background_color: Color::rgb(0, 0, 0),
             surface_color: Color::rgb(20, 20, 20),
             text_color: Color::rgb(255, 255, 255),
-            secondary_text_color: Color::rgb(200, 200, 200),
-            border_color: Color::rgb(100, 100, 100),
+            secondary_text_color: Color::rgb(220, 220, 220),
+            border_color: Color::rgb(200, 200, 200),
             hover_color: Color::rgb(40, 40, 40),
+            selected_color: Color::rgb(0, 200, 255),
             button_background: Color::rgb(60, 60, 60),
             card_background: Color::rgb(30, 30, 30),
             grid_color: Color::rgb(80, 80, 80),
-            canvas_background: Color::rgb(10, 10, 10),
+            canvas_background: Color::rgb(0, 0, 0),
+            connection_thickness: 4.0,
+            node_border_width: 3.0,
             ..Self::default()
         }
     }
@@ -221,3 +361,41 @@ impl Marco2Theme {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_round_trips_through_json() {
+        let mut theme = Marco2Theme::high_contrast();
+        *theme.color_field_mut("accent_color").unwrap() = Color::rgba(12, 34, 56, 78);
+        theme.grid_size = 32.0;
+
+        let loaded = Marco2Theme::load_from_str(&theme.to_json().unwrap()).unwrap();
+        assert_eq!(loaded.color_fields(), theme.color_fields());
+        assert_eq!(loaded.accent_color, Color::rgba(12, 34, 56, 78));
+        assert_eq!(loaded, theme);
+
+        // Partial themes fill in the defaults
+        let partial = Marco2Theme::load_from_str(r#"{"text_color": {"r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0}}"#).unwrap();
+        assert_eq!(partial.text_color, Color::rgb(255, 0, 0));
+        assert_eq!(partial.background_color, Marco2Theme::dark().background_color);
+        assert!(Marco2Theme::load_from_str("{").is_err());
+        assert_eq!(Marco2Theme::preset("light"), Some(Marco2Theme::light()));
+        assert_eq!(Marco2Theme::preset("sepia"), None);
+    }
+
+    #[test]
+    fn test_high_contrast_separates_text_from_background() {
+        let theme = Marco2Theme::high_contrast();
+        assert!(theme.text_color.contrast_ratio(&theme.background_color) > 7.0);
+        assert!(theme.text_color.contrast_ratio(&theme.canvas_background) > 7.0);
+        assert!(theme.secondary_text_color.contrast_ratio(&theme.background_color) > 7.0);
+        assert!(theme.connection_thickness > Marco2Theme::dark().connection_thickness);
+        assert!(theme.node_border_width > Marco2Theme::dark().node_border_width);
+
+        assert!((Color::rgb(255, 255, 255).contrast_ratio(&Color::rgb(0, 0, 0)) - 21.0).abs() < 1e-3);
+        assert_eq!(Color::rgb(90, 90, 90).contrast_ratio(&Color::rgb(90, 90, 90)), 1.0);
+    }
+}
@@ -8,6 +8,15 @@ use std::collections::HashMap;
 use glam::Vec2;
 use std::time::{Duration, Instant};
 
+/// Weight of the newest sample in a pan's smoothed velocity
+const PAN_VELOCITY_SMOOTHING: f32 = 0.5;
+
+/// Shortest interval a pan velocity sample is measured over
+const MIN_PAN_SAMPLE_INTERVAL: Duration = Duration::from_millis(1);
+
+/// A finger resting this long before lifting ends a pan without a fling
+const FLING_MAX_PAUSE: Duration = Duration::from_millis(100);
+
 /// Unique identifier for touch points
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TouchId(pub u64);
@@ -73,12 +82,21 @@ pub enum TouchGesture {
         start_position: Vec2,
         current_position: Vec2,
         delta: Vec2,
+        /// Smoothed finger velocity in pixels per second; zero on the final
+        /// pan of a touch that paused before lifting
+        velocity: Vec2,
     },
     Pinch {
         center: Vec2,
         scale: f32,
         rotation: f32,
     },
+    /// Two fingers twisting; `angle_delta` is the change in radians since
+    /// the last rotate, positive clockwise on screen
+    Rotate {
+        center: Vec2,
+        angle_delta: f32,
+    },
     Swipe {
         start_position: Vec2,
         end_position: Vec2,
@@ -87,6 +105,21 @@ pub enum TouchGesture {
     },
 }
 
+impl TouchGesture {
+    /// Short lowercase name of the gesture kind, for telemetry
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TouchGesture::Tap { .. } => "tap",
+            TouchGesture::DoubleTap { .. } => "double_tap",
+            TouchGesture::LongPress { .. } => "long_press",
+            TouchGesture::Pan { .. } => "pan",
+            TouchGesture::Pinch { .. } => "pinch",
+            TouchGesture::Rotate { .. } => "rotate",
+            TouchGesture::Swipe { .. } => "swipe",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SwipeDirection {
     Up,
@@ -128,6 +161,8 @@ pub struct GestureThresholds {
     pub pan_min_distance: f32,
     pub swipe_min_velocity: f32,
     pub pinch_min_distance: f32,
+    /// Twist in radians accumulated before a rotate is reported
+    pub rotate_min_angle: f32,
 }
 
 impl Default for GestureThresholds {
@@ -140,6 +175,7 @@ impl Default for GestureThresholds {
             pan_min_distance: 5.0,
             swipe_min_velocity: 200.0, // pixels per second
             pinch_min_distance: 20.0,
+            rotate_min_angle: 0.01,
         }
     }
 }
@@ -174,6 +210,12 @@ impl TouchTool {
     pub fn supports_multi_touch(&self) -> bool {
         matches!(self, TouchTool::Pan | TouchTool::Zoom | TouchTool::Gesture)
     }
+    
+    /// Whether one-finger pans and two-finger pinches and twists are
+    /// recognized; Select uses them to drag and rotate nodes
+    pub fn supports_drag(&self) -> bool {
+        self.supports_multi_touch() || *self == TouchTool::Select
+    }
 }
 
 /// Touch tool configuration
@@ -204,8 +246,10 @@ enum GestureState {
     PotentialTap { touch_id: TouchId, start_time: Instant },
     PotentialDoubleTap { first_tap_time: Instant, first_tap_pos: Vec2 },
     LongPressWaiting { touch_id: TouchId, start_time: Instant },
-    Panning { touch_id: TouchId, last_position: Vec2 },
-    Pinching { touch1: TouchId, touch2: TouchId, initial_distance: f32, initial_center: Vec2 },
+    Panning { touch_id: TouchId, last_position: Vec2, last_time: Instant, velocity: Vec2 },
+    /// `initial_span` and `last_span` run from the first touch to the second,
+    /// at the start and at the last reported rotate
+    Pinching { touch1: TouchId, touch2: TouchId, initial_distance: f32, initial_span: Vec2, last_span: Vec2 },
     Swiping { touch_id: TouchId, start_position: Vec2 },
 }
 
@@ -320,45 +364,68 @@ impl TouchHandler {
                         self.recognize_long_press(touch.position, duration);
                         self.gesture_state = GestureState::Idle;
                     } else if distance >= self.thresholds.pan_min_distance {
-                        if self.tools.active_tool.supports_multi_touch() {
+                        if self.tools.active_tool.supports_drag() {
                             self.gesture_state = GestureState::Panning { 
                                 touch_id, 
-                                last_position: touch.position 
+                                last_position: touch.position,
+                                last_time: now,
+                                velocity: Vec2::ZERO,
                             };
                         }
                     }
                 }
             },
             
-            (GestureState::Panning { touch_id, last_position }, 1) => {
+            (GestureState::Panning { touch_id, last_position, last_time, velocity }, 1) => {
                 if let Some(touch) = self.active_touches.get(&touch_id) {
                     let start_pos = touch.start_position;
                     let current_pos = touch.position;
                     let delta = current_pos - last_position;
-                    self.recognize_pan(start_pos, current_pos, delta);
+                    let interval = now.duration_since(last_time).max(MIN_PAN_SAMPLE_INTERVAL);
+                    let velocity = velocity.lerp(delta / interval.as_secs_f32(), PAN_VELOCITY_SMOOTHING);
+                    self.recognize_pan(start_pos, current_pos, delta, velocity);
                     self.gesture_state = GestureState::Panning { 
                         touch_id, 
-                        last_position: current_pos 
+                        last_position: current_pos,
+                        last_time: now,
+                        velocity,
                     };
                 }
             },
             
-            (_, 2) => {
-                if self.tools.active_tool.supports_multi_touch() {
-                    self.start_pinch_gesture();
-                }
-            },
-            
-            (GestureState::Pinching { touch1, touch2, initial_distance, initial_center }, 2) => {
+            (GestureState::Pinching { touch1, touch2, initial_distance, initial_span, last_span }, 2) => {
                 if let (Some(t1), Some(t2)) = (self.active_touches.get(&touch1), self.active_touches.get(&touch2)) {
                     let current_distance = t1.position.distance(t2.position);
                     let current_center = (t1.position + t2.position) * 0.5;
+                    let current_span = t2.position - t1.position;
                     let scale = current_distance / initial_distance;
                     
-                    self.recognize_pinch(current_center, scale, 0.0); // TODO: Calculate rotation
+                    self.recognize_pinch(current_center, scale, initial_span.angle_between(current_span));
+                    
+                    // Small twists accumulate until they pass the threshold
+                    let angle_delta = last_span.angle_between(current_span);
+                    if angle_delta.abs() >= self.thresholds.rotate_min_angle {
+                        self.recognize_rotate(current_center, angle_delta);
+                        self.gesture_state = GestureState::Pinching {
+                            touch1,
+                            touch2,
+                            initial_distance,
+                            initial_span,
+                            last_span: current_span,
+                        };
+                    }
                 }
             },
             
+            (_, 2) => {
+                if self.tools.active_tool.supports_drag() {
+                    self.start_pinch_gesture();
+                }
+            },
+            
+            // A released tap waits for a second one or for `update` to time it out
+            (GestureState::PotentialDoubleTap { .. }, _) => {},
+            
             _ => {
                 if active_count == 0 {
                     self.gesture_state = GestureState::Idle;
@@ -374,13 +441,17 @@ impl TouchHandler {
         let velocity = touch.velocity().length();
         
         match &self.gesture_state {
-            GestureState::TouchDown { .. } | GestureState::PotentialTap { .. } => {
+            GestureState::TouchDown { .. } | GestureState::PotentialTap { .. } | GestureState::PotentialDoubleTap { .. } => {
                 if duration <= self.thresholds.tap_max_duration && distance <= self.thresholds.tap_max_distance {
                     self.handle_potential_tap(touch.position);
                 }
             },
             
-            GestureState::Panning { .. } => {
+            GestureState::Panning { last_time, .. } => {
+                // A finger held still before lifting should not fling
+                if last_time.elapsed() > FLING_MAX_PAUSE {
+                    self.recognize_pan(touch.start_position, touch.position, Vec2::ZERO, Vec2::ZERO);
+                }
                 if velocity >= self.thresholds.swipe_min_velocity {
                     if let Some(direction) = SwipeDirection::from_vector(touch.position - touch.start_position) {
                         self.recognize_swipe(touch.start_position, touch.position, direction, velocity);
@@ -429,14 +500,15 @@ impl TouchHandler {
             let (id2, touch2) = touches[1];
             
             let initial_distance = touch1.position.distance(touch2.position);
-            let initial_center = (touch1.position + touch2.position) * 0.5;
+            let initial_span = touch2.position - touch1.position;
             
             if initial_distance >= self.thresholds.pinch_min_distance {
                 self.gesture_state = GestureState::Pinching {
                     touch1: *id1,
                     touch2: *id2,
                     initial_distance,
-                    initial_center,
+                    initial_span,
+                    last_span: initial_span,
                 };
             }
         }
@@ -461,11 +533,12 @@ impl TouchHandler {
     }
     
     /// Recognize pan gesture
-    fn recognize_pan(&mut self, start_position: Vec2, current_position: Vec2, delta: Vec2) {
+    fn recognize_pan(&mut self, start_position: Vec2, current_position: Vec2, delta: Vec2, velocity: Vec2) {
         self.pending_gestures.push(TouchGesture::Pan { 
             start_position, 
             current_position, 
-            delta 
+            delta,
+            velocity,
         });
     }
     
@@ -474,6 +547,12 @@ impl TouchHandler {
         self.pending_gestures.push(TouchGesture::Pinch { center, scale, rotation });
     }
     
+    /// Recognize rotate gesture
+    fn recognize_rotate(&mut self, center: Vec2, angle_delta: f32) {
+        self.pending_gestures.push(TouchGesture::Rotate { center, angle_delta });
+        tracing::debug!("Recognized rotate of {} rad around {:?}", angle_delta, center);
+    }
+    
     /// Recognize swipe gesture
     fn recognize_swipe(&mut self, start_position: Vec2, end_position: Vec2, direction: SwipeDirection, velocity: f32) {
         self.pending_gestures.push(TouchGesture::Swipe { 
@@ -531,3 +610,46 @@ impl TouchHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_6, PI};
+
+    #[test]
+    fn test_two_finger_twist_reports_the_angle_change() {
+        let mut handler = TouchHandler::new();
+        handler.set_tool(TouchTool::Gesture);
+        let center = Vec2::new(200.0, 200.0);
+        let finger = |angle: f32| center + Vec2::from_angle(angle) * 100.0;
+
+        handler.touch_down(TouchId(1), finger(PI), 1.0);
+        handler.touch_down(TouchId(2), finger(0.0), 1.0);
+        // Turn both fingers 30 degrees clockwise on screen, in three steps
+        for step in 1..=3 {
+            let angle = step as f32 * FRAC_PI_6 / 3.0;
+            handler.touch_move(TouchId(1), finger(PI + angle), 1.0);
+            handler.touch_move(TouchId(2), finger(angle), 1.0);
+        }
+
+        let gestures = handler.drain_gestures();
+        let rotates: Vec<(Vec2, f32)> = gestures.iter()
+            .filter_map(|gesture| match gesture {
+                TouchGesture::Rotate { center, angle_delta } => Some((*center, *angle_delta)),
+                _ => None,
+            })
+            .collect();
+        assert!(rotates.len() >= 3);
+        assert!(rotates.iter().all(|(_, delta)| *delta > 0.0));
+        let total: f32 = rotates.iter().map(|(_, delta)| delta).sum();
+        assert!((total - FRAC_PI_6).abs() < 1e-4, "turned {} rad", total);
+        assert!(rotates.last().unwrap().0.distance(center) < 1e-3);
+
+        // The pinch reports the same twist from the start, at unchanged scale
+        let Some(TouchGesture::Pinch { scale, rotation, .. }) = gestures.iter().rev().find(|g| g.kind() == "pinch") else {
+            panic!("no pinch recognized");
+        };
+        assert!((scale - 1.0).abs() < 1e-4);
+        assert!((rotation - FRAC_PI_6).abs() < 1e-4);
+    }
+}
@@ -1,15 +1,96 @@
 //! Visual Node Editor for Marco 2.0
 //! Provides drag-and-drop node editing with real-time connections
-use crate::core::logic::{Evaluatable, InputMap, OutputMap, EvalContext};
-use crate::core::types::{MetaValue, DotPath};
+use crate::core::logic::{Evaluatable, InputMap, OutputMap, EvalContext, NodeStateStore};
+use crate::core::types::{MetaValue, DotPath, ScopeId};
 use crate::core::types::error::MarcoError;
 use crate::ui::theme::Marco2Theme;
-use crate::core::logic::node_registry::NodeRegistry;
+use crate::ui::node_library_panel::NodeCategory;
+use crate::core::logic::node_registry::{self, NodeRegistry};
+use crate::render::connection_routing::{cubic_bezier, default_controls};
+use crate::graph::runtime::{topological_sort, NodeId};
+use crate::core::registry::{MetaRegistry, Snapshot};
 use glam::Vec2;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use uuid::Uuid;
 use tracing::{info, warn};
 
+/// Distance in canvas units within which a click selects a connection
+const CONNECTION_HIT_TOLERANCE: f32 = 6.0;
+
+/// Distance in canvas units within which a drop lands on a socket
+const SOCKET_HIT_RADIUS: f32 = 10.0;
+
+/// Mouse button index that drags the canvas instead of nodes
+pub const PAN_MOUSE_BUTTON: u32 = 1;
+
+/// Zoom limits for `canvas_scale`, matching the mobile viewport
+pub const MIN_CANVAS_SCALE: f32 = 0.1;
+pub const MAX_CANVAS_SCALE: f32 = 5.0;
+
+/// Minor grid lines per major grid cell in a new editor
+pub const DEFAULT_GRID_SUBDIVISIONS: u32 = 4;
+
+/// Node type standing in for a node this build cannot create
+///
+/// Placeholders keep the original type in their `original_type` property
+/// and grow `Any`-typed sockets for whatever connects to them, so the rest
+/// of an imported graph stays wired.
+pub const PLACEHOLDER_NODE_TYPE: &str = "placeholder";
+
+/// Node type of the stand-in rendered for a collapsed [`NodeGroup`]
+pub const GROUP_NODE_TYPE: &str = "group";
+
+/// Width of a collapsed group's stand-in node
+const COLLAPSED_GROUP_WIDTH: f32 = 160.0;
+
+/// Axis along which `auto_layout` places successive layers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutDirection {
+    /// Sources on the left, one column per layer
+    #[default]
+    LeftToRight,
+    /// Sources at the top, one row per layer
+    TopToBottom,
+}
+
+/// Copied nodes and the connections among them
+///
+/// Self-contained, so it can be pasted into any editor instance, including
+/// the one it was copied from after the originals were deleted.
+#[derive(Debug, Clone, Default)]
+pub struct NodeClipboard {
+    pub nodes: Vec<VisualNode>,
+    /// Connections whose endpoints are both in `nodes`
+    pub connections: Vec<NodeConnectionId>,
+}
+
+impl NodeClipboard {
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Centre of the copied nodes' bounding box
+    pub fn centroid(&self) -> Option<Vec2> {
+        let min = self.nodes.iter().map(|node| node.position - node.size / 2.0).reduce(Vec2::min)?;
+        let max = self.nodes.iter().map(|node| node.position + node.size / 2.0).reduce(Vec2::max)?;
+        Some((min + max) / 2.0)
+    }
+}
+
+/// Nodes that move together and can collapse into a single box
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NodeGroup {
+    pub id: Uuid,
+    pub title: String,
+    pub member_ids: Vec<Uuid>,
+    pub collapsed: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct VisualNode {
     pub id: Uuid,
@@ -21,6 +102,64 @@ pub struct VisualNode {
     pub properties: HashMap<String, MetaValue>,
     pub selected: bool,
     pub title: String,
+    /// Set when the node's properties or inputs changed since it was last
+    /// evaluated; see [`VisualNodeEditor::evaluate_dirty`]
+    pub dirty: bool,
+    /// Stacking order among unselected or selected nodes; higher is in front
+    pub z_order: i32,
+}
+
+impl VisualNode {
+    /// Sort key from back to front: selected nodes above the rest, then
+    /// `z_order`, then id so equal orders stack the same way every frame
+    pub fn stacking_key(&self) -> (bool, i32, Uuid) {
+        (self.selected, self.z_order, self.id)
+    }
+    
+    /// Canvas position of a socket, or None if the node has no such socket
+    ///
+    /// Inputs sit on the left edge and outputs on the right, spaced evenly
+    /// in sorted name order.
+    pub fn socket_position(&self, kind: SocketKind, socket: &str) -> Option<Vec2> {
+        let (mut names, side): (Vec<&String>, f32) = match kind {
+            SocketKind::Input => (self.inputs.keys().collect(), -1.0),
+            SocketKind::Output => (self.outputs.keys().collect(), 1.0),
+        };
+        names.sort();
+        let index = names.iter().position(|name| name.as_str() == socket)?;
+        let spacing = self.size.y / (names.len() + 1) as f32;
+        Some(Vec2::new(
+            self.position.x + side * self.size.x / 2.0,
+            self.position.y - self.size.y / 2.0 + spacing * (index + 1) as f32,
+        ))
+    }
+    
+    /// World position of an input socket on the left edge
+    pub fn input_socket_position(&self, name: &str) -> Option<Vec2> {
+        self.socket_position(SocketKind::Input, name)
+    }
+    
+    /// World position of an output socket on the right edge
+    pub fn output_socket_position(&self, name: &str) -> Option<Vec2> {
+        self.socket_position(SocketKind::Output, name)
+    }
+    
+    /// Unselected copy with a fresh id, moved by `offset` and with every
+    /// connection cleared
+    pub fn detached_copy(&self, offset: Vec2) -> Self {
+        let mut copy = self.clone();
+        copy.id = Uuid::new_v4();
+        copy.position += offset;
+        copy.selected = false;
+        copy.dirty = true;
+        for input in copy.inputs.values_mut() {
+            input.connected_output = None;
+        }
+        for output in copy.outputs.values_mut() {
+            output.connections.clear();
+        }
+        copy
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -50,7 +189,62 @@ pub enum NodeDataType {
     Any,
 }
 
-#[derive(Debug, Clone)]
+impl NodeDataType {
+    /// Value an unconnected input of this type evaluates to without a default
+    pub fn zero_value(&self) -> MetaValue {
+        match self {
+            NodeDataType::Vector2 => MetaValue::Vec2(Vec2::ZERO),
+            NodeDataType::Vector3 => MetaValue::Vec3(glam::Vec3::ZERO),
+            _ => MetaValue::Scalar(0.0),
+        }
+    }
+    
+    /// Socket type for a spec type name such as `"scalar"` or `"bool"`
+    pub fn from_type_name(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "scalar" | "number" | "float" => NodeDataType::Scalar,
+            "bool" | "boolean" => NodeDataType::Boolean,
+            "string" | "text" => NodeDataType::String,
+            "color" => NodeDataType::Color,
+            "vec2" | "vector2" => NodeDataType::Vector2,
+            "vec3" | "vector3" => NodeDataType::Vector3,
+            "list" => NodeDataType::List,
+            _ => NodeDataType::Any,
+        }
+    }
+
+    /// Socket type matching a value, `Any` for values without a socket type
+    pub fn of_value(value: &MetaValue) -> Self {
+        match value {
+            MetaValue::Scalar(_) => NodeDataType::Scalar,
+            MetaValue::Bool(_) => NodeDataType::Boolean,
+            MetaValue::String(_) => NodeDataType::String,
+            MetaValue::Color(_) => NodeDataType::Color,
+            MetaValue::List(_) => NodeDataType::List,
+            MetaValue::Vec2(_) => NodeDataType::Vector2,
+            MetaValue::Vec3(_) => NodeDataType::Vector3,
+            _ => NodeDataType::Any,
+        }
+    }
+}
+
+/// Which side of a node a socket belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SocketKind {
+    Input,
+    Output,
+}
+
+impl std::fmt::Display for SocketKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SocketKind::Input => write!(f, "input"),
+            SocketKind::Output => write!(f, "output"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct NodeConnectionId {
     pub from_node: Uuid,
     pub from_output: String,
@@ -58,44 +252,439 @@ pub struct NodeConnectionId {
     pub to_input: String,
 }
 
+/// Outcome of [`VisualNodeEditor::connect_nodes`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConnectionResult {
+    /// Connection that fed the input before and was removed to make room
+    pub replaced: Option<NodeConnectionId>,
+}
+
+/// Thickness of connections without an override, in canvas units
+pub const DEFAULT_CONNECTION_THICKNESS: f32 = 2.0;
+
 #[derive(Debug, Clone)]
 pub struct NodeConnection {
+    /// Stable handle assigned at creation and kept across reconnects
+    pub uuid: Uuid,
+    /// Endpoints of the connection, used for topology
     pub id: NodeConnectionId,
+    /// Color drawn: the override, or the color of the output's data type
     pub color: [f32; 4],
+    /// Thickness drawn: the override, or `DEFAULT_CONNECTION_THICKNESS`
     pub thickness: f32,
+    /// Visual overrides set on this connection
+    pub style: ConnectionStyle,
+}
+
+/// How a connection's curve is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionRouting {
+    Bezier,
+    Straight,
+    /// Horizontal and vertical segments only
+    Orthogonal,
+}
+
+/// Per-connection visual overrides; None keeps the type-based default
+///
+/// Only set fields are serialized, so saved graphs pick up changes to the
+/// defaults for everything a user did not override.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ConnectionStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<[f32; 4]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thickness: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routing: Option<ConnectionRouting>,
+    /// Animate data flowing along the connection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flow: Option<bool>,
+}
+
+impl ConnectionStyle {
+    /// No field is overridden
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Restricts which connections are shown; hidden connections are kept
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionFilter {
+    /// Connections whose source output has this data type
+    DataType(NodeDataType),
+    /// Connections touching this node
+    Node(Uuid),
+    /// Connections touching any selected node
+    Selected,
+}
+
+/// One property edit applied to several nodes, undone as a unit
+#[derive(Debug, Clone)]
+pub struct BulkPropertyEdit {
+    pub property: String,
+    /// Each affected node with the property value it had before the edit
+    pub previous: Vec<(Uuid, Option<MetaValue>)>,
+}
+
+/// A deleted connection with the settings it had
+#[derive(Debug, Clone)]
+pub struct RemovedConnection {
+    pub connection: NodeConnection,
+    pub properties: Option<HashMap<String, MetaValue>>,
+}
+
+/// A deleted node with everything needed to put it back
+#[derive(Debug, Clone)]
+pub struct RemovedNode {
+    pub node: VisualNode,
+    /// Connections to and from the node, deleted with it
+    pub connections: Vec<RemovedConnection>,
+    /// Whether the node was marked as a sink
+    pub sink: bool,
+    /// Group the node belonged to, as it was before the removal
+    pub group: Option<NodeGroup>,
+}
+
+/// One undoable step: the operation that reverts a change
+///
+/// Applying an edit yields the edit that reverts it in turn, which is how
+/// undo feeds redo and back.
+#[derive(Debug, Clone)]
+pub enum EditorEdit {
+    /// Put each node's property back to the recorded value
+    Properties(BulkPropertyEdit),
+    /// Re-create deleted connections under their original ids
+    RestoreConnections(Vec<RemovedConnection>),
+    /// Delete connections by stable id
+    RemoveConnections(Vec<Uuid>),
+    RestoreNode(Box<RemovedNode>),
+    RemoveNode(Uuid),
+    /// Move nodes back to the recorded positions
+    MoveNodes(Vec<(Uuid, Vec2)>),
+    /// Several edits undone as one, applied last to first
+    Batch(Vec<EditorEdit>),
+}
+
+/// Edits `CommandStack` keeps by default
+pub const DEFAULT_HISTORY_DEPTH: usize = 100;
+
+/// Undo and redo stacks of an editor
+///
+/// Keeps at most `max_depth` undoable edits, dropping the oldest. Recording
+/// an edit clears the redo stack. Edits recorded between `begin_group` and
+/// the matching `end_group` are undone as one step.
+#[derive(Debug, Clone)]
+pub struct CommandStack {
+    undo: VecDeque<EditorEdit>,
+    redo: Vec<EditorEdit>,
+    max_depth: usize,
+    /// Nesting of open groups
+    group_depth: usize,
+    /// Edits recorded in the open group, oldest first
+    group: Vec<EditorEdit>,
+}
+
+impl Default for CommandStack {
+    fn default() -> Self {
+        Self::with_depth(DEFAULT_HISTORY_DEPTH)
+    }
+}
+
+impl CommandStack {
+    /// A stack keeping up to `max_depth` edits, at least one
+    pub fn with_depth(max_depth: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            max_depth: max_depth.max(1),
+            group_depth: 0,
+            group: Vec::new(),
+        }
+    }
+    
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+    
+    /// Change the cap, dropping the oldest edits beyond it
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth.max(1);
+        while self.undo.len() > self.max_depth {
+            self.undo.pop_front();
+        }
+    }
+    
+    /// Number of edits `undo` can revert
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+    
+    /// Number of edits `redo` can re-apply
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+    
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.group.clear();
+    }
+    
+    /// Start collecting edits into one undo step; groups nest
+    pub fn begin_group(&mut self) {
+        self.group_depth += 1;
+    }
+    
+    /// Close a group, recording its edits as one step once the outermost
+    /// group closes
+    pub fn end_group(&mut self) {
+        if self.group_depth == 0 {
+            return;
+        }
+        self.group_depth -= 1;
+        if self.group_depth == 0 {
+            match self.group.len() {
+                0 => {}
+                1 => {
+                    let edit = self.group.pop().expect("group has one edit");
+                    self.push(edit);
+                }
+                _ => {
+                    let edits = std::mem::take(&mut self.group);
+                    self.push(EditorEdit::Batch(edits));
+                }
+            }
+        }
+    }
+    
+    /// Record a new edit, discarding anything that could be redone
+    pub fn push(&mut self, edit: EditorEdit) {
+        if self.group_depth > 0 {
+            self.group.push(edit);
+            return;
+        }
+        self.push_undo(edit);
+        self.redo.clear();
+    }
+    
+    fn push_undo(&mut self, edit: EditorEdit) {
+        self.undo.push_back(edit);
+        if self.undo.len() > self.max_depth {
+            self.undo.pop_front();
+        }
+    }
+}
+
+/// Serializable form of a node in an [`EditorSnapshot`]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NodeSnapshot {
+    pub id: Uuid,
+    pub node_type: String,
+    pub properties: HashMap<String, MetaValue>,
+}
+
+/// Serializable form of a connection in an [`EditorSnapshot`]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ConnectionSnapshot {
+    pub uuid: Uuid,
+    pub from_node: Uuid,
+    pub from_output: String,
+    pub to_node: Uuid,
+    pub to_input: String,
+}
+
+/// Logical content of an editor's graph, for sharing and the runtime
+///
+/// Layout, selection, viewport and undo history are not captured.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EditorSnapshot {
+    pub nodes: Vec<NodeSnapshot>,
+    pub connections: Vec<ConnectionSnapshot>,
+    #[serde(default)]
+    pub connection_properties: HashMap<Uuid, HashMap<String, MetaValue>>,
+    /// Visual overrides of connections that have any, by connection uuid
+    #[serde(default)]
+    pub connection_styles: HashMap<Uuid, ConnectionStyle>,
+    /// Nodes marked as graph outputs, sorted
+    #[serde(default)]
+    pub sinks: Vec<Uuid>,
+}
+
+/// Grid line spacing for drawing, in screen pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridSpacing {
+    pub major: f32,
+    pub minor: f32,
+}
+
+/// Local-session state of an editor that does not affect evaluation
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EditorUiState {
+    /// Node positions; nodes missing here are laid out automatically
+    #[serde(default)]
+    pub positions: HashMap<Uuid, [f32; 2]>,
+    #[serde(default)]
+    pub selected_nodes: Vec<Uuid>,
+    #[serde(default)]
+    pub selected_connections: Vec<Uuid>,
+    /// Node groups, sorted by id
+    #[serde(default)]
+    pub groups: Vec<NodeGroup>,
+    pub canvas_offset: [f32; 2],
+    pub canvas_scale: f32,
+    pub grid_size: f32,
+    pub snap_to_grid: bool,
+    #[serde(default = "default_grid_subdivisions")]
+    pub grid_subdivisions: u32,
+    #[serde(default)]
+    pub snap_to_subdivisions: bool,
+}
+
+fn default_grid_subdivisions() -> u32 {
+    DEFAULT_GRID_SUBDIVISIONS
+}
+
+impl Default for EditorUiState {
+    fn default() -> Self {
+        Self {
+            positions: HashMap::new(),
+            selected_nodes: Vec::new(),
+            selected_connections: Vec::new(),
+            groups: Vec::new(),
+            canvas_offset: [0.0, 0.0],
+            canvas_scale: 1.0,
+            grid_size: 20.0,
+            snap_to_grid: true,
+            grid_subdivisions: DEFAULT_GRID_SUBDIVISIONS,
+            snap_to_subdivisions: false,
+        }
+    }
+}
+
+/// Graph plus UI state, for resuming a local session
+///
+/// Serializes as the logic fields with an extra `ui` object, so logic-only
+/// JSON loads as a session with default UI state.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EditorSession {
+    #[serde(flatten)]
+    pub graph: EditorSnapshot,
+    #[serde(default)]
+    pub ui: EditorUiState,
 }
 
+/// Spacing of automatically laid out nodes
+const AUTO_LAYOUT_SPACING: Vec2 = Vec2::new(200.0, 120.0);
+
+/// Barycentre passes `auto_layout` makes to reduce crossings
+const LAYOUT_ORDERING_SWEEPS: usize = 4;
+
 /// Visual node editor for creating logic graphs
 #[derive(Debug)]
 pub struct VisualNodeEditor {
     pub nodes: HashMap<Uuid, VisualNode>,
     pub connections: Vec<NodeConnection>,
+    /// Per-connection settings (style, color override, flow) keyed by connection uuid
+    pub connection_properties: HashMap<Uuid, HashMap<String, MetaValue>>,
     pub selected_nodes: Vec<Uuid>,
+    /// Stable ids of selected connections
+    pub selected_connections: HashSet<Uuid>,
+    /// Undo and redo history of editor changes
+    pub history: CommandStack,
+    /// Nodes marked as graph outputs (sinks or output portals)
+    pub sink_nodes: HashSet<Uuid>,
+    /// Outputs from the last evaluation, reused by `evaluate_dirty`
+    pub last_outputs: HashMap<Uuid, OutputMap>,
+    /// Node groups by id; a node belongs to at most one group
+    pub groups: HashMap<Uuid, NodeGroup>,
+    /// Seconds advanced by `update`, the clock time-based nodes read
+    pub elapsed_time: f64,
+    /// State stateful nodes (timers) keep between evaluations
+    pub node_state: NodeStateStore,
+    /// Metadata nodes read through path bindings and `system.*` values
+    pub registry: Arc<MetaRegistry>,
+    /// Scope of `registry` this editor evaluates in
+    pub scope_id: ScopeId,
+    /// Direction `auto_layout` arranges layers in
+    pub layout_direction: LayoutDirection,
+    /// Evaluate from sink nodes so `branch` nodes only pull their taken arm,
+    /// matching `RuntimeConfig::lazy_branches`
+    pub lazy_branches: bool,
+    connection_filter: Option<ConnectionFilter>,
     pub node_registry: NodeRegistry,
     pub canvas_offset: Vec2,
     pub canvas_scale: f32,
+    /// Size of the editor area in screen pixels, kept up to date by the host
+    pub viewport_size: Vec2,
     pub grid_size: f32,
     pub snap_to_grid: bool,
+    /// Minor grid lines per major cell; 1 draws no minor lines
+    pub grid_subdivisions: u32,
+    /// Snap to the nearest minor grid line instead of the major grid
+    pub snap_to_subdivisions: bool,
+    /// Offset applied by `duplicate_selected_nodes`
+    pub duplicate_offset: Vec2,
+    /// Extra offset for each repeated paste at the same point
+    pub paste_cascade_offset: Vec2,
+    /// Target of the last paste and how many times in a row it was reused
+    last_paste: Option<(Vec2, u32)>,
     
     // Interaction state
     pub dragging_node: Option<Uuid>,
-    pub connecting_from: Option<(Uuid, String)>,
+    /// Middle-button drag moving the canvas
+    pub panning: bool,
+    /// Whether the multi-select modifier (Shift) is held, as reported by the host
+    pub multi_select_held: bool,
+    /// Positions of the dragged nodes when the drag started
+    drag_origin: Vec<(Uuid, Vec2)>,
+    /// Marquee drag in canvas space: anchor corner and current corner
+    pub selection_rect: Option<(Vec2, Vec2)>,
+    /// Socket an in-progress connection drag started from
+    pub connecting_from: Option<(Uuid, String, SocketKind)>,
     pub mouse_position: Vec2,
     pub context_menu_position: Option<Vec2>,
 }
 
 impl VisualNodeEditor {
     pub fn new() -> Self {
+        let mut registry = MetaRegistry::new();
+        let scope_id = registry.create_scope();
         Self {
             nodes: HashMap::new(),
             connections: Vec::new(),
+            connection_properties: HashMap::new(),
             selected_nodes: Vec::new(),
+            selected_connections: HashSet::new(),
+            history: CommandStack::default(),
+            sink_nodes: HashSet::new(),
+            last_outputs: HashMap::new(),
+            groups: HashMap::new(),
+            elapsed_time: 0.0,
+            node_state: NodeStateStore::new(),
+            registry: Arc::new(registry),
+            scope_id,
+            layout_direction: LayoutDirection::default(),
+            lazy_branches: false,
+            connection_filter: None,
             node_registry: NodeRegistry::new(),
             canvas_offset: Vec2::ZERO,
             canvas_scale: 1.0,
+            viewport_size: Vec2::ZERO,
             grid_size: 20.0,
             snap_to_grid: true,
+            grid_subdivisions: DEFAULT_GRID_SUBDIVISIONS,
+            snap_to_subdivisions: false,
+            duplicate_offset: Vec2::new(50.0, 50.0),
+            paste_cascade_offset: Vec2::new(20.0, 20.0),
+            last_paste: None,
             dragging_node: None,
+            panning: false,
+            multi_select_held: false,
+            drag_origin: Vec::new(),
+            selection_rect: None,
             connecting_from: None,
             mouse_position: Vec2::ZERO,
             context_menu_position: None,
@@ -122,33 +711,193 @@ impl VisualNodeEditor {
             properties: node_spec.properties,
             selected: false,
             title: node_spec.title,
+            dirty: true,
+            z_order: self.front_z_order(),
         };
         
         self.nodes.insert(node_id, visual_node);
+        self.record_edit(EditorEdit::RemoveNode(node_id));
         info!("Added node '{}' at position {:?}", node_type, position);
         
         Ok(node_id)
     }
     
-    pub fn remove_node(&mut self, node_id: Uuid) -> Result<(), MarcoError> {
-        if let Some(_node) = self.nodes.remove(&node_id) {
-            // Remove all connections involving this node
-            self.connections.retain(|conn| {
-                conn.id.from_node != node_id && conn.id.to_node != node_id
-            });
+    /// Add a placeholder for a node of unknown type `original_type`
+    pub fn add_placeholder_node(&mut self, original_type: &str, position: Vec2) -> Result<Uuid, MarcoError> {
+        let node_id = self.add_node(PLACEHOLDER_NODE_TYPE, position)?;
+        let node = self.nodes.get_mut(&node_id).expect("node was just added");
+        node.title = format!("Missing: {}", original_type);
+        node.properties.insert("original_type".to_string(), MetaValue::String(original_type.to_string()));
+        Ok(node_id)
+    }
+    
+    /// Give a placeholder node an `Any` socket named `socket` if it lacks one
+    ///
+    /// Returns false for unknown nodes and nodes that are not placeholders.
+    pub fn ensure_placeholder_socket(&mut self, node_id: Uuid, kind: SocketKind, socket: &str) -> bool {
+        let Some(node) = self.nodes.get_mut(&node_id).filter(|n| n.node_type == PLACEHOLDER_NODE_TYPE) else {
+            return false;
+        };
+        match kind {
+            SocketKind::Input => {
+                node.inputs.entry(socket.to_string()).or_insert_with(|| NodeInput {
+                    name: socket.to_string(),
+                    data_type: NodeDataType::Any,
+                    connected_output: None,
+                    default_value: None,
+                });
+            }
+            SocketKind::Output => {
+                node.outputs.entry(socket.to_string()).or_insert_with(|| NodeOutput {
+                    name: socket.to_string(),
+                    data_type: NodeDataType::Any,
+                    connections: Vec::new(),
+                });
+            }
+        }
+        true
+    }
+    
+    /// Add a node and wire the last selected node into it
+    ///
+    /// The selected node's primary output (`result` if it has one, else its
+    /// first output by name) connects to the new node's first compatible
+    /// input by name. Nothing is wired when no types match. The new node
+    /// becomes the selection so repeated adds build a chain.
+    pub fn add_node_connected(&mut self, node_type: &str, position: Vec2) -> Result<Uuid, MarcoError> {
+        self.grouped(|editor| {
+            let source = editor.selected_nodes.last().copied();
+            let node_id = editor.add_node(node_type, position)?;
             
-            // Remove from selection
-            self.selected_nodes.retain(|&id| id != node_id);
+            if let Some(source) = source.filter(|id| editor.nodes.contains_key(id)) {
+                match editor.auto_connect_sockets(source, node_id) {
+                    Some((output, input)) => {
+                        editor.connect_nodes(source, &output, node_id, &input)?;
+                    }
+                    None => info!("No compatible input on '{}' for auto-connect", node_type),
+                }
+            }
             
-            info!("Removed node {}", node_id);
-            Ok(())
-        } else {
-            Err(MarcoError::NodeEval(format!("Node {} not found", node_id)))
+            editor.select_node(node_id, false);
+            Ok(node_id)
+        })
+    }
+    
+    /// Output of `source` and input of `target` to join when auto-connecting
+    fn auto_connect_sockets(&self, source: Uuid, target: Uuid) -> Option<(String, String)> {
+        let source = self.nodes.get(&source)?;
+        let target = self.nodes.get(&target)?;
+        
+        let (output_name, output) = source.outputs.get_key_value("result")
+            .or_else(|| source.outputs.iter().min_by(|a, b| a.0.cmp(b.0)))?;
+        
+        let mut inputs: Vec<(&String, &NodeInput)> = target.inputs.iter().collect();
+        inputs.sort_by(|a, b| a.0.cmp(b.0));
+        inputs.into_iter()
+            .find(|(_, input)| input.connected_output.is_none() && self.are_types_compatible(&output.data_type, &input.data_type))
+            .map(|(input_name, _)| (output_name.clone(), input_name.clone()))
+    }
+    
+    /// Remove a node and its connections as an undoable edit
+    pub fn remove_node(&mut self, node_id: Uuid) -> Result<(), MarcoError> {
+        let removed = self.take_node(node_id)
+            .ok_or_else(|| MarcoError::NodeEval(format!("Node {} not found", node_id)))?;
+        self.record_edit(EditorEdit::RestoreNode(Box::new(removed)));
+        info!("Removed node {}", node_id);
+        Ok(())
+    }
+    
+    /// Remove a node and its connections without recording an edit
+    fn take_node(&mut self, node_id: Uuid) -> Option<RemovedNode> {
+        if !self.nodes.contains_key(&node_id) {
+            return None;
+        }
+        let connections = self.take_connections(|conn| conn.id.from_node == node_id || conn.id.to_node == node_id);
+        for entry in &connections {
+            self.mark_dirty(entry.connection.id.to_node);
+        }
+        let node = self.nodes.remove(&node_id)?;
+        self.last_outputs.remove(&node_id);
+        self.node_state.remove_node(&NodeId::from_uuid(node_id));
+        
+        // Remove from selection
+        self.selected_nodes.retain(|&id| id != node_id);
+        let sink = self.sink_nodes.remove(&node_id);
+        let group = self.groups.values().find(|group| group.member_ids.contains(&node_id)).cloned();
+        for group in self.groups.values_mut() {
+            group.member_ids.retain(|&id| id != node_id);
+        }
+        self.groups.retain(|_, group| !group.member_ids.is_empty());
+        
+        Some(RemovedNode { node, connections, sink, group })
+    }
+    
+    /// Put a removed node back under its id, with its group and connections
+    fn restore_node(&mut self, removed: RemovedNode) -> Vec<Uuid> {
+        let RemovedNode { mut node, connections, sink, group } = removed;
+        let node_id = node.id;
+        if self.nodes.contains_key(&node_id) {
+            warn!("Cannot restore node {}: id already in use", node_id);
+            return Vec::new();
+        }
+        node.selected = false;
+        node.dirty = true;
+        self.nodes.insert(node_id, node);
+        if sink {
+            self.sink_nodes.insert(node_id);
+        }
+        if let Some(group) = group {
+            let entry = self.groups.entry(group.id).or_insert_with(|| NodeGroup { member_ids: Vec::new(), ..group });
+            if !entry.member_ids.contains(&node_id) {
+                entry.member_ids.push(node_id);
+            }
+        }
+        
+        let mut touched = vec![node_id];
+        for id in self.restore_connections(connections) {
+            if !touched.contains(&id) {
+                touched.push(id);
+            }
         }
+        touched
+    }
+    
+    /// Connect an output to an input
+    ///
+    /// An output may fan out to any number of inputs, but an input takes a
+    /// single connection: connecting to an input that is already fed
+    /// replaces its connection, which is reported in the result. Fails if
+    /// the connection would close a cycle.
+    pub fn connect_nodes(&mut self, from_node: Uuid, from_output: &str, to_node: Uuid, to_input: &str) -> Result<ConnectionResult, MarcoError> {
+        let output_type = self.validate_connection(from_node, from_output, to_node, to_input)?;
+        let uuid = Uuid::new_v4();
+        let replaced = self.link(uuid, from_node, from_output, to_node, to_input, &output_type);
+        let edit = match &replaced {
+            Some(old) => {
+                let id = &old.connection.id;
+                info!("Replaced existing connection {}:{} -> {}:{}", id.from_node, id.from_output, to_node, to_input);
+                EditorEdit::Batch(vec![
+                    EditorEdit::RestoreConnections(vec![old.clone()]),
+                    EditorEdit::RemoveConnections(vec![uuid]),
+                ])
+            }
+            None => EditorEdit::RemoveConnections(vec![uuid]),
+        };
+        self.record_edit(edit);
+        Ok(ConnectionResult { replaced: replaced.map(|old| old.connection.id) })
+    }
+    
+    /// Whether an input is fed by a connection
+    pub fn input_is_connected(&self, node_id: Uuid, input: &str) -> bool {
+        self.nodes.get(&node_id)
+            .and_then(|node| node.inputs.get(input))
+            .is_some_and(|input| input.connected_output.is_some())
     }
     
-    pub fn connect_nodes(&mut self, from_node: Uuid, from_output: &str, to_node: Uuid, to_input: &str) -> Result<(), MarcoError> {
-        // Validate connection first
+    /// Check both sockets exist and their types are compatible
+    ///
+    /// Returns the output's data type on success.
+    fn validate_connection(&self, from_node: Uuid, from_output: &str, to_node: Uuid, to_input: &str) -> Result<NodeDataType, MarcoError> {
         let output_type = {
             let from_node_ref = self.nodes.get(&from_node)
                 .ok_or_else(|| MarcoError::NodeEval(format!("Source node {} not found", from_node)))?;
@@ -177,8 +926,38 @@ impl VisualNodeEditor {
             )));
         }
         
+        if self.would_create_cycle(from_node, to_node) {
+            return Err(MarcoError::NodeEval("connection would create a cycle".to_string()));
+        }
+        
+        Ok(output_type)
+    }
+    
+    /// Whether connecting `from_node` into `to_node` would close a cycle,
+    /// i.e. `to_node` already reaches `from_node` downstream
+    pub fn would_create_cycle(&self, from_node: Uuid, to_node: Uuid) -> bool {
+        let mut stack = vec![to_node];
+        let mut seen = HashSet::new();
+        while let Some(node_id) = stack.pop() {
+            if node_id == from_node {
+                return true;
+            }
+            if !seen.insert(node_id) {
+                continue;
+            }
+            stack.extend(self.connections.iter()
+                .filter(|conn| conn.id.from_node == node_id)
+                .map(|conn| conn.id.to_node));
+        }
+        false
+    }
+    
+    /// Create a validated connection under the given uuid
+    ///
+    /// Returns the connection the input had before, which is removed.
+    fn link(&mut self, uuid: Uuid, from_node: Uuid, from_output: &str, to_node: Uuid, to_input: &str, output_type: &NodeDataType) -> Option<RemovedConnection> {
         // Remove existing connection to the input
-        self.disconnect_input(to_node, to_input);
+        let replaced = self.unlink_input(to_node, to_input);
         
         // Create new connection
         let connection_id = NodeConnectionId {
@@ -189,9 +968,11 @@ impl VisualNodeEditor {
         };
         
         let connection = NodeConnection {
+            uuid,
             id: connection_id.clone(),
-            color: self.get_connection_color(&output_type),
-            thickness: 2.0,
+            color: self.get_connection_color(output_type),
+            thickness: DEFAULT_CONNECTION_THICKNESS,
+            style: ConnectionStyle::default(),
         };
         
         self.connections.push(connection);
@@ -207,103 +988,461 @@ impl VisualNodeEditor {
             if let Some(input_mut) = to_node_mut.inputs.get_mut(to_input) {
                 input_mut.connected_output = Some(connection_id);
             }
+            to_node_mut.dirty = true;
         }
         
         info!("Connected {}:{} -> {}:{}", from_node, from_output, to_node, to_input);
+        replaced
+    }
+    
+    /// Look up a connection by its stable uuid
+    pub fn connection_by_id(&self, uuid: Uuid) -> Option<&NodeConnection> {
+        self.connections.iter().find(|conn| conn.uuid == uuid)
+    }
+    
+    /// Move one end of an existing connection, keeping its uuid and settings
+    ///
+    /// `end` selects which side moves: `Output` replaces the source socket,
+    /// `Input` replaces the target socket. The connection is left untouched
+    /// if the new endpoint is invalid.
+    pub fn reconnect(&mut self, uuid: Uuid, end: SocketKind, node: Uuid, socket: &str) -> Result<(), MarcoError> {
+        let old = self.connection_by_id(uuid)
+            .ok_or_else(|| MarcoError::NodeEval(format!("Connection {} not found", uuid)))?
+            .id.clone();
+        
+        let (from_node, from_output, to_node, to_input) = match end {
+            SocketKind::Output => (node, socket.to_string(), old.to_node, old.to_input.clone()),
+            SocketKind::Input => (old.from_node, old.from_output.clone(), node, socket.to_string()),
+        };
+        let output_type = self.validate_connection(from_node, &from_output, to_node, &to_input)?;
+        
+        let properties = self.connection_properties.remove(&uuid);
+        let style = self.unlink_input(old.to_node, &old.to_input)
+            .map(|removed| removed.connection.style)
+            .unwrap_or_default();
+        self.link(uuid, from_node, &from_output, to_node, &to_input, &output_type);
+        if let Some(properties) = properties {
+            self.connection_properties.insert(uuid, properties);
+        }
+        self.set_connection_style(uuid, style)?;
         Ok(())
     }
     
-    pub fn disconnect_input(&mut self, node_id: Uuid, input_name: &str) {
-        // First, get the connection info we need
-        let connection_to_remove = if let Some(node) = self.nodes.get(&node_id) {
-            if let Some(input) = node.inputs.get(input_name) {
-                input.connected_output.clone()
-            } else {
-                None
-            }
-        } else {
-            None
+    /// Show only connections matching `filter`, or all when None
+    pub fn set_connection_filter(&mut self, filter: Option<ConnectionFilter>) {
+        self.connection_filter = filter;
+    }
+    
+    pub fn connection_filter(&self) -> Option<&ConnectionFilter> {
+        self.connection_filter.as_ref()
+    }
+    
+    /// Connections passing the current filter, in creation order
+    pub fn visible_connections(&self) -> Vec<&NodeConnection> {
+        let Some(filter) = &self.connection_filter else {
+            return self.connections.iter().collect();
         };
         
-        if let Some(connection_id) = connection_to_remove {
-            // Remove from connections list
-            self.connections.retain(|conn| {
-                !(conn.id.from_node == connection_id.from_node &&
-                  conn.id.from_output == connection_id.from_output &&
-                  conn.id.to_node == connection_id.to_node &&
-                  conn.id.to_input == connection_id.to_input)
-            });
-            
-            // Remove from source node's output connections
-            if let Some(source_node) = self.nodes.get_mut(&connection_id.from_node) {
-                if let Some(output) = source_node.outputs.get_mut(&connection_id.from_output) {
-                    output.connections.retain(|conn| {
-                        !(conn.to_node == node_id && conn.to_input == input_name)
-                    });
+        self.connections.iter()
+            .filter(|conn| match filter {
+                ConnectionFilter::DataType(data_type) => self.nodes.get(&conn.id.from_node)
+                    .and_then(|node| node.outputs.get(&conn.id.from_output))
+                    .is_some_and(|output| output.data_type == *data_type),
+                ConnectionFilter::Node(node_id) => {
+                    conn.id.from_node == *node_id || conn.id.to_node == *node_id
                 }
-            }
-            
-            // Clear the input connection
-            if let Some(node) = self.nodes.get_mut(&node_id) {
-                if let Some(input) = node.inputs.get_mut(input_name) {
-                    input.connected_output = None;
+                ConnectionFilter::Selected => {
+                    self.selected_nodes.contains(&conn.id.from_node)
+                        || self.selected_nodes.contains(&conn.id.to_node)
                 }
-            }
+            })
+            .collect()
+    }
+    
+    /// Set a per-connection setting such as a style or color override
+    pub fn set_connection_property(&mut self, uuid: Uuid, name: &str, value: MetaValue) -> Result<(), MarcoError> {
+        if self.connection_by_id(uuid).is_none() {
+            return Err(MarcoError::NodeEval(format!("Connection {} not found", uuid)));
         }
+        self.connection_properties.entry(uuid).or_default().insert(name.to_string(), value);
+        Ok(())
     }
     
-    pub fn evaluate_graph(&self) -> Result<HashMap<Uuid, OutputMap>, MarcoError> {
-        let mut results = HashMap::new();
-        let mut visited = std::collections::HashSet::new();
-        
-        // Topological sort and evaluation
-        for &node_id in self.nodes.keys() {
-            if !visited.contains(&node_id) {
-                self.evaluate_node_recursive(node_id, &mut results, &mut visited)?;
+    pub fn connection_property(&self, uuid: Uuid, name: &str) -> Option<&MetaValue> {
+        self.connection_properties.get(&uuid)?.get(name)
+    }
+    
+    /// Replace a connection's visual overrides
+    ///
+    /// Color and thickness fall back to the type-based values where the
+    /// style leaves them unset.
+    pub fn set_connection_style(&mut self, uuid: Uuid, style: ConnectionStyle) -> Result<(), MarcoError> {
+        let id = self.connection_by_id(uuid)
+            .ok_or_else(|| MarcoError::NodeEval(format!("Connection {} not found", uuid)))?
+            .id.clone();
+        let type_color = self.nodes.get(&id.from_node)
+            .and_then(|node| node.outputs.get(&id.from_output))
+            .map_or(self.get_connection_color(&NodeDataType::Any), |output| self.get_connection_color(&output.data_type));
+        let connection = self.connections.iter_mut()
+            .find(|conn| conn.uuid == uuid)
+            .expect("connection was just found");
+        connection.color = style.color.unwrap_or(type_color);
+        connection.thickness = style.thickness.unwrap_or(DEFAULT_CONNECTION_THICKNESS);
+        connection.style = style;
+        Ok(())
+    }
+    
+    pub fn connection_style(&self, uuid: Uuid) -> Option<&ConnectionStyle> {
+        self.connection_by_id(uuid).map(|conn| &conn.style)
+    }
+    
+    /// Connect two sockets given in either order
+    ///
+    /// Orients the pair output → input before connecting, so drag handlers
+    /// can pass the socket the drag started from first. Two outputs or two
+    /// inputs are rejected with an error naming both sockets.
+    pub fn connect_sockets(
+        &mut self,
+        a: (Uuid, String, SocketKind),
+        b: (Uuid, String, SocketKind),
+    ) -> Result<ConnectionResult, MarcoError> {
+        let ((from_node, from_output, _), (to_node, to_input, _)) = match (a.2, b.2) {
+            (SocketKind::Output, SocketKind::Input) => (a, b),
+            (SocketKind::Input, SocketKind::Output) => (b, a),
+            (kind, _) => {
+                return Err(MarcoError::NodeEval(format!(
+                    "Cannot connect {kind} '{}' on node {} to {kind} '{}' on node {}: \
+                     a connection must join an output to an input",
+                    a.1, a.0, b.1, b.0
+                )));
             }
-        }
+        };
         
-        Ok(results)
+        self.connect_nodes(from_node, &from_output, to_node, &to_input)
     }
     
-    fn evaluate_node_recursive(
-        &self,
-        node_id: Uuid,
-        results: &mut HashMap<Uuid, OutputMap>,
-        visited: &mut std::collections::HashSet<Uuid>,
+    /// Start dragging a connection from a socket
+    ///
+    /// Replaces any connection drag already in progress.
+    pub fn begin_connection(&mut self, from: (Uuid, String, SocketKind)) {
+        if let Some((node, socket, kind)) = &self.connecting_from {
+            info!("Replacing connection drag from {} '{}' on node {}", kind, socket, node);
+        }
+        self.connecting_from = Some(from);
+    }
+    
+    /// Abandon the connection drag in progress, if any
+    pub fn cancel_connection(&mut self) {
+        if let Some((node, socket, kind)) = self.connecting_from.take() {
+            info!("Cancelled connection drag from {} '{}' on node {}", kind, socket, node);
+        }
+    }
+    
+    /// Finish the connection drag onto `to`
+    ///
+    /// The drag is over whatever the outcome: `connecting_from` is cleared
+    /// before validating, so an invalid target cancels the drag and returns
+    /// the reason.
+    pub fn complete_connection(&mut self, to: (Uuid, String, SocketKind)) -> Result<ConnectionResult, MarcoError> {
+        let from = self.connecting_from.take()
+            .ok_or_else(|| MarcoError::InvalidOperation("No connection drag in progress".to_string()))?;
+        self.connect_sockets(from, to)
+    }
+    
+    /// Socket within `radius` of a canvas position, preferring the nearest
+    pub fn socket_at_position(&self, position: Vec2, radius: f32) -> Option<(Uuid, String, SocketKind)> {
+        let mut best: Option<(f32, (Uuid, String, SocketKind))> = None;
+        for (uuid, node) in &self.nodes {
+            let sockets = node.inputs.keys().map(|name| (name, SocketKind::Input))
+                .chain(node.outputs.keys().map(|name| (name, SocketKind::Output)));
+            for (name, kind) in sockets {
+                let Some(socket) = node.socket_position(kind, name) else { continue };
+                let distance = socket.distance(position);
+                if distance <= radius && best.as_ref().is_none_or(|(d, _)| distance < *d) {
+                    best = Some((distance, (*uuid, name.clone(), kind)));
+                }
+            }
+        }
+        best.map(|(_, socket)| socket)
+    }
+    
+    /// Remove the connection feeding an input as an undoable edit
+    pub fn disconnect_input(&mut self, node_id: Uuid, input_name: &str) {
+        if let Some(removed) = self.unlink_input(node_id, input_name) {
+            self.record_edit(EditorEdit::RestoreConnections(vec![removed]));
+        }
+    }
+    
+    /// Remove the connection feeding an input without recording an edit
+    ///
+    /// Returns the connection with its settings.
+    fn unlink_input(&mut self, node_id: Uuid, input_name: &str) -> Option<RemovedConnection> {
+        // First, get the connection info we need
+        let connection_to_remove = if let Some(node) = self.nodes.get(&node_id) {
+            if let Some(input) = node.inputs.get(input_name) {
+                input.connected_output.clone()
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+        
+        let connection_id = connection_to_remove?;
+        let connection = self.connections.iter().find(|conn| conn.id == connection_id).cloned();
+        
+        // Remove from connections list
+        let properties = &mut self.connection_properties;
+        let selected = &mut self.selected_connections;
+        let mut removed_properties = None;
+        self.connections.retain(|conn| {
+            let keep = conn.id != connection_id;
+            if !keep {
+                removed_properties = properties.remove(&conn.uuid);
+                selected.remove(&conn.uuid);
+            }
+            keep
+        });
+            
+        // Remove from source node's output connections
+        if let Some(source_node) = self.nodes.get_mut(&connection_id.from_node) {
+            if let Some(output) = source_node.outputs.get_mut(&connection_id.from_output) {
+                output.connections.retain(|conn| {
+                    !(conn.to_node == node_id && conn.to_input == input_name)
+                });
+            }
+        }
+        
+        // Clear the input connection
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            if let Some(input) = node.inputs.get_mut(input_name) {
+                input.connected_output = None;
+            }
+            node.dirty = true;
+        }
+        
+        connection.map(|connection| RemovedConnection { connection, properties: removed_properties })
+    }
+    
+    /// Evaluate every node, refreshing `last_outputs` and clearing dirty flags
+    ///
+    /// With `lazy_branches` only nodes reachable from a sink through taken
+    /// branch arms are evaluated; the rest keep their dirty flag.
+    pub fn evaluate_graph(&mut self) -> Result<HashMap<Uuid, OutputMap>, MarcoError> {
+        let mut results = HashMap::new();
+        let mut done = HashSet::new();
+        
+        let roots: Vec<Uuid> = self.nodes.keys()
+            .copied()
+            .filter(|id| !self.lazy_branches || !self.connections.iter().any(|conn| conn.id.from_node == *id))
+            .collect();
+        
+        self.evaluate_in_order(&roots, &mut results, &mut done, &mut HashSet::new())?;
+        
+        for node in self.nodes.values_mut() {
+            if results.contains_key(&node.id) {
+                node.dirty = false;
+            }
+        }
+        self.last_outputs = results.clone();
+        Ok(results)
+    }
+    
+    /// Re-evaluate only what changed since the last evaluation
+    ///
+    /// Dirty nodes, nodes without cached outputs and volatile nodes (timers,
+    /// random) are recomputed together with everything downstream of them;
+    /// all other nodes reuse `last_outputs`. Returns the ids of the nodes
+    /// that were evaluated.
+    pub fn evaluate_dirty(&mut self) -> Result<Vec<Uuid>, MarcoError> {
+        let mut stale: HashSet<Uuid> = self.nodes.values()
+            .filter(|node| {
+                node.dirty
+                    || !self.last_outputs.contains_key(&node.id)
+                    || self.node_registry.create_node(&node.node_type).is_ok_and(|n| n.is_volatile())
+            })
+            .map(|node| node.id)
+            .collect();
+        
+        let mut queue: VecDeque<Uuid> = stale.iter().copied().collect();
+        while let Some(node_id) = queue.pop_front() {
+            for conn in self.connections.iter().filter(|conn| conn.id.from_node == node_id) {
+                if stale.insert(conn.id.to_node) {
+                    queue.push_back(conn.id.to_node);
+                }
+            }
+        }
+        
+        // Fresh nodes count as done so their cached outputs are reused
+        let mut results = std::mem::take(&mut self.last_outputs);
+        results.retain(|id, _| self.nodes.contains_key(id));
+        let mut done: HashSet<Uuid> = self.nodes.keys()
+            .copied()
+            .filter(|id| !stale.contains(id))
+            .collect();
+        let mut evaluated: Vec<Uuid> = Vec::with_capacity(stale.len());
+        let roots: Vec<Uuid> = stale.iter().copied().collect();
+        if let Err(e) = self.evaluate_in_order(&roots, &mut results, &mut done, &mut HashSet::new()) {
+            self.last_outputs = results;
+            return Err(e);
+        }
+        
+        for &node_id in &stale {
+            if let Some(node) = self.nodes.get_mut(&node_id) {
+                node.dirty = false;
+                evaluated.push(node_id);
+            }
+        }
+        self.last_outputs = results;
+        Ok(evaluated)
+    }
+    
+    /// Flag a node for re-evaluation by `evaluate_dirty`
+    pub fn mark_dirty(&mut self, node_id: Uuid) {
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            node.dirty = true;
+        }
+    }
+    
+    /// Checkpoint the editor's registry scope
+    pub fn snapshot_registry(&self) -> Snapshot {
+        self.registry.snapshot(&self.scope_id)
+            .unwrap_or_else(|_| Snapshot::new(HashMap::new()))
+    }
+    
+    /// Roll the editor's registry scope back to a checkpoint
+    ///
+    /// Every node is marked dirty, since any of them may read restored values.
+    pub fn restore_registry(&mut self, snapshot: Snapshot) -> Result<(), MarcoError> {
+        self.registry.restore(&self.scope_id, &snapshot)
+            .map_err(|e| MarcoError::Registry(e.to_string()))?;
+        for node in self.nodes.values_mut() {
+            node.dirty = true;
+        }
+        Ok(())
+    }
+    
+    /// Evaluate `roots` and everything they depend on that is not `done`
+    ///
+    /// First orders the nodes so each comes after its dependencies, then
+    /// evaluates them in that order. A lazy branch orders only its
+    /// condition up front; once that is known, the selected arm is ordered
+    /// and evaluated before the branch itself.
+    ///
+    /// `evaluating` holds the nodes whose dependencies are being resolved,
+    /// so reaching one of them again means a cycle.
+    fn evaluate_in_order(
+        &self,
+        roots: &[Uuid],
+        results: &mut HashMap<Uuid, OutputMap>,
+        done: &mut HashSet<Uuid>,
+        evaluating: &mut HashSet<Uuid>,
     ) -> Result<(), MarcoError> {
-        if visited.contains(&node_id) {
-            return Ok(()); // Already processed or circular dependency
+        let mut order = Vec::new();
+        for &root in roots {
+            self.topological_order(root, done, evaluating, &mut HashSet::new(), &mut order)?;
         }
         
-        visited.insert(node_id);
+        for node_id in order {
+            // A branch arm evaluated earlier in this pass may have covered it
+            if done.contains(&node_id) {
+                continue;
+            }
+            let node = self.nodes.get(&node_id)
+                .ok_or_else(|| MarcoError::NodeEval(format!("Node {} not found", node_id)))?;
+            
+            let mut skipped_arm = None;
+            if self.lazy_branches && node.node_type == "branch" {
+                let condition = Self::resolved_input(node, "condition", results)
+                    .and_then(|value| value.as_bool())
+                    .unwrap_or(false);
+                let (arm, other) = if condition { ("true_value", "false_value") } else { ("false_value", "true_value") };
+                skipped_arm = Some(other);
+                if let Some(source) = node.inputs.get(arm).and_then(|input| input.connected_output.as_ref()) {
+                    evaluating.insert(node_id);
+                    let arm_result = self.evaluate_in_order(&[source.from_node], results, done, evaluating);
+                    evaluating.remove(&node_id);
+                    arm_result?;
+                }
+            }
+            
+            let outputs = self.evaluate_node(node, results, skipped_arm)?;
+            results.insert(node_id, outputs);
+            done.insert(node_id);
+        }
+        Ok(())
+    }
+    
+    /// Append `node_id` to `order` after its not-yet-done dependencies
+    ///
+    /// `ordered` holds the nodes already placed in this pass. Fails when a
+    /// dependency leads back to a node in `evaluating`.
+    fn topological_order(
+        &self,
+        node_id: Uuid,
+        done: &HashSet<Uuid>,
+        evaluating: &mut HashSet<Uuid>,
+        ordered: &mut HashSet<Uuid>,
+        order: &mut Vec<Uuid>,
+    ) -> Result<(), MarcoError> {
+        if done.contains(&node_id) || ordered.contains(&node_id) {
+            return Ok(());
+        }
+        if !evaluating.insert(node_id) {
+            return Err(MarcoError::NodeEval(format!("Cycle detected at node {}", node_id)));
+        }
         
         let node = self.nodes.get(&node_id)
             .ok_or_else(|| MarcoError::NodeEval(format!("Node {} not found", node_id)))?;
         
-        // Evaluate dependencies first
-        for input in node.inputs.values() {
-            if let Some(connection) = &input.connected_output {
-                if !visited.contains(&connection.from_node) {
-                    self.evaluate_node_recursive(connection.from_node, results, visited)?;
-                }
+        // A lazy branch needs only its condition before it can pick an arm
+        let dependencies: Vec<Uuid> = node.inputs.iter()
+            .filter(|(name, _)| !(self.lazy_branches && node.node_type == "branch") || name.as_str() == "condition")
+            .filter_map(|(_, input)| input.connected_output.as_ref().map(|connection| connection.from_node))
+            .collect();
+        for dependency in dependencies {
+            if let Err(e) = self.topological_order(dependency, done, evaluating, ordered, order) {
+                evaluating.remove(&node_id);
+                return Err(e);
             }
         }
         
+        evaluating.remove(&node_id);
+        ordered.insert(node_id);
+        order.push(node_id);
+        Ok(())
+    }
+    
+    /// Evaluate one node whose dependencies already have results
+    ///
+    /// `skipped_input` names a connected input that was deliberately left
+    /// unevaluated, the unselected arm of a lazy branch.
+    fn evaluate_node(
+        &self,
+        node: &VisualNode,
+        results: &HashMap<Uuid, OutputMap>,
+        skipped_input: Option<&str>,
+    ) -> Result<OutputMap, MarcoError> {
         // Gather inputs
         let mut input_map = InputMap::new();
         for (input_name, input) in &node.inputs {
-            let value = if let Some(connection) = &input.connected_output {
-                // Get value from connected output
-                if let Some(source_results) = results.get(&connection.from_node) {
-                    source_results.get(&connection.from_output)
-                        .cloned()
-                        .unwrap_or_else(|| input.default_value.clone().unwrap_or(MetaValue::Scalar(0.0)))
-                } else {
-                    input.default_value.clone().unwrap_or(MetaValue::Scalar(0.0))
+            let fallback = || input.default_value.clone().unwrap_or_else(|| input.data_type.zero_value());
+            let connected = input.connected_output.as_ref()
+                .map(|connection| results.get(&connection.from_node).and_then(|outputs| outputs.get(&connection.from_output)));
+            let value = match connected {
+                Some(Some(value)) => value.clone(),
+                Some(None) => {
+                    // A connected input falling back hides wiring bugs
+                    if skipped_input != Some(input_name.as_str()) {
+                        let connection = input.connected_output.as_ref().expect("input is connected");
+                        warn!(
+                            "Input '{}' of node {} is connected to {}:{}, which produced no value; using the default",
+                            input_name, node.id, connection.from_node, connection.from_output
+                        );
+                    }
+                    fallback()
                 }
-            } else {
-                input.default_value.clone().unwrap_or(MetaValue::Scalar(0.0))
+                None => fallback(),
             };
             
             input_map.insert(input_name.clone(), value);
@@ -316,961 +1455,1627 @@ impl VisualNodeEditor {
         
         // Evaluate node
         let evaluatable_node = self.node_registry.create_node(&node.node_type)?;
-        let ctx = EvalContext::default();
-        let outputs = evaluatable_node.evaluate(&input_map, &ctx)?;
-        
-        results.insert(node_id, outputs);
-        
-        Ok(())
+        let ctx = EvalContext::new(self.registry.clone(), self.scope_id.clone())
+            .with_elapsed_time(self.elapsed_time)
+            .with_node_state(self.node_state.clone())
+            .for_node(NodeId::from_uuid(node.id));
+        evaluatable_node.evaluate(&input_map, &ctx)
     }
     
-    pub fn update_node_property(&mut self, node_id: Uuid, property_name: &str, value: MetaValue) -> Result<(), MarcoError> {
-        if let Some(node) = self.nodes.get_mut(&node_id) {
-            node.properties.insert(property_name.to_string(), value);
-            info!("Updated property '{}' for node {}", property_name, node_id);
-            Ok(())
-        } else {
-            Err(MarcoError::NodeEval(format!("Node {} not found", node_id)))
+    /// Value `name` evaluates to on `node`, given the outputs computed so far
+    ///
+    /// Properties override connections, which override the default.
+    fn resolved_input(node: &VisualNode, name: &str, results: &HashMap<Uuid, OutputMap>) -> Option<MetaValue> {
+        if let Some(value) = node.properties.get(name) {
+            return Some(value.clone());
         }
+        let input = node.inputs.get(name)?;
+        input.connected_output.as_ref()
+            .and_then(|connection| results.get(&connection.from_node)?.get(&connection.from_output).cloned())
+            .or_else(|| input.default_value.clone())
     }
     
-    pub fn select_node(&mut self, node_id: Uuid, multi_select: bool) {
-        if !multi_select {
-            // Clear existing selection
-            for node in self.nodes.values_mut() {
-                node.selected = false;
+    /// Evaluate a node type in isolation, without adding it to the graph
+    ///
+    /// Inputs start from the type's default input values and properties, then
+    /// `sample_inputs` overrides them. Used for library hover previews.
+    pub fn preview_node(&self, node_type: &str, sample_inputs: &InputMap) -> Result<OutputMap, MarcoError> {
+        let evaluatable_node = self.node_registry.create_node(node_type)?;
+        
+        let mut input_map = InputMap::new();
+        if let Ok(spec) = self.get_node_specification(node_type) {
+            for (input_name, input) in spec.inputs {
+                if let Some(default) = input.default_value {
+                    input_map.insert(input_name, default);
+                }
             }
-            self.selected_nodes.clear();
+            input_map.extend(spec.properties);
         }
+        input_map.extend(sample_inputs.iter().map(|(name, value)| (name.clone(), value.clone())));
         
-        if let Some(node) = self.nodes.get_mut(&node_id) {
-            node.selected = true;
-            if !self.selected_nodes.contains(&node_id) {
-                self.selected_nodes.push(node_id);
+        evaluatable_node.evaluate(&input_map, &EvalContext::default())
+    }
+    
+    /// Topological rank of every node, for the execution order overlay
+    ///
+    /// Sources are rank 0 and every other node sits one past its deepest
+    /// upstream node, so nodes sharing a rank can evaluate in any order.
+    /// Sorted by rank, then id. Fails if the graph contains a cycle.
+    pub fn execution_order(&self) -> Result<Vec<(Uuid, usize)>, MarcoError> {
+        let mut downstream: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for conn in &self.connections {
+            if self.nodes.contains_key(&conn.id.from_node) && self.nodes.contains_key(&conn.id.to_node) {
+                downstream.entry(conn.id.from_node).or_default().push(conn.id.to_node);
             }
         }
+        let edges = downstream.iter()
+            .flat_map(|(from, targets)| targets.iter().map(move |to| (*from, *to)));
+        let sorted = topological_sort(self.nodes.keys().copied(), edges)
+            .ok_or_else(|| MarcoError::NodeEval("Graph contains cycles and has no execution order".to_string()))?;
+        
+        // Upstream nodes come first, so each rank is final when reached
+        let mut ranks: HashMap<Uuid, usize> = HashMap::new();
+        for node_id in sorted {
+            let rank = *ranks.entry(node_id).or_insert(0);
+            for next in downstream.get(&node_id).into_iter().flatten() {
+                let next_rank = ranks.entry(*next).or_insert(0);
+                *next_rank = (*next_rank).max(rank + 1);
+            }
+        }
+        
+        let mut order: Vec<(Uuid, usize)> = ranks.into_iter().collect();
+        order.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+        Ok(order)
     }
     
-    pub fn deselect_all(&mut self) {
-        for node in self.nodes.values_mut() {
-            node.selected = false;
+    /// Mark or unmark a node as a graph output; false if the node is unknown
+    pub fn set_sink(&mut self, node_id: Uuid, sink: bool) -> bool {
+        if !self.nodes.contains_key(&node_id) {
+            return false;
         }
-        self.selected_nodes.clear();
+        if sink {
+            self.sink_nodes.insert(node_id);
+        } else {
+            self.sink_nodes.remove(&node_id);
+        }
+        true
     }
     
-    pub fn move_selected_nodes(&mut self, delta: Vec2) {
-        let selected_clone = self.selected_nodes.clone();
-        let grid_size = self.grid_size;
-        let snap_to_grid = self.snap_to_grid;
-        
-        for &node_id in &selected_clone {
-            if let Some(node) = self.nodes.get_mut(&node_id) {
-                node.position += delta;
-                if snap_to_grid {
-                    node.position = Vec2::new(
-                        (node.position.x / grid_size).round() * grid_size,
-                        (node.position.y / grid_size).round() * grid_size,
-                    );
-                }
+    /// Sinks that no source feeds, sorted by id
+    ///
+    /// Sources are non-sink nodes with no incoming connections. A sink is
+    /// reachable when a chain of connections leads to it from a source, so
+    /// a sink with nothing connected, or fed only by a cycle with no way in,
+    /// can never produce a meaningful result.
+    pub fn unreachable_sinks(&self) -> Vec<Uuid> {
+        let mut downstream: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        let mut has_input: HashSet<Uuid> = HashSet::new();
+        for conn in &self.connections {
+            if !self.nodes.contains_key(&conn.id.from_node) || !self.nodes.contains_key(&conn.id.to_node) {
+                continue;
             }
+            downstream.entry(conn.id.from_node).or_default().push(conn.id.to_node);
+            has_input.insert(conn.id.to_node);
         }
-    }
-    
-    pub fn duplicate_selected_nodes(&mut self) -> Result<Vec<Uuid>, MarcoError> {
-        let mut new_node_ids = Vec::new();
-        let selected_clone = self.selected_nodes.clone();
-        
-        for &node_id in &selected_clone {
-            if let Some(node) = self.nodes.get(&node_id).cloned() {
-                let new_id = Uuid::new_v4();
-                let mut new_node = node;
-                new_id.clone_into(&mut new_node.id);
-                new_node.position += Vec2::new(50.0, 50.0); // Offset duplicated nodes
-                new_node.selected = false;
-                
-                // Clear connections for duplicated node
-                for input in new_node.inputs.values_mut() {
-                    input.connected_output = None;
-                }
-                for output in new_node.outputs.values_mut() {
-                    output.connections.clear();
+        
+        let mut pending: Vec<Uuid> = self.nodes.keys()
+            .filter(|id| !has_input.contains(id) && !self.sink_nodes.contains(id))
+            .copied()
+            .collect();
+        let mut reached: HashSet<Uuid> = pending.iter().copied().collect();
+        while let Some(node_id) = pending.pop() {
+            for next in downstream.get(&node_id).into_iter().flatten() {
+                if reached.insert(*next) {
+                    pending.push(*next);
                 }
-                
-                self.nodes.insert(new_id, new_node);
-                new_node_ids.push(new_id);
             }
         }
         
-        Ok(new_node_ids)
+        let mut unreachable: Vec<Uuid> = self.sink_nodes.iter()
+            .filter(|id| self.nodes.contains_key(id) && !reached.contains(id))
+            .copied()
+            .collect();
+        unreachable.sort();
+        unreachable
     }
     
-    pub fn duplicate_node(&mut self, node_id: Uuid, offset: Vec2) -> Result<Option<Uuid>, MarcoError> {
-        if let Some(original_node) = self.nodes.get(&node_id).cloned() {
-            let new_id = Uuid::new_v4();
-            let mut new_node = original_node;
-            new_node.id = new_id;
-            new_node.position += offset;
-            new_node.selected = false;
-            
-            // Clear connections for duplicated node
-            for input in new_node.inputs.values_mut() {
-                input.connected_output = None;
-            }
-            for output in new_node.outputs.values_mut() {
-                output.connections.clear();
-            }
-            
-            self.nodes.insert(new_id, new_node);
-            Ok(Some(new_id))
-        } else {
-            Ok(None)
+    /// Export the graph as a Graphviz DOT digraph
+    ///
+    /// Nodes are records with their inputs on the left, title and type in
+    /// the middle and outputs on the right, filled by category. Edges run
+    /// `from:port -> to:port` and are colored like the wire of their
+    /// output's data type. Output is sorted by id so the same graph always
+    /// produces the same text.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph marco {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=Mrecord, style=filled, fontname=\"Helvetica\"];\n");
+        
+        let mut nodes: Vec<&VisualNode> = self.nodes.values().collect();
+        nodes.sort_by_key(|node| node.id);
+        for node in nodes {
+            let title = format!("{}\n({})", node.title, node.node_type);
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{{{{{}}}|{}|{{{}}}}}\", fillcolor=\"{}\"];\n",
+                node.id,
+                dot_record_ports("in", node.inputs.iter().map(|(key, input)| (key, &input.name))),
+                escape_dot_record(&title),
+                dot_record_ports("out", node.outputs.iter().map(|(key, output)| (key, &output.name))),
+                Self::category_fill_color(&Self::node_category(&node.node_type)),
+            ));
+        }
+        
+        let mut connections: Vec<&NodeConnection> = self.connections.iter().collect();
+        connections.sort_by_key(|connection| connection.uuid);
+        for connection in connections {
+            let id = &connection.id;
+            let data_type = self.nodes.get(&id.from_node)
+                .and_then(|node| node.outputs.get(&id.from_output))
+                .map_or(NodeDataType::Any, |output| output.data_type.clone());
+            let [r, g, b, _] = self.get_connection_color(&data_type).map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+            dot.push_str(&format!(
+                "    \"{}\":\"{}\" -> \"{}\":\"{}\" [color=\"#{:02x}{:02x}{:02x}\"];\n",
+                id.from_node,
+                dot_port_id("out", &id.from_output),
+                id.to_node,
+                dot_port_id("in", &id.to_input),
+                r, g, b,
+            ));
         }
+        
+        dot.push_str("}\n");
+        dot
     }
     
-    fn get_node_specification(&self, node_type: &str) -> Result<NodeSpecification, MarcoError> {
+    /// Library category a node type belongs to
+    pub fn node_category(node_type: &str) -> NodeCategory {
         match node_type {
-            // ===== COMPREHENSIVE MATH NODE =====
-            "math" => Ok(NodeSpecification {
-                title: "Math Operations".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("operation".to_string(), NodeInput {
-                        name: "Operation".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("add".to_string())),
-                    });
-                    inputs.insert("a".to_string(), NodeInput {
-                        name: "A".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(0.0)),
-                    });
-                    inputs.insert("b".to_string(), NodeInput {
-                        name: "B".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(0.0)),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("result".to_string(), NodeOutput {
-                        name: "Result".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== STRING PROCESSING NODE =====
-            "string" => Ok(NodeSpecification {
-                title: "String Operations".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("operation".to_string(), NodeInput {
-                        name: "Operation".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("uppercase".to_string())),
-                    });
-                    inputs.insert("text".to_string(), NodeInput {
-                        name: "Text".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("Hello World".to_string())),
-                    });
-                    inputs.insert("parameter".to_string(), NodeInput {
-                        name: "Parameter".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("".to_string())),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("result".to_string(), NodeOutput {
-                        name: "Result".to_string(),
-                        data_type: NodeDataType::String,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("length".to_string(), NodeOutput {
-                        name: "Length".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== ENHANCED TIMER NODE =====
-            "timer" => Ok(NodeSpecification {
-                title: "Timer".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("duration".to_string(), NodeInput {
-                        name: "Duration".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(5.0)),
-                    });
-                    inputs.insert("start".to_string(), NodeInput {
-                        name: "Start".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Bool(false)),
-                    });
-                    inputs.insert("reset".to_string(), NodeInput {
-                        name: "Reset".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Bool(false)),
-                    });
-                    inputs.insert("auto_reset".to_string(), NodeInput {
-                        name: "Auto Reset".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Bool(false)),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("elapsed".to_string(), NodeOutput {
-                        name: "Elapsed".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("progress".to_string(), NodeOutput {
-                        name: "Progress".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("percentage".to_string(), NodeOutput {
-                        name: "Percentage".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("remaining".to_string(), NodeOutput {
-                        name: "Remaining".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("finished".to_string(), NodeOutput {
-                        name: "Finished".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("running".to_string(), NodeOutput {
-                        name: "Running".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== CALCULATOR NODE =====
-            "calculator" => Ok(NodeSpecification {
-                title: "Expression Calculator".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("expression".to_string(), NodeInput {
-                        name: "Expression".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("x * 2 + y".to_string())),
-                    });
-                    inputs.insert("variables".to_string(), NodeInput {
-                        name: "Variables".to_string(),
-                        data_type: NodeDataType::Any,
-                        connected_output: None,
-                        default_value: None,
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("result".to_string(), NodeOutput {
-                        name: "Result".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("error".to_string(), NodeOutput {
-                        name: "Error".to_string(),
-                        data_type: NodeDataType::String,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== DATABASE NODE =====
-            "database" => Ok(NodeSpecification {
-                title: "Database Operations".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("operation".to_string(), NodeInput {
-                        name: "Operation".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("read".to_string())),
-                    });
-                    inputs.insert("table".to_string(), NodeInput {
-                        name: "Table".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("users".to_string())),
-                    });
-                    inputs.insert("data".to_string(), NodeInput {
-                        name: "Data".to_string(),
-                        data_type: NodeDataType::Any,
-                        connected_output: None,
-                        default_value: None,
-                    });
-                    inputs.insert("conditions".to_string(), NodeInput {
-                        name: "Conditions".to_string(),
-                        data_type: NodeDataType::Any,
-                        connected_output: None,
-                        default_value: None,
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("result".to_string(), NodeOutput {
-                        name: "Result".to_string(),
-                        data_type: NodeDataType::Any,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("count".to_string(), NodeOutput {
-                        name: "Count".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("success".to_string(), NodeOutput {
-                        name: "Success".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== VALIDATION NODE =====
-            "validation" => Ok(NodeSpecification {
-                title: "Data Validation".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("validation_type".to_string(), NodeInput {
-                        name: "Validation Type".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("email".to_string())),
-                    });
-                    inputs.insert("input_value".to_string(), NodeInput {
-                        name: "Input Value".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("user@example.com".to_string())),
-                    });
-                    inputs.insert("constraint".to_string(), NodeInput {
-                        name: "Constraint".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("".to_string())),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("is_valid".to_string(), NodeOutput {
-                        name: "Is Valid".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("error_message".to_string(), NodeOutput {
-                        name: "Error Message".to_string(),
-                        data_type: NodeDataType::String,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== API NODE =====
-            "api" => Ok(NodeSpecification {
-                title: "API Request".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("method".to_string(), NodeInput {
-                        name: "Method".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("GET".to_string())),
-                    });
-                    inputs.insert("url".to_string(), NodeInput {
-                        name: "URL".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("https://api.example.com/data".to_string())),
-                    });
-                    inputs.insert("headers".to_string(), NodeInput {
-                        name: "Headers".to_string(),
-                        data_type: NodeDataType::Any,
-                        connected_output: None,
-                        default_value: None,
-                    });
-                    inputs.insert("body".to_string(), NodeInput {
-                        name: "Body".to_string(),
-                        data_type: NodeDataType::Any,
-                        connected_output: None,
-                        default_value: None,
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("response".to_string(), NodeOutput {
-                        name: "Response".to_string(),
-                        data_type: NodeDataType::Any,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("status_code".to_string(), NodeOutput {
-                        name: "Status Code".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("success".to_string(), NodeOutput {
-                        name: "Success".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== DATA TRANSFORM NODE =====
-            "data_transform" => Ok(NodeSpecification {
-                title: "Data Transform".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("operation".to_string(), NodeInput {
-                        name: "Operation".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("filter".to_string())),
-                    });
-                    inputs.insert("data".to_string(), NodeInput {
-                        name: "Data".to_string(),
-                        data_type: NodeDataType::List,
-                        connected_output: None,
-                        default_value: None,
-                    });
-                    inputs.insert("condition".to_string(), NodeInput {
-                        name: "Condition".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("x > 0".to_string())),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("result".to_string(), NodeOutput {
-                        name: "Result".to_string(),
-                        data_type: NodeDataType::List,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("count".to_string(), NodeOutput {
-                        name: "Count".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== AUDIO NODE =====
-            "audio" => Ok(NodeSpecification {
-                title: "Audio Synthesis".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("waveform".to_string(), NodeInput {
-                        name: "Waveform".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("sine".to_string())),
-                    });
-                    inputs.insert("frequency".to_string(), NodeInput {
-                        name: "Frequency".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(440.0)),
-                    });
-                    inputs.insert("amplitude".to_string(), NodeInput {
-                        name: "Amplitude".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(0.5)),
-                    });
-                    inputs.insert("duration".to_string(), NodeInput {
-                        name: "Duration".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(1.0)),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("audio_data".to_string(), NodeOutput {
-                        name: "Audio Data".to_string(),
-                        data_type: NodeDataType::List,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("sample_rate".to_string(), NodeOutput {
-                        name: "Sample Rate".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== ANIMATION NODE =====
-            "animation" => Ok(NodeSpecification {
-                title: "Animation Controller".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("easing_type".to_string(), NodeInput {
-                        name: "Easing Type".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("ease_in_out".to_string())),
-                    });
-                    inputs.insert("progress".to_string(), NodeInput {
-                        name: "Progress".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(0.5)),
-                    });
-                    inputs.insert("start_value".to_string(), NodeInput {
-                        name: "Start Value".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(0.0)),
-                    });
-                    inputs.insert("end_value".to_string(), NodeInput {
-                        name: "End Value".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(100.0)),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("value".to_string(), NodeOutput {
-                        name: "Value".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("eased_progress".to_string(), NodeOutput {
-                        name: "Eased Progress".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== FILE SYSTEM NODE =====
-            "filesystem" => Ok(NodeSpecification {
-                title: "File Operations".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("operation".to_string(), NodeInput {
-                        name: "Operation".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("read".to_string())),
-                    });
-                    inputs.insert("path".to_string(), NodeInput {
-                        name: "Path".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("./data.txt".to_string())),
-                    });
-                    inputs.insert("content".to_string(), NodeInput {
-                        name: "Content".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("".to_string())),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("result".to_string(), NodeOutput {
-                        name: "Result".to_string(),
-                        data_type: NodeDataType::String,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("success".to_string(), NodeOutput {
-                        name: "Success".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("size".to_string(), NodeOutput {
-                        name: "Size".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== NETWORK NODE =====
-            "network" => Ok(NodeSpecification {
-                title: "Network Utilities".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("operation".to_string(), NodeInput {
-                        name: "Operation".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("ping".to_string())),
-                    });
-                    inputs.insert("target".to_string(), NodeInput {
-                        name: "Target".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("google.com".to_string())),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("latency".to_string(), NodeOutput {
-                        name: "Latency".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("bandwidth".to_string(), NodeOutput {
-                        name: "Bandwidth".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("connected".to_string(), NodeOutput {
-                        name: "Connected".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== COLOR PROCESSING NODE =====
-            "color" => Ok(NodeSpecification {
-                title: "Color Processing".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("operation".to_string(), NodeInput {
-                        name: "Operation".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("blend".to_string())),
-                    });
-                    inputs.insert("color1".to_string(), NodeInput {
-                        name: "Color 1".to_string(),
-                        data_type: NodeDataType::Color,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Color(crate::core::types::ColorRGBA { r: 1.0, g: 0.0, b: 0.0, a: 1.0 })),
-                    });
-                    inputs.insert("color2".to_string(), NodeInput {
-                        name: "Color 2".to_string(),
-                        data_type: NodeDataType::Color,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Color(crate::core::types::ColorRGBA { r: 0.0, g: 0.0, b: 1.0, a: 1.0 })),
-                    });
-                    inputs.insert("factor".to_string(), NodeInput {
-                        name: "Factor".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(0.5)),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("result_color".to_string(), NodeOutput {
-                        name: "Result Color".to_string(),
-                        data_type: NodeDataType::Color,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("brightness".to_string(), NodeOutput {
-                        name: "Brightness".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs.insert("contrast".to_string(), NodeOutput {
-                        name: "Contrast".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-
-            // ===== LEGACY NODES (for backward compatibility) =====
-            "add" => Ok(NodeSpecification {
-                title: "Add (Legacy)".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("a".to_string(), NodeInput {
-                        name: "A".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(0.0)),
-                    });
-                    inputs.insert("b".to_string(), NodeInput {
-                        name: "B".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(0.0)),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("result".to_string(), NodeOutput {
-                        name: "Result".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-            
-            "branch" => Ok(NodeSpecification {
-                title: "Branch".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("condition".to_string(), NodeInput {
-                        name: "Condition".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Bool(false)),
-                    });
-                    inputs.insert("true_value".to_string(), NodeInput {
-                        name: "True".to_string(),
-                        data_type: NodeDataType::Any,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(1.0)),
-                    });
-                    inputs.insert("false_value".to_string(), NodeInput {
-                        name: "False".to_string(),
-                        data_type: NodeDataType::Any,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(0.0)),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("result".to_string(), NodeOutput {
-                        name: "Result".to_string(),
-                        data_type: NodeDataType::Any,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-            
-            "button" => Ok(NodeSpecification {
-                title: "Button".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("label".to_string(), NodeInput {
-                        name: "Label".to_string(),
-                        data_type: NodeDataType::String,
-                        connected_output: None,
-                        default_value: Some(MetaValue::String("Button".to_string())),
-                    });
-                    inputs.insert("enabled".to_string(), NodeInput {
-                        name: "Enabled".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Bool(true)),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("clicked".to_string(), NodeOutput {
-                        name: "Clicked".to_string(),
-                        data_type: NodeDataType::Boolean,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-            
-            "slider" => Ok(NodeSpecification {
-                title: "Slider".to_string(),
-                inputs: {
-                    let mut inputs = HashMap::new();
-                    inputs.insert("min".to_string(), NodeInput {
-                        name: "Min".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(0.0)),
-                    });
-                    inputs.insert("max".to_string(), NodeInput {
-                        name: "Max".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(100.0)),
-                    });
-                    inputs.insert("value".to_string(), NodeInput {
-                        name: "Value".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connected_output: None,
-                        default_value: Some(MetaValue::Scalar(50.0)),
-                    });
-                    inputs
-                },
-                outputs: {
-                    let mut outputs = HashMap::new();
-                    outputs.insert("value".to_string(), NodeOutput {
-                        name: "Value".to_string(),
-                        data_type: NodeDataType::Scalar,
-                        connections: Vec::new(),
-                    });
-                    outputs
-                },
-                properties: HashMap::new(),
-            }),
-            
-            _ => Err(MarcoError::NodeEval(format!("Unknown node type: {}", node_type)))
+            "add" | "multiply" | "math" | "calculator" | "clamp" | "random" => NodeCategory::Math,
+            "branch" | "compare" | "validation" => NodeCategory::Logic,
+            "string" => NodeCategory::Text,
+            "timer" | "throttle" => NodeCategory::Time,
+            "database" | "data_transform" | "probe" => NodeCategory::Data,
+            "api" | "network" => NodeCategory::Network,
+            "filesystem" => NodeCategory::Files,
+            "audio" => NodeCategory::Audio,
+            "animation" => NodeCategory::Animation,
+            "color" | "sample_image" => NodeCategory::Color,
+            "button" | "slider" => NodeCategory::UI,
+            _ => NodeCategory::Custom,
         }
     }
     
-    fn are_types_compatible(&self, output_type: &NodeDataType, input_type: &NodeDataType) -> bool {
-        output_type == input_type || 
-        *input_type == NodeDataType::Any || 
-        *output_type == NodeDataType::Any
-    }
-    
-    fn get_connection_color(&self, data_type: &NodeDataType) -> [f32; 4] {
-        match data_type {
-            NodeDataType::Scalar => [0.2, 0.8, 0.2, 1.0],     // Green
-            NodeDataType::Boolean => [0.8, 0.2, 0.2, 1.0],    // Red
-            NodeDataType::String => [0.2, 0.2, 0.8, 1.0],     // Blue
-            NodeDataType::Color => [0.8, 0.8, 0.2, 1.0],      // Yellow
-            NodeDataType::Vector2 => [0.8, 0.2, 0.8, 1.0],    // Magenta
-            NodeDataType::Vector3 => [0.2, 0.8, 0.8, 1.0],    // Cyan
-            NodeDataType::List => [0.8, 0.6, 0.2, 1.0],       // Orange
-            NodeDataType::Any => [0.5, 0.5, 0.5, 1.0],        // Gray
+    fn category_fill_color(category: &NodeCategory) -> &'static str {
+        match category {
+            NodeCategory::Math => "#cfe2ff",
+            NodeCategory::Logic => "#ffe5b4",
+            NodeCategory::Text => "#e2f0d9",
+            NodeCategory::Time | NodeCategory::Animation => "#f8d7da",
+            NodeCategory::Data | NodeCategory::Files => "#e8dff5",
+            NodeCategory::Network => "#d1ecf1",
+            NodeCategory::Audio => "#fff3cd",
+            NodeCategory::Color => "#fde2e4",
+            NodeCategory::UI => "#dee2e6",
+            NodeCategory::All | NodeCategory::Custom => "#f1f3f5",
         }
     }
     
-    fn snap_position_to_grid(&self, position: Vec2) -> Vec2 {
-        Vec2::new(
-            (position.x / self.grid_size).round() * self.grid_size,
-            (position.y / self.grid_size).round() * self.grid_size,
-        )
+    pub fn update_node_property(&mut self, node_id: Uuid, property_name: &str, value: MetaValue) -> Result<(), MarcoError> {
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            let old = node.properties.insert(property_name.to_string(), value);
+            node.dirty = true;
+            self.record_edit(EditorEdit::Properties(BulkPropertyEdit {
+                property: property_name.to_string(),
+                previous: vec![(node_id, old)],
+            }));
+            info!("Updated property '{}' for node {}", property_name, node_id);
+            Ok(())
+        } else {
+            Err(MarcoError::NodeEval(format!("Node {} not found", node_id)))
+        }
     }
     
-    pub fn render(&self, theme: &Marco2Theme) {
-        // Placeholder rendering - in a real implementation, this would use WGPU
-        info!("Rendering node editor with {} nodes, {} connections", 
-              self.nodes.len(), self.connections.len());
-        
-        // Render grid
-        if self.snap_to_grid {
-            info!("Grid enabled: {} units", self.grid_size);
-        }
+    /// Set a property on every selected node that has it
+    ///
+    /// A node has the property if it is one of its properties or inputs.
+    /// Nodes without it, or whose declared type rejects `value`, are skipped.
+    /// The change is recorded as one undo step and the affected node ids
+    /// are returned.
+    pub fn set_property_on_selection(&mut self, property: &str, value: MetaValue) -> Vec<Uuid> {
+        let mut previous = Vec::new();
         
-        // Render nodes
-        for node in self.nodes.values() {
-            info!("Node '{}' at {:?} - Selected: {}", 
-                  node.title, node.position, node.selected);
+        for node_id in &self.selected_nodes {
+            let Some(node) = self.nodes.get_mut(node_id) else {
+                continue;
+            };
+            
+            let accepts = match (node.properties.get(property), node.inputs.get(property)) {
+                (_, Some(input)) => Self::data_type_accepts(&input.data_type, &value),
+                (Some(existing), None) => existing.type_name() == value.type_name(),
+                (None, None) => false,
+            };
+            if !accepts {
+                continue;
+            }
+            
+            let old = node.properties.insert(property.to_string(), value.clone());
+            node.dirty = true;
+            previous.push((*node_id, old));
         }
         
-        // Render connections
-        for connection in &self.connections {
-            info!("Connection: {}:{} -> {}:{}", 
-                  connection.id.from_node, connection.id.from_output,
-                  connection.id.to_node, connection.id.to_input);
+        let affected: Vec<Uuid> = previous.iter().map(|(id, _)| *id).collect();
+        if !previous.is_empty() {
+            info!("Set '{}' on {} selected nodes", property, affected.len());
+            self.record_edit(EditorEdit::Properties(BulkPropertyEdit {
+                property: property.to_string(),
+                previous,
+            }));
         }
+        affected
     }
     
-    /// Handle UI events (placeholder implementation for build compatibility)
-    pub fn handle_event(&mut self, _event: &crate::ui::event::UIEvent) -> Result<(), MarcoError> {
-        // TODO: Implement proper event handling for visual node editor
-        Ok(())
+    /// Push a new edit, discarding anything that could be redone
+    fn record_edit(&mut self, edit: EditorEdit) {
+        self.history.push(edit);
     }
     
-    /// Update method for frame-based updates (placeholder implementation)
-    pub fn update(&mut self, _delta_time: f32) -> Result<(), MarcoError> {
-        // TODO: Implement frame updates (animations, etc.)
-        Ok(())
+    /// Run `f` with every edit it records undone as one step
+    fn grouped<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.history.begin_group();
+        let result = f(self);
+        self.history.end_group();
+        result
     }
     
-    /// Get IDs of currently selected nodes
-    pub fn get_selected_node_ids(&self) -> Vec<Uuid> {
-        self.nodes.iter()
-            .filter(|(_, node)| node.selected)
-            .map(|(id, _)| *id)
-            .collect()
+    /// Collect the edits recorded until `end_edit_group` into one undo step,
+    /// e.g. for the moves of a touch drag
+    pub fn begin_edit_group(&mut self) {
+        self.history.begin_group();
     }
     
-    /// Get reference to nodes collection
-    pub fn get_nodes(&self) -> &HashMap<Uuid, VisualNode> {
-        &self.nodes
+    pub fn end_edit_group(&mut self) {
+        self.history.end_group();
     }
     
-    /// Get reference to connections collection  
-    pub fn get_connections(&self) -> &Vec<NodeConnection> {
-        &self.connections
+    /// Revert the most recent edit, returning the ids of the nodes it touched
+    ///
+    /// Fails when there is nothing to undo.
+    pub fn undo(&mut self) -> Result<Vec<Uuid>, MarcoError> {
+        let edit = self.history.undo.pop_back()
+            .ok_or_else(|| MarcoError::InvalidOperation("Nothing to undo".to_string()))?;
+        let mut touched = Vec::new();
+        let inverse = self.apply_edit(edit, &mut touched);
+        self.history.redo.push(inverse);
+        Ok(Self::dedup_ids(touched))
     }
     
-    /// Handle mouse press events
-    pub fn handle_mouse_press(&mut self, position: Vec2, button: u32) {
-        // Implementation placeholder
-        self.mouse_position = position;
+    /// Re-apply the most recently undone edit, returning the ids of the nodes it touched
+    ///
+    /// Fails when there is nothing to redo.
+    pub fn redo(&mut self) -> Result<Vec<Uuid>, MarcoError> {
+        let edit = self.history.redo.pop()
+            .ok_or_else(|| MarcoError::InvalidOperation("Nothing to redo".to_string()))?;
+        let mut touched = Vec::new();
+        let inverse = self.apply_edit(edit, &mut touched);
+        self.history.push_undo(inverse);
+        Ok(Self::dedup_ids(touched))
     }
     
-    /// Handle mouse release events
-    pub fn handle_mouse_release(&mut self, position: Vec2, button: u32) {
-        // Implementation placeholder
-        self.mouse_position = position;
+    /// Perform `edit` without recording it, returning the edit that reverts it
+    ///
+    /// Nodes and connections that no longer exist are skipped.
+    fn apply_edit(&mut self, edit: EditorEdit, touched: &mut Vec<Uuid>) -> EditorEdit {
+        match edit {
+            EditorEdit::Properties(edit) => {
+                let inverse = self.current_values(&edit);
+                touched.extend(self.undo_property_edit(edit));
+                EditorEdit::Properties(inverse)
+            }
+            EditorEdit::RestoreConnections(removed) => {
+                let uuids = removed.iter().map(|entry| entry.connection.uuid).collect();
+                touched.extend(self.restore_connections(removed));
+                EditorEdit::RemoveConnections(uuids)
+            }
+            EditorEdit::RemoveConnections(uuids) => {
+                let removed = self.take_connections(|conn| uuids.contains(&conn.uuid));
+                for entry in &removed {
+                    touched.extend([entry.connection.id.from_node, entry.connection.id.to_node]);
+                }
+                EditorEdit::RestoreConnections(removed)
+            }
+            EditorEdit::RestoreNode(removed) => {
+                let node_id = removed.node.id;
+                touched.extend(self.restore_node(*removed));
+                EditorEdit::RemoveNode(node_id)
+            }
+            EditorEdit::RemoveNode(node_id) => match self.take_node(node_id) {
+                Some(removed) => {
+                    touched.push(node_id);
+                    touched.extend(removed.connections.iter().map(|entry| entry.connection.id.to_node));
+                    EditorEdit::RestoreNode(Box::new(removed))
+                }
+                None => EditorEdit::Batch(Vec::new()),
+            },
+            EditorEdit::MoveNodes(positions) => {
+                let mut inverse = Vec::with_capacity(positions.len());
+                for (node_id, position) in positions {
+                    if let Some(node) = self.nodes.get_mut(&node_id) {
+                        inverse.push((node_id, node.position));
+                        node.position = position;
+                        touched.push(node_id);
+                    }
+                }
+                EditorEdit::MoveNodes(inverse)
+            }
+            EditorEdit::Batch(edits) => {
+                let inverses = edits.into_iter().rev()
+                    .map(|edit| self.apply_edit(edit, touched))
+                    .collect();
+                EditorEdit::Batch(inverses)
+            }
+        }
     }
     
-    /// Handle mouse move events
-    pub fn handle_mouse_move(&mut self, position: Vec2) {
-        self.mouse_position = position;
+    /// `ids` without repeats, in first-seen order
+    fn dedup_ids(ids: Vec<Uuid>) -> Vec<Uuid> {
+        let mut unique = Vec::with_capacity(ids.len());
+        for id in ids {
+            if !unique.contains(&id) {
+                unique.push(id);
+            }
+        }
+        unique
+    }
+    
+    /// The inverse of `edit`: the values its nodes hold right now
+    fn current_values(&self, edit: &BulkPropertyEdit) -> BulkPropertyEdit {
+        BulkPropertyEdit {
+            property: edit.property.clone(),
+            previous: edit.previous.iter()
+                .map(|(node_id, _)| {
+                    let current = self.nodes.get(node_id).and_then(|node| node.properties.get(&edit.property).cloned());
+                    (*node_id, current)
+                })
+                .collect(),
+        }
+    }
+    
+    fn undo_property_edit(&mut self, edit: BulkPropertyEdit) -> Vec<Uuid> {
+        let mut restored = Vec::new();
+        for (node_id, old) in edit.previous {
+            if let Some(node) = self.nodes.get_mut(&node_id) {
+                match old {
+                    Some(value) => node.properties.insert(edit.property.clone(), value),
+                    None => node.properties.remove(&edit.property),
+                };
+                node.dirty = true;
+                restored.push(node_id);
+            }
+        }
+        restored
+    }
+    
+    /// Re-create deleted connections under their original ids
+    ///
+    /// Connections whose endpoints no longer exist are skipped.
+    fn restore_connections(&mut self, removed: Vec<RemovedConnection>) -> Vec<Uuid> {
+        let mut touched = Vec::new();
+        for RemovedConnection { connection, properties } in removed {
+            let id = &connection.id;
+            let Ok(output_type) = self.validate_connection(id.from_node, &id.from_output, id.to_node, &id.to_input) else {
+                warn!("Cannot restore connection {}: endpoints changed", connection.uuid);
+                continue;
+            };
+            self.link(connection.uuid, id.from_node, &id.from_output, id.to_node, &id.to_input, &output_type);
+            if let Some(properties) = properties {
+                self.connection_properties.insert(connection.uuid, properties);
+            }
+            if !connection.style.is_default() {
+                let _ = self.set_connection_style(connection.uuid, connection.style.clone());
+            }
+            for node_id in [id.from_node, id.to_node] {
+                if !touched.contains(&node_id) {
+                    touched.push(node_id);
+                }
+            }
+        }
+        touched
+    }
+    
+    /// Add a connection to the selection
+    pub fn select_connection(&mut self, uuid: Uuid, multi_select: bool) -> bool {
+        if !multi_select {
+            self.selected_connections.clear();
+        }
+        if self.connection_by_id(uuid).is_none() {
+            return false;
+        }
+        self.selected_connections.insert(uuid);
+        true
+    }
+    
+    pub fn deselect_connections(&mut self) {
+        self.selected_connections.clear();
+    }
+    
+    /// Delete every selected connection as a single undoable edit
+    ///
+    /// Returns the number of connections removed.
+    pub fn delete_selected_connections(&mut self) -> usize {
+        let selected = std::mem::take(&mut self.selected_connections);
+        let count = self.remove_connections(|conn| selected.contains(&conn.uuid));
+        if count > 0 {
+            info!("Deleted {} selected connections", count);
+        }
+        count
+    }
+    
+    /// Delete one connection as an undoable edit
+    pub fn delete_connection(&mut self, uuid: Uuid) -> bool {
+        let removed = self.remove_connections(|conn| conn.uuid == uuid) > 0;
+        if removed {
+            info!("Deleted connection {}", uuid);
+        }
+        removed
+    }
+    
+    /// Delete the connection under a screen position, if no node covers it
+    ///
+    /// Uses the same hit tolerance as clicking, so it lines up with what
+    /// `handle_mouse_press` would select.
+    pub fn delete_connection_at(&mut self, screen: Vec2) -> Option<Uuid> {
+        let canvas = self.screen_to_canvas(screen);
+        if self.node_at_position(canvas).is_some() {
+            return None;
+        }
+        let uuid = self.connection_at_position(canvas, CONNECTION_HIT_TOLERANCE / self.canvas_scale)?;
+        self.delete_connection(uuid).then_some(uuid)
+    }
+    
+    /// Remove matching connections, recording them as one undoable edit
+    fn remove_connections(&mut self, matches: impl FnMut(&NodeConnection) -> bool) -> usize {
+        let removed = self.take_connections(matches);
+        let count = removed.len();
+        if count > 0 {
+            self.record_edit(EditorEdit::RestoreConnections(removed));
+        }
+        count
+    }
+    
+    /// Remove matching connections without recording an edit
+    fn take_connections(&mut self, mut matches: impl FnMut(&NodeConnection) -> bool) -> Vec<RemovedConnection> {
+        let targets: Vec<(Uuid, String)> = self.connections.iter()
+            .filter(|conn| matches(conn))
+            .map(|conn| (conn.id.to_node, conn.id.to_input.clone()))
+            .collect();
+        targets.into_iter()
+            .filter_map(|(node_id, input)| self.unlink_input(node_id, &input))
+            .collect()
+    }
+    
+    /// Topmost node whose body contains `position`
+    ///
+    /// Members of collapsed groups are hidden and never hit.
+    ///
+    /// Where nodes overlap, the one drawn in front wins.
+    pub fn node_at_position(&self, position: Vec2) -> Option<Uuid> {
+        self.nodes.values()
+            .filter(|node| !self.is_node_hidden(node.id))
+            .filter(|node| {
+                let half = node.size / 2.0;
+                (position - node.position).abs().cmple(half).all()
+            })
+            .max_by_key(|node| node.stacking_key())
+            .map(|node| node.id)
+    }
+    
+    /// Stack a node in front of every other node
+    ///
+    /// Returns false if there is no such node.
+    pub fn bring_to_front(&mut self, node_id: Uuid) -> bool {
+        let z_order = self.front_z_order();
+        match self.nodes.get_mut(&node_id) {
+            Some(node) => {
+                node.z_order = z_order;
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Stack a node behind every other node
+    ///
+    /// Returns false if there is no such node.
+    pub fn send_to_back(&mut self, node_id: Uuid) -> bool {
+        let z_order = self.nodes.values().map(|node| node.z_order).min().unwrap_or_default() - 1;
+        match self.nodes.get_mut(&node_id) {
+            Some(node) => {
+                node.z_order = z_order;
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// `z_order` that stacks above every existing node
+    fn front_z_order(&self) -> i32 {
+        self.nodes.values().map(|node| node.z_order + 1).max().unwrap_or_default()
+    }
+    
+    /// Visible nodes whose body intersects the canvas rectangle `min`..`max`
+    pub fn nodes_in_rect(&self, min: Vec2, max: Vec2) -> Vec<Uuid> {
+        self.nodes.values()
+            .filter(|node| !self.is_node_hidden(node.id))
+            .filter(|node| {
+                let half = node.size / 2.0;
+                (node.position - half).cmple(max).all() && (node.position + half).cmpge(min).all()
+            })
+            .map(|node| node.id)
+            .collect()
+    }
+    
+    /// Marquee being dragged, as (min, max) corners in canvas space
+    pub fn get_selection_rect(&self) -> Option<(Vec2, Vec2)> {
+        self.selection_rect.map(|(a, b)| (a.min(b), a.max(b)))
+    }
+    
+    /// Connection whose curve passes within `tolerance` of `position`
+    ///
+    /// Tests against the default (unrouted) curve shape; the closest
+    /// connection wins when several are in range.
+    pub fn connection_at_position(&self, position: Vec2, tolerance: f32) -> Option<Uuid> {
+        const SAMPLES: usize = 32;
+        let mut best: Option<(f32, Uuid)> = None;
+        
+        for conn in &self.connections {
+            let (Some(from), Some(to)) = (self.nodes.get(&conn.id.from_node), self.nodes.get(&conn.id.to_node)) else {
+                continue;
+            };
+            let (Some(start), Some(end)) = (
+                from.socket_position(SocketKind::Output, &conn.id.from_output),
+                to.socket_position(SocketKind::Input, &conn.id.to_input),
+            ) else {
+                continue;
+            };
+            
+            let [p0, p1, p2, p3] = default_controls(start, end);
+            let mut previous = p0;
+            for i in 1..=SAMPLES {
+                let point = cubic_bezier(p0, p1, p2, p3, i as f32 / SAMPLES as f32);
+                let distance = distance_to_segment(position, previous, point);
+                if distance <= tolerance && best.is_none_or(|(d, _)| distance < d) {
+                    best = Some((distance, conn.uuid));
+                }
+                previous = point;
+            }
+        }
+        best.map(|(_, uuid)| uuid)
+    }
+    
+    /// Whether a value can be assigned to a socket of the given type
+    fn data_type_accepts(data_type: &NodeDataType, value: &MetaValue) -> bool {
+        match data_type {
+            NodeDataType::Any => true,
+            NodeDataType::Scalar => matches!(value, MetaValue::Scalar(_)),
+            NodeDataType::Boolean => matches!(value, MetaValue::Bool(_)),
+            NodeDataType::String => matches!(value, MetaValue::String(_)),
+            NodeDataType::Color => matches!(value, MetaValue::Color(_)),
+            NodeDataType::List => matches!(value, MetaValue::List(_)),
+            NodeDataType::Vector2 => {
+                matches!(value, MetaValue::Vec2(_) | MetaValue::List(_) | MetaValue::Object(_))
+            }
+            NodeDataType::Vector3 => {
+                matches!(value, MetaValue::Vec3(_) | MetaValue::List(_) | MetaValue::Object(_))
+            }
+        }
+    }
+    
+    pub fn select_node(&mut self, node_id: Uuid, multi_select: bool) {
+        if !multi_select {
+            // Clear existing selection
+            for node in self.nodes.values_mut() {
+                node.selected = false;
+            }
+            self.selected_nodes.clear();
+        }
+        
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            node.selected = true;
+            if !self.selected_nodes.contains(&node_id) {
+                self.selected_nodes.push(node_id);
+            }
+        }
+    }
+    
+    pub fn deselect_all(&mut self) {
+        for node in self.nodes.values_mut() {
+            node.selected = false;
+        }
+        self.selected_nodes.clear();
+    }
+    
+    /// Group nodes under a new group, returning its id
+    ///
+    /// Fails if the list is empty or any node is unknown or already grouped.
+    pub fn create_group(&mut self, node_ids: &[Uuid]) -> Result<Uuid, MarcoError> {
+        if node_ids.is_empty() {
+            return Err(MarcoError::InvalidOperation("Cannot create an empty group".to_string()));
+        }
+        let mut member_ids = Vec::with_capacity(node_ids.len());
+        for &node_id in node_ids {
+            if !self.nodes.contains_key(&node_id) {
+                return Err(MarcoError::NodeEval(format!("Node {} not found", node_id)));
+            }
+            if let Some(group) = self.group_of(node_id) {
+                return Err(MarcoError::InvalidOperation(format!(
+                    "Node {} is already in group '{}'", node_id, group.title
+                )));
+            }
+            if !member_ids.contains(&node_id) {
+                member_ids.push(node_id);
+            }
+        }
+        
+        let id = Uuid::new_v4();
+        let title = format!("Group {}", self.groups.len() + 1);
+        info!("Created group '{}' with {} nodes", title, member_ids.len());
+        self.groups.insert(id, NodeGroup { id, title, member_ids, collapsed: false });
+        Ok(id)
+    }
+    
+    /// Dissolve a group, leaving its members in place and visible
+    pub fn ungroup(&mut self, group_id: Uuid) -> Option<NodeGroup> {
+        let group = self.groups.remove(&group_id)?;
+        info!("Ungrouped '{}'", group.title);
+        Some(group)
+    }
+    
+    /// Collapse or expand a group, returning whether it is now collapsed
+    ///
+    /// Collapsing deselects the members so hidden nodes are not edited by
+    /// selection-wide operations.
+    pub fn toggle_group_collapsed(&mut self, group_id: Uuid) -> Option<bool> {
+        let group = self.groups.get_mut(&group_id)?;
+        group.collapsed = !group.collapsed;
+        let (collapsed, members) = (group.collapsed, group.member_ids.clone());
+        if collapsed {
+            for node_id in &members {
+                if let Some(node) = self.nodes.get_mut(node_id) {
+                    node.selected = false;
+                }
+            }
+            self.selected_nodes.retain(|id| !members.contains(id));
+        }
+        Some(collapsed)
+    }
+    
+    /// Group containing a node, if any
+    pub fn group_of(&self, node_id: Uuid) -> Option<&NodeGroup> {
+        self.groups.values().find(|group| group.member_ids.contains(&node_id))
+    }
+    
+    /// Whether a node is hidden inside a collapsed group
+    pub fn is_node_hidden(&self, node_id: Uuid) -> bool {
+        self.group_of(node_id).is_some_and(|group| group.collapsed)
+    }
+    
+    /// Select every member of a group
+    pub fn select_group(&mut self, group_id: Uuid, multi_select: bool) {
+        let Some(members) = self.groups.get(&group_id).map(|group| group.member_ids.clone()) else {
+            return;
+        };
+        if !multi_select {
+            self.deselect_all();
+        }
+        for node_id in members {
+            if let Some(node) = self.nodes.get_mut(&node_id) {
+                node.selected = true;
+            }
+            if !self.selected_nodes.contains(&node_id) {
+                self.selected_nodes.push(node_id);
+            }
+        }
+    }
+    
+    /// Collapsed group whose stand-in box contains `position`
+    pub fn group_at_position(&self, position: Vec2) -> Option<Uuid> {
+        self.groups.values()
+            .filter(|group| group.collapsed)
+            .filter_map(|group| self.group_proxy_node(group))
+            .find(|proxy| (position - proxy.position).abs().cmple(proxy.size / 2.0).all())
+            .map(|proxy| proxy.id)
+    }
+    
+    /// Name of the proxy port standing in for a member socket on a collapsed group
+    pub fn group_proxy_port(node_id: Uuid, socket: &str) -> String {
+        format!("{}.{}", node_id, socket)
+    }
+    
+    /// Nodes to draw: visible nodes plus one stand-in per collapsed group
+    ///
+    /// A stand-in has the group's id, type [`GROUP_NODE_TYPE`], sits at the
+    /// centre of its members and exposes one proxy port per member socket
+    /// that connects outside the group.
+    pub fn rendered_nodes(&self) -> HashMap<Uuid, VisualNode> {
+        let mut nodes: HashMap<Uuid, VisualNode> = self.nodes.values()
+            .filter(|node| !self.is_node_hidden(node.id))
+            .map(|node| (node.id, node.clone()))
+            .collect();
+        for group in self.groups.values().filter(|group| group.collapsed) {
+            if let Some(proxy) = self.group_proxy_node(group) {
+                nodes.insert(proxy.id, proxy);
+            }
+        }
+        nodes
+    }
+    
+    /// Visible connections routed for drawing
+    ///
+    /// Connections inside a collapsed group are dropped, and endpoints on a
+    /// hidden member are moved to the group's proxy port.
+    pub fn rendered_connections(&self) -> Vec<NodeConnection> {
+        self.visible_connections().into_iter()
+            .filter_map(|conn| {
+                let from_group = self.group_of(conn.id.from_node).filter(|group| group.collapsed).map(|group| group.id);
+                let to_group = self.group_of(conn.id.to_node).filter(|group| group.collapsed).map(|group| group.id);
+                if from_group.is_some() && from_group == to_group {
+                    return None;
+                }
+                let mut routed = conn.clone();
+                if let Some(group_id) = from_group {
+                    routed.id.from_output = Self::group_proxy_port(conn.id.from_node, &conn.id.from_output);
+                    routed.id.from_node = group_id;
+                }
+                if let Some(group_id) = to_group {
+                    routed.id.to_input = Self::group_proxy_port(conn.id.to_node, &conn.id.to_input);
+                    routed.id.to_node = group_id;
+                }
+                Some(routed)
+            })
+            .collect()
+    }
+    
+    /// Stand-in node for a collapsed group
+    fn group_proxy_node(&self, group: &NodeGroup) -> Option<VisualNode> {
+        let members: Vec<&VisualNode> = group.member_ids.iter().filter_map(|id| self.nodes.get(id)).collect();
+        let min = members.iter().map(|node| node.position - node.size / 2.0).reduce(Vec2::min)?;
+        let max = members.iter().map(|node| node.position + node.size / 2.0).reduce(Vec2::max)?;
+        
+        let mut inputs = HashMap::new();
+        let mut outputs = HashMap::new();
+        for conn in &self.connections {
+            let from_inside = group.member_ids.contains(&conn.id.from_node);
+            let to_inside = group.member_ids.contains(&conn.id.to_node);
+            if from_inside && !to_inside {
+                let name = Self::group_proxy_port(conn.id.from_node, &conn.id.from_output);
+                let Some(output) = self.nodes.get(&conn.id.from_node).and_then(|node| node.outputs.get(&conn.id.from_output)) else {
+                    continue;
+                };
+                let proxy = outputs.entry(name.clone()).or_insert_with(|| NodeOutput {
+                    name,
+                    data_type: output.data_type.clone(),
+                    connections: Vec::new(),
+                });
+                proxy.connections.push(conn.id.clone());
+            } else if to_inside && !from_inside {
+                let name = Self::group_proxy_port(conn.id.to_node, &conn.id.to_input);
+                let Some(input) = self.nodes.get(&conn.id.to_node).and_then(|node| node.inputs.get(&conn.id.to_input)) else {
+                    continue;
+                };
+                inputs.insert(name.clone(), NodeInput { name, ..input.clone() });
+            }
+        }
+        
+        let ports = inputs.len().max(outputs.len()) as f32;
+        Some(VisualNode {
+            id: group.id,
+            node_type: GROUP_NODE_TYPE.to_string(),
+            position: (min + max) / 2.0,
+            size: Vec2::new(COLLAPSED_GROUP_WIDTH, (24.0 * (ports + 1.0)).max(80.0)),
+            inputs,
+            outputs,
+            properties: HashMap::new(),
+            selected: group.member_ids.iter().all(|id| self.selected_nodes.contains(id)),
+            title: group.title.clone(),
+            dirty: false,
+            z_order: group.member_ids.iter()
+                .filter_map(|id| self.nodes.get(id))
+                .map(|node| node.z_order)
+                .max()
+                .unwrap_or_default(),
+        })
+    }
+    
+    /// Selected nodes plus the other members of any group they belong to
+    fn selection_with_groups(&self) -> Vec<Uuid> {
+        let mut nodes = self.selected_nodes.clone();
+        for &node_id in &self.selected_nodes {
+            if let Some(group) = self.group_of(node_id) {
+                for member in &group.member_ids {
+                    if !nodes.contains(member) {
+                        nodes.push(*member);
+                    }
+                }
+            }
+        }
+        nodes
+    }
+    
+    /// Move the selected nodes, together with every member of their groups
+    pub fn move_selected_nodes(&mut self, delta: Vec2) {
+        let previous = self.shift_nodes(&self.selection_with_groups(), delta);
+        if !previous.is_empty() {
+            self.record_edit(EditorEdit::MoveNodes(previous));
+        }
+    }
+    
+    /// Move nodes by `delta`, snapping to the grid, without recording an edit
+    ///
+    /// Returns the previous positions of the nodes that moved.
+    fn shift_nodes(&mut self, node_ids: &[Uuid], delta: Vec2) -> Vec<(Uuid, Vec2)> {
+        let mut previous = Vec::new();
+        for &node_id in node_ids {
+            if let Some(old) = self.nodes.get(&node_id).map(|node| node.position) {
+                let position = old + delta;
+                let position = if self.snap_to_grid { self.snap_position_to_grid(position) } else { position };
+                if let Some(node) = self.nodes.get_mut(&node_id) {
+                    node.position = position;
+                }
+                if position != old {
+                    previous.push((node_id, old));
+                }
+            }
+        }
+        previous
+    }
+    
+    /// Remove every selected node along with its connections
+    ///
+    /// Returns the number of nodes removed.
+    pub fn delete_selected_nodes(&mut self) -> usize {
+        let selected = std::mem::take(&mut self.selected_nodes);
+        let removed = self.grouped(|editor| {
+            selected.into_iter()
+                .filter(|&node_id| editor.remove_node(node_id).is_ok())
+                .count()
+        });
+        if removed > 0 {
+            info!("Deleted {} selected nodes", removed);
+        }
+        removed
+    }
+    
+    pub fn duplicate_selected_nodes(&mut self) -> Result<Vec<Uuid>, MarcoError> {
+        let selected = self.selected_nodes.clone();
+        let offset = self.duplicate_offset;
+        // An empty group records nothing, so duplicating an empty selection
+        // leaves the redo stack alone
+        self.grouped(|editor| {
+            let mut new_node_ids = Vec::new();
+            for node_id in selected {
+                if let Some(new_id) = editor.duplicate_node(node_id, offset)? {
+                    new_node_ids.push(new_id);
+                }
+            }
+            Ok(new_node_ids)
+        })
+    }
+    
+    /// Paste copies of `node_ids` with their bounding box's top-left at `target`
+    ///
+    /// Behaves like [`Self::paste`] of those nodes, including the cascade
+    /// and the connections between them, anchored at the top-left corner
+    /// instead of the centre.
+    pub fn paste_nodes_at(&mut self, node_ids: &[Uuid], target: Vec2) -> Result<Vec<Uuid>, MarcoError> {
+        let clipboard = self.copy_nodes(node_ids);
+        let top_left = clipboard.nodes.iter()
+            .map(|node| node.position - node.size / 2.0)
+            .reduce(Vec2::min);
+        match (top_left, clipboard.centroid()) {
+            (Some(top_left), Some(centroid)) => self.paste(&clipboard, target + centroid - top_left),
+            _ => Ok(Vec::new()),
+        }
+    }
+    
+    /// Copy the selected nodes and the connections between them
+    pub fn copy_selection(&self) -> NodeClipboard {
+        self.copy_nodes(&self.selected_nodes)
+    }
+    
+    /// Copy `node_ids` and the connections between them
+    pub fn copy_nodes(&self, node_ids: &[Uuid]) -> NodeClipboard {
+        let nodes: Vec<VisualNode> = node_ids.iter()
+            .filter_map(|id| self.nodes.get(id).cloned())
+            .collect();
+        let ids: HashSet<Uuid> = nodes.iter().map(|node| node.id).collect();
+        let connections = self.connections.iter()
+            .filter(|conn| ids.contains(&conn.id.from_node) && ids.contains(&conn.id.to_node))
+            .map(|conn| conn.id.clone())
+            .collect();
+        NodeClipboard { nodes, connections }
+    }
+    
+    /// Paste a clipboard with its bounding box centred on `at`
+    ///
+    /// Every node gets a fresh id and the copied connections are rebuilt
+    /// between the new nodes. Pasting again at the same point shifts each
+    /// successive group by `paste_cascade_offset` so the copies don't stack
+    /// exactly. The pasted nodes become the selection.
+    pub fn paste(&mut self, clipboard: &NodeClipboard, at: Vec2) -> Result<Vec<Uuid>, MarcoError> {
+        let Some(centroid) = clipboard.centroid() else {
+            return Ok(Vec::new());
+        };
+        let repeat = match self.last_paste {
+            Some((last, count)) if last.distance(at) < 1.0 => count + 1,
+            _ => 0,
+        };
+        self.last_paste = Some((at, repeat));
+        let delta = at + self.paste_cascade_offset * repeat as f32 - centroid;
+        
+        self.history.begin_group();
+        let result = self.paste_clipboard(clipboard, delta);
+        self.history.end_group();
+        let pasted = result?;
+        
+        self.deselect_all();
+        for &id in &pasted {
+            self.select_node(id, true);
+        }
+        info!("Pasted {} nodes and {} connections at {:?}", pasted.len(), clipboard.connections.len(), at);
+        Ok(pasted)
+    }
+    
+    /// Add fresh copies of the clipboard's nodes offset by `delta` and
+    /// rebuild its connections between them
+    fn paste_clipboard(&mut self, clipboard: &NodeClipboard, delta: Vec2) -> Result<Vec<Uuid>, MarcoError> {
+        let mut id_map = HashMap::new();
+        let mut pasted = Vec::with_capacity(clipboard.nodes.len());
+        for node in &clipboard.nodes {
+            let new_node = node.detached_copy(delta);
+            id_map.insert(node.id, new_node.id);
+            pasted.push(new_node.id);
+            self.record_edit(EditorEdit::RemoveNode(new_node.id));
+            self.nodes.insert(new_node.id, new_node);
+        }
+        
+        for conn in &clipboard.connections {
+            if let (Some(&from), Some(&to)) = (id_map.get(&conn.from_node), id_map.get(&conn.to_node)) {
+                self.connect_nodes(from, &conn.from_output, to, &conn.to_input)?;
+            }
+        }
+        Ok(pasted)
+    }
+    
+    pub fn duplicate_node(&mut self, node_id: Uuid, offset: Vec2) -> Result<Option<Uuid>, MarcoError> {
+        if let Some(new_node) = self.nodes.get(&node_id).map(|node| node.detached_copy(offset)) {
+            let new_id = new_node.id;
+            self.nodes.insert(new_id, new_node);
+            self.record_edit(EditorEdit::RemoveNode(new_id));
+            Ok(Some(new_id))
+        } else {
+            Ok(None)
+        }
+    }
+    
+    fn get_node_specification(&self, node_type: &str) -> Result<NodeSpecification, MarcoError> {
+        if node_type == PLACEHOLDER_NODE_TYPE {
+            return Ok(NodeSpecification {
+                title: "Missing Node".to_string(),
+                inputs: HashMap::new(),
+                outputs: HashMap::new(),
+                properties: HashMap::new(),
+            });
+        }
+        self.node_registry.specification(node_type)
+            .map(NodeSpecification::from)
+            .ok_or_else(|| MarcoError::NodeEval(format!("Unknown node type: {}", node_type)))
+    }
+    
+    fn are_types_compatible(&self, output_type: &NodeDataType, input_type: &NodeDataType) -> bool {
+        output_type == input_type || 
+        *input_type == NodeDataType::Any || 
+        *output_type == NodeDataType::Any
+    }
+    
+    /// Wire color for a live value, so `Any` sockets carrying vectors or
+    /// colors are drawn like their typed counterparts
+    pub fn connection_color_for_value(&self, value: &MetaValue) -> [f32; 4] {
+        self.get_connection_color(&NodeDataType::of_value(value))
+    }
+    
+    fn get_connection_color(&self, data_type: &NodeDataType) -> [f32; 4] {
+        match data_type {
+            NodeDataType::Scalar => [0.2, 0.8, 0.2, 1.0],     // Green
+            NodeDataType::Boolean => [0.8, 0.2, 0.2, 1.0],    // Red
+            NodeDataType::String => [0.2, 0.2, 0.8, 1.0],     // Blue
+            NodeDataType::Color => [0.8, 0.8, 0.2, 1.0],      // Yellow
+            NodeDataType::Vector2 => [0.8, 0.2, 0.8, 1.0],    // Magenta
+            NodeDataType::Vector3 => [0.2, 0.8, 0.8, 1.0],    // Cyan
+            NodeDataType::List => [0.8, 0.6, 0.2, 1.0],       // Orange
+            NodeDataType::Any => [0.5, 0.5, 0.5, 1.0],        // Gray
+        }
+    }
+    
+    fn snap_position_to_grid(&self, position: Vec2) -> Vec2 {
+        let spacing = self.snap_spacing();
+        if spacing <= 0.0 {
+            return position;
+        }
+        (position / spacing).round() * spacing
+    }
+    
+    /// Distance between snap points in canvas units
+    pub fn snap_spacing(&self) -> f32 {
+        if self.snap_to_subdivisions {
+            self.grid_size / self.grid_subdivisions.max(1) as f32
+        } else {
+            self.grid_size
+        }
+    }
+    
+    /// Set the major grid spacing; non-positive sizes are ignored
+    pub fn set_grid_size(&mut self, grid_size: f32) {
+        if grid_size.is_finite() && grid_size > 0.0 {
+            self.grid_size = grid_size;
+        }
+    }
+    
+    /// Flip grid snapping, returning whether it is now on
+    pub fn toggle_snap_to_grid(&mut self) -> bool {
+        self.snap_to_grid = !self.snap_to_grid;
+        self.snap_to_grid
+    }
+    
+    /// Major and minor grid line spacing in screen pixels at the current zoom
+    ///
+    /// Lines of both grids pass through `canvas_offset`; every
+    /// `grid_subdivisions`-th minor line coincides with a major one.
+    pub fn grid_spacing(&self) -> GridSpacing {
+        let major = self.grid_size * self.canvas_scale;
+        GridSpacing {
+            major,
+            minor: major / self.grid_subdivisions.max(1) as f32,
+        }
+    }
+    
+    pub fn render(&self, theme: &Marco2Theme) {
+        // Placeholder rendering - in a real implementation, this would use WGPU
+        info!("Rendering node editor with {} nodes, {} connections", 
+              self.nodes.len(), self.connections.len());
+        
+        // Render grid
+        let spacing = self.grid_spacing();
+        info!("Grid: major every {}px, minor every {}px (snap {})", spacing.major, spacing.minor,
+              if self.snap_to_grid { "on" } else { "off" });
+        
+        // Render nodes
+        for node in self.nodes.values() {
+            info!("Node '{}' at {:?} - Selected: {}", 
+                  node.title, node.position, node.selected);
+        }
+        
+        // Render connections
+        for connection in &self.connections {
+            info!("Connection: {}:{} -> {}:{}", 
+                  connection.id.from_node, connection.id.from_output,
+                  connection.id.to_node, connection.id.to_input);
+        }
+    }
+    
+    /// Handle UI events (placeholder implementation for build compatibility)
+    pub fn handle_event(&mut self, _event: &crate::ui::event::UIEvent) -> Result<(), MarcoError> {
+        // TODO: Implement proper event handling for visual node editor
+        Ok(())
+    }
+    
+    /// Advance the editor clock by `delta_time` seconds
+    ///
+    /// Time-based nodes see the new time on the next evaluation.
+    pub fn update(&mut self, delta_time: f32) -> Result<(), MarcoError> {
+        self.elapsed_time += delta_time.max(0.0) as f64;
+        Ok(())
+    }
+    
+    /// Capture the logical graph for sharing or saving
+    pub fn snapshot(&self) -> EditorSnapshot {
+        let mut nodes: Vec<NodeSnapshot> = self.nodes.values()
+            .map(|node| NodeSnapshot {
+                id: node.id,
+                node_type: node.node_type.clone(),
+                properties: node.properties.clone(),
+            })
+            .collect();
+        nodes.sort_by_key(|node| node.id);
+        
+        let connections = self.connections.iter()
+            .map(|conn| ConnectionSnapshot {
+                uuid: conn.uuid,
+                from_node: conn.id.from_node,
+                from_output: conn.id.from_output.clone(),
+                to_node: conn.id.to_node,
+                to_input: conn.id.to_input.clone(),
+            })
+            .collect();
+        
+        let connection_styles = self.connections.iter()
+            .filter(|conn| !conn.style.is_default())
+            .map(|conn| (conn.uuid, conn.style.clone()))
+            .collect();
+        
+        let mut sinks: Vec<Uuid> = self.sink_nodes.iter().copied().collect();
+        sinks.sort();
+        
+        EditorSnapshot {
+            nodes,
+            connections,
+            connection_properties: self.connection_properties.clone(),
+            connection_styles,
+            sinks,
+        }
+    }
+    
+    /// Capture layout, selection and viewport
+    pub fn ui_state(&self) -> EditorUiState {
+        let mut selected_connections: Vec<Uuid> = self.selected_connections.iter().copied().collect();
+        selected_connections.sort();
+        let mut groups: Vec<NodeGroup> = self.groups.values().cloned().collect();
+        groups.sort_by_key(|group| group.id);
+        EditorUiState {
+            positions: self.nodes.values().map(|node| (node.id, node.position.to_array())).collect(),
+            selected_nodes: self.selected_nodes.clone(),
+            selected_connections,
+            groups,
+            canvas_offset: self.canvas_offset.to_array(),
+            canvas_scale: self.canvas_scale,
+            grid_size: self.grid_size,
+            snap_to_grid: self.snap_to_grid,
+            grid_subdivisions: self.grid_subdivisions,
+            snap_to_subdivisions: self.snap_to_subdivisions,
+        }
+    }
+    
+    /// Restore layout, selection and viewport, ignoring unknown ids
+    pub fn apply_ui_state(&mut self, ui: &EditorUiState) {
+        for (id, position) in &ui.positions {
+            if let Some(node) = self.nodes.get_mut(id) {
+                node.position = Vec2::from_array(*position);
+            }
+        }
+        self.deselect_all();
+        for id in &ui.selected_nodes {
+            self.select_node(*id, true);
+        }
+        self.selected_connections = ui.selected_connections.iter()
+            .copied()
+            .filter(|uuid| self.connection_by_id(*uuid).is_some())
+            .collect();
+        self.groups = ui.groups.iter()
+            .map(|group| NodeGroup {
+                member_ids: group.member_ids.iter().copied().filter(|id| self.nodes.contains_key(id)).collect(),
+                ..group.clone()
+            })
+            .filter(|group| !group.member_ids.is_empty())
+            .map(|group| (group.id, group))
+            .collect();
+        self.canvas_offset = Vec2::from_array(ui.canvas_offset);
+        self.canvas_scale = ui.canvas_scale;
+        self.grid_size = ui.grid_size;
+        self.snap_to_grid = ui.snap_to_grid;
+        self.grid_subdivisions = ui.grid_subdivisions;
+        self.snap_to_subdivisions = ui.snap_to_subdivisions;
+    }
+    
+    /// Capture graph and UI state for resuming this session
+    pub fn session(&self) -> EditorSession {
+        EditorSession {
+            graph: self.snapshot(),
+            ui: self.ui_state(),
+        }
+    }
+    
+    /// Rebuild an editor from a saved session
+    pub fn from_session(session: &EditorSession) -> Result<Self, MarcoError> {
+        let mut editor = Self::from_snapshot(&session.graph)?;
+        editor.apply_ui_state(&session.ui);
+        Ok(editor)
+    }
+    
+    /// Graph JSON without UI state, for sharing and the runtime
+    pub fn to_logic_json(&self) -> Result<String, MarcoError> {
+        serde_json::to_string_pretty(&self.snapshot())
+            .map_err(|e| MarcoError::Persistence(format!("Failed to serialize graph: {}", e)))
+    }
+    
+    /// Graph JSON including UI state, for resuming a local session
+    pub fn to_workspace_json(&self) -> Result<String, MarcoError> {
+        serde_json::to_string_pretty(&self.session())
+            .map_err(|e| MarcoError::Persistence(format!("Failed to serialize graph: {}", e)))
+    }
+    
+    /// Load JSON from either `to_logic_json` or `to_workspace_json`
+    ///
+    /// Logic-only input gets default UI state and an automatic layout.
+    pub fn from_json(json: &str) -> Result<Self, MarcoError> {
+        let session: EditorSession = serde_json::from_str(json)
+            .map_err(|e| MarcoError::Persistence(format!("Failed to parse graph: {}", e)))?;
+        Self::from_session(&session)
+    }
+    
+    /// Rebuild an editor from a snapshot, keeping node and connection ids
+    ///
+    /// Nodes are laid out in columns by execution rank.
+    pub fn from_snapshot(snapshot: &EditorSnapshot) -> Result<Self, MarcoError> {
+        let mut editor = Self::new();
+        editor.snap_to_grid = false;
+        
+        for saved in &snapshot.nodes {
+            let temp_id = editor.add_node(&saved.node_type, Vec2::ZERO)?;
+            let mut node = editor.nodes.remove(&temp_id)
+                .expect("node was just added");
+            node.id = saved.id;
+            node.properties.extend(saved.properties.clone());
+            if let (PLACEHOLDER_NODE_TYPE, Some(MetaValue::String(original))) = (node.node_type.as_str(), node.properties.get("original_type")) {
+                node.title = format!("Missing: {}", original);
+            }
+            editor.nodes.insert(saved.id, node);
+        }
+        
+        for saved in &snapshot.connections {
+            editor.ensure_placeholder_socket(saved.from_node, SocketKind::Output, &saved.from_output);
+            editor.ensure_placeholder_socket(saved.to_node, SocketKind::Input, &saved.to_input);
+            let output_type = editor.validate_connection(saved.from_node, &saved.from_output, saved.to_node, &saved.to_input)?;
+            editor.link(saved.uuid, saved.from_node, &saved.from_output, saved.to_node, &saved.to_input, &output_type);
+        }
+        
+        editor.connection_properties = snapshot.connection_properties.iter()
+            .filter(|(uuid, _)| editor.connection_by_id(**uuid).is_some())
+            .map(|(uuid, properties)| (*uuid, properties.clone()))
+            .collect();
+        for (uuid, style) in &snapshot.connection_styles {
+            if editor.set_connection_style(*uuid, style.clone()).is_err() {
+                warn!("Dropping style overrides for missing connection {}", uuid);
+            }
+        }
+        editor.sink_nodes = snapshot.sinks.iter()
+            .copied()
+            .filter(|id| editor.nodes.contains_key(id))
+            .collect();
+        editor.auto_layout();
+        editor.snap_to_grid = true;
+        // Loading is not an edit
+        editor.history.clear();
+        
+        Ok(editor)
+    }
+    
+    /// Arrange nodes in layers along `layout_direction` (Sugiyama style)
+    ///
+    /// A node's layer is its longest path from a source. Nodes within a
+    /// layer are ordered by the barycentre of their neighbours to reduce
+    /// crossings, and connected components are packed side by side across
+    /// the layers. Positions snap to `grid_size`. Graphs with cycles are
+    /// placed in a single layer.
+    pub fn auto_layout(&mut self) {
+        let ranks: HashMap<Uuid, usize> = self.execution_order()
+            .map(|order| order.into_iter().collect())
+            .unwrap_or_else(|_| self.nodes.keys().map(|id| (*id, 0)).collect());
+        let mut neighbours: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for conn in &self.connections {
+            if ranks.contains_key(&conn.id.from_node) && ranks.contains_key(&conn.id.to_node) {
+                neighbours.entry(conn.id.from_node).or_default().push(conn.id.to_node);
+                neighbours.entry(conn.id.to_node).or_default().push(conn.id.from_node);
+            }
+        }
+        
+        let mut cross_offset = 0;
+        for component in Self::connected_components(&ranks, &neighbours) {
+            let layer_count = component.iter().map(|id| ranks[id]).max().unwrap_or(0) + 1;
+            let mut layers: Vec<Vec<Uuid>> = vec![Vec::new(); layer_count];
+            for id in component {
+                layers[ranks[&id]].push(id);
+            }
+            Self::order_layers(&mut layers, &ranks, &neighbours);
+            
+            for (rank, layer) in layers.iter().enumerate() {
+                for (index, id) in layer.iter().enumerate() {
+                    let (main, cross) = (rank as f32, (cross_offset + index) as f32);
+                    let position = match self.layout_direction {
+                        LayoutDirection::LeftToRight => Vec2::new(main, cross) * AUTO_LAYOUT_SPACING,
+                        LayoutDirection::TopToBottom => Vec2::new(cross, main) * AUTO_LAYOUT_SPACING,
+                    };
+                    let position = self.snap_position_to_grid(position);
+                    if let Some(node) = self.nodes.get_mut(id) {
+                        node.position = position;
+                    }
+                }
+            }
+            cross_offset += layers.iter().map(Vec::len).max().unwrap_or(0);
+        }
+    }
+    
+    /// Groups of nodes joined by connections, each sorted by id
+    fn connected_components(ranks: &HashMap<Uuid, usize>, neighbours: &HashMap<Uuid, Vec<Uuid>>) -> Vec<Vec<Uuid>> {
+        let mut ids: Vec<Uuid> = ranks.keys().copied().collect();
+        ids.sort();
+        
+        let mut seen = HashSet::new();
+        let mut components = Vec::new();
+        for start in ids {
+            if !seen.insert(start) {
+                continue;
+            }
+            let mut component = vec![start];
+            let mut queue = VecDeque::from([start]);
+            while let Some(id) = queue.pop_front() {
+                for &next in neighbours.get(&id).into_iter().flatten() {
+                    if seen.insert(next) {
+                        component.push(next);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components
+    }
+    
+    /// Reorder each layer by the mean position of its neighbours
+    ///
+    /// Sweeps alternate downward (using earlier layers) and upward (using
+    /// later layers); nodes without neighbours on that side keep their place.
+    fn order_layers(layers: &mut [Vec<Uuid>], ranks: &HashMap<Uuid, usize>, neighbours: &HashMap<Uuid, Vec<Uuid>>) {
+        let mut index: HashMap<Uuid, f32> = layers.iter()
+            .flat_map(|layer| layer.iter().enumerate().map(|(i, id)| (*id, i as f32)))
+            .collect();
+        
+        for sweep in 0..LAYOUT_ORDERING_SWEEPS {
+            let downward = sweep % 2 == 0;
+            let order: Vec<usize> = if downward {
+                (1..layers.len()).collect()
+            } else {
+                (0..layers.len().saturating_sub(1)).rev().collect()
+            };
+            for rank in order {
+                let mut keyed: Vec<(f32, Uuid)> = layers[rank].iter()
+                    .map(|id| {
+                        let fixed: Vec<f32> = neighbours.get(id).into_iter().flatten()
+                            .filter(|n| if downward { ranks[*n] < rank } else { ranks[*n] > rank })
+                            .map(|n| index[n])
+                            .collect();
+                        let key = if fixed.is_empty() {
+                            index[id]
+                        } else {
+                            fixed.iter().sum::<f32>() / fixed.len() as f32
+                        };
+                        (key, *id)
+                    })
+                    .collect();
+                keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| index[&a.1].total_cmp(&index[&b.1])));
+                layers[rank] = keyed.into_iter().map(|(_, id)| id).collect();
+                for (i, id) in layers[rank].iter().enumerate() {
+                    index.insert(*id, i as f32);
+                }
+            }
+        }
+    }
+    
+    /// Get IDs of currently selected nodes
+    pub fn get_selected_node_ids(&self) -> Vec<Uuid> {
+        self.nodes.iter()
+            .filter(|(_, node)| node.selected)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+    
+    /// Get reference to nodes collection
+    pub fn get_nodes(&self) -> &HashMap<Uuid, VisualNode> {
+        &self.nodes
+    }
+    
+    /// Get reference to connections collection  
+    pub fn get_connections(&self) -> &Vec<NodeConnection> {
+        &self.connections
+    }
+    
+    /// Convert a screen position to canvas space
+    pub fn screen_to_canvas(&self, screen: Vec2) -> Vec2 {
+        (screen - self.canvas_offset) / self.canvas_scale
+    }
+    
+    /// Convert a canvas position to screen space
+    pub fn canvas_to_screen(&self, canvas: Vec2) -> Vec2 {
+        canvas * self.canvas_scale + self.canvas_offset
+    }
+    
+    /// Move the view by a screen-space delta
+    pub fn pan_canvas(&mut self, delta: Vec2) {
+        self.canvas_offset += delta;
+    }
+    
+    /// Nodes whose title or type contains `query`, ignoring case
+    ///
+    /// Results are ordered by title, then id, so a command palette lists
+    /// them stably. An empty query matches every node.
+    pub fn find_nodes(&self, query: &str) -> Vec<Uuid> {
+        let query = query.trim().to_lowercase();
+        let mut matches: Vec<&VisualNode> = self.nodes.values()
+            .filter(|node| node.title.to_lowercase().contains(&query) || node.node_type.to_lowercase().contains(&query))
+            .collect();
+        matches.sort_by(|a, b| a.title.cmp(&b.title).then(a.id.cmp(&b.id)));
+        matches.into_iter().map(|node| node.id).collect()
+    }
+    
+    /// Pan so the canvas point `canvas` sits at the center of
+    /// `viewport_size`, keeping the zoom
+    pub fn center_view_on(&mut self, canvas: Vec2) {
+        self.canvas_offset = self.viewport_size * 0.5 - canvas * self.canvas_scale;
+    }
+    
+    /// Select `node_id` alone and pan so it sits at the center of
+    /// `viewport_size`, keeping the zoom
+    ///
+    /// Returns false if there is no such node.
+    pub fn focus_node(&mut self, node_id: Uuid) -> bool {
+        let Some(center) = self.nodes.get(&node_id).map(|node| node.position + node.size * 0.5) else {
+            return false;
+        };
+        self.select_node(node_id, false);
+        self.center_view_on(center);
+        true
+    }
+    
+    /// Multiply the zoom by `scale_delta`, keeping the canvas point under
+    /// `screen` fixed
+    ///
+    /// The scale is clamped to `MIN_CANVAS_SCALE`..`MAX_CANVAS_SCALE`.
+    pub fn zoom_canvas_at(&mut self, screen: Vec2, scale_delta: f32) {
+        let anchor = self.screen_to_canvas(screen);
+        self.canvas_scale = (self.canvas_scale * scale_delta).clamp(MIN_CANVAS_SCALE, MAX_CANVAS_SCALE);
+        self.canvas_offset = screen - anchor * self.canvas_scale;
+    }
+    
+    /// Handle mouse press events (screen coordinates)
+    ///
+    /// A left click on an output socket starts a connection drag. On a node
+    /// it selects the node, adding to the selection while the multi-select
+    /// modifier is held, and starts dragging it; clicking an already selected
+    /// node keeps the selection so the whole group drags. A click on a
+    /// connection selects it. A click on empty canvas clears both
+    /// selections unless the modifier is held, and starts a marquee.
+    pub fn handle_mouse_press(&mut self, position: Vec2, button: u32) {
+        self.mouse_position = position;
+        if button == PAN_MOUSE_BUTTON {
+            self.panning = true;
+            return;
+        }
+        if button != 0 {
+            return;
+        }
+        let canvas = self.screen_to_canvas(position);
+        
+        if let Some(socket) = self.socket_at_position(canvas, SOCKET_HIT_RADIUS / self.canvas_scale) {
+            if socket.2 == SocketKind::Output {
+                self.begin_connection(socket);
+                return;
+            }
+        }
+        if let Some(node_id) = self.node_at_position(canvas) {
+            if self.multi_select_held || !self.selected_nodes.contains(&node_id) {
+                self.select_node(node_id, self.multi_select_held);
+            }
+            self.dragging_node = Some(node_id);
+            self.drag_origin = self.selection_positions();
+            return;
+        }
+        if let Some(group_id) = self.group_at_position(canvas) {
+            self.select_group(group_id, self.multi_select_held);
+            self.dragging_node = self.groups[&group_id].member_ids.first().copied();
+            self.drag_origin = self.selection_positions();
+            return;
+        }
+        match self.connection_at_position(canvas, CONNECTION_HIT_TOLERANCE / self.canvas_scale) {
+            Some(uuid) => {
+                self.select_connection(uuid, self.multi_select_held);
+            }
+            None => {
+                if !self.multi_select_held {
+                    self.deselect_all();
+                    self.deselect_connections();
+                }
+                self.selection_rect = Some((canvas, canvas));
+            }
+        }
+    }
+    
+    /// Handle mouse release events (screen coordinates)
+    ///
+    /// Ends a node drag, snapping the dragged nodes to the grid, or a
+    /// marquee, selecting every node it touches. Releasing a
+    /// connection drag over a socket completes it; releasing anywhere else,
+    /// or over an incompatible socket, cancels it.
+    pub fn handle_mouse_release(&mut self, position: Vec2, button: u32) {
+        self.mouse_position = position;
+        if button == PAN_MOUSE_BUTTON {
+            self.panning = false;
+            return;
+        }
+        if button != 0 {
+            return;
+        }
+        if self.dragging_node.take().is_some() {
+            // The whole drag is one undo step back to where it started
+            self.shift_nodes(&self.selection_with_groups(), Vec2::ZERO);
+            let moved: Vec<(Uuid, Vec2)> = std::mem::take(&mut self.drag_origin).into_iter()
+                .filter(|(node_id, start)| self.nodes.get(node_id).is_some_and(|node| node.position != *start))
+                .collect();
+            if !moved.is_empty() {
+                self.record_edit(EditorEdit::MoveNodes(moved));
+            }
+        }
+        if let Some((min, max)) = self.get_selection_rect() {
+            self.selection_rect = None;
+            if !self.multi_select_held {
+                self.deselect_all();
+            }
+            for node_id in self.nodes_in_rect(min, max) {
+                self.select_node(node_id, true);
+            }
+        }
+        if self.connecting_from.is_none() {
+            return;
+        }
+        
+        let canvas = self.screen_to_canvas(position);
+        match self.socket_at_position(canvas, SOCKET_HIT_RADIUS / self.canvas_scale) {
+            Some(target) => {
+                if let Err(e) = self.complete_connection(target) {
+                    warn!("Connection drop rejected: {}", e);
+                }
+            }
+            None => self.cancel_connection(),
+        }
+    }
+    
+    /// Handle the Escape key: cancel a connection drag, else close the context menu
+    pub fn handle_escape(&mut self) {
+        if self.connecting_from.is_some() {
+            self.cancel_connection();
+        } else {
+            self.context_menu_position = None;
+        }
+    }
+    
+    /// Handle mouse move events (screen coordinates)
+    ///
+    /// While a node drag is active the selection follows the cursor freely;
+    /// grid snapping is applied on release. A marquee stretches to the cursor.
+    /// While panning the canvas follows the cursor instead.
+    pub fn handle_mouse_move(&mut self, position: Vec2) {
+        if self.panning {
+            self.pan_canvas(position - self.mouse_position);
+            self.mouse_position = position;
+            return;
+        }
+        let delta = (position - self.mouse_position) / self.canvas_scale;
+        self.mouse_position = position;
+        if let Some((anchor, _)) = self.selection_rect {
+            self.selection_rect = Some((anchor, self.screen_to_canvas(position)));
+        }
+        if self.dragging_node.is_some() {
+            for node_id in self.selection_with_groups() {
+                if let Some(node) = self.nodes.get_mut(&node_id) {
+                    node.position += delta;
+                }
+            }
+        }
+    }
+    
+    /// Current positions of the selected nodes and their group members
+    fn selection_positions(&self) -> Vec<(Uuid, Vec2)> {
+        self.selection_with_groups().into_iter()
+            .filter_map(|node_id| self.nodes.get(&node_id).map(|node| (node_id, node.position)))
+            .collect()
     }
     
     /// Set theme for visual styling
@@ -1279,9 +3084,1270 @@ impl VisualNodeEditor {
     }
 }
 
+/// Distance from `point` to the segment `a`-`b`
+fn distance_to_segment(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_squared() > 0.0 {
+        ((point - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    point.distance(a + ab * t)
+}
+
+/// Escape text for use inside a double-quoted DOT string
+fn escape_dot(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape text for one field of a DOT record label
+fn escape_dot_record(text: &str) -> String {
+    escape_dot(text).chars().fold(String::new(), |mut escaped, c| {
+        if matches!(c, '{' | '}' | '|' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        escaped
+    })
+}
+
+/// Record port id for a socket; characters other than letters, digits and
+/// `_` become `_`
+fn dot_port_id(prefix: &str, socket: &str) -> String {
+    let socket: String = socket.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    format!("{}_{}", prefix, socket)
+}
+
+/// `<port> label` fields for a node's sockets, sorted by socket name
+fn dot_record_ports<'a>(prefix: &str, sockets: impl Iterator<Item = (&'a String, &'a String)>) -> String {
+    let mut sockets: Vec<(&String, &String)> = sockets.collect();
+    sockets.sort();
+    sockets.iter()
+        .map(|(key, label)| format!("<{}> {}", dot_port_id(prefix, key), escape_dot_record(label)))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
 struct NodeSpecification {
     title: String,
     inputs: HashMap<String, NodeInput>,
     outputs: HashMap<String, NodeOutput>,
     properties: HashMap<String, MetaValue>,
 }
+
+impl From<node_registry::NodeSpecification> for NodeSpecification {
+    fn from(spec: node_registry::NodeSpecification) -> Self {
+        let inputs = spec.inputs.into_iter()
+            .map(|input| {
+                let socket = NodeInput {
+                    name: input.label.unwrap_or_else(|| socket_label(&input.name)),
+                    data_type: NodeDataType::from_type_name(&input.expected_type),
+                    connected_output: None,
+                    default_value: input.default_value,
+                };
+                (input.name, socket)
+            })
+            .collect();
+        let outputs = spec.outputs.into_iter()
+            .map(|output| {
+                let socket = NodeOutput {
+                    name: output.label.unwrap_or_else(|| socket_label(&output.name)),
+                    data_type: NodeDataType::from_type_name(&output.output_type),
+                    connections: Vec::new(),
+                };
+                (output.name, socket)
+            })
+            .collect();
+
+        Self { title: spec.title, inputs, outputs, properties: HashMap::new() }
+    }
+}
+
+/// `snake_case` socket name as a title-cased label
+fn socket_label(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_add_nodes() -> (VisualNodeEditor, Uuid, Uuid) {
+        let mut editor = VisualNodeEditor::new();
+        let first = editor.add_node("add", Vec2::new(0.0, 0.0)).unwrap();
+        let second = editor.add_node("add", Vec2::new(300.0, 0.0)).unwrap();
+        (editor, first, second)
+    }
+
+    #[test]
+    fn test_connect_two_outputs_reports_socket_kinds() {
+        let (mut editor, first, second) = two_add_nodes();
+
+        let err = editor.connect_sockets(
+            (first, "result".to_string(), SocketKind::Output),
+            (second, "result".to_string(), SocketKind::Output),
+        ).unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains("Cannot connect output 'result'"), "{}", message);
+        assert!(message.contains("must join an output to an input"), "{}", message);
+        assert!(editor.connections.is_empty());
+    }
+
+    #[test]
+    fn test_connect_sockets_orients_input_first_pair() {
+        let (mut editor, first, second) = two_add_nodes();
+
+        editor.connect_sockets(
+            (second, "a".to_string(), SocketKind::Input),
+            (first, "result".to_string(), SocketKind::Output),
+        ).unwrap();
+
+        assert_eq!(editor.connections.len(), 1);
+        let id = &editor.connections[0].id;
+        assert_eq!((id.from_node, id.from_output.as_str()), (first, "result"));
+        assert_eq!((id.to_node, id.to_input.as_str()), (second, "a"));
+        assert!(editor.nodes[&second].inputs["a"].connected_output.is_some());
+    }
+
+    #[test]
+    fn test_connecting_a_fed_input_reports_the_replaced_connection() {
+        let (mut editor, first, second) = two_add_nodes();
+        let third = editor.add_node("add", Vec2::new(0.0, 300.0)).unwrap();
+
+        assert!(!editor.input_is_connected(second, "a"));
+        let result = editor.connect_nodes(first, "result", second, "a").unwrap();
+        assert_eq!(result.replaced, None);
+        assert!(editor.input_is_connected(second, "a"));
+
+        // Outputs fan out freely
+        assert_eq!(editor.connect_nodes(first, "result", second, "b").unwrap().replaced, None);
+        assert_eq!(editor.nodes[&first].outputs["result"].connections.len(), 2);
+
+        let old = editor.nodes[&second].inputs["a"].connected_output.clone();
+        let result = editor.connect_nodes(third, "result", second, "a").unwrap();
+        assert_eq!(result.replaced, old);
+        assert_eq!(editor.nodes[&first].outputs["result"].connections.len(), 1);
+        assert_eq!(editor.connections.len(), 2);
+    }
+
+    #[test]
+    fn test_node_sockets_come_from_registry_specifications() {
+        use crate::core::logic::{InputSpec, OutputSpec};
+
+        let mut editor = VisualNodeEditor::new();
+        let branch = editor.add_node("branch", Vec2::ZERO).unwrap();
+        assert_eq!(editor.nodes[&branch].inputs["true_value"].name, "True");
+        assert_eq!(editor.nodes[&branch].inputs["condition"].data_type, NodeDataType::Boolean);
+        assert!(editor.add_node("gain", Vec2::ZERO).is_err());
+
+        editor.node_registry.register_specification(node_registry::NodeSpecification::new("gain", "Gain")
+            .with_input(InputSpec::optional("input_level", "scalar", MetaValue::Scalar(1.0)))
+            .with_output(OutputSpec::new("level", "scalar").with_label("Out")));
+        let gain = editor.add_node("gain", Vec2::new(300.0, 0.0)).unwrap();
+
+        let node = &editor.nodes[&gain];
+        assert_eq!(node.title, "Gain");
+        assert_eq!(node.inputs["input_level"].name, "Input Level");
+        assert_eq!(node.inputs["input_level"].default_value, Some(MetaValue::Scalar(1.0)));
+        assert_eq!(node.outputs["level"].name, "Out");
+        assert!(editor.connect_nodes(branch, "result", gain, "input_level").is_ok());
+    }
+
+    #[test]
+    fn test_runtime_registered_node_evaluates_in_graph() {
+        use crate::core::logic::{InputSpec, OutputSpec};
+
+        #[derive(Debug)]
+        struct DoubleNode;
+
+        impl Evaluatable for DoubleNode {
+            fn evaluate(&self, inputs: &InputMap, _ctx: &EvalContext) -> Result<OutputMap, MarcoError> {
+                let value = inputs.get("value").and_then(|v| v.as_scalar()).unwrap_or(0.0);
+                Ok(HashMap::from([("result".to_string(), MetaValue::Scalar(value * 2.0))]))
+            }
+
+            fn input_specs(&self) -> Vec<InputSpec> {
+                vec![InputSpec::optional("value", "scalar", MetaValue::Scalar(0.0))]
+            }
+
+            fn output_specs(&self) -> Vec<OutputSpec> {
+                vec![OutputSpec::new("result", "scalar")]
+            }
+        }
+
+        let mut editor = VisualNodeEditor::new();
+        editor.node_registry.register("double", Arc::new(|| Arc::new(DoubleNode)));
+        assert!(editor.node_registry.list_available_nodes().contains(&"double".to_string()));
+
+        let add = editor.add_node("add", Vec2::ZERO).unwrap();
+        let double = editor.add_node("double", Vec2::new(300.0, 0.0)).unwrap();
+        editor.update_node_property(add, "a", MetaValue::Scalar(2.0)).unwrap();
+        editor.update_node_property(add, "b", MetaValue::Scalar(1.5)).unwrap();
+        editor.connect_nodes(add, "result", double, "value").unwrap();
+
+        let results = editor.evaluate_graph().unwrap();
+        assert_eq!(results[&double]["result"], MetaValue::Scalar(7.0));
+
+        // Registered factories shadow builtins of the same name
+        editor.node_registry.register("add", Arc::new(|| Arc::new(DoubleNode)));
+        assert_eq!(editor.evaluate_graph().unwrap()[&double]["result"], MetaValue::Scalar(0.0));
+    }
+
+    #[test]
+    fn test_snapping_uses_major_grid_or_subdivisions() {
+        let mut editor = VisualNodeEditor::new();
+        editor.set_grid_size(40.0);
+        editor.set_grid_size(-5.0);
+        assert_eq!(editor.grid_size, 40.0);
+
+        let node = editor.add_node("add", Vec2::new(27.0, 9.0)).unwrap();
+        assert_eq!(editor.nodes[&node].position, Vec2::new(40.0, 0.0));
+
+        editor.snap_to_subdivisions = true;
+        let fine = editor.add_node("add", Vec2::new(27.0, 9.0)).unwrap();
+        assert_eq!(editor.nodes[&fine].position, Vec2::new(30.0, 10.0));
+
+        editor.select_node(fine, false);
+        editor.move_selected_nodes(Vec2::new(4.0, 6.0));
+        assert_eq!(editor.nodes[&fine].position, Vec2::new(30.0, 20.0));
+
+        assert!(!editor.toggle_snap_to_grid());
+        editor.move_selected_nodes(Vec2::new(4.0, 6.0));
+        assert_eq!(editor.nodes[&fine].position, Vec2::new(34.0, 26.0));
+
+        editor.canvas_scale = 2.0;
+        assert_eq!(editor.grid_spacing(), GridSpacing { major: 80.0, minor: 20.0 });
+        let restored: EditorUiState = serde_json::from_str(&serde_json::to_string(&editor.ui_state()).unwrap()).unwrap();
+        assert_eq!((restored.grid_subdivisions, restored.snap_to_subdivisions), (4, true));
+    }
+
+    #[test]
+    fn test_find_nodes_matches_title_or_type_and_focus_centers_it() {
+        let mut editor = VisualNodeEditor::new();
+        let math = editor.add_node("math", Vec2::ZERO).unwrap();
+        let timer = editor.add_node("timer", Vec2::new(300.0, 0.0)).unwrap();
+        let add = editor.add_node("add", Vec2::new(600.0, 200.0)).unwrap();
+        editor.nodes.get_mut(&add).unwrap().title = "Score Total".to_string();
+
+        assert_eq!(editor.find_nodes("MATH"), vec![math]);
+        assert_eq!(editor.find_nodes("total"), vec![add]);
+        assert_eq!(editor.find_nodes("add"), vec![add]);
+        assert_eq!(editor.find_nodes("TiMeR"), vec![timer]);
+        assert!(editor.find_nodes("slider").is_empty());
+        assert_eq!(editor.find_nodes("").len(), 3);
+
+        editor.viewport_size = Vec2::new(800.0, 600.0);
+        editor.canvas_scale = 2.0;
+        editor.select_node(math, false);
+        assert!(editor.focus_node(add));
+        assert_eq!(editor.selected_nodes, vec![add]);
+        let node = &editor.nodes[&add];
+        assert_eq!(editor.canvas_to_screen(node.position + node.size * 0.5), Vec2::new(400.0, 300.0));
+        assert!(!editor.focus_node(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn test_reconnect_preserves_connection_uuid() {
+        let (mut editor, first, second) = two_add_nodes();
+        let third = editor.add_node("add", Vec2::new(600.0, 0.0)).unwrap();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        let uuid = editor.connections[0].uuid;
+        editor.set_connection_property(uuid, "color_override", MetaValue::String("#ff0000".to_string())).unwrap();
+
+        editor.reconnect(uuid, SocketKind::Input, third, "b").unwrap();
+
+        let connection = editor.connection_by_id(uuid).expect("id should still resolve");
+        assert_eq!((connection.id.to_node, connection.id.to_input.as_str()), (third, "b"));
+        assert_eq!(editor.connections.len(), 1);
+        assert!(editor.nodes[&second].inputs["a"].connected_output.is_none());
+        assert!(editor.nodes[&third].inputs["b"].connected_output.is_some());
+        assert_eq!(
+            editor.connection_property(uuid, "color_override"),
+            Some(&MetaValue::String("#ff0000".to_string()))
+        );
+
+        // An invalid endpoint leaves the connection where it was
+        assert!(editor.reconnect(uuid, SocketKind::Output, third, "missing").is_err());
+        assert_eq!(editor.connection_by_id(uuid).unwrap().id.from_node, first);
+    }
+
+    #[test]
+    fn test_execution_order_ranks_diamond() {
+        let mut editor = VisualNodeEditor::new();
+        let source = editor.add_node("add", Vec2::new(0.0, 0.0)).unwrap();
+        let left = editor.add_node("add", Vec2::new(200.0, -100.0)).unwrap();
+        let right = editor.add_node("add", Vec2::new(200.0, 100.0)).unwrap();
+        let sink = editor.add_node("add", Vec2::new(400.0, 0.0)).unwrap();
+        editor.connect_nodes(source, "result", left, "a").unwrap();
+        editor.connect_nodes(source, "result", right, "a").unwrap();
+        editor.connect_nodes(left, "result", sink, "a").unwrap();
+        editor.connect_nodes(right, "result", sink, "b").unwrap();
+
+        let ranks: HashMap<Uuid, usize> = editor.execution_order().unwrap().into_iter().collect();
+        assert_eq!(ranks[&source], 0);
+        assert_eq!(ranks[&left], 1);
+        assert_eq!(ranks[&right], 1);
+        assert_eq!(ranks[&sink], 2);
+
+        // Closing a loop makes the order undefined; connect_nodes refuses
+        // to, so link directly
+        editor.link(Uuid::new_v4(), sink, "result", source, "b", &NodeDataType::Scalar);
+        assert!(editor.execution_order().unwrap_err().to_string().contains("cycles"));
+    }
+
+    #[test]
+    fn test_connections_closing_a_cycle_are_rejected() {
+        let (mut editor, first, second) = two_add_nodes();
+        let third = editor.add_node("add", Vec2::new(600.0, 0.0)).unwrap();
+
+        // A node feeding itself
+        assert!(editor.would_create_cycle(first, first));
+        let err = editor.connect_nodes(first, "result", first, "a").unwrap_err();
+        assert!(matches!(err, MarcoError::NodeEval(ref msg) if msg == "connection would create a cycle"));
+
+        // first -> second -> third -> first
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        editor.connect_nodes(second, "result", third, "a").unwrap();
+        assert!(editor.would_create_cycle(third, first));
+        assert!(editor.connect_nodes(third, "result", first, "b").is_err());
+        assert_eq!(editor.connections.len(), 2);
+        assert!(editor.nodes[&first].inputs["b"].connected_output.is_none());
+
+        // Feeding forward along the chain is still fine
+        assert!(!editor.would_create_cycle(first, third));
+        editor.connect_nodes(first, "result", third, "b").unwrap();
+    }
+
+    #[test]
+    fn test_shared_dependency_evaluates_before_both_consumers() {
+        let mut editor = VisualNodeEditor::new();
+        let source = editor.add_node("add", Vec2::new(0.0, 0.0)).unwrap();
+        let left = editor.add_node("add", Vec2::new(200.0, -100.0)).unwrap();
+        let right = editor.add_node("add", Vec2::new(200.0, 100.0)).unwrap();
+        let sink = editor.add_node("add", Vec2::new(400.0, 0.0)).unwrap();
+        editor.update_node_property(source, "a", MetaValue::Scalar(2.0)).unwrap();
+        editor.update_node_property(source, "b", MetaValue::Scalar(3.0)).unwrap();
+        editor.connect_nodes(left, "result", sink, "a").unwrap();
+        editor.connect_nodes(right, "result", sink, "b").unwrap();
+        editor.connect_nodes(source, "result", left, "a").unwrap();
+        editor.connect_nodes(source, "result", right, "a").unwrap();
+
+        let results = editor.evaluate_graph().unwrap();
+        assert_eq!(results[&left].get("result"), Some(&MetaValue::Scalar(5.0)));
+        assert_eq!(results[&right].get("result"), Some(&MetaValue::Scalar(5.0)));
+        assert_eq!(results[&sink].get("result"), Some(&MetaValue::Scalar(10.0)));
+
+        // A cycle slipped in around connect_nodes is reported, not half-evaluated
+        editor.link(Uuid::new_v4(), sink, "result", source, "a", &NodeDataType::Scalar);
+        let err = editor.evaluate_graph().unwrap_err();
+        assert!(err.to_string().contains("Cycle"));
+    }
+
+    /// Log sink for asserting on warnings emitted during a test
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl CapturedLogs {
+        fn text(&self) -> String {
+            String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+        }
+    }
+
+    #[test]
+    fn test_missing_upstream_value_warns_even_with_property() {
+        let mut editor = VisualNodeEditor::new();
+        let source = editor.add_node("add", Vec2::new(0.0, 0.0)).unwrap();
+        let sink = editor.add_node("add", Vec2::new(300.0, 0.0)).unwrap();
+        editor.update_node_property(sink, "a", MetaValue::Scalar(4.0)).unwrap();
+        // Bypasses connect_nodes validation to wire up an output that is never produced
+        editor.link(Uuid::new_v4(), source, "missing", sink, "a", &NodeDataType::Scalar);
+
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        let results = tracing::subscriber::with_default(subscriber, || editor.evaluate_graph()).unwrap();
+
+        // The property still wins, but the broken wire is reported
+        assert_eq!(results[&sink].get("result"), Some(&MetaValue::Scalar(4.0)));
+        let text = logs.text();
+        assert!(text.contains("Input 'a'") && text.contains("produced no value"), "{}", text);
+    }
+
+    #[test]
+    fn test_scalar_filter_hides_other_connections() {
+        let mut editor = VisualNodeEditor::new();
+        let source = editor.add_node("string", Vec2::new(0.0, 0.0)).unwrap();
+        let text_sink = editor.add_node("string", Vec2::new(300.0, 0.0)).unwrap();
+        let math = editor.add_node("math", Vec2::new(300.0, 200.0)).unwrap();
+        editor.connect_nodes(source, "result", text_sink, "text").unwrap();
+        editor.connect_nodes(source, "length", math, "a").unwrap();
+
+        editor.set_connection_filter(Some(ConnectionFilter::DataType(NodeDataType::Scalar)));
+        let visible = editor.visible_connections();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].id.from_output, "length");
+        assert_eq!(editor.connections.len(), 2);
+
+        editor.set_connection_filter(Some(ConnectionFilter::Node(text_sink)));
+        assert_eq!(editor.visible_connections()[0].id.to_node, text_sink);
+
+        editor.set_connection_filter(None);
+        assert_eq!(editor.visible_connections().len(), 2);
+    }
+
+    #[test]
+    fn test_to_dot_declares_record_nodes_and_colored_port_edges() {
+        let (mut editor, first, second) = two_add_nodes();
+        let branch = editor.add_node("branch", Vec2::new(600.0, 0.0)).unwrap();
+        editor.connect_nodes(first, "result", second, "b").unwrap();
+        editor.connect_nodes(second, "result", branch, "true_value").unwrap();
+
+        let dot = editor.to_dot();
+        assert!(dot.starts_with("digraph marco {"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert_eq!(dot.lines().filter(|line| line.contains("[label=")).count(), 3);
+        assert_eq!(dot.lines().filter(|line| line.contains(" -> ")).count(), 2);
+        assert!(dot.contains(&format!("\"{}\" [label=\"{{{{<in_a> A|<in_b> B}}|Add (Legacy)\\n(add)|{{<out_result> Result}}}}\"", first)));
+        assert!(dot.contains("<in_true_value> True"));
+        assert!(dot.contains(&format!("\"{}\":\"out_result\" -> \"{}\":\"in_b\" [color=\"#33cc33\"];", first, second)));
+        assert!(dot.contains(&format!("\"{}\":\"out_result\" -> \"{}\":\"in_true_value\" [color=\"#33cc33\"];", second, branch)));
+    }
+
+    #[test]
+    fn test_to_dot_escapes_quotes_in_labels() {
+        let (mut editor, first, _) = two_add_nodes();
+        editor.nodes.get_mut(&first).unwrap().title = r#"Say "hi" \ {a|b}"#.to_string();
+
+        let dot = editor.to_dot();
+        assert!(dot.contains(r#"|Say \"hi\" \\ \{a\|b\}\n(add)|"#), "{}", dot);
+    }
+
+    #[test]
+    fn test_set_property_on_selection_skips_nodes_without_it() {
+        let mut editor = VisualNodeEditor::new();
+        let maths: Vec<Uuid> = (0..3)
+            .map(|i| editor.add_node("math", Vec2::new(i as f32 * 200.0, 0.0)).unwrap())
+            .collect();
+        let adder = editor.add_node("add", Vec2::new(0.0, 300.0)).unwrap();
+        for id in maths.iter().chain(std::iter::once(&adder)) {
+            editor.select_node(*id, true);
+        }
+
+        let saturating = MetaValue::String("saturating".to_string());
+        let recorded = editor.history.undo_len();
+        let mut affected = editor.set_property_on_selection("overflow", saturating.clone());
+        affected.sort();
+        let mut expected = maths.clone();
+        expected.sort();
+        assert_eq!(affected, expected);
+
+        for id in &maths {
+            assert_eq!(editor.nodes[id].properties.get("overflow"), Some(&saturating));
+        }
+        assert!(!editor.nodes[&adder].properties.contains_key("overflow"));
+        assert_eq!(editor.history.undo_len(), recorded + 1);
+
+        // Wrong type is rejected by every node's constraint
+        assert!(editor.set_property_on_selection("overflow", MetaValue::Scalar(1.0)).is_empty());
+
+        assert_eq!(editor.undo().unwrap().len(), 3);
+        assert!(maths.iter().all(|id| !editor.nodes[id].properties.contains_key("overflow")));
+    }
+
+    #[test]
+    fn test_delete_selected_connection_cleans_up_and_undoes() {
+        let (mut editor, first, second) = two_add_nodes();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        let uuid = editor.connections[0].uuid;
+        editor.set_connection_property(uuid, "style", MetaValue::String("dashed".to_string())).unwrap();
+
+        // Click on the curve midway between the two sockets
+        let start = editor.nodes[&first].socket_position(SocketKind::Output, "result").unwrap();
+        let end = editor.nodes[&second].socket_position(SocketKind::Input, "a").unwrap();
+        editor.handle_mouse_press((start + end) / 2.0, 0);
+        assert!(editor.selected_connections.contains(&uuid));
+
+        assert_eq!(editor.delete_selected_connections(), 1);
+        assert!(editor.connections.is_empty());
+        assert!(editor.nodes[&first].outputs["result"].connections.is_empty());
+        assert!(editor.nodes[&second].inputs["a"].connected_output.is_none());
+        assert!(editor.connection_property(uuid, "style").is_none());
+
+        editor.undo().unwrap();
+        assert_eq!(editor.connection_by_id(uuid).map(|conn| conn.id.to_node), Some(second));
+        assert_eq!(editor.nodes[&first].outputs["result"].connections.len(), 1);
+        assert!(editor.connection_property(uuid, "style").is_some());
+
+        // Clicking empty space clears the connection selection
+        editor.select_connection(uuid, false);
+        editor.handle_mouse_press(Vec2::new(150.0, 500.0), 0);
+        assert!(editor.selected_connections.is_empty());
+    }
+
+    #[test]
+    fn test_clicking_near_curve_deletes_only_that_connection() {
+        let (mut editor, first, second) = two_add_nodes();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        let uuid = editor.connections[0].uuid;
+
+        let start = editor.nodes[&first].socket_position(SocketKind::Output, "result").unwrap();
+        let end = editor.nodes[&second].socket_position(SocketKind::Input, "a").unwrap();
+        let midpoint = (start + end) / 2.0;
+        assert_eq!(editor.connection_at_position(midpoint + Vec2::new(0.0, 3.0), 6.0), Some(uuid));
+        assert!(editor.connection_at_position(midpoint + Vec2::new(0.0, 200.0), 6.0).is_none());
+
+        assert!(editor.delete_connection_at(editor.canvas_to_screen(midpoint + Vec2::new(0.0, 200.0))).is_none());
+        assert_eq!(editor.delete_connection_at(editor.canvas_to_screen(midpoint)), Some(uuid));
+        assert!(editor.connections.is_empty());
+        assert!(editor.nodes[&second].inputs["a"].connected_output.is_none());
+
+        editor.undo().unwrap();
+        assert!(editor.connection_by_id(uuid).is_some());
+    }
+
+    #[test]
+    fn test_redo_reapplies_undone_edits_until_a_new_edit() {
+        let (mut editor, first, second) = two_add_nodes();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        let uuid = editor.connections[0].uuid;
+
+        editor.select_node(first, false);
+        let recorded = editor.history.undo_len();
+        editor.set_property_on_selection("b", MetaValue::Scalar(4.0));
+        editor.select_connection(uuid, false);
+        editor.delete_selected_connections();
+
+        editor.undo().unwrap();
+        editor.undo().unwrap();
+        assert!(editor.nodes[&first].properties.get("b").is_none());
+        assert!(editor.connection_by_id(uuid).is_some());
+
+        assert_eq!(editor.redo().unwrap(), vec![first]);
+        assert_eq!(editor.nodes[&first].properties.get("b"), Some(&MetaValue::Scalar(4.0)));
+        editor.redo().unwrap();
+        assert!(editor.connections.is_empty());
+        assert_eq!(editor.history.undo_len(), recorded + 2);
+
+        // A fresh edit after undoing drops the redo stack
+        editor.undo().unwrap();
+        editor.set_property_on_selection("b", MetaValue::Scalar(5.0));
+        assert_eq!(editor.history.redo_len(), 0);
+        assert!(editor.redo().is_err());
+    }
+
+    #[test]
+    fn test_duplicating_an_empty_selection_keeps_redo() {
+        let (mut editor, first, _) = two_add_nodes();
+        editor.select_node(first, false);
+        editor.set_property_on_selection("b", MetaValue::Scalar(4.0));
+        editor.undo().unwrap();
+        let recorded = editor.history.undo_len();
+
+        editor.deselect_all();
+        assert!(editor.duplicate_selected_nodes().unwrap().is_empty());
+        assert_eq!(editor.history.undo_len(), recorded);
+        assert_eq!(editor.history.redo_len(), 1);
+    }
+
+    #[test]
+    fn test_undoing_remove_node_restores_connections_and_properties() {
+        let (mut editor, first, second) = two_add_nodes();
+        let third = editor.add_node("add", Vec2::new(600.0, 0.0)).unwrap();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        editor.connect_nodes(second, "result", third, "b").unwrap();
+        editor.update_node_property(second, "b", MetaValue::Scalar(7.0)).unwrap();
+        let uuids: Vec<Uuid> = editor.connections.iter().map(|conn| conn.uuid).collect();
+
+        editor.remove_node(second).unwrap();
+        assert!(editor.connections.is_empty());
+        assert!(editor.nodes[&first].outputs["result"].connections.is_empty());
+        assert!(editor.nodes[&third].inputs["b"].connected_output.is_none());
+
+        let touched = editor.undo().unwrap();
+        assert!(touched.contains(&second));
+        assert_eq!(editor.nodes[&second].properties.get("b"), Some(&MetaValue::Scalar(7.0)));
+        for uuid in &uuids {
+            assert!(editor.connection_by_id(*uuid).is_some());
+        }
+        assert_eq!(editor.nodes[&first].outputs["result"].connections.len(), 1);
+        assert!(editor.nodes[&third].inputs["b"].connected_output.is_some());
+
+        editor.redo().unwrap();
+        assert!(!editor.nodes.contains_key(&second));
+        assert!(editor.connections.is_empty());
+    }
+
+    #[test]
+    fn test_add_connect_and_move_undo_in_order() {
+        let (mut editor, first, second) = two_add_nodes();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        editor.select_node(first, false);
+        editor.snap_to_grid = false;
+        editor.move_selected_nodes(Vec2::new(25.0, 10.0));
+        assert_eq!(editor.nodes[&first].position, Vec2::new(25.0, 10.0));
+
+        editor.undo().unwrap();
+        assert_eq!(editor.nodes[&first].position, Vec2::ZERO);
+        editor.undo().unwrap();
+        assert!(editor.connections.is_empty());
+        assert!(editor.nodes[&first].outputs["result"].connections.is_empty());
+        editor.undo().unwrap();
+        assert!(!editor.nodes.contains_key(&second));
+        editor.undo().unwrap();
+        assert!(editor.nodes.is_empty());
+        assert!(editor.undo().is_err());
+
+        // Replaying brings back the same node under its id, then its link
+        editor.redo().unwrap();
+        editor.redo().unwrap();
+        editor.redo().unwrap();
+        assert_eq!(editor.nodes[&second].inputs["a"].connected_output.as_ref().map(|out| out.from_node), Some(first));
+        editor.redo().unwrap();
+        assert_eq!(editor.nodes[&first].position, Vec2::new(25.0, 10.0));
+    }
+
+    #[test]
+    fn test_replacing_a_connection_undoes_to_the_old_one() {
+        let (mut editor, first, second) = two_add_nodes();
+        let third = editor.add_node("add", Vec2::new(0.0, 300.0)).unwrap();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        let original = editor.connections[0].uuid;
+        editor.connect_nodes(third, "result", second, "a").unwrap();
+        assert_eq!(editor.connections.len(), 1);
+
+        editor.undo().unwrap();
+        assert_eq!(editor.connections.len(), 1);
+        assert_eq!(editor.connection_by_id(original).map(|conn| conn.id.from_node), Some(first));
+        assert!(editor.nodes[&third].outputs["result"].connections.is_empty());
+    }
+
+    #[test]
+    fn test_history_is_capped_and_new_edits_clear_redo() {
+        let mut editor = VisualNodeEditor::new();
+        assert_eq!(editor.history.max_depth(), DEFAULT_HISTORY_DEPTH);
+        let node = editor.add_node("add", Vec2::ZERO).unwrap();
+        for i in 0..150 {
+            editor.update_node_property(node, "a", MetaValue::Scalar(i as f64)).unwrap();
+        }
+        assert_eq!(editor.history.undo_len(), DEFAULT_HISTORY_DEPTH);
+
+        // The oldest edits were dropped, so undo stops at value 49
+        while editor.undo().is_ok() {}
+        assert_eq!(editor.nodes[&node].properties.get("a"), Some(&MetaValue::Scalar(49.0)));
+        assert_eq!(editor.history.redo_len(), DEFAULT_HISTORY_DEPTH);
+
+        editor.update_node_property(node, "b", MetaValue::Scalar(1.0)).unwrap();
+        assert_eq!(editor.history.redo_len(), 0);
+        assert!(editor.redo().is_err());
+
+        editor.history.set_max_depth(0);
+        assert_eq!(editor.history.max_depth(), 1);
+    }
+
+    #[test]
+    fn test_preview_node_evaluates_without_adding() {
+        let editor = VisualNodeEditor::new();
+        let inputs: InputMap = [
+            ("a".to_string(), MetaValue::Scalar(2.0)),
+            ("b".to_string(), MetaValue::Scalar(3.0)),
+        ].into_iter().collect();
+
+        let outputs = editor.preview_node("add", &inputs).unwrap();
+        assert_eq!(outputs.get("result"), Some(&MetaValue::Scalar(5.0)));
+        assert!(editor.nodes.is_empty());
+
+        assert!(editor.preview_node("no_such_node", &inputs).is_err());
+    }
+
+    #[test]
+    fn test_logic_json_omits_ui_state() {
+        let (mut editor, first, second) = two_add_nodes();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        editor.select_node(first, false);
+        editor.canvas_offset = Vec2::new(40.0, -20.0);
+
+        let logic = editor.to_logic_json().unwrap();
+        for ui_field in ["selected", "canvas_offset", "canvas_scale", "positions", "\"ui\""] {
+            assert!(!logic.contains(ui_field), "logic JSON contains {}", ui_field);
+        }
+
+        let workspace = editor.to_workspace_json().unwrap();
+        let restored = VisualNodeEditor::from_json(&workspace).unwrap();
+        assert_eq!(restored.selected_nodes, vec![first]);
+        assert_eq!(restored.canvas_offset, Vec2::new(40.0, -20.0));
+        assert_eq!(restored.nodes[&second].position, editor.nodes[&second].position);
+    }
+
+    #[test]
+    fn test_connection_style_overrides_round_trip() {
+        let (mut editor, first, second) = two_add_nodes();
+        let third = editor.add_node("add", Vec2::new(600.0, 0.0)).unwrap();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        editor.connect_nodes(second, "result", third, "a").unwrap();
+        let styled = editor.connections[0].uuid;
+        let plain = editor.connections[1].uuid;
+        let style = ConnectionStyle {
+            color: Some([1.0, 0.5, 0.0, 1.0]),
+            thickness: Some(5.0),
+            ..Default::default()
+        };
+        editor.set_connection_style(styled, style.clone()).unwrap();
+
+        let json = editor.to_logic_json().unwrap();
+        assert!(!json.contains("routing"), "unset overrides are not written");
+        let restored = VisualNodeEditor::from_json(&json).unwrap();
+        let connection = restored.connection_by_id(styled).unwrap();
+        assert_eq!(connection.style, style);
+        assert_eq!((connection.color, connection.thickness), ([1.0, 0.5, 0.0, 1.0], 5.0));
+
+        let connection = restored.connection_by_id(plain).unwrap();
+        assert!(connection.style.is_default());
+        assert_eq!(connection.color, restored.get_connection_color(&NodeDataType::Scalar));
+        assert_eq!(connection.thickness, DEFAULT_CONNECTION_THICKNESS);
+
+        // Overrides of a connection missing from the graph are dropped
+        let mut snapshot = editor.snapshot();
+        snapshot.connections.retain(|conn| conn.uuid != styled);
+        let restored = VisualNodeEditor::from_snapshot(&snapshot).unwrap();
+        assert!(restored.connection_by_id(styled).is_none());
+        assert!(restored.snapshot().connection_styles.is_empty());
+    }
+
+    #[test]
+    fn test_logic_only_import_defaults_ui_state() {
+        let (mut editor, first, second) = two_add_nodes();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        editor.select_node(second, false);
+        editor.canvas_scale = 2.5;
+
+        let restored = VisualNodeEditor::from_json(&editor.to_logic_json().unwrap()).unwrap();
+        assert_eq!(restored.snapshot(), editor.snapshot());
+        assert!(restored.selected_nodes.is_empty());
+        assert!(restored.nodes.values().all(|node| !node.selected));
+        assert_eq!(restored.canvas_scale, 1.0);
+        assert!(restored.nodes[&second].inputs["a"].connected_output.is_some());
+        // Downstream node is laid out to the right of its source
+        assert!(restored.nodes[&second].position.x > restored.nodes[&first].position.x);
+    }
+
+    #[test]
+    fn test_add_node_connected_wires_selected_output() {
+        let mut editor = VisualNodeEditor::new();
+        let source = editor.add_node("add", Vec2::ZERO).unwrap();
+        editor.select_node(source, false);
+
+        let multiply = editor.add_node_connected("multiply", Vec2::new(200.0, 0.0)).unwrap();
+        assert_eq!(editor.connections.len(), 1);
+        let id = &editor.connections[0].id;
+        assert_eq!((id.from_node, id.from_output.as_str()), (source, "result"));
+        assert_eq!((id.to_node, id.to_input.as_str()), (multiply, "a"));
+        assert_eq!(editor.selected_nodes, vec![multiply]);
+
+        // A bool output has no compatible input on an add node
+        let compare = editor.add_node("compare", Vec2::new(0.0, 200.0)).unwrap();
+        editor.select_node(compare, false);
+        editor.add_node_connected("add", Vec2::new(200.0, 200.0)).unwrap();
+        assert_eq!(editor.connections.len(), 1);
+    }
+
+    #[test]
+    fn test_cancel_connection_clears_drag_without_connecting() {
+        let (mut editor, first, _second) = two_add_nodes();
+        editor.begin_connection((first, "result".to_string(), SocketKind::Output));
+        assert!(editor.connecting_from.is_some());
+
+        editor.handle_escape();
+        assert!(editor.connecting_from.is_none());
+        assert!(editor.connections.is_empty());
+        assert!(editor.complete_connection((first, "a".to_string(), SocketKind::Input)).is_err());
+    }
+
+    #[test]
+    fn test_complete_connection_onto_valid_and_invalid_targets() {
+        let (mut editor, first, second) = two_add_nodes();
+        editor.begin_connection((first, "result".to_string(), SocketKind::Output));
+        editor.complete_connection((second, "a".to_string(), SocketKind::Input)).unwrap();
+        assert!(editor.connecting_from.is_none());
+        assert_eq!(editor.connections.len(), 1);
+
+        // Output onto output fails but still ends the drag
+        editor.begin_connection((first, "result".to_string(), SocketKind::Output));
+        assert!(editor.complete_connection((second, "result".to_string(), SocketKind::Output)).is_err());
+        assert!(editor.connecting_from.is_none());
+
+        // Dropping on empty canvas cancels
+        editor.begin_connection((second, "result".to_string(), SocketKind::Output));
+        editor.handle_mouse_release(Vec2::new(5000.0, 5000.0), 0);
+        assert!(editor.connecting_from.is_none());
+        assert_eq!(editor.connections.len(), 1);
+
+        // Dropping on a socket completes
+        let third = editor.add_node("add", Vec2::new(600.0, 0.0)).unwrap();
+        let target = editor.nodes[&third].socket_position(SocketKind::Input, "b").unwrap();
+        editor.begin_connection((second, "result".to_string(), SocketKind::Output));
+        editor.handle_mouse_release(target + Vec2::new(2.0, 0.0), 0);
+        assert_eq!(editor.connections.len(), 2);
+        assert!(editor.nodes[&third].inputs["b"].connected_output.is_some());
+    }
+
+    #[test]
+    fn test_mouse_press_hit_tests_in_canvas_space() {
+        let (mut editor, first, second) = two_add_nodes();
+        editor.canvas_offset = Vec2::new(100.0, 50.0);
+        editor.canvas_scale = 2.0;
+        let screen = |editor: &VisualNodeEditor, canvas: Vec2| editor.canvas_to_screen(canvas);
+
+        // Clicking a node selects it and starts a drag that moves it
+        let start = screen(&editor, editor.nodes[&first].position);
+        editor.handle_mouse_press(start, 0);
+        assert_eq!(editor.selected_nodes, vec![first]);
+        assert_eq!(editor.dragging_node, Some(first));
+        let before = editor.nodes[&first].position;
+        editor.handle_mouse_move(start + Vec2::new(80.0, 0.0));
+        assert_eq!(editor.nodes[&first].position, before + Vec2::new(40.0, 0.0));
+        editor.handle_mouse_release(start + Vec2::new(80.0, 0.0), 0);
+        assert!(editor.dragging_node.is_none());
+
+        // The modifier adds to the selection; empty canvas without it clears
+        editor.multi_select_held = true;
+        editor.handle_mouse_press(screen(&editor, editor.nodes[&second].position), 0);
+        editor.handle_mouse_release(Vec2::ZERO, 0);
+        assert_eq!(editor.selected_nodes, vec![first, second]);
+        editor.multi_select_held = false;
+        editor.handle_mouse_press(screen(&editor, Vec2::new(5000.0, 5000.0)), 0);
+        assert!(editor.selected_nodes.is_empty());
+
+        // Clicking an output socket starts a connection drag
+        let socket = editor.nodes[&second].socket_position(SocketKind::Output, "result").unwrap();
+        editor.handle_mouse_press(screen(&editor, socket), 0);
+        assert_eq!(editor.connecting_from, Some((second, "result".to_string(), SocketKind::Output)));
+        assert!(editor.dragging_node.is_none());
+    }
+
+    #[test]
+    fn test_middle_drag_pans_and_zoom_keeps_cursor_anchor() {
+        let (mut editor, first, _) = two_add_nodes();
+        let position = editor.nodes[&first].position;
+
+        // Middle drag moves the view, not the node under the cursor
+        let start = editor.canvas_to_screen(position);
+        editor.handle_mouse_press(start, PAN_MOUSE_BUTTON);
+        editor.handle_mouse_move(start + Vec2::new(30.0, -10.0));
+        editor.handle_mouse_release(start + Vec2::new(30.0, -10.0), PAN_MOUSE_BUTTON);
+        assert!(!editor.panning && editor.selected_nodes.is_empty());
+        assert_eq!(editor.nodes[&first].position, position);
+        assert_eq!(editor.canvas_offset, Vec2::new(30.0, -10.0));
+
+        let cursor = Vec2::new(120.0, 80.0);
+        let anchor = editor.screen_to_canvas(cursor);
+        editor.zoom_canvas_at(cursor, 2.0);
+        assert_eq!(editor.canvas_scale, 2.0);
+        assert!(editor.canvas_to_screen(anchor).distance(cursor) < 1e-3);
+
+        editor.zoom_canvas_at(cursor, 100.0);
+        assert_eq!(editor.canvas_scale, MAX_CANVAS_SCALE);
+        assert!(editor.canvas_to_screen(anchor).distance(cursor) < 1e-3);
+    }
+
+    #[test]
+    fn test_marquee_selects_touched_nodes_and_shift_adds() {
+        let (mut editor, first, second) = two_add_nodes();
+        let third = editor.add_node("add", Vec2::new(0.0, 400.0)).unwrap();
+
+        // Drag from empty canvas across the first node's right edge
+        editor.handle_mouse_press(Vec2::new(-200.0, -200.0), 0);
+        editor.handle_mouse_move(Vec2::new(50.0, 50.0));
+        assert_eq!(editor.get_selection_rect(), Some((Vec2::new(-200.0, -200.0), Vec2::new(50.0, 50.0))));
+        editor.handle_mouse_release(Vec2::new(50.0, 50.0), 0);
+        assert!(editor.get_selection_rect().is_none());
+        assert_eq!(editor.selected_nodes, vec![first]);
+
+        // Shift-drag adds, dragging up-left from the anchor works too
+        editor.multi_select_held = true;
+        editor.handle_mouse_press(Vec2::new(100.0, 500.0), 0);
+        editor.handle_mouse_move(Vec2::new(-10.0, 390.0));
+        editor.handle_mouse_release(Vec2::new(-10.0, 390.0), 0);
+        assert_eq!(editor.selected_nodes, vec![first, third]);
+
+        // A plain drag replaces the selection
+        editor.multi_select_held = false;
+        editor.handle_mouse_press(Vec2::new(250.0, -200.0), 0);
+        editor.handle_mouse_move(Vec2::new(400.0, 200.0));
+        editor.handle_mouse_release(Vec2::new(400.0, 200.0), 0);
+        assert_eq!(editor.selected_nodes, vec![second]);
+        assert!(!editor.nodes[&first].selected);
+    }
+
+    #[test]
+    fn test_create_group_and_ungroup() {
+        let (mut editor, first, second) = two_add_nodes();
+        let third = editor.add_node("add", Vec2::new(0.0, 300.0)).unwrap();
+        editor.snap_to_grid = false;
+
+        let group = editor.create_group(&[first, second]).unwrap();
+        assert_eq!(editor.groups[&group].member_ids, vec![first, second]);
+        assert_eq!(editor.group_of(second).map(|g| g.id), Some(group));
+        assert!(editor.create_group(&[second, third]).is_err());
+        assert!(editor.create_group(&[]).is_err());
+
+        // Moving one member moves the whole group
+        editor.select_node(first, false);
+        editor.move_selected_nodes(Vec2::new(10.0, 5.0));
+        assert_eq!(editor.nodes[&second].position, Vec2::new(310.0, 5.0));
+        assert_eq!(editor.nodes[&third].position, Vec2::new(0.0, 300.0));
+
+        let removed = editor.ungroup(group).unwrap();
+        assert_eq!(removed.member_ids.len(), 2);
+        assert!(editor.group_of(first).is_none());
+        assert!(editor.ungroup(group).is_none());
+        editor.move_selected_nodes(Vec2::new(10.0, 0.0));
+        assert_eq!(editor.nodes[&second].position, Vec2::new(310.0, 5.0));
+    }
+
+    #[test]
+    fn test_collapsed_group_hides_members_and_proxies_connections() {
+        let (mut editor, first, second) = two_add_nodes();
+        let third = editor.add_node("add", Vec2::new(600.0, 0.0)).unwrap();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        editor.connect_nodes(second, "result", third, "a").unwrap();
+        let group = editor.create_group(&[first, second]).unwrap();
+
+        assert_eq!(editor.toggle_group_collapsed(group), Some(true));
+        let nodes = editor.rendered_nodes();
+        assert!(!nodes.contains_key(&first) && !nodes.contains_key(&second));
+        let proxy_port = VisualNodeEditor::group_proxy_port(second, "result");
+        assert!(nodes[&group].outputs.contains_key(&proxy_port));
+        assert_eq!(nodes[&group].node_type, GROUP_NODE_TYPE);
+
+        let routed = editor.rendered_connections();
+        assert_eq!(routed.len(), 1);
+        assert_eq!((routed[0].id.from_node, &routed[0].id.from_output), (group, &proxy_port));
+        assert_eq!(routed[0].id.to_node, third);
+
+        // Clicking the collapsed box selects the members
+        let centre = nodes[&group].position;
+        editor.handle_mouse_press(centre, 0);
+        assert_eq!(editor.selected_nodes, vec![first, second]);
+
+        // Group state survives a session round trip
+        let restored = VisualNodeEditor::from_session(&editor.session()).unwrap();
+        assert_eq!(restored.groups[&group], editor.groups[&group]);
+
+        assert_eq!(editor.toggle_group_collapsed(group), Some(false));
+        assert_eq!(editor.rendered_nodes().len(), 3);
+        assert_eq!(editor.rendered_connections().len(), 2);
+    }
+
+    #[test]
+    fn test_socket_positions_sit_on_node_edges() {
+        let (editor, first, _second) = two_add_nodes();
+        let node = &editor.nodes[&first];
+        let left = node.position.x - node.size.x / 2.0;
+        let top = node.position.y - node.size.y / 2.0;
+
+        // Inputs "a" and "b" split the left edge into thirds
+        assert_eq!(node.input_socket_position("a"), Some(Vec2::new(left, top + node.size.y / 3.0)));
+        assert_eq!(node.input_socket_position("b"), Some(Vec2::new(left, top + node.size.y * 2.0 / 3.0)));
+        assert_eq!(node.output_socket_position("result"), Some(Vec2::new(left + node.size.x, node.position.y)));
+        assert!(node.output_socket_position("a").is_none());
+    }
+
+    #[test]
+    fn test_evaluate_dirty_recomputes_only_downstream_of_change() {
+        const CHAIN: usize = 20;
+        let mut editor = VisualNodeEditor::new();
+        let mut chains = Vec::new();
+        for row in 0..2 {
+            let mut chain: Vec<Uuid> = Vec::new();
+            for i in 0..CHAIN {
+                let node = editor.add_node("add", Vec2::new(i as f32 * 200.0, row as f32 * 200.0)).unwrap();
+                editor.update_node_property(node, "b", MetaValue::Scalar(1.0)).unwrap();
+                if let Some(&previous) = chain.last() {
+                    editor.connect_nodes(previous, "result", node, "a").unwrap();
+                }
+                chain.push(node);
+            }
+            chains.push(chain);
+        }
+
+        editor.evaluate_graph().unwrap();
+        assert!(editor.evaluate_dirty().unwrap().is_empty());
+        let last = chains[0][CHAIN - 1];
+        assert_eq!(editor.last_outputs[&last]["result"], MetaValue::Scalar(CHAIN as f64));
+
+        // Only the changed node and the nodes after it in its chain re-run
+        editor.update_node_property(chains[0][10], "b", MetaValue::Scalar(5.0)).unwrap();
+        let evaluated = editor.evaluate_dirty().unwrap();
+        assert_eq!(evaluated.len(), CHAIN - 10);
+        assert!(evaluated.iter().all(|id| chains[0][10..].contains(id)));
+        assert_eq!(editor.last_outputs[&last]["result"], MetaValue::Scalar(CHAIN as f64 + 4.0));
+        assert_eq!(editor.last_outputs[&chains[1][CHAIN - 1]]["result"], MetaValue::Scalar(CHAIN as f64));
+        assert!(editor.evaluate_dirty().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_registry_rolls_back_and_marks_dirty() {
+        let (mut editor, first, second) = two_add_nodes();
+        let path = DotPath::from("experiment.gain");
+        editor.registry.set_scoped(&editor.scope_id, &path, MetaValue::Scalar(1.0)).unwrap();
+        let checkpoint = editor.snapshot_registry();
+
+        editor.registry.set_scoped(&editor.scope_id, &path, MetaValue::Scalar(9.0)).unwrap();
+        editor.evaluate_graph().unwrap();
+        assert!(!editor.nodes[&first].dirty);
+
+        editor.restore_registry(checkpoint).unwrap();
+        assert_eq!(editor.registry.get_scoped(&editor.scope_id, &path).unwrap(), MetaValue::Scalar(1.0));
+        assert!(editor.nodes[&first].dirty && editor.nodes[&second].dirty);
+    }
+
+    #[test]
+    fn test_lazy_branch_evaluates_only_taken_arm() {
+        let mut editor = VisualNodeEditor::new();
+        editor.lazy_branches = true;
+        let taken = editor.add_node("add", Vec2::new(0.0, 200.0)).unwrap();
+        let skipped = editor.add_node("multiply", Vec2::new(0.0, 400.0)).unwrap();
+        let branch = editor.add_node("branch", Vec2::new(300.0, 200.0)).unwrap();
+        editor.update_node_property(branch, "condition", MetaValue::Bool(true)).unwrap();
+        editor.update_node_property(taken, "a", MetaValue::Scalar(3.0)).unwrap();
+        editor.connect_nodes(taken, "result", branch, "true_value").unwrap();
+        editor.connect_nodes(skipped, "result", branch, "false_value").unwrap();
+
+        let results = editor.evaluate_graph().unwrap();
+        assert_eq!(results[&branch]["result"], MetaValue::Scalar(3.0));
+        assert!(results.contains_key(&taken) && !results.contains_key(&skipped));
+        assert!(editor.nodes[&skipped].dirty && !editor.nodes[&taken].dirty);
+
+        editor.lazy_branches = false;
+        assert!(editor.evaluate_graph().unwrap().contains_key(&skipped));
+    }
+
+    #[test]
+    fn test_timer_progress_accumulates_across_updates() {
+        let mut editor = VisualNodeEditor::new();
+        let timer = editor.add_node("timer", Vec2::ZERO).unwrap();
+        editor.update_node_property(timer, "duration", MetaValue::Scalar(1.0)).unwrap();
+
+        let mut progress = Vec::new();
+        for _ in 0..8 {
+            editor.evaluate_graph().unwrap();
+            progress.push(editor.last_outputs[&timer]["progress"].as_scalar().unwrap());
+            editor.update(0.25).unwrap();
+        }
+
+        assert_eq!(progress[0], 0.0);
+        assert!(progress.windows(2).all(|w| w[1] >= w[0]));
+        assert!(progress[1] > progress[0]);
+        assert_eq!(progress[4], 1.0);
+        assert_eq!(progress[7], 1.0);
+        assert_eq!(editor.last_outputs[&timer]["finished"], MetaValue::Bool(true));
+    }
+
+    #[test]
+    fn test_throttle_suppresses_across_evaluation_passes() {
+        let mut editor = VisualNodeEditor::new();
+        let throttle = editor.add_node("throttle", Vec2::ZERO).unwrap();
+        editor.update_node_property(throttle, "interval", MetaValue::Scalar(1.0)).unwrap();
+
+        let mut passed = Vec::new();
+        for i in 0..6 {
+            editor.update_node_property(throttle, "value", MetaValue::Scalar(i as f64)).unwrap();
+            editor.evaluate_graph().unwrap();
+            passed.push(editor.last_outputs[&throttle]["passed"] == MetaValue::Bool(true));
+            editor.update(0.25).unwrap();
+        }
+
+        // Each pass builds a fresh node instance; the timing state survives
+        assert_eq!(passed, vec![true, false, false, false, true, false]);
+        assert_eq!(editor.last_outputs[&throttle]["value"], MetaValue::Scalar(4.0));
+    }
+
+    #[test]
+    fn test_auto_layout_places_sources_before_sinks() {
+        // Diamond: top -> (left, right) -> bottom, plus an unconnected node
+        let mut editor = VisualNodeEditor::new();
+        let top = editor.add_node("add", Vec2::ZERO).unwrap();
+        let left = editor.add_node("add", Vec2::ZERO).unwrap();
+        let right = editor.add_node("add", Vec2::ZERO).unwrap();
+        let bottom = editor.add_node("add", Vec2::ZERO).unwrap();
+        let lone = editor.add_node("add", Vec2::ZERO).unwrap();
+        editor.connect_nodes(top, "result", left, "a").unwrap();
+        editor.connect_nodes(top, "result", right, "a").unwrap();
+        editor.connect_nodes(left, "result", bottom, "a").unwrap();
+        editor.connect_nodes(right, "result", bottom, "b").unwrap();
+
+        for direction in [LayoutDirection::LeftToRight, LayoutDirection::TopToBottom] {
+            editor.layout_direction = direction;
+            editor.auto_layout();
+            let axis = |id: Uuid| match direction {
+                LayoutDirection::LeftToRight => editor.nodes[&id].position.x,
+                LayoutDirection::TopToBottom => editor.nodes[&id].position.y,
+            };
+            assert!(axis(top) < axis(left) && axis(left) == axis(right) && axis(right) < axis(bottom));
+            assert_ne!(editor.nodes[&left].position, editor.nodes[&right].position);
+
+            // Nodes sit on the grid and no two overlap
+            let positions: Vec<Vec2> = editor.nodes.values().map(|node| node.position).collect();
+            assert!(positions.iter().all(|p| *p == editor.snap_position_to_grid(*p)));
+            let distinct: HashSet<(i32, i32)> = positions.iter().map(|p| (p.x as i32, p.y as i32)).collect();
+            assert_eq!(distinct.len(), positions.len());
+            assert_eq!(axis(lone), axis(top));
+        }
+    }
+
+    #[test]
+    fn test_clipboard_paste_keeps_internal_connections() {
+        let (mut editor, first, second) = two_add_nodes();
+        let outside = editor.add_node("add", Vec2::new(0.0, 400.0)).unwrap();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        editor.connect_nodes(outside, "result", second, "b").unwrap();
+        editor.select_node(first, false);
+        editor.select_node(second, true);
+
+        let clipboard = editor.copy_selection();
+        assert_eq!((clipboard.len(), clipboard.connections.len()), (2, 1));
+        let centroid = clipboard.centroid().unwrap();
+
+        // Paste into a different editor, after the originals are gone
+        editor.remove_node(first).unwrap();
+        let mut target = VisualNodeEditor::new();
+        let pasted = target.paste(&clipboard, Vec2::new(500.0, 500.0)).unwrap();
+        assert_eq!(pasted.len(), 2);
+        assert!(pasted.iter().all(|id| ![first, second].contains(id)));
+        assert_eq!(target.selected_nodes, pasted);
+        assert_eq!(target.connections.len(), 1);
+        let conn = &target.connections[0].id;
+        assert_eq!((conn.from_node, conn.to_node), (pasted[0], pasted[1]));
+        assert!(target.nodes[&pasted[1]].inputs["b"].connected_output.is_none());
+
+        let shift = Vec2::new(500.0, 500.0) - centroid;
+        assert_eq!(target.nodes[&pasted[0]].position, clipboard.nodes[0].position + shift);
+
+        // The whole paste is one undo step
+        target.undo().unwrap();
+        assert!(target.nodes.is_empty() && target.connections.is_empty());
+    }
+
+    #[test]
+    fn test_paste_places_group_top_left_at_cursor() {
+        let (mut editor, first, second) = two_add_nodes();
+        editor.nodes.get_mut(&second).unwrap().position = Vec2::new(300.0, 100.0);
+        let size = editor.nodes[&first].size;
+
+        let pasted = editor.paste_nodes_at(&[first, second], Vec2::new(1000.0, 500.0)).unwrap();
+        assert_eq!(pasted.len(), 2);
+        assert_eq!(editor.selected_nodes, pasted);
+        let top_left = pasted.iter()
+            .map(|id| editor.nodes[id].position - editor.nodes[id].size / 2.0)
+            .reduce(Vec2::min)
+            .unwrap();
+        assert_eq!(top_left, Vec2::new(1000.0, 500.0));
+        // Relative layout is preserved
+        assert_eq!(editor.nodes[&pasted[1]].position - editor.nodes[&pasted[0]].position, Vec2::new(300.0, 100.0));
+        assert_eq!(editor.nodes[&pasted[0]].position, Vec2::new(1000.0, 500.0) + size / 2.0);
+
+        // A second paste at the same point cascades
+        let again = editor.paste_nodes_at(&[first, second], Vec2::new(1000.0, 500.0)).unwrap();
+        assert_eq!(
+            editor.nodes[&again[0]].position - editor.nodes[&pasted[0]].position,
+            editor.paste_cascade_offset
+        );
+    }
+
+    #[test]
+    fn test_paste_nodes_at_matches_clipboard_paste() {
+        let (mut editor, first, second) = two_add_nodes();
+        editor.connect_nodes(first, "result", second, "a").unwrap();
+        let recorded = editor.history.undo_len();
+
+        let pasted = editor.paste_nodes_at(&[first, second], Vec2::new(1000.0, 500.0)).unwrap();
+        assert_eq!(editor.connections.len(), 2);
+        assert!(editor.nodes[&pasted[1]].inputs["a"].connected_output.is_some());
+        assert_eq!(editor.history.undo_len(), recorded + 1);
+
+        // Repeated clipboard pastes at one point cascade too
+        editor.select_node(first, false);
+        let clipboard = editor.copy_selection();
+        let once = editor.paste(&clipboard, Vec2::ZERO).unwrap();
+        let twice = editor.paste(&clipboard, Vec2::ZERO).unwrap();
+        assert_eq!(
+            editor.nodes[&twice[0]].position - editor.nodes[&once[0]].position,
+            editor.paste_cascade_offset
+        );
+    }
+
+    #[test]
+    fn test_unreachable_sinks_reports_sink_without_feed() {
+        let (mut editor, source, fed) = two_add_nodes();
+        editor.connect_nodes(source, "result", fed, "a").unwrap();
+        let middle = editor.add_node("add", Vec2::new(0.0, 200.0)).unwrap();
+        let orphan = editor.add_node("add", Vec2::new(300.0, 200.0)).unwrap();
+        editor.connect_nodes(middle, "result", orphan, "a").unwrap();
+        editor.set_sink(fed, true);
+        editor.set_sink(orphan, true);
+        assert!(editor.unreachable_sinks().is_empty());
+
+        // Deleting the orphan's only dependency leaves it unreachable
+        editor.remove_node(middle).unwrap();
+        assert_eq!(editor.unreachable_sinks(), vec![orphan]);
+
+        let restored = VisualNodeEditor::from_json(&editor.to_logic_json().unwrap()).unwrap();
+        assert_eq!(restored.unreachable_sinks(), vec![orphan]);
+    }
+
+    #[test]
+    fn test_evaluate_graph_carries_vectors() {
+        let mut editor = VisualNodeEditor::new();
+        let math = editor.add_node("math", Vec2::ZERO).unwrap();
+        let node = editor.nodes.get_mut(&math).unwrap();
+        node.properties.insert("a".to_string(), MetaValue::Vec2(Vec2::new(1.0, 2.0)));
+        node.properties.insert("b".to_string(), MetaValue::Vec2(Vec2::new(3.0, 4.0)));
+        let results = editor.evaluate_graph().unwrap();
+        assert_eq!(results[&math]["add"], MetaValue::Vec2(Vec2::new(4.0, 6.0)));
+        assert_eq!(editor.connection_color_for_value(&results[&math]["add"]), editor.get_connection_color(&NodeDataType::Vector2));
+
+        // Unconnected vector inputs without a default evaluate to zero vectors
+        let zero = editor.add_node("math", Vec2::ZERO).unwrap();
+        let node = editor.nodes.get_mut(&zero).unwrap();
+        node.inputs.insert("a".to_string(), NodeInput {
+            name: "A".to_string(),
+            data_type: NodeDataType::Vector2,
+            connected_output: None,
+            default_value: None,
+        });
+        let results = editor.evaluate_graph().unwrap();
+        assert_eq!(results[&zero]["add"], MetaValue::Vec2(Vec2::ZERO));
+    }
+}
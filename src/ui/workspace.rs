@@ -0,0 +1,287 @@
+//! Tabbed workspace of node editors
+//!
+//! Holds one `VisualNodeEditor` per open scope, mirroring the runtime's
+//! per-scope graphs. Each tab keeps its own graph, selection and undo
+//! history; only the active tab receives edits routed through the workspace.
+use crate::core::types::error::MarcoError;
+use crate::core::types::ScopeId;
+use crate::ui::visual_node_editor::{EditorSession, VisualNodeEditor};
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+/// An open editor tab
+#[derive(Debug)]
+pub struct WorkspaceTab {
+    pub scope: ScopeId,
+    pub name: String,
+    pub editor: VisualNodeEditor,
+}
+
+/// Serializable form of a tab in a [`WorkspaceSnapshot`]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TabSnapshot {
+    pub scope: ScopeId,
+    pub name: String,
+    pub editor: EditorSession,
+}
+
+/// Persistent state of every open tab
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WorkspaceSnapshot {
+    pub tabs: Vec<TabSnapshot>,
+    pub active: Option<ScopeId>,
+}
+
+/// Open editor tabs in display order, with one active tab
+#[derive(Debug, Default)]
+pub struct Workspace {
+    tabs: Vec<WorkspaceTab>,
+    active: Option<ScopeId>,
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a tab for `scope` and make it active
+    ///
+    /// If the scope is already open its existing editor is activated and
+    /// `name` is ignored.
+    pub fn open_tab(&mut self, scope: ScopeId, name: &str) -> &mut VisualNodeEditor {
+        let index = match self.index_of(&scope) {
+            Some(index) => index,
+            None => {
+                info!("Opened workspace tab '{}' for scope {}", name, scope);
+                self.tabs.push(WorkspaceTab {
+                    scope: scope.clone(),
+                    name: name.to_string(),
+                    editor: VisualNodeEditor::new(),
+                });
+                self.tabs.len() - 1
+            }
+        };
+        self.active = Some(scope);
+        &mut self.tabs[index].editor
+    }
+
+    /// Close the tab for `scope`, returning its editor
+    ///
+    /// Closing the active tab activates its right neighbour, or the left one
+    /// when it was the last tab.
+    pub fn close_tab(&mut self, scope: &ScopeId) -> Option<VisualNodeEditor> {
+        let index = self.index_of(scope)?;
+        let tab = self.tabs.remove(index);
+
+        if self.active.as_ref() == Some(scope) {
+            self.active = self.tabs.get(index)
+                .or_else(|| self.tabs.last())
+                .map(|tab| tab.scope.clone());
+        }
+        info!("Closed workspace tab '{}'", tab.name);
+        Some(tab.editor)
+    }
+
+    /// Make an open tab active
+    pub fn switch_to(&mut self, scope: &ScopeId) -> Result<(), MarcoError> {
+        if self.index_of(scope).is_none() {
+            return Err(MarcoError::InvalidOperation(format!("No open tab for scope {}", scope)));
+        }
+        self.active = Some(scope.clone());
+        Ok(())
+    }
+
+    pub fn active_scope(&self) -> Option<&ScopeId> {
+        self.active.as_ref()
+    }
+
+    pub fn active_editor(&self) -> Option<&VisualNodeEditor> {
+        self.active.as_ref().and_then(|scope| self.editor(scope))
+    }
+
+    pub fn active_editor_mut(&mut self) -> Option<&mut VisualNodeEditor> {
+        let scope = self.active.clone()?;
+        self.editor_mut(&scope)
+    }
+
+    pub fn editor(&self, scope: &ScopeId) -> Option<&VisualNodeEditor> {
+        self.tabs.iter().find(|tab| &tab.scope == scope).map(|tab| &tab.editor)
+    }
+
+    pub fn editor_mut(&mut self, scope: &ScopeId) -> Option<&mut VisualNodeEditor> {
+        self.tabs.iter_mut().find(|tab| &tab.scope == scope).map(|tab| &mut tab.editor)
+    }
+
+    /// Undo the last edit in the active tab only
+    pub fn undo(&mut self) -> Vec<uuid::Uuid> {
+        self.active_editor_mut()
+            .and_then(|editor| editor.undo().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn rename_tab(&mut self, scope: &ScopeId, name: &str) -> bool {
+        match self.tabs.iter_mut().find(|tab| &tab.scope == scope) {
+            Some(tab) => {
+                tab.name = name.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn tabs(&self) -> &[WorkspaceTab] {
+        &self.tabs
+    }
+
+    pub fn tab_count(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Capture every tab for saving
+    pub fn snapshot(&self) -> WorkspaceSnapshot {
+        WorkspaceSnapshot {
+            tabs: self.tabs.iter()
+                .map(|tab| TabSnapshot {
+                    scope: tab.scope.clone(),
+                    name: tab.name.clone(),
+                    editor: tab.editor.session(),
+                })
+                .collect(),
+            active: self.active.clone(),
+        }
+    }
+
+    /// Rebuild all tabs from a snapshot
+    pub fn from_snapshot(snapshot: &WorkspaceSnapshot) -> Result<Self, MarcoError> {
+        let tabs = snapshot.tabs.iter()
+            .map(|tab| Ok(WorkspaceTab {
+                scope: tab.scope.clone(),
+                name: tab.name.clone(),
+                editor: VisualNodeEditor::from_session(&tab.editor)?,
+            }))
+            .collect::<Result<Vec<_>, MarcoError>>()?;
+
+        let active = snapshot.active.clone()
+            .filter(|scope| tabs.iter().any(|tab| &tab.scope == scope))
+            .or_else(|| tabs.first().map(|tab| tab.scope.clone()));
+
+        Ok(Self { tabs, active })
+    }
+
+    /// Write all tabs to a project file
+    pub fn save_to_path(&self, file_path: &Path) -> Result<(), MarcoError> {
+        let project_data = serde_json::json!({
+            "version": "2.0",
+            "created": chrono::Utc::now(),
+            "workspace": self.snapshot(),
+        });
+
+        let contents = serde_json::to_string_pretty(&project_data)
+            .map_err(|e| MarcoError::Persistence(format!("Failed to serialize workspace: {}", e)))?;
+
+        fs::write(file_path, contents)
+            .map_err(|e| MarcoError::Persistence(format!("Failed to write project file: {}", e)))?;
+
+        info!("Saved {} workspace tabs to {:?}", self.tabs.len(), file_path);
+        Ok(())
+    }
+
+    /// Load all tabs from a project file written by [`Workspace::save_to_path`]
+    pub fn load_from_path(file_path: &Path) -> Result<Self, MarcoError> {
+        let contents = fs::read_to_string(file_path)
+            .map_err(|e| MarcoError::Persistence(format!("Failed to read project file: {}", e)))?;
+
+        let mut project_data: serde_json::Value = serde_json::from_str(&contents)
+            .map_err(|e| MarcoError::Persistence(format!("Failed to parse project file: {}", e)))?;
+
+        let snapshot: WorkspaceSnapshot = match project_data.get_mut("workspace") {
+            Some(workspace) => serde_json::from_value(workspace.take())
+                .map_err(|e| MarcoError::Persistence(format!("Invalid workspace data: {}", e)))?,
+            None => WorkspaceSnapshot::default(),
+        };
+
+        Self::from_snapshot(&snapshot)
+    }
+
+    fn index_of(&self, scope: &ScopeId) -> Option<usize> {
+        self.tabs.iter().position(|tab| &tab.scope == scope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::MetaValue;
+    use glam::Vec2;
+
+    #[test]
+    fn test_tabs_keep_independent_nodes_and_undo() {
+        let mut workspace = Workspace::new();
+        let first = ScopeId::new();
+        let second = ScopeId::new();
+
+        let a = workspace.open_tab(first.clone(), "First").add_node("add", Vec2::ZERO).unwrap();
+        let editor = workspace.active_editor_mut().unwrap();
+        editor.select_node(a, false);
+        editor.set_property_on_selection("a", MetaValue::Scalar(1.0));
+
+        let b = workspace.open_tab(second.clone(), "Second").add_node("add", Vec2::ZERO).unwrap();
+        let editor = workspace.active_editor_mut().unwrap();
+        editor.select_node(b, false);
+        editor.set_property_on_selection("a", MetaValue::Scalar(2.0));
+        editor.set_property_on_selection("b", MetaValue::Scalar(3.0));
+
+        assert_eq!(workspace.active_scope(), Some(&second));
+        // Adding the node is an edit too
+        assert_eq!(workspace.active_editor().unwrap().history.undo_len(), 3);
+
+        workspace.switch_to(&first).unwrap();
+        let editor = workspace.active_editor().unwrap();
+        assert!(editor.nodes.contains_key(&a) && !editor.nodes.contains_key(&b));
+        assert_eq!(editor.history.undo_len(), 2);
+
+        // Undo only touches the active tab
+        assert_eq!(workspace.undo(), vec![a]);
+        assert_eq!(workspace.editor(&first).unwrap().history.undo_len(), 1);
+        assert_eq!(workspace.editor(&second).unwrap().history.undo_len(), 3);
+        assert_eq!(workspace.editor(&second).unwrap().nodes[&b].properties.get("b"), Some(&MetaValue::Scalar(3.0)));
+    }
+
+    #[test]
+    fn test_close_tab_activates_neighbour() {
+        let mut workspace = Workspace::new();
+        let scopes: Vec<ScopeId> = (0..3).map(|_| ScopeId::new()).collect();
+        for (i, scope) in scopes.iter().enumerate() {
+            workspace.open_tab(scope.clone(), &format!("Tab {}", i));
+        }
+
+        workspace.switch_to(&scopes[1]).unwrap();
+        assert!(workspace.close_tab(&scopes[1]).is_some());
+        assert_eq!(workspace.active_scope(), Some(&scopes[2]));
+        assert!(workspace.switch_to(&scopes[1]).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_restores_all_tabs() {
+        let mut workspace = Workspace::new();
+        let first = ScopeId::new();
+        let second = ScopeId::new();
+
+        let editor = workspace.open_tab(first.clone(), "First");
+        let x = editor.add_node("add", Vec2::new(0.0, 0.0)).unwrap();
+        let y = editor.add_node("add", Vec2::new(200.0, 0.0)).unwrap();
+        editor.connect_nodes(x, "result", y, "a").unwrap();
+        workspace.open_tab(second.clone(), "Second").add_node("math", Vec2::ZERO).unwrap();
+        workspace.switch_to(&first).unwrap();
+
+        let json = serde_json::to_string(&workspace.snapshot()).unwrap();
+        let restored = Workspace::from_snapshot(&serde_json::from_str(&json).unwrap()).unwrap();
+
+        assert_eq!(restored.snapshot(), workspace.snapshot());
+        assert_eq!(restored.active_scope(), Some(&first));
+        let editor = restored.editor(&first).unwrap();
+        assert_eq!(editor.connections.len(), 1);
+        assert!(editor.nodes[&y].inputs["a"].connected_output.is_some());
+    }
+}
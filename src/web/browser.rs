@@ -0,0 +1,604 @@
+//! Browser bindings: the `Marco2Web` application and its DOM event wiring
+
+use wasm_bindgen::prelude::*;
+use web_sys::{console, window, HtmlCanvasElement, CanvasRenderingContext2d, Event, MouseEvent, TouchEvent, WheelEvent};
+use serde::{Serialize, Deserialize};
+use super::config::WebConfig;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+// Import our cross-platform UI system
+use crate::ui::{CrossPlatformUI, TouchId};
+use crate::ui::cross_platform::MouseButton;
+use glam::Vec2;
+
+// Set up panic hook and allocator for web
+#[cfg(feature = "console_error_panic_hook")]
+pub use console_error_panic_hook::set_once as set_panic_hook;
+
+#[cfg(feature = "wee_alloc")]
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+/// Initialize logging for web
+pub fn init_logging() {
+    #[cfg(feature = "debug")]
+    {
+        tracing_wasm::set_as_global_default();
+        tracing::info!("Marco 2.0 WASM logging initialized");
+    }
+}
+
+/// localStorage key holding the saved `WebConfig`
+const CONFIG_STORAGE_KEY: &str = "marco2-config";
+
+/// Wheel delta in pixels treated as one line (one notch on most mice)
+const WHEEL_PIXELS_PER_LINE: f64 = 100.0;
+
+/// Main Marco 2.0 Web Application
+///
+/// The application state is shared with the browser callbacks that drive
+/// it, so it lives behind `Rc<RefCell<_>>`.
+#[wasm_bindgen]
+pub struct Marco2Web {
+    app: Rc<RefCell<WebApp>>,
+    /// Pending requestAnimationFrame callback; dropping it ends the loop
+    animation_frame: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>>,
+    animation_frame_id: Rc<Cell<Option<i32>>>,
+    /// Canvas event listeners by event type, kept so `stop` can remove them
+    listeners: Vec<(&'static str, Closure<dyn FnMut(Event)>)>,
+}
+
+/// State updated by the public API and by the animation loop
+struct WebApp {
+    cross_platform_ui: CrossPlatformUI,
+    canvas: HtmlCanvasElement,
+    context_2d: Option<CanvasRenderingContext2d>,
+    config: WebConfig,
+    is_running: bool,
+    last_frame_time: f64,
+    performance: web_sys::Performance,
+}
+
+#[wasm_bindgen]
+impl Marco2Web {
+    /// Create a new Marco 2.0 web application
+    #[wasm_bindgen(constructor)]
+    pub fn new(config_js: JsValue) -> Result<Marco2Web, JsValue> {
+        // Set up panic hook for better error reporting
+        #[cfg(feature = "console_error_panic_hook")]
+        set_panic_hook();
+        
+        // Initialize logging
+        init_logging();
+        
+        // Parse configuration from JavaScript, else use the saved one
+        let config: WebConfig = if config_js.is_undefined() {
+            load_stored_config().unwrap_or_default()
+        } else {
+            serde_wasm_bindgen::from_value(config_js)
+                .map_err(|e| JsValue::from_str(&format!("Config parse error: {}", e)))?
+        };
+        
+        // Get the document and canvas
+        let window = window().ok_or("No window object")?;
+        let document = window.document().ok_or("No document object")?;
+        let canvas = document
+            .get_element_by_id(&config.canvas_id)
+            .ok_or("Canvas element not found")?
+            .dyn_into::<HtmlCanvasElement>()
+            .map_err(|_| "Element is not a canvas")?;
+        
+        // Get 2D rendering context for UI overlay
+        let context_2d = canvas
+            .get_context("2d")
+            .map_err(|_| "Failed to get 2D context")?
+            .map(|ctx| ctx.dyn_into::<CanvasRenderingContext2d>().unwrap());
+        
+        // Detect screen size and touch capabilities
+        let screen_width = canvas.client_width() as f32;
+        let screen_height = canvas.client_height() as f32;
+        let screen_bounds = Vec2::new(screen_width, screen_height);
+        
+        // Detect touch support
+        let has_touch = window.navigator().max_touch_points() > 0;
+        
+        // Create cross-platform UI
+        let mut cross_platform_ui = CrossPlatformUI::new(screen_bounds, has_touch);
+        
+        // Apply mobile mode override if specified
+        if let Some(force_mobile) = config.mobile_mode {
+            let mut adaptation_config = crate::ui::cross_platform::UIAdaptationConfig::default();
+            adaptation_config.force_mobile_mode = force_mobile;
+            cross_platform_ui.set_adaptation_config(adaptation_config);
+        }
+        
+        // Get performance object for timing
+        let performance = window.performance().ok_or("No performance object")?;
+        
+        tracing::info!("Marco 2.0 Web initialized: {}x{}, touch: {}", 
+                      screen_width, screen_height, has_touch);
+        
+        Ok(Marco2Web {
+            app: Rc::new(RefCell::new(WebApp {
+                cross_platform_ui,
+                canvas,
+                context_2d,
+                config,
+                is_running: false,
+                last_frame_time: 0.0,
+                performance,
+            })),
+            animation_frame: Rc::new(RefCell::new(None)),
+            animation_frame_id: Rc::new(Cell::new(None)),
+            listeners: Vec::new(),
+        })
+    }
+    
+    /// Start the application
+    #[wasm_bindgen]
+    pub fn start(&mut self) -> Result<(), JsValue> {
+        if self.app.borrow().is_running {
+            return Ok(());
+        }
+        
+        self.app.borrow_mut().is_running = true;
+        self.setup_event_listeners()?;
+        self.start_animation_loop()?;
+        
+        tracing::info!("Marco 2.0 Web application started");
+        Ok(())
+    }
+    
+    /// Stop the application
+    #[wasm_bindgen]
+    pub fn stop(&mut self) {
+        self.app.borrow_mut().is_running = false;
+        self.stop_animation_loop();
+        self.remove_event_listeners();
+        tracing::info!("Marco 2.0 Web application stopped");
+    }
+    
+    /// Handle window resize
+    #[wasm_bindgen]
+    pub fn resize(&mut self, width: f32, height: f32) {
+        let screen_bounds = Vec2::new(width, height);
+        let mut app = self.app.borrow_mut();
+        
+        // Update canvas size
+        app.canvas.set_width(width as u32);
+        app.canvas.set_height(height as u32);
+        
+        // Update cross-platform UI
+        app.cross_platform_ui.update(0.0, screen_bounds);
+        
+        tracing::debug!("Canvas resized to {}x{}", width, height);
+    }
+    
+    /// Handle touch start event
+    #[wasm_bindgen]
+    pub fn handle_touch_start(&mut self, touch_data: JsValue) -> Result<bool, JsValue> {
+        let touches: Vec<TouchData> = serde_wasm_bindgen::from_value(touch_data)
+            .map_err(|e| JsValue::from_str(&format!("Touch data parse error: {}", e)))?;
+        
+        let mut handled = false;
+        for touch in touches {
+            let id = TouchId(touch.identifier as u64);
+            let position = Vec2::new(touch.client_x, touch.client_y);
+            let pressure = touch.force.unwrap_or(1.0);
+            
+            if self.app.borrow_mut().cross_platform_ui.handle_touch_down(id, position, pressure) {
+                handled = true;
+            }
+        }
+        
+        Ok(handled)
+    }
+    
+    /// Handle touch move event  
+    #[wasm_bindgen]
+    pub fn handle_touch_move(&mut self, touch_data: JsValue) -> Result<bool, JsValue> {
+        let touches: Vec<TouchData> = serde_wasm_bindgen::from_value(touch_data)?;
+        
+        let mut handled = false;
+        for touch in touches {
+            let id = TouchId(touch.identifier as u64);
+            let position = Vec2::new(touch.client_x, touch.client_y);
+            let pressure = touch.force.unwrap_or(1.0);
+            
+            if self.app.borrow_mut().cross_platform_ui.handle_touch_move(id, position, pressure) {
+                handled = true;
+            }
+        }
+        
+        Ok(handled)
+    }
+    
+    /// Handle touch end event
+    #[wasm_bindgen]
+    pub fn handle_touch_end(&mut self, touch_data: JsValue) -> Result<bool, JsValue> {
+        let touches: Vec<TouchData> = serde_wasm_bindgen::from_value(touch_data)?;
+        
+        let mut handled = false;
+        for touch in touches {
+            let id = TouchId(touch.identifier as u64);
+            
+            if self.app.borrow_mut().cross_platform_ui.handle_touch_up(id) {
+                handled = true;
+            }
+        }
+        
+        Ok(handled)
+    }
+    
+    /// Handle mouse events (for desktop browsers)
+    #[wasm_bindgen]
+    pub fn handle_mouse_down(&mut self, x: f32, y: f32, button: u32) -> bool {
+        let position = Vec2::new(x, y);
+        self.app.borrow_mut().cross_platform_ui.handle_mouse_down(position, mouse_button(button))
+    }
+    
+    #[wasm_bindgen]
+    pub fn handle_mouse_move(&mut self, x: f32, y: f32) -> bool {
+        let position = Vec2::new(x, y);
+        self.app.borrow_mut().cross_platform_ui.handle_mouse_move(position)
+    }
+    
+    #[wasm_bindgen]
+    pub fn handle_mouse_up(&mut self, x: f32, y: f32, button: u32) -> bool {
+        let position = Vec2::new(x, y);
+        self.app.borrow_mut().cross_platform_ui.handle_mouse_up(position, mouse_button(button))
+    }
+    
+    /// Get current UI configuration for JavaScript
+    #[wasm_bindgen]
+    pub fn get_ui_config(&self) -> Result<JsValue, JsValue> {
+        let config = self.app.borrow().cross_platform_ui.get_ui_config();
+        serde_wasm_bindgen::to_value(&config)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+    
+    /// Check if in mobile mode
+    #[wasm_bindgen]
+    pub fn is_mobile_mode(&self) -> bool {
+        self.app.borrow().cross_platform_ui.is_mobile_mode()
+    }
+    
+    /// Toggle mobile mode and remember the choice
+    #[wasm_bindgen]
+    pub fn toggle_mobile_mode(&mut self) {
+        {
+            let mut app = self.app.borrow_mut();
+            app.cross_platform_ui.toggle_mobile_mode();
+            app.config.mobile_mode = Some(app.cross_platform_ui.adaptation_config().force_mobile_mode);
+        }
+        self.save_config();
+    }
+    
+    /// Save the configuration to localStorage
+    ///
+    /// Returns false when storage is unavailable (e.g. private browsing) or
+    /// the write fails; the app keeps working with the in-memory config.
+    #[wasm_bindgen]
+    pub fn save_config(&self) -> bool {
+        let Some(storage) = local_storage() else {
+            return false;
+        };
+        let json = match self.app.borrow().config.to_json() {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!("Failed to serialize web config: {}", e);
+                return false;
+            }
+        };
+        match storage.set_item(CONFIG_STORAGE_KEY, &json) {
+            Ok(()) => true,
+            Err(_) => {
+                tracing::warn!("Failed to write web config to localStorage");
+                false
+            }
+        }
+    }
+    
+    /// Get UI telemetry (frame times, input latency, event counts)
+    #[wasm_bindgen]
+    pub fn get_performance_metrics(&self) -> Result<JsValue, JsValue> {
+        let app = self.app.borrow();
+        serde_wasm_bindgen::to_value(app.cross_platform_ui.metrics())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+}
+
+// Private implementation methods
+impl Marco2Web {
+    /// Attach pointer listeners to the canvas
+    ///
+    /// Touch events are converted per changed touch and have their default
+    /// action prevented so the page does not scroll or zoom under the
+    /// canvas. Positions are client coordinates, as in the public handlers.
+    fn setup_event_listeners(&mut self) -> Result<(), JsValue> {
+        self.add_listener("touchstart", |app, event| {
+            let Some(event) = event.dyn_ref::<TouchEvent>() else { return };
+            event.prevent_default();
+            for (id, position, pressure) in changed_touches(event) {
+                app.cross_platform_ui.handle_touch_down(id, position, pressure);
+            }
+        })?;
+        self.add_listener("touchmove", |app, event| {
+            let Some(event) = event.dyn_ref::<TouchEvent>() else { return };
+            event.prevent_default();
+            for (id, position, pressure) in changed_touches(event) {
+                app.cross_platform_ui.handle_touch_move(id, position, pressure);
+            }
+        })?;
+        for event_type in ["touchend", "touchcancel"] {
+            self.add_listener(event_type, |app, event| {
+                let Some(event) = event.dyn_ref::<TouchEvent>() else { return };
+                event.prevent_default();
+                for (id, _, _) in changed_touches(event) {
+                    app.cross_platform_ui.handle_touch_up(id);
+                }
+            })?;
+        }
+        
+        self.add_listener("mousedown", |app, event| {
+            let Some(event) = event.dyn_ref::<MouseEvent>() else { return };
+            app.cross_platform_ui.handle_mouse_down(client_position(event), mouse_button(event.button() as u32));
+        })?;
+        self.add_listener("mousemove", |app, event| {
+            let Some(event) = event.dyn_ref::<MouseEvent>() else { return };
+            app.cross_platform_ui.handle_mouse_move(client_position(event));
+        })?;
+        self.add_listener("mouseup", |app, event| {
+            let Some(event) = event.dyn_ref::<MouseEvent>() else { return };
+            app.cross_platform_ui.handle_mouse_up(client_position(event), mouse_button(event.button() as u32));
+        })?;
+        self.add_listener("wheel", |app, event| {
+            let Some(event) = event.dyn_ref::<WheelEvent>() else { return };
+            let lines = match event.delta_mode() {
+                WheelEvent::DOM_DELTA_PIXEL => event.delta_y() / WHEEL_PIXELS_PER_LINE,
+                _ => event.delta_y(),
+            };
+            // Scrolling down (positive delta) zooms out
+            if app.cross_platform_ui.handle_mouse_wheel(client_position(event), -lines as f32) {
+                event.prevent_default();
+            }
+        })?;
+        
+        Ok(())
+    }
+    
+    /// Add a canvas listener that runs `handler` against the shared app state
+    fn add_listener<F>(&mut self, event_type: &'static str, mut handler: F) -> Result<(), JsValue>
+    where
+        F: FnMut(&mut WebApp, &Event) + 'static,
+    {
+        let app = self.app.clone();
+        let closure = Closure::wrap(Box::new(move |event: Event| {
+            handler(&mut app.borrow_mut(), &event);
+        }) as Box<dyn FnMut(Event)>);
+        
+        self.app.borrow().canvas
+            .add_event_listener_with_callback(event_type, closure.as_ref().unchecked_ref())?;
+        self.listeners.push((event_type, closure));
+        Ok(())
+    }
+    
+    /// Detach and drop every canvas listener
+    fn remove_event_listeners(&mut self) {
+        let app = self.app.borrow();
+        for (event_type, closure) in self.listeners.drain(..) {
+            if let Err(e) = app.canvas.remove_event_listener_with_callback(event_type, closure.as_ref().unchecked_ref()) {
+                console::warn_2(&format!("Failed to remove {} listener:", event_type).into(), &e);
+            }
+        }
+    }
+    
+    /// Start the animation loop using requestAnimationFrame
+    ///
+    /// Each frame updates and renders the app, then schedules the next frame
+    /// while the app is running. The callback holds a handle to its own slot
+    /// so it can reschedule itself; emptying the slot breaks that cycle.
+    fn start_animation_loop(&mut self) -> Result<(), JsValue> {
+        {
+            let mut app = self.app.borrow_mut();
+            app.last_frame_time = app.performance.now();
+        }
+        
+        let app = self.app.clone();
+        let slot = self.animation_frame.clone();
+        let frame_id = self.animation_frame_id.clone();
+        *self.animation_frame.borrow_mut() = Some(Closure::wrap(Box::new(move |_timestamp: f64| {
+            frame_id.set(None);
+            let running = {
+                let mut app = app.borrow_mut();
+                let now = app.performance.now();
+                app.update(now);
+                app.is_running
+            };
+            
+            if !running {
+                // Drop this closure; nothing references the app any more
+                let _ = slot.borrow_mut().take();
+                return;
+            }
+            if let Some(callback) = slot.borrow().as_ref() {
+                match request_animation_frame(callback) {
+                    Ok(id) => frame_id.set(Some(id)),
+                    Err(e) => console::error_2(&"requestAnimationFrame failed:".into(), &e),
+                }
+            }
+        }) as Box<dyn FnMut(f64)>));
+        
+        let id = request_animation_frame(self.animation_frame.borrow().as_ref().ok_or("Animation frame missing")?)?;
+        self.animation_frame_id.set(Some(id));
+        Ok(())
+    }
+    
+    /// Cancel the pending frame and drop the loop's callback
+    fn stop_animation_loop(&mut self) {
+        if let Some(id) = self.animation_frame_id.take() {
+            if let Some(window) = window() {
+                let _ = window.cancel_animation_frame(id);
+            }
+        }
+        let _ = self.animation_frame.borrow_mut().take();
+    }
+}
+
+impl WebApp {
+    /// Update the application (called each frame)
+    fn update(&mut self, current_time: f64) {
+        if !self.is_running {
+            return;
+        }
+        
+        let delta_time = ((current_time - self.last_frame_time) / 1000.0) as f32;
+        self.last_frame_time = current_time;
+        
+        // Update cross-platform UI
+        let screen_bounds = Vec2::new(
+            self.canvas.client_width() as f32,
+            self.canvas.client_height() as f32,
+        );
+        
+        self.cross_platform_ui.update(delta_time, screen_bounds);
+        
+        // Render (placeholder)
+        self.render();
+    }
+    
+    /// Render the application
+    fn render(&self) {
+        if let Some(ref ctx) = self.context_2d {
+            // Clear canvas
+            let width = self.canvas.width() as f64;
+            let height = self.canvas.height() as f64;
+            ctx.clear_rect(0.0, 0.0, width, height);
+            
+            // TODO: Implement actual rendering
+            // This would render the visual node editor and GUI canvas
+            
+            if self.config.enable_debug {
+                self.render_debug_info(ctx);
+            }
+        }
+    }
+    
+    /// Render debug information
+    fn render_debug_info(&self, ctx: &CanvasRenderingContext2d) {
+        ctx.set_fill_style_str("rgba(0, 255, 0, 0.8)");
+        ctx.set_font("12px monospace");
+        
+        let metrics = self.cross_platform_ui.performance();
+        let fps_text = format!("FPS: {:.1}", metrics.fps);
+        let _ = ctx.fill_text(&fps_text, 10.0, 20.0);
+        
+        let mode_text = if self.cross_platform_ui.is_mobile_mode() {
+            "Mode: Mobile"
+        } else {
+            "Mode: Desktop"
+        };
+        let _ = ctx.fill_text(mode_text, 10.0, 40.0);
+    }
+}
+
+/// The page's localStorage, if the browser allows access to it
+fn local_storage() -> Option<web_sys::Storage> {
+    window()?.local_storage().ok().flatten()
+}
+
+/// Configuration saved by `Marco2Web::save_config`, if any can be read
+fn load_stored_config() -> Option<WebConfig> {
+    let json = local_storage()?.get_item(CONFIG_STORAGE_KEY).ok().flatten()?;
+    match WebConfig::from_json(&json) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            tracing::warn!("Ignoring unreadable saved web config: {}", e);
+            None
+        }
+    }
+}
+
+/// DOM `MouseEvent.button` to the UI's mouse button
+fn mouse_button(button: u32) -> MouseButton {
+    match button {
+        0 => MouseButton::Left,
+        1 => MouseButton::Middle,
+        2 => MouseButton::Right,
+        3 => MouseButton::Back,
+        4 => MouseButton::Forward,
+        _ => MouseButton::Left,
+    }
+}
+
+fn client_position(event: &MouseEvent) -> Vec2 {
+    Vec2::new(event.client_x() as f32, event.client_y() as f32)
+}
+
+/// The touches that changed in `event`, with a pressure of 1.0 where the
+/// device reports none
+fn changed_touches(event: &TouchEvent) -> Vec<(TouchId, Vec2, f32)> {
+    let touches = event.changed_touches();
+    (0..touches.length())
+        .filter_map(|index| touches.get(index))
+        .map(|touch| {
+            let force = touch.force();
+            (
+                TouchId(touch.identifier() as u64),
+                Vec2::new(touch.client_x() as f32, touch.client_y() as f32),
+                if force > 0.0 { force } else { 1.0 },
+            )
+        })
+        .collect()
+}
+
+/// Schedule `callback` for the next repaint, returning the request id
+fn request_animation_frame(callback: &Closure<dyn FnMut(f64)>) -> Result<i32, JsValue> {
+    window()
+        .ok_or("No window object")?
+        .request_animation_frame(callback.as_ref().unchecked_ref())
+}
+
+/// Touch data structure for JavaScript interop
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TouchData {
+    identifier: i32,
+    client_x: f32,
+    client_y: f32,
+    page_x: f32,
+    page_y: f32,
+    screen_x: f32,
+    screen_y: f32,
+    radius_x: Option<f32>,
+    radius_y: Option<f32>,
+    rotation_angle: Option<f32>,
+    force: Option<f32>,
+}
+
+/// Utility functions for JavaScript interop
+#[wasm_bindgen]
+pub fn set_panic_hook() {
+    #[cfg(feature = "console_error_panic_hook")]
+    console_error_panic_hook::set_once();
+}
+
+#[wasm_bindgen]
+pub fn init_marco2_logging() {
+    init_logging();
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console)]
+    pub(super) fn log(s: &str);
+}
+
+/// Macro for console logging from WASM
+#[macro_export]
+macro_rules! console_log {
+    ($($t:tt)*) => (crate::web::browser::log(&format_args!($($t)*).to_string()))
+}
+
+// Export the main module
+pub use Marco2Web as Marco2WebApp;
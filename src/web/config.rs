@@ -0,0 +1,74 @@
+//! Web configuration, independent of the browser so it can be tested natively
+
+use serde::{Deserialize, Serialize};
+
+/// Web-compatible configuration for Marco 2.0
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebConfig {
+    pub canvas_id: String,
+    pub enable_touch: bool,
+    pub enable_debug: bool,
+    pub performance_mode: PerformanceMode,
+    pub mobile_mode: Option<bool>, // None = auto-detect
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PerformanceMode {
+    Auto,
+    High,
+    Balanced,
+    PowerSaver,
+}
+
+impl Default for WebConfig {
+    fn default() -> Self {
+        Self {
+            canvas_id: "marco2-canvas".to_string(),
+            enable_touch: true,
+            enable_debug: false,
+            performance_mode: PerformanceMode::Auto,
+            mobile_mode: None,
+        }
+    }
+}
+
+impl WebConfig {
+    /// JSON form stored in localStorage
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Parse a configuration saved with [`WebConfig::to_json`]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_round_trips_through_json() {
+        let config = WebConfig {
+            canvas_id: "editor".to_string(),
+            enable_touch: false,
+            enable_debug: true,
+            performance_mode: PerformanceMode::PowerSaver,
+            mobile_mode: Some(true),
+        };
+
+        let restored = WebConfig::from_json(&config.to_json().unwrap()).unwrap();
+        assert_eq!(restored.canvas_id, "editor");
+        assert!(!restored.enable_touch);
+        assert!(restored.enable_debug);
+        assert!(matches!(restored.performance_mode, PerformanceMode::PowerSaver));
+        assert_eq!(restored.mobile_mode, Some(true));
+
+        // Auto-detection is saved as null and restored as None
+        let json = WebConfig::default().to_json().unwrap();
+        assert!(json.contains("\"mobile_mode\":null"), "{}", json);
+        assert_eq!(WebConfig::from_json(&json).unwrap().mobile_mode, None);
+        assert!(WebConfig::from_json("{\"canvas_id\": 3}").is_err());
+    }
+}